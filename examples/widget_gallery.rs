@@ -0,0 +1,49 @@
+//! Shows every widget in every state from the current theme
+//!
+//! Press `T` to toggle between the default and a light theme.
+//!
+//! ```sh
+//! cargo run --example widget_gallery --features gallery
+//! ```
+
+use bevy::prelude::*;
+use sf_ui_common::{ButtonPalette, TextPalette, Theme, UiCommonPlugin, WidgetGallery};
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins((DefaultPlugins, UiCommonPlugin, WidgetGallery))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_theme);
+    sf_ui_common::systems::update(&mut app);
+    app.run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+fn light_theme() -> Theme {
+    Theme {
+        button: ButtonPalette {
+            normal: Color::srgb(0.85, 0.85, 0.88),
+            hovered: Color::srgb(0.75, 0.78, 0.9),
+            pressed: Color::srgb(0.6, 0.65, 0.85),
+            disabled: Color::srgb(0.7, 0.7, 0.7),
+        },
+        text: TextPalette {
+            normal: Color::srgb(0.1, 0.1, 0.12),
+            disabled: Color::srgb(0.45, 0.45, 0.45),
+        },
+        ..default()
+    }
+}
+
+fn toggle_theme(keyboard_input: Res<ButtonInput<KeyCode>>, mut theme: ResMut<Theme>) {
+    if keyboard_input.just_pressed(KeyCode::KeyT) {
+        *theme = if *theme == Theme::default() {
+            light_theme()
+        } else {
+            Theme::default()
+        };
+    }
+}
@@ -0,0 +1,430 @@
+//! Accordion containers
+//!
+//! An [`Accordion`] stacks [`Collapsible`] sections, each with a clickable
+//! header and a body that slides open and closed. In an exclusive accordion,
+//! opening one section closes the others. Each section can show a badge, such
+//! as a count of new recipes in a crafting category. Opening and closing a
+//! section sends [`PanelToggled`], like a collapsible panel.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{accordion_bundle, accordion_section, Accordion, StatusChip, StatusKind, Theme};
+//! fn setup(mut commands: Commands, theme: Res<Theme>) {
+//!     commands
+//!         .spawn(accordion_bundle(Accordion::exclusive()))
+//!         .with_children(|accordion| {
+//!             accordion_section("Weapons", &theme)
+//!                 .badge(StatusChip::new("3 new", StatusKind::Info))
+//!                 .expanded()
+//!                 .spawn(accordion, |body| {
+//!                     // Section content
+//!                 });
+//!             accordion_section("Armor", &theme).spawn(accordion, |_| {});
+//!         });
+//! }
+//! ```
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::chip::{status_chip_bundle, StatusChip};
+use crate::clock::UiClock;
+use crate::components::{Collapsible, FocusState, Focusable, FocusableType};
+use crate::interaction::UiInteractionState;
+use crate::panel::PanelToggled;
+use crate::quality::UiMotion;
+use crate::theme::Theme;
+
+/// A stack of collapsible sections
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Accordion {
+    /// Whether opening a section closes the others
+    pub exclusive: bool,
+    /// Open/close animation speed (higher is faster, 0 snaps immediately)
+    pub animation_speed: f32,
+}
+
+impl Default for Accordion {
+    fn default() -> Self {
+        Self {
+            exclusive: false,
+            animation_speed: 12.0,
+        }
+    }
+}
+
+impl Accordion {
+    /// An accordion that keeps at most one section open
+    pub fn exclusive() -> Self {
+        Self {
+            exclusive: true,
+            ..default()
+        }
+    }
+}
+
+/// A section of an [`Accordion`]; its open state lives in [`Collapsible`]
+#[derive(Component, Debug, Clone, PartialEq, Reflect, Default)]
+#[reflect(Component)]
+pub struct AccordionSection {
+    /// Badge shown in the header, hidden when `None`
+    pub badge: Option<StatusChip>,
+}
+
+/// Child nodes of an [`AccordionSection`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum AccordionPart {
+    /// The clickable header row
+    Header,
+    /// The open/closed indicator in the header
+    Chevron,
+    /// The badge in the header
+    Badge,
+    /// The clipping node whose height animates
+    Body,
+    /// The node holding the section content
+    Content,
+}
+
+/// How far a section body is open, from 0 (closed) to 1 (open)
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect, Default)]
+#[reflect(Component)]
+pub struct AccordionOpenness(pub f32);
+
+/// Creates the container bundle for an accordion
+pub fn accordion_bundle(accordion: Accordion) -> (NodeBundle, Accordion) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.0),
+                ..default()
+            },
+            ..default()
+        },
+        accordion,
+    )
+}
+
+/// Builder for an accordion section
+///
+/// Created by [`accordion_section`]. Sections start closed.
+#[derive(Debug, Clone)]
+pub struct AccordionSectionBuilder {
+    title: String,
+    badge: Option<StatusChip>,
+    expanded: bool,
+    font: Handle<Font>,
+    theme: Theme,
+}
+
+/// Starts an accordion section using colors from `theme`
+pub fn accordion_section(title: impl Into<String>, theme: &Theme) -> AccordionSectionBuilder {
+    AccordionSectionBuilder {
+        title: title.into(),
+        badge: None,
+        expanded: false,
+        font: UiFonts::BOLD,
+        theme: theme.clone(),
+    }
+}
+
+impl AccordionSectionBuilder {
+    /// Shows a badge in the header
+    pub fn badge(mut self, badge: StatusChip) -> Self {
+        self.badge = Some(badge);
+        self
+    }
+
+    /// Starts the section open
+    pub fn expanded(mut self) -> Self {
+        self.expanded = true;
+        self
+    }
+
+    /// Spawns the section under the accordion being built
+    ///
+    /// `content` builds the section body. Returns the section entity.
+    pub fn spawn(self, accordion: &mut ChildBuilder, content: impl FnOnce(&mut ChildBuilder)) -> Entity {
+        let openness = if self.expanded { 1.0 } else { 0.0 };
+        let text_style = TextStyle {
+            font: self.font.clone(),
+            font_size: 18.0,
+            color: self.theme.text.normal,
+        };
+
+        accordion
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    ..default()
+                },
+                Collapsible {
+                    title: self.title.clone(),
+                    collapsible: true,
+                    collapsed: !self.expanded,
+                },
+                AccordionSection {
+                    badge: self.badge.clone(),
+                },
+                Name::new(format!("AccordionSection_{}", self.title)),
+            ))
+            .with_children(|section| {
+                section
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Row,
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(6.0),
+                                padding: UiRect::axes(Val::Px(8.0), Val::Px(6.0)),
+                                ..default()
+                            },
+                            background_color: self.theme.button.normal.into(),
+                            ..default()
+                        },
+                        AccordionPart::Header,
+                        Focusable {
+                            state: FocusState::NotFocused,
+                            focus_type: FocusableType::Button,
+                        },
+                    ))
+                    .with_children(|header| {
+                        header.spawn((
+                            TextBundle::from_section("v", text_style.clone()),
+                            AccordionPart::Chevron,
+                        ));
+                        header.spawn(
+                            TextBundle::from_section(self.title.clone(), text_style.clone()).with_style(
+                                Style {
+                                    flex_grow: 1.0,
+                                    ..default()
+                                },
+                            ),
+                        );
+                        header.spawn((
+                            status_chip_bundle(self.badge.clone().unwrap_or_default()),
+                            AccordionPart::Badge,
+                        ));
+                    });
+
+                section
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                height: if self.expanded { Val::Auto } else { Val::Px(0.0) },
+                                overflow: Overflow::clip(),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        AccordionPart::Body,
+                        AccordionOpenness(openness),
+                    ))
+                    .with_children(|body| {
+                        body.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    flex_shrink: 0.0,
+                                    width: Val::Percent(100.0),
+                                    padding: UiRect::all(Val::Px(8.0)),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            AccordionPart::Content,
+                        ))
+                        .with_children(content);
+                    });
+            })
+            .id()
+    }
+}
+
+/// System to toggle a section when its header is pressed and color headers on hover
+pub fn accordion_header_system(
+    mut headers: Query<(Entity, &AccordionPart, &Parent, Ref<Interaction>, &mut BackgroundColor)>,
+    mut sections: Query<&mut Collapsible, With<AccordionSection>>,
+    interaction_state: Res<UiInteractionState>,
+    theme: Res<Theme>,
+    mut toggled_events: EventWriter<PanelToggled>,
+) {
+    for (entity, part, parent, interaction, mut background) in &mut headers {
+        if *part != AccordionPart::Header {
+            continue;
+        }
+        if interaction.is_changed() || theme.is_changed() {
+            background.0 = theme.button_color(*interaction);
+        }
+        if !interaction_state.just_pressed(entity) {
+            continue;
+        }
+        if let Ok(mut collapsible) = sections.get_mut(parent.get()) {
+            if collapsible.collapsible {
+                collapsible.collapsed = !collapsible.collapsed;
+                toggled_events.send(PanelToggled {
+                    panel: parent.get(),
+                    collapsed: collapsible.collapsed,
+                });
+            }
+        }
+    }
+}
+
+/// System to close the other sections of an exclusive accordion when one opens
+pub fn accordion_exclusive_system(
+    accordions: Query<(&Accordion, &Children)>,
+    mut sections: Query<&mut Collapsible, With<AccordionSection>>,
+    mut toggled_events: EventWriter<PanelToggled>,
+) {
+    for (accordion, children) in &accordions {
+        if !accordion.exclusive {
+            continue;
+        }
+        let opened = children.iter().copied().find(|&child| {
+            sections
+                .get_mut(child)
+                .is_ok_and(|section| section.is_changed() && !section.collapsed)
+        });
+        let Some(opened) = opened else {
+            continue;
+        };
+        for &child in children.iter().filter(|&&child| child != opened) {
+            let Ok(mut section) = sections.get_mut(child) else {
+                continue;
+            };
+            if !section.collapsed {
+                section.collapsed = true;
+                toggled_events.send(PanelToggled {
+                    panel: child,
+                    collapsed: true,
+                });
+            }
+        }
+    }
+}
+
+/// System to slide section bodies open and closed
+pub fn accordion_animation_system(
+    clock: Res<UiClock>,
+    motion: UiMotion,
+    accordions: Query<&Accordion>,
+    sections: Query<(&Collapsible, &Parent, &Children), With<AccordionSection>>,
+    mut bodies: Query<(&mut Style, &mut AccordionOpenness, &Children)>,
+    contents: Query<&Node>,
+) {
+    for (collapsible, parent, children) in &sections {
+        let speed = accordions
+            .get(parent.get())
+            .map_or(Accordion::default().animation_speed, |accordion| accordion.animation_speed);
+        let blend = if speed > 0.0 && motion.animations() {
+            1.0 - (-speed * clock.delta_seconds()).exp()
+        } else {
+            1.0
+        };
+        let target = if collapsible.collapsed { 0.0 } else { 1.0 };
+
+        let mut section_bodies = bodies.iter_many_mut(children);
+        while let Some((mut style, mut openness, body_children)) = section_bodies.fetch_next() {
+            if openness.0 == target {
+                continue;
+            }
+            openness.0 += (target - openness.0) * blend;
+            if (openness.0 - target).abs() < 0.01 {
+                openness.0 = target;
+            }
+            let content_height = body_children
+                .first()
+                .and_then(|&content| contents.get(content).ok())
+                .map_or(0.0, |node| node.size().y);
+            // Fully open bodies size to their content so it can still grow
+            style.height = if openness.0 == 1.0 {
+                Val::Auto
+            } else {
+                Val::Px(openness.0 * content_height)
+            };
+        }
+    }
+}
+
+/// System to rotate each header's chevron with its section's openness
+///
+/// The chevron points down when open and right when closed.
+pub fn accordion_chevron_system(
+    sections: Query<&Children, With<AccordionSection>>,
+    bodies: Query<&AccordionOpenness, Changed<AccordionOpenness>>,
+    headers: Query<&Children, With<Button>>,
+    mut chevrons: Query<(&AccordionPart, &mut Transform)>,
+) {
+    for children in &sections {
+        let Some(openness) = bodies.iter_many(children).next() else {
+            continue;
+        };
+        let rotation = Quat::from_rotation_z(-FRAC_PI_2 * (1.0 - openness.0));
+        for header_children in headers.iter_many(children) {
+            for &header_child in header_children.iter() {
+                if let Ok((AccordionPart::Chevron, mut transform)) = chevrons.get_mut(header_child) {
+                    transform.rotation = rotation;
+                }
+            }
+        }
+    }
+}
+
+/// System to show, hide and refresh section badges
+pub fn accordion_badge_system(
+    sections: Query<(Ref<AccordionSection>, &Children)>,
+    headers: Query<(&AccordionPart, &Children)>,
+    mut badges: Query<(&AccordionPart, &mut StatusChip, &mut Style)>,
+) {
+    for (section, children) in &sections {
+        if !section.is_changed() {
+            continue;
+        }
+        for (part, header_children) in headers.iter_many(children) {
+            if *part != AccordionPart::Header {
+                continue;
+            }
+            for &header_child in header_children.iter() {
+                let Ok((&AccordionPart::Badge, mut chip, mut style)) = badges.get_mut(header_child) else {
+                    continue;
+                };
+                match &section.badge {
+                    Some(badge) => {
+                        if *chip != *badge {
+                            *chip = badge.clone();
+                        }
+                        style.display = Display::Flex;
+                    }
+                    None => style.display = Display::None,
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Accordion>()
+        .register_type::<AccordionSection>()
+        .register_type::<AccordionPart>()
+        .register_type::<AccordionOpenness>()
+        .add_systems(
+            Update,
+            (
+                accordion_header_system,
+                accordion_exclusive_system,
+                accordion_animation_system,
+                accordion_chevron_system,
+                accordion_badge_system,
+            )
+                .chain(),
+        );
+}
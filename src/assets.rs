@@ -0,0 +1,482 @@
+//! Fonts widgets use, and a check that they load
+//!
+//! Widgets draw text with the four weights of [`UiFonts`], loaded from the
+//! paths in [`UiFontPaths`]. By default these are `fonts/FiraSans-*.ttf` in
+//! the game's own `assets` folder, which the game has to ship; a game with
+//! its own layout or typeface sets the paths instead:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{UiCommonPlugin, UiFontPaths};
+//! # let mut app = App::new();
+//! app.add_plugins(UiCommonPlugin).insert_resource(UiFontPaths {
+//!     regular: "ui/Inter-Regular.ttf".into(),
+//!     medium: "ui/Inter-Medium.ttf".into(),
+//!     semibold: "ui/Inter-SemiBold.ttf".into(),
+//!     bold: "ui/Inter-Bold.ttf".into(),
+//! });
+//! ```
+//!
+//! Widgets don't hold the loaded handles but the fixed [`UiFonts::REGULAR`],
+//! [`UiFonts::MEDIUM`], [`UiFonts::SEMIBOLD`] and [`UiFonts::BOLD`], which
+//! always show whatever font the matching weight loaded. Changing the paths
+//! at runtime therefore restyles the text already on screen.
+//!
+//! With the `embedded_assets` feature the crate brings its own fonts and
+//! icons instead: the files under the crate's `assets` folder are compiled
+//! in with `include_bytes!`, the paths point into Bevy's `embedded://` asset
+//! source, and the plugin registers the files there at startup, so widgets
+//! render text in a new project or a test app with no asset folder at all.
+//! The embedded typeface is DejaVu Sans, which comes in book and bold:
+//! regular and medium text use the book weight, semibold and bold text the
+//! bold one. The `UiIcons` resource holds the embedded icon sheet, a row of
+//! white `UiIcon`s to tint with the image color.
+//!
+//! Either way, [`UiFonts`] holds the fonts for the whole session and
+//! [`UiAssetAudit`] checks they load. A font that fails, say because the
+//! game forgot to ship it, is reported once with its path and replaced by
+//! Bevy's built-in font wherever text uses it, so widgets never render
+//! invisible text. Games can add their own UI images, such as icon atlas
+//! sheets, to the audit with [`UiAssetAudit::watch`].
+
+use bevy::asset::{LoadState, UntypedAssetId};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Path of the only embedded text font, used for every weight with the
+/// `embedded_assets` feature
+pub const EMBEDDED_TEXT_FONT: &str = "embedded://sf_ui_common/fonts/Ubuntu-Light.ttf";
+
+/// Builds the path of a font weight from the game's assets, or of the
+/// embedded font standing in for it
+macro_rules! font_path {
+    ($file:literal, $embedded:literal) => {
+        if cfg!(feature = "embedded_assets") {
+            concat!("embedded://sf_ui_common/fonts/", $embedded)
+        } else {
+            concat!("fonts/", $file)
+        }
+    };
+}
+
+/// Default path of the font for body text
+pub const FONT_REGULAR: &str = font_path!("FiraSans-Regular.ttf", "DejaVuSans.ttf");
+
+/// Default path of the font for labels, buttons and list rows
+pub const FONT_MEDIUM: &str = font_path!("FiraSans-Medium.ttf", "DejaVuSans.ttf");
+
+/// Default path of the font for section headings
+pub const FONT_SEMIBOLD: &str = font_path!("FiraSans-SemiBold.ttf", "DejaVuSans-Bold.ttf");
+
+/// Default path of the font for titles and emphasis
+pub const FONT_BOLD: &str = font_path!("FiraSans-Bold.ttf", "DejaVuSans-Bold.ttf");
+
+/// Path of the embedded icon sheet, a row of [`UiIcon`]s
+#[cfg(feature = "embedded_assets")]
+pub const ICON_SHEET: &str = "embedded://sf_ui_common/icons/ui_icons.png";
+
+/// Files compiled into the crate, by path under `embedded://sf_ui_common`
+#[cfg(feature = "embedded_assets")]
+const EMBEDDED_FILES: [(&str, &[u8]); 3] = [
+    (
+        "fonts/DejaVuSans.ttf",
+        include_bytes!("../assets/fonts/DejaVuSans.ttf"),
+    ),
+    (
+        "fonts/DejaVuSans-Bold.ttf",
+        include_bytes!("../assets/fonts/DejaVuSans-Bold.ttf"),
+    ),
+    (
+        "icons/ui_icons.png",
+        include_bytes!("../assets/icons/ui_icons.png"),
+    ),
+];
+
+/// Registers the embedded fonts and icons under their paths
+#[cfg(feature = "embedded_assets")]
+fn register_embedded_assets(app: &mut App) {
+    use std::path::Path;
+
+    use bevy::asset::io::embedded::EmbeddedAssetRegistry;
+
+    let Some(registry) = app.world().get_resource::<EmbeddedAssetRegistry>() else {
+        warn!("AssetPlugin is missing, so the embedded UI assets are not registered");
+        return;
+    };
+    for (path, bytes) in EMBEDDED_FILES {
+        let path = Path::new("sf_ui_common").join(path);
+        registry.insert_asset(path.clone(), &path, bytes);
+    }
+}
+
+/// An icon of the embedded icon sheet
+#[cfg(feature = "embedded_assets")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum UiIcon {
+    /// Check mark
+    Check,
+    /// Diagonal cross, for close and remove buttons
+    Close,
+    /// Plus sign
+    Plus,
+    /// Minus sign
+    Minus,
+    /// Chevron pointing up
+    ChevronUp,
+    /// Chevron pointing down
+    ChevronDown,
+    /// Chevron pointing left
+    ChevronLeft,
+    /// Chevron pointing right
+    ChevronRight,
+}
+
+#[cfg(feature = "embedded_assets")]
+impl UiIcon {
+    /// Width and height of each icon on the sheet, in pixels
+    pub const SIZE: u32 = 32;
+
+    /// Every icon, in sheet order
+    pub const ALL: [Self; 8] = [
+        Self::Check,
+        Self::Close,
+        Self::Plus,
+        Self::Minus,
+        Self::ChevronUp,
+        Self::ChevronDown,
+        Self::ChevronLeft,
+        Self::ChevronRight,
+    ];
+}
+
+/// The embedded icon sheet and its atlas layout
+#[cfg(feature = "embedded_assets")]
+#[derive(Resource, Debug, Clone)]
+pub struct UiIcons {
+    /// The sheet image, loaded from [`ICON_SHEET`]
+    pub image: Handle<Image>,
+    /// One atlas cell per [`UiIcon`]
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+#[cfg(feature = "embedded_assets")]
+impl UiIcons {
+    /// Loads the sheet and adds its layout
+    pub fn load(asset_server: &AssetServer, layouts: &mut Assets<TextureAtlasLayout>) -> Self {
+        let layout = TextureAtlasLayout::from_grid(
+            UVec2::splat(UiIcon::SIZE),
+            UiIcon::ALL.len() as u32,
+            1,
+            None,
+            None,
+        );
+        Self {
+            image: asset_server.load(ICON_SHEET),
+            layout: layouts.add(layout),
+        }
+    }
+
+    /// An image node showing `icon` at `size` pixels, tinted `color`
+    pub fn bundle(&self, icon: UiIcon, size: f32, color: Color) -> (ImageBundle, TextureAtlas) {
+        (
+            ImageBundle {
+                style: Style {
+                    width: Val::Px(size),
+                    height: Val::Px(size),
+                    ..default()
+                },
+                image: UiImage::new(self.image.clone()).with_color(color),
+                ..default()
+            },
+            TextureAtlas {
+                layout: self.layout.clone(),
+                index: icon as usize,
+            },
+        )
+    }
+}
+
+/// Startup system to load the [`UiIcons`] and add the sheet to the audit
+#[cfg(feature = "embedded_assets")]
+fn load_ui_icons(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut audit: ResMut<UiAssetAudit>,
+) {
+    let icons = UiIcons::load(&asset_server, &mut layouts);
+    audit.watch(icons.image.clone());
+    commands.insert_resource(icons);
+}
+
+/// Asset paths the [`UiFonts`] are loaded from
+///
+/// Changing them reloads the fonts.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct UiFontPaths {
+    /// Font for body text
+    pub regular: String,
+    /// Font for labels, buttons and list rows
+    pub medium: String,
+    /// Font for section headings
+    pub semibold: String,
+    /// Font for titles and emphasis
+    pub bold: String,
+}
+
+impl Default for UiFontPaths {
+    fn default() -> Self {
+        Self {
+            regular: FONT_REGULAR.into(),
+            medium: FONT_MEDIUM.into(),
+            semibold: FONT_SEMIBOLD.into(),
+            bold: FONT_BOLD.into(),
+        }
+    }
+}
+
+/// The fonts widgets use, kept loaded for the whole session
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct UiFonts {
+    /// [`UiFontPaths::regular`], shown through [`UiFonts::REGULAR`]
+    pub regular: Handle<Font>,
+    /// [`UiFontPaths::medium`], shown through [`UiFonts::MEDIUM`]
+    pub medium: Handle<Font>,
+    /// [`UiFontPaths::semibold`], shown through [`UiFonts::SEMIBOLD`]
+    pub semibold: Handle<Font>,
+    /// [`UiFontPaths::bold`], shown through [`UiFonts::BOLD`]
+    pub bold: Handle<Font>,
+}
+
+impl FromWorld for UiFonts {
+    fn from_world(world: &mut World) -> Self {
+        let paths = world
+            .get_resource::<UiFontPaths>()
+            .cloned()
+            .unwrap_or_default();
+        Self::load(world.resource::<AssetServer>(), &paths)
+    }
+}
+
+impl UiFonts {
+    /// Body text font used by widgets
+    pub const REGULAR: Handle<Font> =
+        Handle::weak_from_u128(0x5f0c_93a1_7d2e_4b8f_a1c6_2e90_41d7_b3a1);
+    /// Label, button and list row font used by widgets
+    pub const MEDIUM: Handle<Font> =
+        Handle::weak_from_u128(0x5f0c_93a1_7d2e_4b8f_a1c6_2e90_41d7_b3a2);
+    /// Section heading font used by widgets
+    pub const SEMIBOLD: Handle<Font> =
+        Handle::weak_from_u128(0x5f0c_93a1_7d2e_4b8f_a1c6_2e90_41d7_b3a3);
+    /// Title and emphasis font used by widgets
+    pub const BOLD: Handle<Font> =
+        Handle::weak_from_u128(0x5f0c_93a1_7d2e_4b8f_a1c6_2e90_41d7_b3a4);
+
+    /// Loads the fonts at `paths`
+    pub fn load(asset_server: &AssetServer, paths: &UiFontPaths) -> Self {
+        Self {
+            regular: asset_server.load(paths.regular.clone()),
+            medium: asset_server.load(paths.medium.clone()),
+            semibold: asset_server.load(paths.semibold.clone()),
+            bold: asset_server.load(paths.bold.clone()),
+        }
+    }
+
+    /// Every font handle
+    pub fn iter(&self) -> impl Iterator<Item = &Handle<Font>> {
+        [&self.regular, &self.medium, &self.semibold, &self.bold].into_iter()
+    }
+
+    /// Pairs of the fixed widget handles and the loaded fonts they show
+    fn weights(&self) -> [(Handle<Font>, &Handle<Font>); 4] {
+        [
+            (Self::REGULAR, &self.regular),
+            (Self::MEDIUM, &self.medium),
+            (Self::SEMIBOLD, &self.semibold),
+            (Self::BOLD, &self.bold),
+        ]
+    }
+}
+
+/// Tracks whether the UI's fonts and watched images loaded
+#[derive(Resource, Debug, Default)]
+pub struct UiAssetAudit {
+    watched: Vec<UntypedHandle>,
+    missing: Vec<String>,
+    missing_fonts: HashSet<AssetId<Font>>,
+    reported: HashSet<UntypedAssetId>,
+    finished: bool,
+}
+
+impl UiAssetAudit {
+    /// Adds an asset, such as an icon atlas image, to the audit
+    ///
+    /// Assets watched after the audit finished are checked again.
+    pub fn watch(&mut self, handle: impl Into<UntypedHandle>) {
+        self.watched.push(handle.into());
+        self.finished = false;
+    }
+
+    /// Paths of the assets that failed to load
+    pub fn missing(&self) -> &[String] {
+        &self.missing
+    }
+
+    /// Whether every audited asset finished loading or failed
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Whether text using `font` is drawn with the fallback font
+    pub fn is_font_missing(&self, font: &Handle<Font>) -> bool {
+        self.missing_fonts.contains(&font.id())
+    }
+}
+
+/// System to check the UI fonts and watched assets until all have loaded
+///
+/// Assets that fail are logged once, together, with their paths.
+pub fn ui_asset_audit_system(
+    fonts: Option<Res<UiFonts>>,
+    mut audit: ResMut<UiAssetAudit>,
+    asset_server: Res<AssetServer>,
+) {
+    let Some(fonts) = fonts else {
+        return;
+    };
+    if audit.finished && !fonts.is_changed() {
+        return;
+    }
+    let handles: Vec<UntypedHandle> = fonts
+        .iter()
+        .map(|font| font.clone().untyped())
+        .chain(audit.watched.iter().cloned())
+        .collect();
+
+    let mut pending = false;
+    let mut failed = Vec::new();
+    for handle in handles {
+        match asset_server.get_load_state(handle.id()) {
+            Some(LoadState::Failed(_)) => {
+                if audit.reported.insert(handle.id()) {
+                    let path = handle
+                        .path()
+                        .map_or_else(|| format!("{:?}", handle.id()), ToString::to_string);
+                    failed.push(path);
+                    if let Ok(font) = handle.try_typed::<Font>() {
+                        audit.missing_fonts.insert(font.id());
+                    }
+                }
+            }
+            Some(LoadState::Loaded) => {}
+            _ => pending = true,
+        }
+    }
+    if !failed.is_empty() {
+        error!(
+            "UI assets failed to load: {}. Missing fonts are replaced by Bevy's built-in font",
+            failed.join(", ")
+        );
+        audit.missing.extend(failed);
+    }
+    audit.finished = !pending;
+}
+
+/// Startup system to load the [`UiFonts`], unless the game inserted its own
+fn load_ui_fonts(mut commands: Commands) {
+    commands.init_resource::<UiFonts>();
+}
+
+/// System to reload the [`UiFonts`] when the game changes the [`UiFontPaths`]
+pub fn ui_font_paths_system(
+    mut commands: Commands,
+    paths: Res<UiFontPaths>,
+    asset_server: Res<AssetServer>,
+) {
+    // Fonts set before startup were loaded with the resource
+    if paths.is_changed() && !paths.is_added() {
+        commands.insert_resource(UiFonts::load(&asset_server, &paths));
+    }
+}
+
+/// System to show each loaded font through its fixed widget handle
+///
+/// A weight that failed to load shows Bevy's built-in font instead.
+pub fn ui_font_weights_system(
+    ui_fonts: Option<Res<UiFonts>>,
+    audit: Res<UiAssetAudit>,
+    mut events: EventReader<AssetEvent<Font>>,
+    mut fonts: ResMut<Assets<Font>>,
+) {
+    let Some(ui_fonts) = ui_fonts else {
+        return;
+    };
+    let loaded: Vec<AssetId<Font>> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    if !ui_fonts.is_changed() && !audit.is_changed() && loaded.is_empty() {
+        return;
+    }
+    for (weight, source) in ui_fonts.weights() {
+        let source = if audit.is_font_missing(source) {
+            Handle::default()
+        } else {
+            source.clone()
+        };
+        let refresh = ui_fonts.is_changed() || audit.is_changed() || loaded.contains(&source.id());
+        if let Some(font) = fonts.get(&source).filter(|_| refresh).cloned() {
+            fonts.insert(&weight, font);
+        }
+    }
+}
+
+/// System to draw text whose font failed to load with Bevy's built-in font
+pub fn ui_font_fallback_system(audit: Res<UiAssetAudit>, mut texts: Query<&mut Text>) {
+    if audit.missing_fonts.is_empty() {
+        return;
+    }
+    for mut text in &mut texts {
+        if !text.is_changed() && !audit.is_changed() {
+            continue;
+        }
+        let missing = |section: &TextSection| audit.is_font_missing(&section.style.font);
+        if text.sections.iter().any(missing) {
+            for section in &mut text.sections {
+                if audit.is_font_missing(&section.style.font) {
+                    section.style.font = Handle::default();
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    #[cfg(feature = "embedded_assets")]
+    {
+        register_embedded_assets(app);
+        app.register_type::<UiIcon>()
+            .add_systems(Startup, load_ui_icons);
+    }
+
+    app.register_type::<UiFonts>()
+        .register_type::<UiFontPaths>()
+        .init_resource::<UiFontPaths>()
+        .init_resource::<UiAssetAudit>()
+        .add_systems(Startup, load_ui_fonts)
+        .add_systems(
+            Update,
+            (
+                ui_font_paths_system,
+                ui_asset_audit_system,
+                ui_font_weights_system,
+                ui_font_fallback_system,
+            )
+                .chain(),
+        );
+}
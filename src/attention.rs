@@ -0,0 +1,284 @@
+//! One-shot attention animations
+//!
+//! Inserting one of these components plays a short animation on the node and
+//! removes the component when it ends:
+//!
+//! - [`Shake`] jiggles the node sideways, for rejected input
+//! - [`Pulse`] briefly scales the node up, for new content
+//! - [`Flash`] tints the background and fades back, for a changed value
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{Flash, Shake};
+//! fn on_gold_changed(mut commands: Commands, counter: Entity, field: Entity) {
+//!     commands.entity(counter).insert(Flash::new(Color::srgb(1.0, 0.85, 0.3)));
+//!     commands.entity(field).insert(Shake::default());
+//! }
+//! ```
+//!
+//! All three are driven by [`attention_system`], which puts the node's
+//! position, scale or background back when the animation ends or its
+//! component is removed. Inserting an animation that is already playing
+//! restarts it from the resting values. Animations run on [`UiClock`] and
+//! are skipped when [`UiQuality`](crate::UiQuality) or
+//! [`ReducedMotion`](crate::ReducedMotion) turns animations off.
+//!
+//! [`Shake`] moves the node through `Style::left`, so it only shows on nodes
+//! positioned in pixels or not positioned at all.
+
+use std::f32::consts::{PI, TAU};
+
+use bevy::color::Mix;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+use crate::clock::UiClock;
+use crate::quality::UiMotion;
+
+/// Shakes a node from side to side, for invalid input
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Shake {
+    /// Largest sideways offset in pixels
+    pub amplitude: f32,
+    /// Back-and-forth movements per second
+    pub frequency: f32,
+    /// Length in seconds
+    pub duration: f32,
+    /// Seconds played so far
+    pub elapsed: f32,
+}
+
+impl Default for Shake {
+    fn default() -> Self {
+        Self::new(6.0)
+    }
+}
+
+impl Shake {
+    /// Creates a shake moving up to `amplitude` pixels each way
+    pub fn new(amplitude: f32) -> Self {
+        Self {
+            amplitude,
+            frequency: 12.0,
+            duration: 0.4,
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// Scales a node up and back down, for new content
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Pulse {
+    /// Scale at the peak, relative to the resting scale
+    pub scale: f32,
+    /// Length in seconds
+    pub duration: f32,
+    /// Seconds played so far
+    pub elapsed: f32,
+}
+
+impl Default for Pulse {
+    fn default() -> Self {
+        Self::new(1.2)
+    }
+}
+
+impl Pulse {
+    /// Creates a pulse peaking at `scale`
+    pub fn new(scale: f32) -> Self {
+        Self {
+            scale,
+            duration: 0.35,
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// Tints a node's background and fades back, for a changed value
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Flash {
+    /// Color shown at the start
+    pub color: Color,
+    /// Length in seconds
+    pub duration: f32,
+    /// Seconds played so far
+    pub elapsed: f32,
+}
+
+impl Flash {
+    /// Creates a flash starting at `color`
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            duration: 0.5,
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// Values a node goes back to when its animations end
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Resting {
+    left: Option<Val>,
+    scale: Option<Vec3>,
+    background: Option<Color>,
+}
+
+impl Resting {
+    fn is_empty(&self) -> bool {
+        self.left.is_none() && self.scale.is_none() && self.background.is_none()
+    }
+}
+
+/// Advances an animation; returns its linear progress, or `None` once it has ended
+fn advance(elapsed: &mut f32, duration: f32, delta: f32, animate: bool) -> Option<f32> {
+    *elapsed += delta;
+    (animate && *elapsed < duration).then(|| *elapsed / duration)
+}
+
+/// The parts of a node the animations change
+type AnimatedNode<'a> = (
+    Entity,
+    Option<Mut<'a, Shake>>,
+    Option<Mut<'a, Pulse>>,
+    Option<Mut<'a, Flash>>,
+    Option<Mut<'a, Style>>,
+    Option<Mut<'a, Transform>>,
+    Option<Mut<'a, BackgroundColor>>,
+);
+
+/// System to play [`Shake`], [`Pulse`] and [`Flash`] animations
+///
+/// Runs in `PostUpdate` before layout, after every widget has set its colors
+/// for the frame, so a flash shows over them.
+pub fn attention_system(
+    mut commands: Commands,
+    clock: Res<UiClock>,
+    motion: UiMotion,
+    animated: Query<Entity, Or<(With<Shake>, With<Pulse>, With<Flash>)>>,
+    mut nodes: Query<AnimatedNode>,
+    mut resting: Local<HashMap<Entity, Resting>>,
+) {
+    let delta = clock.delta_seconds();
+    let animate = motion.animations();
+    // Nodes still holding resting values lost their animation some other way
+    let entities: Vec<Entity> = animated
+        .iter()
+        .chain(resting.keys().copied())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    for entity in entities {
+        let Ok((_, shake, pulse, flash, style, transform, background)) = nodes.get_mut(entity)
+        else {
+            resting.remove(&entity);
+            continue;
+        };
+        // Animations the node has nothing to play on are dropped
+        if shake.is_some() && style.is_none() {
+            commands.entity(entity).remove::<Shake>();
+        }
+        if pulse.is_some() && transform.is_none() {
+            commands.entity(entity).remove::<Pulse>();
+        }
+        if flash.is_some() && background.is_none() {
+            commands.entity(entity).remove::<Flash>();
+        }
+        let saved = resting.entry(entity).or_default();
+
+        if let Some(mut style) = style {
+            match shake {
+                Some(mut shake) => {
+                    let rest = *saved.left.get_or_insert(style.left);
+                    let duration = shake.duration;
+                    match advance(&mut shake.elapsed, duration, delta, animate) {
+                        Some(t) => {
+                            let offset = shake.amplitude
+                                * (1.0 - t)
+                                * (t * duration * shake.frequency * TAU).sin();
+                            style.left = match rest {
+                                Val::Px(left) => Val::Px(left + offset),
+                                Val::Auto => Val::Px(offset),
+                                other => other,
+                            };
+                        }
+                        None => {
+                            style.left = rest;
+                            saved.left = None;
+                            commands.entity(entity).remove::<Shake>();
+                        }
+                    }
+                }
+                None => {
+                    if let Some(rest) = saved.left.take() {
+                        style.left = rest;
+                    }
+                }
+            }
+        }
+
+        if let Some(mut transform) = transform {
+            match pulse {
+                Some(mut pulse) => {
+                    let rest = *saved.scale.get_or_insert(transform.scale);
+                    let duration = pulse.duration;
+                    match advance(&mut pulse.elapsed, duration, delta, animate) {
+                        Some(t) => {
+                            transform.scale = rest * (1.0 + (pulse.scale - 1.0) * (t * PI).sin());
+                        }
+                        None => {
+                            transform.scale = rest;
+                            saved.scale = None;
+                            commands.entity(entity).remove::<Pulse>();
+                        }
+                    }
+                }
+                None => {
+                    if let Some(rest) = saved.scale.take() {
+                        transform.scale = rest;
+                    }
+                }
+            }
+        }
+
+        if let Some(mut background) = background {
+            match flash {
+                Some(mut flash) => {
+                    let rest = *saved.background.get_or_insert(background.0);
+                    let duration = flash.duration;
+                    match advance(&mut flash.elapsed, duration, delta, animate) {
+                        Some(t) => background.0 = flash.color.mix(&rest, t * t),
+                        None => {
+                            background.0 = rest;
+                            saved.background = None;
+                            commands.entity(entity).remove::<Flash>();
+                        }
+                    }
+                }
+                None => {
+                    if let Some(rest) = saved.background.take() {
+                        background.0 = rest;
+                    }
+                }
+            }
+        }
+
+        if saved.is_empty() {
+            resting.remove(&entity);
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Shake>()
+        .register_type::<Pulse>()
+        .register_type::<Flash>()
+        .add_systems(
+            PostUpdate,
+            attention_system.before(bevy::ui::UiSystem::Layout),
+        );
+}
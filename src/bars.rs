@@ -0,0 +1,569 @@
+//! Progress bars and the bar widgets extending them
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::clock::UiClock;
+use crate::components::{ProgressBar, Tooltip, TooltipPosition};
+use crate::quality::UiMotion;
+use crate::utils::first_section;
+
+/// Child nodes of a [`ProgressBar`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum ProgressBarPart {
+    /// The filled portion of the track
+    Fill,
+    /// The percentage text
+    Label,
+}
+
+/// The fill node of a progress bar
+fn progress_fill_bundle() -> (NodeBundle, ProgressBarPart) {
+    (
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            ..default()
+        },
+        ProgressBarPart::Fill,
+    )
+}
+
+/// The percentage label of a progress bar
+fn progress_label_bundle() -> (TextBundle, ProgressBarPart) {
+    (
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: UiFonts::MEDIUM,
+                font_size: 14.0,
+                color: crate::colors::text::NORMAL,
+            },
+        ),
+        ProgressBarPart::Label,
+    )
+}
+
+/// Spawns a [`ProgressBar`] and returns its root entity
+///
+/// The root is the background track sized by `style`; the fill and
+/// percentage label are added as children and follow later edits to the
+/// component.
+pub fn spawn_progress_bar(commands: &mut Commands, bar: ProgressBar, style: Style) -> Entity {
+    let fill = commands.spawn(progress_fill_bundle()).id();
+    let label = commands.spawn(progress_label_bundle()).id();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    overflow: Overflow::clip(),
+                    ..style
+                },
+                ..default()
+            },
+            bar,
+        ))
+        .push_children(&[fill, label])
+        .id()
+}
+
+/// System to add the fill and label to progress bars built without them
+///
+/// Bars from [`spawn_progress_bar`] already have both; a bar assembled by
+/// hand gets the missing parts with a warning rather than drawing nothing.
+pub fn progress_bar_parts_system(
+    mut commands: Commands,
+    bars: Query<(Entity, Option<&Children>), Added<ProgressBar>>,
+    parts: Query<&ProgressBarPart>,
+) {
+    for (entity, children) in &bars {
+        let has = |wanted: ProgressBarPart| {
+            children.is_some_and(|children| parts.iter_many(children).any(|&part| part == wanted))
+        };
+        if !has(ProgressBarPart::Fill) {
+            warn!("Progress bar {entity} has no fill node, spawning one");
+            let fill = commands.spawn(progress_fill_bundle()).id();
+            // Behind any existing children, like the label
+            commands.entity(entity).insert_children(0, &[fill]);
+        }
+        if !has(ProgressBarPart::Label) {
+            warn!("Progress bar {entity} has no label node, spawning one");
+            let label = commands.spawn(progress_label_bundle()).id();
+            commands.entity(entity).add_child(label);
+        }
+    }
+}
+
+/// System to update progress bar colors, fill width and label
+pub fn update_progress_bars(
+    mut bars: Query<(Entity, Ref<ProgressBar>, &mut BackgroundColor, &Children)>,
+    mut parts: Query<
+        (&ProgressBarPart, &mut Style, Option<&mut BackgroundColor>, Option<&mut Text>),
+        Without<ProgressBar>,
+    >,
+) {
+    for (entity, bar, mut background, children) in &mut bars {
+        if !bar.is_changed() {
+            continue;
+        }
+        background.0 = bar.background_color;
+        let percent = (bar.value * 100.0).clamp(0.0, 100.0);
+
+        let mut bar_parts = parts.iter_many_mut(children);
+        while let Some((part, mut style, fill, text)) = bar_parts.fetch_next() {
+            match part {
+                ProgressBarPart::Fill => {
+                    style.width = Val::Percent(percent);
+                    if let Some(mut fill) = fill {
+                        fill.0 = bar.fill_color;
+                    }
+                }
+                ProgressBarPart::Label => {
+                    style.display = if bar.show_text { Display::Flex } else { Display::None };
+                    if let Some(mut text) = text {
+                        let value = format!("{percent:.0}%");
+                        match text.sections.first_mut() {
+                            Some(section) => section.value = value,
+                            None => {
+                                warn!(
+                                    "Progress bar {entity} label has no text section, adding one"
+                                );
+                                text.sections.push(TextSection::from(value));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A single colored segment of a [`StackedBar`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct BarSegment {
+    /// Segment value, in the same units as [`StackedBar::max`]
+    pub value: f32,
+    /// Segment fill color
+    pub color: Color,
+    /// Optional tooltip shown when hovering the segment
+    pub tooltip: Option<String>,
+}
+
+impl BarSegment {
+    /// Creates a segment without a tooltip
+    pub fn new(value: f32, color: Color) -> Self {
+        Self {
+            value,
+            color,
+            tooltip: None,
+        }
+    }
+
+    /// Sets the segment tooltip
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+}
+
+/// A progress bar made of multiple colored segments laid end to end
+///
+/// Useful for shield + health + armor, or resource breakdowns. Segment nodes
+/// are spawned as children and resize smoothly when values change.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct StackedBar {
+    /// Segments in display order
+    pub segments: Vec<BarSegment>,
+    /// Value corresponding to a full bar
+    pub max: f32,
+    /// Resize animation speed (higher is faster, 0 snaps immediately)
+    pub animation_speed: f32,
+}
+
+impl Default for StackedBar {
+    fn default() -> Self {
+        Self {
+            segments: Vec::new(),
+            max: 1.0,
+            animation_speed: 10.0,
+        }
+    }
+}
+
+impl StackedBar {
+    /// Returns the target width of each segment as a percentage of the bar
+    pub fn segment_percents(&self) -> impl Iterator<Item = f32> + '_ {
+        let max = self.max.max(f32::EPSILON);
+        let mut remaining = 100.0;
+        self.segments.iter().map(move |segment| {
+            let percent = (segment.value.max(0.0) / max * 100.0).min(remaining);
+            remaining -= percent;
+            percent
+        })
+    }
+}
+
+/// A segment node spawned under a [`StackedBar`]
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct StackedBarSegment {
+    /// Index into [`StackedBar::segments`]
+    pub index: usize,
+    /// Width currently displayed, as a percentage of the bar
+    pub displayed: f32,
+}
+
+/// Creates the container bundle for a stacked bar
+pub fn stacked_bar_bundle(style: Style) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            overflow: Overflow::clip(),
+            ..style
+        },
+        background_color: crate::colors::slider::BACKGROUND.into(),
+        ..default()
+    }
+}
+
+/// System to keep segment children in sync with their [`StackedBar`]
+pub fn stacked_bar_sync_system(
+    mut commands: Commands,
+    bars: Query<(Entity, &StackedBar, Option<&Children>), Changed<StackedBar>>,
+    mut segments: Query<(&StackedBarSegment, &mut BackgroundColor, Option<&mut Tooltip>)>,
+) {
+    for (entity, bar, children) in &bars {
+        let mut existing = 0;
+        for &child in children.into_iter().flatten() {
+            let Ok((segment, mut color, tooltip)) = segments.get_mut(child) else {
+                continue;
+            };
+            let Some(data) = bar.segments.get(segment.index) else {
+                commands.entity(child).despawn_recursive();
+                continue;
+            };
+            existing = existing.max(segment.index + 1);
+
+            if color.0 != data.color {
+                color.0 = data.color;
+            }
+            match (tooltip, &data.tooltip) {
+                (Some(mut tooltip), Some(text)) if tooltip.text != *text => {
+                    tooltip.text.clone_from(text);
+                }
+                (Some(_), None) => {
+                    commands.entity(child).remove::<Tooltip>();
+                }
+                (None, Some(text)) => {
+                    commands.entity(child).insert(segment_tooltip(text));
+                }
+                _ => {}
+            }
+        }
+
+        commands.entity(entity).with_children(|parent| {
+            for (index, data) in bar.segments.iter().enumerate().skip(existing) {
+                let mut segment = parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        background_color: data.color.into(),
+                        ..default()
+                    },
+                    Interaction::default(),
+                    StackedBarSegment {
+                        index,
+                        displayed: 0.0,
+                    },
+                ));
+                if let Some(text) = &data.tooltip {
+                    segment.insert(segment_tooltip(text));
+                }
+            }
+        });
+    }
+}
+
+fn segment_tooltip(text: &str) -> Tooltip {
+    Tooltip {
+        text: text.to_string(),
+        position: TooltipPosition::Top,
+        offset: 4.0,
+    }
+}
+
+/// System to animate segment widths toward their target values
+pub fn stacked_bar_animation_system(
+    clock: Res<UiClock>,
+    motion: UiMotion,
+    bars: Query<(&StackedBar, &Children)>,
+    mut segments: Query<(&mut StackedBarSegment, &mut Style)>,
+) {
+    for (bar, children) in &bars {
+        let targets: Vec<f32> = bar.segment_percents().collect();
+        let blend = if bar.animation_speed > 0.0 && motion.animations() {
+            1.0 - (-bar.animation_speed * clock.delta_seconds()).exp()
+        } else {
+            1.0
+        };
+
+        for &child in children.iter() {
+            let Ok((mut segment, mut style)) = segments.get_mut(child) else {
+                continue;
+            };
+            let Some(&target) = targets.get(segment.index) else {
+                continue;
+            };
+            if (segment.displayed - target).abs() < 0.01 {
+                if segment.displayed != target {
+                    segment.displayed = target;
+                    style.width = Val::Percent(target);
+                }
+                continue;
+            }
+            segment.displayed += (target - segment.displayed) * blend;
+            style.width = Val::Percent(segment.displayed);
+        }
+    }
+}
+
+/// How a [`ComparisonBar`] formats its signed delta label
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum DeltaLabel {
+    /// Relative change from the current value, e.g. `+15%`
+    #[default]
+    Percent,
+    /// Absolute change, e.g. `+3.0`
+    Absolute,
+    /// No label
+    Hidden,
+}
+
+/// A bar comparing a current value with a projected one
+///
+/// Shows the shared base, a delta segment in an accent color and a signed delta
+/// label. Used for upgrade previews in the tech tree and unit tooltips.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ComparisonBar {
+    /// Current value
+    pub current: f32,
+    /// Projected value after the change
+    pub projected: f32,
+    /// Value corresponding to a full bar
+    pub max: f32,
+    /// Color of the shared base segment
+    pub base_color: Color,
+    /// Delta segment color when the projected value is higher
+    pub increase_color: Color,
+    /// Delta segment color when the projected value is lower
+    pub decrease_color: Color,
+    /// Delta label format
+    pub label: DeltaLabel,
+}
+
+impl Default for ComparisonBar {
+    fn default() -> Self {
+        Self {
+            current: 0.0,
+            projected: 0.0,
+            max: 1.0,
+            base_color: crate::colors::slider::FOREGROUND,
+            increase_color: Color::srgb(0.3, 0.8, 0.3),
+            decrease_color: Color::srgb(0.9, 0.3, 0.3),
+            label: DeltaLabel::default(),
+        }
+    }
+}
+
+impl ComparisonBar {
+    /// Creates a comparison between `current` and `projected` on a `0..max` scale
+    pub fn new(current: f32, projected: f32, max: f32) -> Self {
+        Self {
+            current,
+            projected,
+            max,
+            ..default()
+        }
+    }
+
+    /// Returns the signed delta label text, or `None` when hidden
+    pub fn delta_text(&self) -> Option<String> {
+        let delta = self.projected - self.current;
+        match self.label {
+            DeltaLabel::Percent if self.current != 0.0 => {
+                Some(format!("{:+.0}%", delta / self.current.abs() * 100.0))
+            }
+            DeltaLabel::Percent | DeltaLabel::Absolute => Some(format!("{:+.1}", delta)),
+            DeltaLabel::Hidden => None,
+        }
+    }
+
+    /// Returns the accent color for the delta segment and label
+    pub fn delta_color(&self) -> Color {
+        if self.projected < self.current {
+            self.decrease_color
+        } else {
+            self.increase_color
+        }
+    }
+}
+
+/// Child parts spawned under a [`ComparisonBar`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum ComparisonBarPart {
+    /// Track containing the base and delta segments
+    Track,
+    /// Segment covering the smaller of current and projected
+    Base,
+    /// Segment covering the difference
+    Delta,
+    /// Signed delta label
+    Label,
+}
+
+/// Creates the container bundle for a comparison bar
+pub fn comparison_bar_bundle(style: Style) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.0),
+            ..style
+        },
+        ..default()
+    }
+}
+
+/// System to spawn the track, segments and label of new comparison bars
+pub fn comparison_bar_spawn_system(
+    mut commands: Commands,
+    bars: Query<Entity, Added<ComparisonBar>>,
+) {
+    for entity in &bars {
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_grow: 1.0,
+                            height: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Row,
+                            overflow: Overflow::clip(),
+                            ..default()
+                        },
+                        background_color: crate::colors::slider::BACKGROUND.into(),
+                        ..default()
+                    },
+                    ComparisonBarPart::Track,
+                ))
+                .with_children(|track| {
+                    for part in [ComparisonBarPart::Base, ComparisonBarPart::Delta] {
+                        track.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    height: Val::Percent(100.0),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            part,
+                        ));
+                    }
+                });
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: UiFonts::MEDIUM,
+                        font_size: 14.0,
+                        ..default()
+                    },
+                ),
+                ComparisonBarPart::Label,
+            ));
+        });
+    }
+}
+
+/// System to resize segments and refresh the label when values change
+pub fn comparison_bar_update_system(
+    bars: Query<(&ComparisonBar, &Children), Changed<ComparisonBar>>,
+    tracks: Query<&Children, With<ComparisonBarPart>>,
+    mut parts: Query<(
+        &ComparisonBarPart,
+        Option<&mut Style>,
+        Option<&mut BackgroundColor>,
+        Option<&mut Text>,
+    )>,
+) {
+    for (bar, children) in &bars {
+        let max = bar.max.max(f32::EPSILON);
+        let base = (bar.current.min(bar.projected) / max * 100.0).clamp(0.0, 100.0);
+        let delta = ((bar.projected - bar.current).abs() / max * 100.0).clamp(0.0, 100.0 - base);
+        let accent = bar.delta_color();
+
+        let nested = children
+            .iter()
+            .filter_map(|&child| tracks.get(child).ok())
+            .flat_map(|track| track.iter());
+        for &child in children.iter().chain(nested) {
+            let Ok((part, style, background, text)) = parts.get_mut(child) else {
+                continue;
+            };
+            match part {
+                ComparisonBarPart::Base => {
+                    if let (Some(mut style), Some(mut background)) = (style, background) {
+                        style.width = Val::Percent(base);
+                        background.0 = bar.base_color;
+                    }
+                }
+                ComparisonBarPart::Delta => {
+                    if let (Some(mut style), Some(mut background)) = (style, background) {
+                        style.width = Val::Percent(delta);
+                        background.0 = accent;
+                    }
+                }
+                ComparisonBarPart::Label => {
+                    if let Some(mut text) = text {
+                        let section = first_section(&mut text);
+                        section.value = bar.delta_text().unwrap_or_default();
+                        section.style.color = accent;
+                    }
+                }
+                ComparisonBarPart::Track => {}
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<ProgressBar>()
+        .register_type::<ProgressBarPart>()
+        .register_type::<StackedBar>()
+        .register_type::<StackedBarSegment>()
+        .register_type::<ComparisonBar>()
+        .register_type::<ComparisonBarPart>()
+        .add_systems(
+            Update,
+            (
+                (progress_bar_parts_system, update_progress_bars).chain(),
+                (stacked_bar_sync_system, stacked_bar_animation_system).chain(),
+                (comparison_bar_spawn_system, comparison_bar_update_system).chain(),
+            ),
+        );
+}
@@ -0,0 +1,316 @@
+//! Two-way binding between widgets and settings resources
+//!
+//! A [`Bind<R>`] on a slider, checkbox or dropdown links it to a field of the
+//! resource `R`, addressed by a reflection path such as `"audio.master"`.
+//! Changing the resource moves the widget, and interacting with the widget
+//! writes the new value back, so settings screens need no glue code:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{Bind, BindPlugin, SliderBuilder, Theme};
+//! #[derive(Resource, Reflect, Default)]
+//! struct AudioSettings {
+//!     master: f32,
+//! }
+//!
+//! fn setup(mut commands: Commands, theme: Res<Theme>) {
+//!     let slider = SliderBuilder::new(&theme).spawn(&mut commands);
+//!     commands.entity(slider).insert(Bind::<AudioSettings>::new("master"));
+//! }
+//!
+//! # let mut app = App::new();
+//! app.init_resource::<AudioSettings>()
+//!     .add_plugins(BindPlugin::<AudioSettings>::default());
+//! ```
+//!
+//! Sliders bind to numeric fields, checkboxes to `bool`, and dropdowns to an
+//! integer index, a `String` holding the option text, or a unit-variant enum
+//! whose variant names match the options. `Bind` goes on the widget entity
+//! itself, which for labeled builder widgets is the row's control child.
+
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, DynamicVariant, GetPath, ReflectRef};
+
+use crate::components::{Dropdown, DropdownEntry, UiCheckbox, UiSlider};
+use crate::pending_changes::{
+    pending_changes_banner_spawn_system, pending_changes_banner_system,
+    pending_changes_input_system, pending_changes_system, pending_confirmation_system,
+    ConfirmApply, PendingChangesPart, SettingsApplied, SettingsConfirmed, SettingsReverted,
+};
+use crate::theme::Theme;
+
+/// Links a widget to the field at `path` in the resource `R`
+#[derive(Component, Debug)]
+pub struct Bind<R: Resource> {
+    /// Reflection path of the bound field, such as `"video.fullscreen"`
+    pub path: String,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<R: Resource> Bind<R> {
+    /// Binds to the field at `path`
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Resource> Clone for Bind<R> {
+    fn clone(&self) -> Self {
+        Self::new(self.path.clone())
+    }
+}
+
+/// Reads a numeric field as `f32`
+fn read_number(field: &dyn Reflect) -> Option<f32> {
+    let any = field.as_any();
+    any.downcast_ref::<f32>()
+        .copied()
+        .or_else(|| any.downcast_ref::<f64>().map(|&v| v as f32))
+        .or_else(|| any.downcast_ref::<i32>().map(|&v| v as f32))
+        .or_else(|| any.downcast_ref::<u32>().map(|&v| v as f32))
+        .or_else(|| any.downcast_ref::<i64>().map(|&v| v as f32))
+        .or_else(|| any.downcast_ref::<u64>().map(|&v| v as f32))
+        .or_else(|| any.downcast_ref::<usize>().map(|&v| v as f32))
+        .or_else(|| any.downcast_ref::<u8>().map(|&v| v as f32))
+}
+
+/// Writes `value` into a numeric field, rounding for integers; false if the field isn't numeric
+fn write_number(field: &mut dyn Reflect, value: f32) -> bool {
+    let any = field.as_any_mut();
+    if let Some(v) = any.downcast_mut::<f32>() {
+        *v = value;
+    } else if let Some(v) = any.downcast_mut::<f64>() {
+        *v = value as f64;
+    } else if let Some(v) = any.downcast_mut::<i32>() {
+        *v = value.round() as i32;
+    } else if let Some(v) = any.downcast_mut::<u32>() {
+        *v = value.round() as u32;
+    } else if let Some(v) = any.downcast_mut::<i64>() {
+        *v = value.round() as i64;
+    } else if let Some(v) = any.downcast_mut::<u64>() {
+        *v = value.round() as u64;
+    } else if let Some(v) = any.downcast_mut::<usize>() {
+        *v = value.round() as usize;
+    } else if let Some(v) = any.downcast_mut::<u8>() {
+        *v = value.round() as u8;
+    } else {
+        return false;
+    }
+    true
+}
+
+/// Reads a dropdown field as an option index
+fn read_selection(field: &dyn Reflect, options: &[DropdownEntry]) -> Option<usize> {
+    let position = |name: &str| {
+        options
+            .iter()
+            .position(|option| option.text() == Some(name))
+    };
+    if let Some(text) = field.as_any().downcast_ref::<String>() {
+        return position(text);
+    }
+    if let ReflectRef::Enum(value) = field.reflect_ref() {
+        return position(value.variant_name());
+    }
+    read_number(field).map(|index| index as usize)
+}
+
+/// Writes option `index` into a dropdown field; false if the field can't hold it
+fn write_selection(field: &mut dyn Reflect, options: &[DropdownEntry], index: usize) -> bool {
+    let Some(option) = options.get(index).and_then(DropdownEntry::text) else {
+        return false;
+    };
+    if let Some(text) = field.as_any_mut().downcast_mut::<String>() {
+        option.clone_into(text);
+        return true;
+    }
+    if let ReflectRef::Enum(_) = field.reflect_ref() {
+        let variant = DynamicEnum::new(option, DynamicVariant::Unit);
+        return field.try_apply(&variant).is_ok();
+    }
+    write_number(field, index as f32)
+}
+
+/// System to write widget changes made by the player back to the resource
+///
+/// Widgets changed on the frame they or their [`Bind`] were added are
+/// skipped, so a freshly spawned widget doesn't overwrite the resource with
+/// its initial value.
+pub fn bind_widget_to_resource_system<R: Resource + Reflect>(
+    mut resource: ResMut<R>,
+    sliders: Query<(Ref<Bind<R>>, Ref<UiSlider>)>,
+    checkboxes: Query<(Ref<Bind<R>>, Ref<UiCheckbox>)>,
+    dropdowns: Query<(Ref<Bind<R>>, Ref<Dropdown>)>,
+) {
+    let edited =
+        |bind: &Ref<Bind<R>>, added: bool, changed: bool| changed && !added && !bind.is_added();
+    let mut write = |path: &str, write: &dyn Fn(&mut dyn Reflect) -> bool| {
+        let target = resource.bypass_change_detection();
+        let Ok(field) = target.reflect_path_mut(path) else {
+            warn!("no field at `{path}` to bind to");
+            return;
+        };
+        let before = field.clone_value();
+        if !write(field) {
+            warn!("field at `{path}` has the wrong type for its widget");
+            return;
+        }
+        if field.reflect_partial_eq(before.as_ref()) != Some(true) {
+            resource.set_changed();
+        }
+    };
+
+    for (bind, slider) in &sliders {
+        if edited(&bind, slider.is_added(), slider.is_changed()) {
+            write(&bind.path, &|field| write_number(field, slider.value));
+        }
+    }
+    for (bind, checkbox) in &checkboxes {
+        if edited(&bind, checkbox.is_added(), checkbox.is_changed()) {
+            write(
+                &bind.path,
+                &|field| match field.as_any_mut().downcast_mut::<bool>() {
+                    Some(value) => {
+                        *value = checkbox.checked;
+                        true
+                    }
+                    None => false,
+                },
+            );
+        }
+    }
+    for (bind, dropdown) in &dropdowns {
+        if edited(&bind, dropdown.is_added(), dropdown.is_changed()) {
+            write(&bind.path, &|field| {
+                write_selection(field, &dropdown.options, dropdown.selected_index)
+            });
+        }
+    }
+}
+
+/// Checks or unchecks a checkbox from code, with the same visuals as a
+/// checkbox toggled by the player
+pub(crate) fn set_checked(
+    checkbox: &mut Mut<UiCheckbox>,
+    background: &mut Mut<BackgroundColor>,
+    checked: bool,
+    theme: &Theme,
+) {
+    if checkbox.checked == checked {
+        return;
+    }
+    checkbox.checked = checked;
+    if !checkbox.disabled {
+        background.0 = if checked {
+            theme.button.pressed
+        } else {
+            theme.button.normal
+        };
+    }
+}
+
+/// System to move bound widgets when the resource changes or a binding is added
+pub fn bind_resource_to_widget_system<R: Resource + Reflect>(
+    resource: Res<R>,
+    theme: Res<Theme>,
+    mut sliders: Query<(Ref<Bind<R>>, &mut UiSlider)>,
+    mut checkboxes: Query<(Ref<Bind<R>>, &mut UiCheckbox, &mut BackgroundColor)>,
+    mut dropdowns: Query<(Ref<Bind<R>>, &mut Dropdown)>,
+) {
+    let changed = resource.is_changed();
+    let field = |bind: &Bind<R>| resource.reflect_path(bind.path.as_str()).ok();
+
+    for (bind, mut slider) in &mut sliders {
+        if !changed && !bind.is_added() {
+            continue;
+        }
+        if let Some(value) = field(&bind).and_then(read_number) {
+            if slider.value != value {
+                slider.value = value;
+            }
+        }
+    }
+    for (bind, mut checkbox, mut background) in &mut checkboxes {
+        if !changed && !bind.is_added() {
+            continue;
+        }
+        if let Some(&checked) = field(&bind).and_then(|field| field.as_any().downcast_ref::<bool>())
+        {
+            set_checked(&mut checkbox, &mut background, checked, &theme);
+        }
+    }
+    for (bind, mut dropdown) in &mut dropdowns {
+        if !changed && !bind.is_added() {
+            continue;
+        }
+        let selected = field(&bind).and_then(|field| read_selection(field, &dropdown.options));
+        let selectable = |&index: &usize| {
+            dropdown
+                .options
+                .get(index)
+                .is_some_and(DropdownEntry::is_selectable)
+        };
+        if let Some(selected) = selected.filter(selectable) {
+            if dropdown.selected_index != selected {
+                dropdown.selected_index = selected;
+            }
+        }
+    }
+}
+
+/// Syncs widgets carrying [`Bind<R>`] with the resource `R`
+///
+/// Also tracks the applied values of `R` for
+/// [`PendingChangesBanner<R>`](crate::PendingChangesBanner).
+pub struct BindPlugin<R> {
+    confirm_apply: Option<f32>,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<R> Default for BindPlugin<R> {
+    fn default() -> Self {
+        Self {
+            confirm_apply: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R> BindPlugin<R> {
+    /// Asks the player to keep values applied from a banner, reverting them
+    /// after `seconds` (see [`ConfirmApply`](crate::ConfirmApply))
+    pub fn confirm_apply(mut self, seconds: f32) -> Self {
+        self.confirm_apply = Some(seconds);
+        self
+    }
+}
+
+impl<R: Resource + Reflect> Plugin for BindPlugin<R> {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PendingChangesPart>()
+            .add_event::<SettingsApplied<R>>()
+            .add_event::<SettingsReverted<R>>()
+            .add_event::<SettingsConfirmed<R>>()
+            .add_systems(
+                Update,
+                (
+                    bind_widget_to_resource_system::<R>,
+                    pending_changes_input_system::<R>,
+                    pending_confirmation_system::<R>,
+                    bind_resource_to_widget_system::<R>,
+                    pending_changes_system::<R>,
+                    pending_changes_banner_spawn_system::<R>,
+                    pending_changes_banner_system::<R>,
+                )
+                    .chain(),
+            );
+        if let Some(seconds) = self.confirm_apply {
+            app.insert_resource(ConfirmApply::<R>::new(seconds));
+        }
+    }
+}
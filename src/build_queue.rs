@@ -0,0 +1,336 @@
+//! Production queues
+//!
+//! A [`BuildQueue`] shows the items a building is producing as a row of
+//! icons. The first item is the one in production and carries a
+//! [`ProgressBar`] following [`BuildQueue::progress`]. Each item has a small
+//! cancel button; clicking it, or right-clicking the item, sends
+//! [`QueueItemCancelled`]. When the queue is
+//! [`reorderable`](BuildQueue::reorderable), items can be dragged onto one
+//! another, which sends [`QueueItemMoved`].
+//!
+//! As with other HUD widgets the queue only reports what the player asked
+//! for: the game changes its production and writes the new
+//! [`items`](BuildQueue::items) back.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{build_queue_bundle, BuildQueue, QueueItem, QueueItemCancelled};
+//! fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     let queue = BuildQueue::new(vec![
+//!         QueueItem::new(asset_server.load("icons/worker.png")),
+//!         QueueItem::new(asset_server.load("icons/soldier.png")),
+//!     ]);
+//!     commands.spawn(build_queue_bundle(queue));
+//! }
+//!
+//! fn cancel(mut cancelled: EventReader<QueueItemCancelled>, mut queues: Query<&mut BuildQueue>) {
+//!     for event in cancelled.read() {
+//!         if let Ok(mut queue) = queues.get_mut(event.queue) {
+//!             queue.items.remove(event.index);
+//!         }
+//!     }
+//! }
+//! ```
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::bars::spawn_progress_bar;
+use crate::components::ProgressBar;
+use crate::drag::{Draggable, DropTarget, DroppedOn};
+use crate::interaction::{HitboxPadding, UiInteractionState};
+use crate::theme::Theme;
+
+/// [`Draggable::kind`] of build queue items
+pub const QUEUE_ITEM_DRAG_KIND: &str = "build_queue_item";
+
+/// An item waiting in a [`BuildQueue`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct QueueItem {
+    /// Icon of what is being produced
+    pub icon: Handle<Image>,
+}
+
+impl QueueItem {
+    /// Creates an item showing `icon`
+    pub fn new(icon: Handle<Image>) -> Self {
+        Self { icon }
+    }
+}
+
+/// A row of queued production items
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct BuildQueue {
+    /// Queued items; the first one is in production
+    pub items: Vec<QueueItem>,
+    /// Progress of the first item, from 0 to 1
+    pub progress: f32,
+    /// Whether items can be dragged to a new place
+    pub reorderable: bool,
+    /// Width and height of each item in pixels
+    pub item_size: f32,
+    /// Items the nodes were spawned for
+    #[reflect(ignore)]
+    built: Option<(Vec<QueueItem>, bool, f32)>,
+}
+
+impl BuildQueue {
+    /// Creates a reorderable queue of `items`
+    pub fn new(items: Vec<QueueItem>) -> Self {
+        Self {
+            items,
+            progress: 0.0,
+            reorderable: true,
+            item_size: 48.0,
+            built: None,
+        }
+    }
+
+    /// Turns drag reordering on or off
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+}
+
+/// What a [`BuildQueuePart`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum BuildQueuePartKind {
+    /// The item's icon button
+    Item,
+    /// The item's cancel button
+    Cancel,
+    /// The progress bar of the item in production
+    Progress,
+}
+
+/// A node spawned for one item of a [`BuildQueue`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct BuildQueuePart {
+    /// The queue this node belongs to
+    pub queue: Entity,
+    /// Index of the item in [`BuildQueue::items`]
+    pub index: usize,
+    /// What this node is
+    pub kind: BuildQueuePartKind,
+}
+
+/// Sent when the player cancels a queued item
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueItemCancelled {
+    /// The queue entity
+    pub queue: Entity,
+    /// Index of the item in [`BuildQueue::items`]
+    pub index: usize,
+}
+
+/// Sent when the player drags a queued item onto another one
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueItemMoved {
+    /// The queue entity
+    pub queue: Entity,
+    /// Index of the dragged item in [`BuildQueue::items`]
+    pub from: usize,
+    /// Index of the item it was dropped on, where it should go
+    pub to: usize,
+}
+
+/// Creates the container bundle for a build queue
+pub fn build_queue_bundle(queue: BuildQueue) -> (NodeBundle, BuildQueue) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(4.0)),
+                ..default()
+            },
+            ..default()
+        },
+        queue,
+    )
+}
+
+/// System to spawn the items of build queues, and rebuild them when the items
+/// change
+pub fn build_queue_spawn_system(
+    mut commands: Commands,
+    mut queues: Query<(Entity, &mut BuildQueue), Changed<BuildQueue>>,
+    theme: Res<Theme>,
+) {
+    for (entity, mut queue) in &mut queues {
+        let layout = (queue.items.clone(), queue.reorderable, queue.item_size);
+        if queue.built.as_ref() == Some(&layout) {
+            continue;
+        }
+        queue.bypass_change_detection().built = Some(layout);
+        commands.entity(entity).despawn_descendants();
+
+        let size = queue.item_size;
+        let part = |index, kind| BuildQueuePart {
+            queue: entity,
+            index,
+            kind,
+        };
+        for (index, item) in queue.items.iter().enumerate() {
+            let mut slot = commands.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Px(size),
+                        height: Val::Px(size),
+                        padding: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    background_color: theme.button.normal.into(),
+                    ..default()
+                },
+                Interaction::default(),
+                part(index, BuildQueuePartKind::Item),
+            ));
+            if queue.reorderable {
+                slot.insert((
+                    Draggable::new(QUEUE_ITEM_DRAG_KIND),
+                    DropTarget::accepting([QUEUE_ITEM_DRAG_KIND]),
+                ));
+            }
+            slot.with_children(|slot| {
+                slot.spawn(ImageBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    image: UiImage::new(item.icon.clone()),
+                    ..default()
+                });
+                slot.spawn((
+                    ButtonBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(0.0),
+                            right: Val::Px(0.0),
+                            width: Val::Px(14.0),
+                            height: Val::Px(14.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: theme.button.normal.into(),
+                        ..default()
+                    },
+                    HitboxPadding::all(Val::Px(4.0)),
+                    part(index, BuildQueuePartKind::Cancel),
+                ))
+                .with_children(|cancel| {
+                    cancel.spawn(TextBundle::from_section(
+                        "x",
+                        TextStyle {
+                            font: UiFonts::BOLD,
+                            font_size: 12.0,
+                            color: theme.text.normal,
+                        },
+                    ));
+                });
+            });
+            let slot = slot.id();
+
+            if index == 0 {
+                let bar = spawn_progress_bar(
+                    &mut commands,
+                    ProgressBar {
+                        value: queue.progress.clamp(0.0, 1.0),
+                        background_color: Color::BLACK.with_alpha(0.6),
+                        fill_color: theme.status.success,
+                        show_text: false,
+                    },
+                    Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(2.0),
+                        right: Val::Px(2.0),
+                        bottom: Val::Px(2.0),
+                        height: Val::Px(5.0),
+                        ..default()
+                    },
+                );
+                commands
+                    .entity(bar)
+                    .insert(part(index, BuildQueuePartKind::Progress));
+                commands.entity(slot).add_child(bar);
+            }
+            commands.entity(entity).add_child(slot);
+        }
+    }
+}
+
+/// System to move the progress bar of the item in production
+pub fn build_queue_progress_system(
+    queues: Query<&BuildQueue, Changed<BuildQueue>>,
+    mut bars: Query<(&BuildQueuePart, &mut ProgressBar)>,
+) {
+    for (part, mut bar) in &mut bars {
+        let Ok(queue) = queues.get(part.queue) else {
+            continue;
+        };
+        let progress = queue.progress.clamp(0.0, 1.0);
+        if bar.value != progress {
+            bar.value = progress;
+        }
+    }
+}
+
+/// System to report cancelled and reordered queue items
+pub fn build_queue_input_system(
+    parts: Query<(Entity, &BuildQueuePart, &Interaction)>,
+    interaction_state: Res<UiInteractionState>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut drops: EventReader<DroppedOn>,
+    mut cancelled_events: EventWriter<QueueItemCancelled>,
+    mut moved_events: EventWriter<QueueItemMoved>,
+) {
+    let right_click = mouse_buttons.just_pressed(MouseButton::Right);
+    for (entity, part, interaction) in &parts {
+        let cancelled = match part.kind {
+            BuildQueuePartKind::Cancel => interaction_state.just_pressed(entity),
+            BuildQueuePartKind::Item => right_click && *interaction != Interaction::None,
+            BuildQueuePartKind::Progress => false,
+        };
+        if cancelled {
+            cancelled_events.send(QueueItemCancelled {
+                queue: part.queue,
+                index: part.index,
+            });
+        }
+    }
+
+    for drop in drops.read() {
+        let (Ok((_, from, _)), Ok((_, to, _))) = (parts.get(drop.source), parts.get(drop.target))
+        else {
+            continue;
+        };
+        if from.queue == to.queue && from.index != to.index {
+            moved_events.send(QueueItemMoved {
+                queue: from.queue,
+                from: from.index,
+                to: to.index,
+            });
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<BuildQueue>()
+        .register_type::<BuildQueuePart>()
+        .add_event::<QueueItemCancelled>()
+        .add_event::<QueueItemMoved>()
+        .add_systems(
+            Update,
+            (
+                build_queue_spawn_system,
+                build_queue_progress_system,
+                build_queue_input_system,
+            )
+                .chain(),
+        );
+}
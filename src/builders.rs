@@ -0,0 +1,648 @@
+//! Builder-style widget construction
+//!
+//! The helpers in [`utils`](crate::utils) and [`bundles`](crate::bundles)
+//! return loose bundles that callers have to spawn and wire up themselves.
+//! The builders here spawn the complete widget hierarchy in one call, with
+//! the marker components, [`Focusable`], [`Tooltip`] and [`UiId`] already in
+//! place:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{Theme, UiButtonBuilder};
+//! fn setup(mut commands: Commands, theme: Res<Theme>) {
+//!     let quit = commands.register_one_shot_system(|mut exit: EventWriter<AppExit>| {
+//!         exit.send(AppExit::Success);
+//!     });
+//!     UiButtonBuilder::new(&theme)
+//!         .label("Quit")
+//!         .tooltip("Exit to desktop")
+//!         .on_click(quit)
+//!         .spawn(&mut commands);
+//! }
+//! ```
+//!
+//! Each `spawn` returns the root entity so it can be parented with
+//! [`BuildChildren::add_child`]. When a label is placed beside the widget
+//! (checkboxes and sliders) the root is the row holding both.
+
+use bevy::ecs::system::{EntityCommands, SystemId};
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::components::{
+    CheckmarkNode, Focusable, FocusState, FocusableType, SliderFill, SliderHandle, TabPane,
+    TabbedContainer, Tooltip, TooltipPosition, UiButton, UiCheckbox, UiId, UiSlider,
+};
+use crate::events::{UiButtonClicked, UiCheckboxToggled, UiSliderChanged};
+use crate::localization::{LocalizedText, LocalizedTooltip};
+use crate::skin::{SkinDefinition, WidgetSkin};
+use crate::slider_entry::slider_value_label_bundle;
+use crate::systems::slider_changed_event_system;
+use crate::tabs::{spawn_tab_buttons, tab_pane_bundle, tab_row_bundle, tab_strip_bundle};
+use crate::theme::Theme;
+use crate::tooltip::RichTooltip;
+
+/// Default slider track width in pixels
+const SLIDER_WIDTH: f32 = 200.0;
+
+/// Runs a one-shot system when the widget is clicked or toggled
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnClick(pub SystemId);
+
+/// Runs a one-shot system when the slider's value changes
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnChange(pub SystemId);
+
+/// Options shared by every widget builder
+#[derive(Debug, Clone, Default)]
+struct WidgetOptions {
+    label: Option<String>,
+    tooltip: Option<String>,
+    rich_tooltip: Option<RichTooltip>,
+    disabled: bool,
+    id: Option<UiId>,
+    skin: Option<Handle<SkinDefinition>>,
+    localized: bool,
+}
+
+impl WidgetOptions {
+    /// Components added to the interactive entity of every widget
+    fn insert_common(&self, entity: &mut EntityCommands, focus_type: FocusableType) {
+        entity.insert((
+            Interaction::default(),
+            Focusable {
+                state: FocusState::NotFocused,
+                focus_type,
+            },
+        ));
+        if let Some(tooltip) = &self.tooltip {
+            entity.insert(Tooltip {
+                text: tooltip.clone(),
+                position: TooltipPosition::Top,
+                offset: 4.0,
+            });
+            if self.localized {
+                entity.insert(LocalizedTooltip(tooltip.clone()));
+            }
+        }
+        if let Some(rich_tooltip) = &self.rich_tooltip {
+            entity.insert(rich_tooltip.clone());
+        }
+        if let Some(id) = &self.id {
+            entity.insert(id.clone());
+        }
+        if let Some(skin) = &self.skin {
+            entity.insert(WidgetSkin(skin.clone()));
+        }
+    }
+
+    /// Marks a spawned label for translation when the widget is localized
+    fn localize_label(&self, label: &mut EntityCommands, key: &str) {
+        if self.localized {
+            label.insert(LocalizedText(key.to_string()));
+        }
+    }
+
+    /// Label text bundle, dimmed when the widget is disabled
+    fn label_bundle(&self, text: &str, font: &Handle<Font>, font_size: f32, theme: &Theme) -> TextBundle {
+        TextBundle::from_section(
+            text,
+            TextStyle {
+                font: font.clone(),
+                font_size,
+                color: if self.disabled {
+                    theme.text.disabled
+                } else {
+                    theme.text.normal
+                },
+            },
+        )
+    }
+}
+
+/// Spawns a row laying out `children` left to right and returns it
+fn spawn_row(commands: &mut Commands, children: &[Entity]) -> Entity {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(8.0),
+                ..default()
+            },
+            ..default()
+        })
+        .push_children(children)
+        .id()
+}
+
+macro_rules! widget_options {
+    () => {
+        /// Sets the label text
+        pub fn label(mut self, label: impl Into<String>) -> Self {
+            self.options.label = Some(label.into());
+            self
+        }
+
+        /// Shows a tooltip while the widget is hovered
+        pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+            self.options.tooltip = Some(tooltip.into());
+            self
+        }
+
+        /// Shows a [`RichTooltip`](crate::RichTooltip) while the widget is
+        /// hovered, placed like [`tooltip`](Self::tooltip) text when both are set
+        pub fn rich_tooltip(mut self, tooltip: RichTooltip) -> Self {
+            self.options.rich_tooltip = Some(tooltip);
+            self
+        }
+
+        /// Sets whether the widget ignores input
+        pub fn disabled(mut self, disabled: bool) -> Self {
+            self.options.disabled = disabled;
+            self
+        }
+
+        /// Tags the widget with a [`UiId`] for the widget `SystemParam` helpers
+        pub fn id(mut self, id: impl Into<String>) -> Self {
+            self.options.id = Some(UiId::new(id));
+            self
+        }
+
+        /// Treats the label and tooltip as [`UiLocalizer`](crate::localization::UiLocalizer) keys
+        pub fn localized(mut self) -> Self {
+            self.options.localized = true;
+            self
+        }
+
+        /// Draws the widget with an image skin instead of flat colors
+        pub fn skin(mut self, skin: Handle<SkinDefinition>) -> Self {
+            self.options.skin = Some(skin);
+            self
+        }
+    };
+}
+
+/// Builds a button with a text label
+///
+/// The root is the button itself; it is the entity carried by
+/// [`UiButtonClicked`].
+#[derive(Debug, Clone)]
+pub struct UiButtonBuilder {
+    options: WidgetOptions,
+    on_click: Option<SystemId>,
+    font: Handle<Font>,
+    font_size: f32,
+    style: Style,
+    theme: Theme,
+}
+
+impl UiButtonBuilder {
+    /// Starts a button using colors from `theme`
+    pub fn new(theme: &Theme) -> Self {
+        Self {
+            options: WidgetOptions::default(),
+            on_click: None,
+            font: UiFonts::BOLD,
+            font_size: 24.0,
+            style: Style::default(),
+            theme: theme.clone(),
+        }
+    }
+
+    widget_options!();
+
+    /// Runs `system` each time the button is clicked
+    pub fn on_click(mut self, system: SystemId) -> Self {
+        self.on_click = Some(system);
+        self
+    }
+
+    /// Sets the label font size
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the button's layout style; alignment, padding and margin are set as in [`button_bundle`](crate::button_bundle)
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Spawns the button and its label, returning the button entity
+    pub fn spawn(self, commands: &mut Commands) -> Entity {
+        let Self {
+            options,
+            on_click,
+            font,
+            font_size,
+            style,
+            theme,
+        } = self;
+
+        let mut button = commands.spawn((
+            ButtonBundle {
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    margin: UiRect::all(Val::Px(5.0)),
+                    ..style
+                },
+                background_color: if options.disabled {
+                    theme.button.disabled.into()
+                } else {
+                    theme.button.normal.into()
+                },
+                ..default()
+            },
+            UiButton {
+                disabled: options.disabled,
+                tooltip: options.tooltip.clone(),
+                ..default()
+            },
+        ));
+        options.insert_common(&mut button, FocusableType::Button);
+        if let Some(system) = on_click {
+            button.insert(OnClick(system));
+        }
+        if let Some(label) = &options.label {
+            let bundle = options.label_bundle(label, &font, font_size, &theme);
+            button.with_children(|button| {
+                let mut label_entity = button.spawn(bundle);
+                options.localize_label(&mut label_entity, label);
+            });
+        }
+        button.id()
+    }
+}
+
+/// Builds a checkbox with an optional label to its right
+#[derive(Debug, Clone)]
+pub struct CheckboxBuilder {
+    options: WidgetOptions,
+    on_click: Option<SystemId>,
+    checked: bool,
+    font: Handle<Font>,
+    label_font: Handle<Font>,
+    theme: Theme,
+}
+
+impl CheckboxBuilder {
+    /// Starts an unchecked checkbox using colors from `theme`
+    pub fn new(theme: &Theme) -> Self {
+        Self {
+            options: WidgetOptions::default(),
+            on_click: None,
+            checked: false,
+            font: UiFonts::BOLD,
+            label_font: UiFonts::MEDIUM,
+            theme: theme.clone(),
+        }
+    }
+
+    widget_options!();
+
+    /// Sets the initial checked state
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// Runs `system` each time the checkbox is toggled
+    pub fn on_click(mut self, system: SystemId) -> Self {
+        self.on_click = Some(system);
+        self
+    }
+
+    /// Spawns the checkbox, returning the root entity
+    pub fn spawn(self, commands: &mut Commands) -> Entity {
+        let Self {
+            options,
+            on_click,
+            checked,
+            font,
+            label_font,
+            theme,
+        } = self;
+
+        let mark = commands
+            .spawn((
+                TextBundle::from_section(
+                    if checked { "X" } else { "" },
+                    TextStyle {
+                        font,
+                        font_size: 20.0,
+                        color: theme.text.normal,
+                    },
+                ),
+                CheckmarkNode,
+            ))
+            .id();
+        let mut checkbox = commands.spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Px(24.0),
+                    height: Val::Px(24.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(5.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                background_color: if options.disabled {
+                    theme.button.disabled.into()
+                } else if checked {
+                    theme.button.pressed.into()
+                } else {
+                    theme.button.normal.into()
+                },
+                border_color: theme.text.normal.into(),
+                ..default()
+            },
+            UiCheckbox {
+                checked,
+                disabled: options.disabled,
+                tooltip: options.tooltip.clone(),
+            },
+        ));
+        checkbox.add_child(mark);
+        options.insert_common(&mut checkbox, FocusableType::Checkbox);
+        if let Some(system) = on_click {
+            checkbox.insert(OnClick(system));
+        }
+        let checkbox = checkbox.id();
+
+        let Some(label) = &options.label else {
+            return checkbox;
+        };
+        let mut label_entity = commands.spawn(options.label_bundle(label, &label_font, 18.0, &theme));
+        options.localize_label(&mut label_entity, label);
+        let label = label_entity.id();
+        spawn_row(commands, &[checkbox, label])
+    }
+}
+
+/// Builds a slider with fill and handle, an optional label to its left and an
+/// optional value label to its right
+#[derive(Debug, Clone)]
+pub struct SliderBuilder {
+    options: WidgetOptions,
+    on_change: Option<SystemId>,
+    slider: UiSlider,
+    width: f32,
+    show_value: bool,
+    font: Handle<Font>,
+    theme: Theme,
+}
+
+impl SliderBuilder {
+    /// Starts a `0.0..=1.0` slider using colors from `theme`
+    pub fn new(theme: &Theme) -> Self {
+        Self {
+            options: WidgetOptions::default(),
+            on_change: None,
+            slider: UiSlider {
+                min: 0.0,
+                max: 1.0,
+                ..default()
+            },
+            width: SLIDER_WIDTH,
+            show_value: false,
+            font: UiFonts::MEDIUM,
+            theme: theme.clone(),
+        }
+    }
+
+    widget_options!();
+
+    /// Sets the value range
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.slider.min = min;
+        self.slider.max = max;
+        self
+    }
+
+    /// Sets the initial value
+    pub fn value(mut self, value: f32) -> Self {
+        self.slider.value = value;
+        self
+    }
+
+    /// Snaps values to multiples of `step`
+    pub fn step(mut self, step: f32) -> Self {
+        self.slider.step = Some(step);
+        self
+    }
+
+    /// Sets the display format string
+    pub fn format(mut self, format: impl Into<String>) -> Self {
+        self.slider.format = format.into();
+        self
+    }
+
+    /// Sets the track width in pixels
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Runs `system` each time the value changes
+    pub fn on_change(mut self, system: SystemId) -> Self {
+        self.on_change = Some(system);
+        self
+    }
+
+    /// Shows the value after the track; clicking it lets the player type a number
+    pub fn show_value(mut self) -> Self {
+        self.show_value = true;
+        self
+    }
+
+    /// Spawns the slider, returning the root entity
+    pub fn spawn(self, commands: &mut Commands) -> Entity {
+        let Self {
+            options,
+            on_change,
+            mut slider,
+            width,
+            show_value,
+            font,
+            theme,
+        } = self;
+
+        slider.value = slider.snap(slider.value);
+        slider.disabled = options.disabled;
+        let normalized = slider.normalized();
+        let palette = theme.slider;
+
+        let mut track = commands.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(width),
+                height: Val::Px(palette.height),
+                margin: UiRect::horizontal(Val::Px(10.0)),
+                ..default()
+            },
+            background_color: palette.background.into(),
+            ..default()
+        });
+        track.insert(slider).with_children(|track| {
+            track.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(normalized * 100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    background_color: palette.foreground.into(),
+                    ..default()
+                },
+                SliderFill,
+            ));
+            track.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(palette.handle_size),
+                        height: Val::Px(palette.handle_size),
+                        left: Val::Percent(normalized * 100.0),
+                        top: Val::Px((palette.height - palette.handle_size) / 2.0),
+                        margin: UiRect::left(Val::Px(-palette.handle_size / 2.0)),
+                        ..default()
+                    },
+                    background_color: if options.disabled {
+                        theme.button.disabled.into()
+                    } else {
+                        palette.handle.into()
+                    },
+                    ..default()
+                },
+                SliderHandle,
+            ));
+        });
+        options.insert_common(&mut track, FocusableType::Slider);
+        if let Some(system) = on_change {
+            track.insert(OnChange(system));
+        }
+        let track = track.id();
+
+        let mut row = Vec::new();
+        if let Some(label) = &options.label {
+            let mut label_entity = commands.spawn(options.label_bundle(label, &font, 18.0, &theme));
+            options.localize_label(&mut label_entity, label);
+            row.push(label_entity.id());
+        }
+        row.push(track);
+        if show_value {
+            row.push(commands.spawn(slider_value_label_bundle(track, font, &theme)).id());
+        }
+        match row.as_slice() {
+            [track] => *track,
+            _ => spawn_row(commands, &row),
+        }
+    }
+}
+
+/// Spawns a [`TabbedContainer`] with a tab bar and one empty pane per tab
+///
+/// Returns the container and the panes in tab order; spawn each tab's
+/// content as children of its pane. The first tab starts active.
+pub fn spawn_tabbed_container(
+    commands: &mut Commands,
+    theme: &Theme,
+    tabs: &[&str],
+) -> (Entity, Vec<Entity>) {
+    spawn_tabbed_container_with(commands, theme, TabbedContainer::new(tabs.iter().copied()))
+}
+
+/// Spawns `container` with a tab bar and one empty pane per tab
+///
+/// Like [`spawn_tabbed_container`], for containers with close buttons or
+/// drag reordering turned on.
+pub fn spawn_tabbed_container_with(
+    commands: &mut Commands,
+    theme: &Theme,
+    mut container: TabbedContainer,
+) -> (Entity, Vec<Entity>) {
+    let font = UiFonts::BOLD;
+    let tab_count = container.tabs.len();
+    container.built = Some((
+        container.tabs.clone(),
+        container.closable,
+        container.reorderable,
+    ));
+    let entity = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            Name::new("TabbedContainer"),
+        ))
+        .id();
+
+    let buttons = spawn_tab_buttons(commands, &font, theme, entity, &container);
+    let row = commands.spawn(tab_row_bundle()).push_children(&buttons).id();
+    let bar = commands.spawn(tab_strip_bundle(entity)).add_child(row).id();
+
+    let panes: Vec<_> = (0..tab_count)
+        .map(|index| {
+            let mut pane = tab_pane_bundle();
+            if index == container.active_tab {
+                pane.style.display = Display::Flex;
+            }
+            commands
+                .spawn((
+                    pane,
+                    TabPane {
+                        container: entity,
+                        index,
+                    },
+                ))
+                .id()
+        })
+        .collect();
+
+    commands
+        .entity(entity)
+        .insert(container)
+        .add_child(bar)
+        .push_children(&panes);
+    (entity, panes)
+}
+
+/// System to run [`OnClick`] and [`OnChange`] callbacks from widget events
+pub fn widget_callback_system(
+    mut commands: Commands,
+    mut clicked: EventReader<UiButtonClicked>,
+    mut toggled: EventReader<UiCheckboxToggled>,
+    mut changed: EventReader<UiSliderChanged>,
+    on_click: Query<&OnClick>,
+    on_change: Query<&OnChange>,
+) {
+    let clicked = clicked
+        .read()
+        .map(|event| event.0)
+        .chain(toggled.read().map(|event| event.entity));
+    for entity in clicked {
+        if let Ok(OnClick(system)) = on_click.get(entity) {
+            commands.run_system(*system);
+        }
+    }
+    for event in changed.read() {
+        if let Ok(OnChange(system)) = on_change.get(event.entity) {
+            commands.run_system(*system);
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        widget_callback_system.after(slider_changed_event_system),
+    );
+}
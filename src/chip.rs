@@ -0,0 +1,254 @@
+//! Status chips
+//!
+//! A [`StatusChip`] is a small pill label such as a lobby "Ready", a mod's
+//! "Outdated" or a server's "Full", colored by its [`StatusKind`] from the
+//! theme's [`StatusPalette`](crate::theme::StatusPalette). Spawn one with
+//! [`status_chip_bundle`]; the icon, label and dismiss button are added as
+//! children and follow later edits to the component.
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::interaction::UiInteractionState;
+use crate::theme::Theme;
+use crate::utils::first_section;
+
+/// Semantic meaning of a [`StatusChip`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum StatusKind {
+    /// Positive state
+    Success,
+    /// Needs attention
+    Warning,
+    /// Failure or blocked
+    Error,
+    /// Neutral information
+    #[default]
+    Info,
+}
+
+impl StatusKind {
+    /// Returns the theme color for this kind
+    pub fn color(self, theme: &Theme) -> Color {
+        match self {
+            StatusKind::Success => theme.status.success,
+            StatusKind::Warning => theme.status.warning,
+            StatusKind::Error => theme.status.error,
+            StatusKind::Info => theme.status.info,
+        }
+    }
+}
+
+/// A pill-shaped status label
+#[derive(Component, Debug, Clone, PartialEq, Reflect, Default)]
+#[reflect(Component)]
+pub struct StatusChip {
+    /// Label text
+    pub text: String,
+    /// Semantic color
+    pub kind: StatusKind,
+    /// Optional icon shown before the text
+    pub icon: Option<Handle<Image>>,
+    /// Whether to show a dismiss button
+    pub dismissible: bool,
+}
+
+impl StatusChip {
+    /// Creates a chip without icon or dismiss button
+    pub fn new(text: impl Into<String>, kind: StatusKind) -> Self {
+        Self {
+            text: text.into(),
+            kind,
+            ..default()
+        }
+    }
+
+    /// Shows an icon before the text
+    pub fn with_icon(mut self, icon: Handle<Image>) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Shows a dismiss button after the text
+    pub fn dismissible(mut self) -> Self {
+        self.dismissible = true;
+        self
+    }
+}
+
+/// Child nodes of a [`StatusChip`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum StatusChipPart {
+    /// The icon image
+    Icon,
+    /// The text label
+    Label,
+    /// The dismiss button
+    Dismiss,
+}
+
+/// Sent when a chip's dismiss button is pressed; the chip is despawned
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusChipDismissed {
+    /// The chip entity (already despawned when this is read)
+    pub chip: Entity,
+}
+
+/// Creates the container bundle for a status chip
+pub fn status_chip_bundle(chip: StatusChip) -> (NodeBundle, StatusChip) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(4.0),
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            border_radius: BorderRadius::MAX,
+            ..default()
+        },
+        chip,
+    )
+}
+
+/// System to spawn the icon, label and dismiss button of new chips
+pub fn status_chip_spawn_system(mut commands: Commands, chips: Query<Entity, Added<StatusChip>>) {
+    for entity in &chips {
+        commands.entity(entity).with_children(|chip| {
+            chip.spawn((
+                ImageBundle {
+                    style: Style {
+                        width: Val::Px(14.0),
+                        height: Val::Px(14.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                StatusChipPart::Icon,
+            ));
+            chip.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: UiFonts::MEDIUM,
+                        font_size: 14.0,
+                        ..default()
+                    },
+                ),
+                StatusChipPart::Label,
+            ));
+            chip.spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(14.0),
+                        height: Val::Px(14.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: Color::NONE.into(),
+                    ..default()
+                },
+                StatusChipPart::Dismiss,
+            ))
+            .with_children(|button| {
+                button.spawn(TextBundle::from_section(
+                    "x",
+                    TextStyle {
+                        font: UiFonts::BOLD,
+                        font_size: 12.0,
+                        ..default()
+                    },
+                ));
+            });
+        });
+    }
+}
+
+/// System to refresh chip content and colors when the chip or theme changes
+pub fn status_chip_update_system(
+    theme: Res<Theme>,
+    mut chips: Query<(Ref<StatusChip>, &Children, &mut BackgroundColor, &mut BorderColor)>,
+    mut parts: Query<(
+        &StatusChipPart,
+        &mut Style,
+        Option<&mut UiImage>,
+        Option<&mut Text>,
+        Option<&Children>,
+    )>,
+    mut glyphs: Query<&mut Text, Without<StatusChipPart>>,
+) {
+    for (chip, children, mut background, mut border) in &mut chips {
+        if !chip.is_changed() && !theme.is_changed() {
+            continue;
+        }
+        let accent = chip.kind.color(&theme);
+        background.0 = accent.with_alpha(0.2);
+        border.0 = accent;
+
+        for &child in children.iter() {
+            let Ok((part, mut style, image, text, part_children)) = parts.get_mut(child) else {
+                continue;
+            };
+            let shown = match part {
+                StatusChipPart::Icon => {
+                    if let (Some(mut image), Some(icon)) = (image, &chip.icon) {
+                        image.texture = icon.clone();
+                        image.color = accent;
+                    }
+                    chip.icon.is_some()
+                }
+                StatusChipPart::Label => {
+                    if let Some(mut text) = text {
+                        let section = first_section(&mut text);
+                        section.value.clone_from(&chip.text);
+                        section.style.color = theme.text.normal;
+                    }
+                    true
+                }
+                StatusChipPart::Dismiss => {
+                    for &glyph in part_children.into_iter().flatten() {
+                        if let Ok(mut text) = glyphs.get_mut(glyph) {
+                            first_section(&mut text).style.color = accent;
+                        }
+                    }
+                    chip.dismissible
+                }
+            };
+            style.display = if shown { Display::Flex } else { Display::None };
+        }
+    }
+}
+
+/// System to despawn chips whose dismiss button was pressed
+pub fn status_chip_dismiss_system(
+    mut commands: Commands,
+    buttons: Query<(Entity, &StatusChipPart, &Parent)>,
+    interaction_state: Res<UiInteractionState>,
+    mut dismissed: EventWriter<StatusChipDismissed>,
+) {
+    for (entity, part, parent) in &buttons {
+        if *part == StatusChipPart::Dismiss && interaction_state.just_pressed(entity) {
+            commands.entity(parent.get()).despawn_recursive();
+            dismissed.send(StatusChipDismissed { chip: parent.get() });
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<StatusChip>()
+        .register_type::<StatusChipPart>()
+        .add_event::<StatusChipDismissed>()
+        .add_systems(
+            Update,
+            (
+                status_chip_dismiss_system,
+                status_chip_spawn_system,
+                status_chip_update_system,
+            )
+                .chain(),
+        );
+}
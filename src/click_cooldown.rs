@@ -0,0 +1,120 @@
+//! Click cooldowns
+//!
+//! A widget with a [`ClickCooldown`] ignores presses for a while after it
+//! fires, so a double click or a frame-timing hiccup cannot submit "Join
+//! Game" or "Buy" twice. The block happens on the resolved interaction
+//! state, right after arbitration, so it covers every widget system and
+//! keyboard or gamepad activation alike. While cooling down the widget is
+//! drawn in the theme's disabled button color.
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{ClickCooldown, Theme, UiButtonBuilder};
+//! fn setup(mut commands: Commands, theme: Res<Theme>) {
+//!     let join = UiButtonBuilder::new(&theme)
+//!         .label("Join Game")
+//!         .spawn(&mut commands);
+//!     commands
+//!         .entity(join)
+//!         .insert(ClickCooldown(Duration::from_secs(1)));
+//! }
+//! ```
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::clock::UiClock;
+use crate::interaction::{InteractionArbitrationSet, UiInteractionState};
+use crate::theme::Theme;
+
+/// Keeps a widget from firing again within this long of its last press
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ClickCooldown(pub Duration);
+
+/// Time left before a [`ClickCooldown`] widget takes presses again
+///
+/// Added when the widget fires and removed once the time is up and the
+/// pointer has let go of it.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ClickCoolingDown(pub Duration);
+
+/// System to start cooldowns on press and hide presses during them
+///
+/// Runs after arbitration and activation, and removes cooling widgets from
+/// the frame's pressed and activated targets.
+pub fn click_cooldown_system(
+    mut commands: Commands,
+    mut widgets: Query<(
+        Entity,
+        &ClickCooldown,
+        Option<&mut ClickCoolingDown>,
+        &Interaction,
+    )>,
+    mut interaction_state: ResMut<UiInteractionState>,
+    clock: Res<UiClock>,
+) {
+    for (entity, cooldown, cooling, interaction) in &mut widgets {
+        let Some(mut cooling) = cooling else {
+            if interaction_state.just_pressed(entity) {
+                commands.entity(entity).insert(ClickCoolingDown(cooldown.0));
+            }
+            continue;
+        };
+        cooling.0 = cooling.0.saturating_sub(clock.delta());
+        // Holding the button past the cooldown must not fire it on expiry
+        if cooling.0.is_zero() && *interaction != Interaction::Pressed {
+            commands.entity(entity).remove::<ClickCoolingDown>();
+        }
+        let frame = &mut interaction_state.current;
+        if frame.pressed == Some(entity) {
+            frame.pressed = None;
+        }
+        if frame.activated == Some(entity) {
+            frame.activated = None;
+        }
+    }
+}
+
+/// System to draw cooling widgets in the disabled color
+///
+/// Runs after the generic button colors, which would otherwise restore the
+/// normal color on hover.
+pub fn click_cooldown_visual_system(
+    mut widgets: Query<
+        (&Interaction, &mut BackgroundColor, Has<ClickCoolingDown>),
+        With<ClickCooldown>,
+    >,
+    mut cooled: RemovedComponents<ClickCoolingDown>,
+    theme: Res<Theme>,
+) {
+    for (_, mut background, cooling) in &mut widgets {
+        if cooling && background.0 != theme.button.disabled {
+            background.0 = theme.button.disabled;
+        }
+    }
+    for entity in cooled.read() {
+        if let Ok((interaction, mut background, false)) = widgets.get_mut(entity) {
+            background.0 = theme.button_color(*interaction);
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<ClickCooldown>()
+        .register_type::<ClickCoolingDown>()
+        .add_systems(
+            PreUpdate,
+            click_cooldown_system
+                .after(InteractionArbitrationSet)
+                .after(crate::focus::focus_activation_system)
+                .after(crate::split_screen::hud_focus_activation_system),
+        )
+        .add_systems(
+            Update,
+            click_cooldown_visual_system.after(crate::button_interaction_system),
+        );
+}
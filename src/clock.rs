@@ -0,0 +1,78 @@
+//! UI time source independent of game time
+//!
+//! Animation, tween and timer systems read [`UiClock`] instead of `Time`, so
+//! menus keep animating while the simulation's virtual time is paused.
+
+use bevy::prelude::*;
+use bevy::time::TimeSystem;
+use std::time::Duration;
+
+/// Real-time based clock for UI animations
+///
+/// Advances from `Time<Real>` every frame, scaled by [`UiClock::scale`]. Pausing
+/// virtual time does not affect it; set `paused` to freeze UI animations
+/// explicitly.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct UiClock {
+    /// Multiplier applied to real time (1.0 is real time)
+    pub scale: f32,
+    /// Whether the UI clock is paused
+    pub paused: bool,
+    delta: Duration,
+    elapsed: Duration,
+}
+
+impl Default for UiClock {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            paused: false,
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl UiClock {
+    /// Time elapsed since the last frame
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Time elapsed since the last frame, in seconds
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Total UI time elapsed
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Total UI time elapsed, in seconds
+    pub fn elapsed_seconds(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    /// Advances the clock by a real-time delta
+    pub fn advance(&mut self, real_delta: Duration) {
+        self.delta = if self.paused {
+            Duration::ZERO
+        } else {
+            real_delta.mul_f32(self.scale.max(0.0))
+        };
+        self.elapsed += self.delta;
+    }
+}
+
+/// System to advance the UI clock from real time
+pub fn ui_clock_system(real_time: Res<Time<Real>>, mut clock: ResMut<UiClock>) {
+    clock.advance(real_time.delta());
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiClock>()
+        .init_resource::<UiClock>()
+        .add_systems(First, ui_clock_system.after(TimeSystem));
+}
@@ -0,0 +1,468 @@
+//! Command cards
+//!
+//! A [`CommandCard`] is the grid of ability and build buttons in an RTS HUD.
+//! Each cell holds an optional [`CommandSlot`] with an icon, hotkey, tooltip
+//! and cooldown. Clicking a ready slot, or pressing its hotkey, sends
+//! [`CommandIssued`]. Slots the player cannot afford are greyed out, and a
+//! slot on cooldown is covered by a dark overlay that drains toward the
+//! bottom as the cooldown runs out.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{command_card_bundle, CommandCard, CommandIssued, CommandSlot};
+//! fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     let card = CommandCard::new(4, 3)
+//!         .with_slot(
+//!             0,
+//!             CommandSlot::new("Move")
+//!                 .with_icon(asset_server.load("icons/move.png"))
+//!                 .with_hotkey(KeyCode::KeyM),
+//!         )
+//!         .with_slot(1, CommandSlot::new("Stop").with_hotkey(KeyCode::KeyS));
+//!     commands.spawn(command_card_bundle(card));
+//! }
+//!
+//! fn issue(mut issued: EventReader<CommandIssued>) {
+//!     for command in issued.read() {
+//!         info!("slot {} pressed", command.slot);
+//!     }
+//! }
+//! ```
+//!
+//! The game owns the slots: it updates [`CommandSlot::cooldown`] and
+//! [`CommandSlot::affordable`] as they change, and the card redraws in place.
+//! Only resizing the grid or its buttons rebuilds them.
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::components::{Tooltip, TooltipPosition};
+use crate::interaction::UiInteractionState;
+use crate::keybind::InputBinding;
+use crate::theme::Theme;
+use crate::utils::first_section;
+
+/// An ability or build action in a [`CommandCard`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct CommandSlot {
+    /// Text shown when there is no icon
+    pub label: String,
+    /// Icon filling the button
+    pub icon: Option<Handle<Image>>,
+    /// Key that issues the command, shown in the button's corner
+    pub hotkey: Option<KeyCode>,
+    /// Tooltip text
+    pub tooltip: Option<String>,
+    /// Fraction of the cooldown left, from 1 when just used to 0 when ready
+    pub cooldown: f32,
+    /// Whether the player can pay for the command
+    pub affordable: bool,
+}
+
+impl CommandSlot {
+    /// A ready, affordable slot showing `label`
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            icon: None,
+            hotkey: None,
+            tooltip: None,
+            cooldown: 0.0,
+            affordable: true,
+        }
+    }
+
+    /// Sets the icon
+    pub fn with_icon(mut self, icon: Handle<Image>) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Sets the hotkey
+    pub fn with_hotkey(mut self, hotkey: KeyCode) -> Self {
+        self.hotkey = Some(hotkey);
+        self
+    }
+
+    /// Sets the tooltip
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Whether the command can be issued now
+    pub fn is_ready(&self) -> bool {
+        self.affordable && self.cooldown <= 0.0
+    }
+}
+
+/// A grid of command buttons
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct CommandCard {
+    /// Slots in row-major order; `None` leaves the cell empty
+    pub slots: Vec<Option<CommandSlot>>,
+    /// Buttons per row
+    pub columns: usize,
+    /// Width and height of each button in pixels
+    pub slot_size: f32,
+    /// Grid the buttons were spawned for, as `(columns, slots, slot size)`
+    #[reflect(ignore)]
+    built: Option<(usize, usize, f32)>,
+}
+
+impl CommandCard {
+    /// Creates an empty `columns` by `rows` card
+    pub fn new(columns: usize, rows: usize) -> Self {
+        let columns = columns.max(1);
+        Self {
+            slots: vec![None; columns * rows.max(1)],
+            columns,
+            slot_size: 56.0,
+            built: None,
+        }
+    }
+
+    /// Puts `slot` in the cell at `index`, counted row by row
+    ///
+    /// Indices past the end grow the card.
+    pub fn with_slot(mut self, index: usize, slot: CommandSlot) -> Self {
+        if index >= self.slots.len() {
+            self.slots.resize(index + 1, None);
+        }
+        self.slots[index] = Some(slot);
+        self
+    }
+
+    /// The slot in the cell at `index`, if any
+    pub fn slot(&self, index: usize) -> Option<&CommandSlot> {
+        self.slots.get(index).and_then(Option::as_ref)
+    }
+}
+
+/// What a [`CommandCardPart`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum CommandCardPartKind {
+    /// The button
+    Button,
+    /// The icon image
+    Icon,
+    /// The label, shown without an icon
+    Label,
+    /// The hotkey text in the corner
+    Hotkey,
+    /// The cooldown overlay
+    Cooldown,
+}
+
+/// A node spawned for one cell of a [`CommandCard`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct CommandCardPart {
+    /// The card this node belongs to
+    pub card: Entity,
+    /// Index of the cell in [`CommandCard::slots`]
+    pub slot: usize,
+    /// What this node is
+    pub kind: CommandCardPartKind,
+}
+
+/// Sent when a ready command is clicked or its hotkey pressed
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandIssued {
+    /// The card entity
+    pub card: Entity,
+    /// Index of the slot in [`CommandCard::slots`]
+    pub slot: usize,
+}
+
+/// Creates the container bundle for a command card
+pub fn command_card_bundle(card: CommandCard) -> (NodeBundle, CommandCard) {
+    (
+        NodeBundle {
+            style: Style {
+                display: Display::Grid,
+                column_gap: Val::Px(4.0),
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(4.0)),
+                ..default()
+            },
+            ..default()
+        },
+        card,
+    )
+}
+
+/// System to spawn the buttons of command cards, and rebuild them when the
+/// grid or button size changes
+///
+/// The buttons start blank; [`command_card_visual_system`] fills them in.
+pub fn command_card_spawn_system(
+    mut commands: Commands,
+    mut cards: Query<(Entity, &mut CommandCard, &mut Style), Changed<CommandCard>>,
+    theme: Res<Theme>,
+) {
+    for (entity, mut card, mut style) in &mut cards {
+        let layout = (card.columns, card.slots.len(), card.slot_size);
+        if card.built == Some(layout) {
+            continue;
+        }
+        card.bypass_change_detection().built = Some(layout);
+        let size = card.slot_size;
+        style.grid_template_columns = vec![RepeatedGridTrack::px(card.columns as u16, size)];
+        style.grid_auto_rows = vec![GridTrack::px(size)];
+
+        let part = |slot, kind| CommandCardPart {
+            card: entity,
+            slot,
+            kind,
+        };
+        commands
+            .entity(entity)
+            .despawn_descendants()
+            .with_children(|grid| {
+                for slot in 0..card.slots.len() {
+                    grid.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Px(size),
+                                height: Val::Px(size),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                padding: UiRect::all(Val::Px(2.0)),
+                                overflow: Overflow::clip(),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        Interaction::default(),
+                        part(slot, CommandCardPartKind::Button),
+                    ))
+                    .with_children(|button| {
+                        button.spawn((
+                            ImageBundle {
+                                style: Style {
+                                    width: Val::Percent(100.0),
+                                    height: Val::Percent(100.0),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            part(slot, CommandCardPartKind::Icon),
+                        ));
+                        button.spawn((
+                            TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font: UiFonts::MEDIUM,
+                                    font_size: 13.0,
+                                    color: theme.text.normal,
+                                },
+                            )
+                            .with_style(Style {
+                                position_type: PositionType::Absolute,
+                                ..default()
+                            }),
+                            part(slot, CommandCardPartKind::Label),
+                        ));
+                        button.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    left: Val::Px(0.0),
+                                    bottom: Val::Px(0.0),
+                                    width: Val::Percent(100.0),
+                                    ..default()
+                                },
+                                background_color: Color::BLACK.with_alpha(0.6).into(),
+                                ..default()
+                            },
+                            part(slot, CommandCardPartKind::Cooldown),
+                        ));
+                        button.spawn((
+                            TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font: UiFonts::BOLD,
+                                    font_size: 12.0,
+                                    color: theme.text.normal,
+                                },
+                            )
+                            .with_style(Style {
+                                position_type: PositionType::Absolute,
+                                top: Val::Px(2.0),
+                                right: Val::Px(4.0),
+                                ..default()
+                            }),
+                            part(slot, CommandCardPartKind::Hotkey),
+                        ));
+                    });
+                }
+            });
+    }
+}
+
+/// System to send [`CommandIssued`] for clicked buttons and pressed hotkeys
+///
+/// Empty cells and slots that are unaffordable or cooling down ignore both.
+pub fn command_card_input_system(
+    cards: Query<(Entity, &CommandCard)>,
+    parts: Query<(Entity, &CommandCardPart)>,
+    interaction_state: Res<UiInteractionState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut issued_events: EventWriter<CommandIssued>,
+) {
+    for (entity, part) in &parts {
+        if part.kind != CommandCardPartKind::Button || !interaction_state.just_pressed(entity) {
+            continue;
+        }
+        let ready = cards
+            .get(part.card)
+            .is_ok_and(|(_, card)| card.slot(part.slot).is_some_and(CommandSlot::is_ready));
+        if ready {
+            issued_events.send(CommandIssued {
+                card: part.card,
+                slot: part.slot,
+            });
+        }
+    }
+
+    for (entity, card) in &cards {
+        for (slot, command) in card.slots.iter().enumerate() {
+            let Some(command) = command.as_ref().filter(|command| command.is_ready()) else {
+                continue;
+            };
+            if command
+                .hotkey
+                .is_some_and(|key| keyboard_input.just_pressed(key))
+            {
+                issued_events.send(CommandIssued { card: entity, slot });
+            }
+        }
+    }
+}
+
+/// The parts of a command card cell its visuals change
+type CommandCardLook<'a> = (
+    Entity,
+    &'a CommandCardPart,
+    Option<Ref<'a, Interaction>>,
+    &'a mut Style,
+    Option<&'a mut BackgroundColor>,
+    Option<&'a mut UiImage>,
+    Option<&'a mut Text>,
+    Option<&'a Tooltip>,
+);
+
+/// System to draw command card cells from their slots
+///
+/// Runs after the generic button colors so unaffordable and empty cells keep
+/// their own background when hovered.
+pub fn command_card_visual_system(
+    mut commands: Commands,
+    cards: Query<Ref<CommandCard>>,
+    mut parts: Query<CommandCardLook>,
+    theme: Res<Theme>,
+) {
+    for (entity, part, interaction, mut style, background, image, text, tooltip) in &mut parts {
+        let Ok(card) = cards.get(part.card) else {
+            continue;
+        };
+        let interaction_changed = interaction.as_ref().is_some_and(Ref::is_changed);
+        if !card.is_changed() && !interaction_changed && !theme.is_changed() {
+            continue;
+        }
+        let slot = card.slot(part.slot);
+        let affordable = slot.is_some_and(|slot| slot.affordable);
+        let text_color = if affordable {
+            theme.text.normal
+        } else {
+            theme.text.disabled
+        };
+        match part.kind {
+            CommandCardPartKind::Button => {
+                let color = match (slot, interaction) {
+                    (None, _) => theme.button.normal.with_alpha(0.3),
+                    (Some(slot), _) if !slot.affordable => theme.button.disabled,
+                    (Some(_), Some(interaction)) => theme.button_color(*interaction),
+                    (Some(_), None) => theme.button.normal,
+                };
+                if let Some(mut background) = background {
+                    background.0 = color;
+                }
+                let wanted = slot.and_then(|slot| slot.tooltip.as_deref());
+                match (wanted, tooltip) {
+                    (Some(wanted), Some(tooltip)) if tooltip.text == wanted => {}
+                    (Some(text), _) => {
+                        commands.entity(entity).insert(Tooltip {
+                            text: text.to_string(),
+                            position: TooltipPosition::Top,
+                            offset: 4.0,
+                        });
+                    }
+                    (None, Some(_)) => {
+                        commands.entity(entity).remove::<Tooltip>();
+                    }
+                    (None, None) => {}
+                }
+            }
+            CommandCardPartKind::Icon => {
+                let icon = slot.and_then(|slot| slot.icon.clone());
+                style.display = if icon.is_some() {
+                    Display::Flex
+                } else {
+                    Display::None
+                };
+                if let Some(mut image) = image {
+                    image.texture = icon.unwrap_or_default();
+                    image.color = if affordable {
+                        Color::WHITE
+                    } else {
+                        Color::srgb(0.4, 0.4, 0.4)
+                    };
+                }
+            }
+            CommandCardPartKind::Label | CommandCardPartKind::Hotkey => {
+                let value = match (part.kind, slot) {
+                    (CommandCardPartKind::Label, Some(slot)) if slot.icon.is_none() => {
+                        slot.label.clone()
+                    }
+                    (CommandCardPartKind::Hotkey, Some(slot)) => slot
+                        .hotkey
+                        .map_or_else(String::new, |key| InputBinding::Key(key).name()),
+                    _ => String::new(),
+                };
+                let Some(mut text) = text else {
+                    continue;
+                };
+                let stale = text.sections.first().is_none_or(|section| {
+                    section.value != value || section.style.color != text_color
+                });
+                if stale {
+                    let section = first_section(&mut text);
+                    section.value = value;
+                    section.style.color = text_color;
+                }
+            }
+            CommandCardPartKind::Cooldown => {
+                let cooldown = slot.map_or(0.0, |slot| slot.cooldown.clamp(0.0, 1.0));
+                style.height = Val::Percent(cooldown * 100.0);
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<CommandCard>()
+        .register_type::<CommandCardPart>()
+        .add_event::<CommandIssued>()
+        .add_systems(
+            Update,
+            (
+                command_card_spawn_system,
+                command_card_input_system,
+                command_card_visual_system.after(crate::button_interaction_system),
+            )
+                .chain(),
+        );
+}
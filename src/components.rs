@@ -2,6 +2,30 @@
 
 use bevy::prelude::*;
 
+use crate::state_machine::{UiState, UiStateMachine};
+
+/// Stable identifier for looking up a widget by name
+///
+/// Used by the widget [`SystemParam`](bevy::ecs::system::SystemParam) helpers
+/// such as [`Buttons`](crate::params::Buttons) so gameplay code can refer to
+/// widgets without holding on to their entities.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct UiId(pub String);
+
+impl UiId {
+    /// Creates an identifier from a string
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+impl From<&str> for UiId {
+    fn from(id: &str) -> Self {
+        Self::new(id)
+    }
+}
+
 /// A UI button component with visual states
 #[derive(Component, Debug, Clone, Reflect, Default)]
 #[reflect(Component)]
@@ -46,6 +70,107 @@ pub struct UiSlider {
     pub disabled: bool,
 }
 
+impl UiSlider {
+    /// Returns the value mapped to `0.0..=1.0`
+    ///
+    /// When no range is configured (`max <= min`) the value is treated as
+    /// already normalized.
+    pub fn normalized(&self) -> f32 {
+        if self.max > self.min {
+            ((self.value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            self.value.clamp(0.0, 1.0)
+        }
+    }
+
+    /// Converts a normalized position into a value, honoring `step`
+    pub fn value_at(&self, normalized: f32) -> f32 {
+        let (min, max) = self.range();
+        self.snap(min + normalized.clamp(0.0, 1.0) * (max - min))
+    }
+
+    /// Clamps `value` to the slider's range and snaps it to `step`
+    pub fn snap(&self, value: f32) -> f32 {
+        let (min, max) = self.range();
+        match self.step {
+            Some(step) if step > 0.0 => (min + ((value - min) / step).round() * step).clamp(min, max),
+            _ => value.clamp(min, max),
+        }
+    }
+
+    /// Formats `value` as the number in [`format`](Self::format)
+    ///
+    /// A `{:.N}` placeholder gives `N` decimals; `{}` or an empty format
+    /// prints the value as is.
+    pub fn format_number(&self, value: f32) -> String {
+        let precision = self
+            .format
+            .split_once('{')
+            .and_then(|(_, rest)| rest.split_once('}'))
+            .and_then(|(spec, _)| spec.strip_prefix(":."))
+            .and_then(|precision| precision.parse::<usize>().ok());
+        match precision {
+            Some(precision) => format!("{value:.precision$}"),
+            None => value.to_string(),
+        }
+    }
+
+    /// The current value as text, following [`format`](Self::format)
+    pub fn display_value(&self) -> String {
+        let number = self.format_number(self.value);
+        match self
+            .format
+            .split_once('{')
+            .and_then(|(prefix, rest)| Some((prefix, rest.split_once('}')?.1)))
+        {
+            Some((prefix, suffix)) => format!("{prefix}{number}{suffix}"),
+            None => number,
+        }
+    }
+
+    /// Returns the effective `(min, max)` range, defaulting to `0.0..=1.0`
+    fn range(&self) -> (f32, f32) {
+        if self.max > self.min {
+            (self.min, self.max)
+        } else {
+            (0.0, 1.0)
+        }
+    }
+}
+
+/// Query data reading the `disabled` flag of whichever widget an entity is
+///
+/// Pass the query item to [`is_disabled`].
+pub type WidgetDisabled = (
+    Option<&'static UiButton>,
+    Option<&'static UiCheckbox>,
+    Option<&'static UiSlider>,
+);
+
+/// Whether a button, checkbox or slider read through [`WidgetDisabled`] is disabled
+pub fn is_disabled(
+    (button, checkbox, slider): (Option<&UiButton>, Option<&UiCheckbox>, Option<&UiSlider>),
+) -> bool {
+    button.is_some_and(|button| button.disabled)
+        || checkbox.is_some_and(|checkbox| checkbox.disabled)
+        || slider.is_some_and(|slider| slider.disabled)
+}
+
+/// Marker for the text node showing a [`UiCheckbox`]'s check mark
+#[derive(Component, Debug, Clone, Copy, Reflect, Default)]
+#[reflect(Component)]
+pub struct CheckmarkNode;
+
+/// Marker for the fill node inside a [`UiSlider`]
+#[derive(Component, Debug, Clone, Copy, Reflect, Default)]
+#[reflect(Component)]
+pub struct SliderFill;
+
+/// Marker for the draggable handle node inside a [`UiSlider`]
+#[derive(Component, Debug, Clone, Copy, Reflect, Default)]
+#[reflect(Component)]
+pub struct SliderHandle;
+
 /// A dropdown menu component
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
@@ -160,6 +285,8 @@ pub enum FocusableType {
     /// Dropdown selector
     #[default]
     Dropdown,
+    /// List view
+    List,
 }
 
 /// Component for setting rows in configuration menus
@@ -172,16 +299,125 @@ pub struct SettingRow {
     pub help_text: Option<String>,
 }
 
+/// One entry in a [`Dropdown`]'s list
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub enum DropdownEntry {
+    /// A selectable option
+    Item {
+        /// Option text, also shown in the dropdown's label when selected
+        text: String,
+        /// Disabled options are dimmed and can't be selected
+        disabled: bool,
+        /// Icon drawn before the text
+        icon: Option<Handle<Image>>,
+        /// Tooltip shown while the option is hovered
+        tooltip: Option<String>,
+    },
+    /// A non-selectable group title
+    Header(String),
+    /// A thin line between groups
+    Separator,
+}
+
+impl DropdownEntry {
+    /// Creates an enabled option without icon or tooltip
+    pub fn item(text: impl Into<String>) -> Self {
+        DropdownEntry::Item {
+            text: text.into(),
+            disabled: false,
+            icon: None,
+            tooltip: None,
+        }
+    }
+
+    /// Creates a group header
+    pub fn header(text: impl Into<String>) -> Self {
+        DropdownEntry::Header(text.into())
+    }
+
+    /// Marks an option as disabled
+    pub fn disabled(mut self) -> Self {
+        if let DropdownEntry::Item { disabled, .. } = &mut self {
+            *disabled = true;
+        }
+        self
+    }
+
+    /// Sets an option's icon
+    pub fn with_icon(mut self, image: Handle<Image>) -> Self {
+        if let DropdownEntry::Item { icon, .. } = &mut self {
+            *icon = Some(image);
+        }
+        self
+    }
+
+    /// Sets an option's tooltip
+    pub fn with_tooltip(mut self, text: impl Into<String>) -> Self {
+        if let DropdownEntry::Item { tooltip, .. } = &mut self {
+            *tooltip = Some(text.into());
+        }
+        self
+    }
+
+    /// The option text; `None` for headers and separators
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            DropdownEntry::Item { text, .. } => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Whether the entry is an enabled option
+    pub fn is_selectable(&self) -> bool {
+        matches!(self, DropdownEntry::Item { disabled: false, .. })
+    }
+}
+
+impl From<&str> for DropdownEntry {
+    fn from(text: &str) -> Self {
+        Self::item(text)
+    }
+}
+
+impl From<String> for DropdownEntry {
+    fn from(text: String) -> Self {
+        Self::item(text)
+    }
+}
+
+/// Whether a [`Dropdown`]'s list is showing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum DropdownState {
+    /// Only the selected option is shown
+    #[default]
+    Closed,
+    /// The list of options is shown
+    Open,
+}
+
+/// Dropdowns move between closed and open
+impl UiState for DropdownState {
+    const TRANSITIONS: &'static [(Self, Self)] = &[
+        (DropdownState::Closed, DropdownState::Open),
+        (DropdownState::Open, DropdownState::Closed),
+    ];
+}
+
+impl DropdownState {
+    /// The machine a dropdown starts in, closed
+    pub const MACHINE: UiStateMachine<DropdownState> = UiStateMachine::new(DropdownState::Closed);
+}
+
 /// Component for dropdown selectors
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
 pub struct Dropdown {
-    /// Available options in the dropdown
-    pub options: Vec<String>,
-    /// Currently selected option index
+    /// Entries in the dropdown's list: options, group headers and separators
+    pub options: Vec<DropdownEntry>,
+    /// Index into `options` of the selected option
     pub selected_index: usize,
-    /// Whether the dropdown is currently open
-    pub opened: bool,
+    /// Whether the list is open
+    pub state: UiStateMachine<DropdownState>,
 }
 
 impl Default for Dropdown {
@@ -189,28 +425,132 @@ impl Default for Dropdown {
         Self {
             options: Vec::new(),
             selected_index: 0,
-            opened: false,
+            state: DropdownState::MACHINE,
+        }
+    }
+}
+
+impl Dropdown {
+    /// Creates a closed dropdown with the first selectable entry selected
+    pub fn new(options: impl IntoIterator<Item = impl Into<DropdownEntry>>) -> Self {
+        let options: Vec<DropdownEntry> = options.into_iter().map(Into::into).collect();
+        let selected_index = options
+            .iter()
+            .position(DropdownEntry::is_selectable)
+            .unwrap_or_default();
+        Self {
+            options,
+            selected_index,
+            state: DropdownState::MACHINE,
         }
     }
+
+    /// Whether the list is open
+    pub fn is_open(&self) -> bool {
+        self.state.is(DropdownState::Open)
+    }
+
+    /// Opens or closes the list; returns whether it changed
+    pub fn set_open(&mut self, open: bool) -> bool {
+        self.state.go(if open {
+            DropdownState::Open
+        } else {
+            DropdownState::Closed
+        })
+    }
+
+    /// Returns the currently selected option text, if any
+    pub fn selected(&self) -> Option<&str> {
+        self.options
+            .get(self.selected_index)
+            .and_then(DropdownEntry::text)
+    }
+}
+
+/// Marker for the text child showing a [`Dropdown`]'s selected option
+#[derive(Component, Debug, Clone, Copy, Reflect, Default)]
+#[reflect(Component)]
+pub struct DropdownLabel;
+
+/// The option list spawned under an open [`Dropdown`]
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct DropdownList {
+    /// The dropdown that owns this list
+    pub dropdown: Entity,
+}
+
+/// An option button inside a [`DropdownList`]
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct DropdownOption {
+    /// The dropdown that owns this option
+    pub dropdown: Entity,
+    /// Index of the entry in [`Dropdown::options`]
+    pub index: usize,
 }
 
 /// Component for tabbed interfaces
-#[derive(Component, Debug, Clone, Reflect)]
+///
+/// Tabs are added and removed at runtime with
+/// [`AddTab`](crate::tabs::AddTab) and [`RemoveTab`](crate::tabs::RemoveTab),
+/// which keep the panes in step; the tab bar follows changes to `tabs`.
+#[derive(Component, Debug, Clone, Reflect, Default)]
 #[reflect(Component)]
 pub struct TabbedContainer {
     /// Names of available tabs
     pub tabs: Vec<String>,
     /// Currently active tab index
     pub active_tab: usize,
+    /// Whether tabs have a close button sending [`TabCloseRequested`](crate::tabs::TabCloseRequested)
+    pub closable: bool,
+    /// Whether tabs can be dragged to a new place
+    pub reorderable: bool,
+    /// Tab buttons the tab bar was built for
+    #[reflect(ignore)]
+    pub(crate) built: Option<(Vec<String>, bool, bool)>,
 }
 
-impl Default for TabbedContainer {
-    fn default() -> Self {
+impl TabbedContainer {
+    /// Creates a container with the given tabs, the first one active
+    pub fn new<S: Into<String>>(tabs: impl IntoIterator<Item = S>) -> Self {
         Self {
-            tabs: Vec::new(),
-            active_tab: 0,
+            tabs: tabs.into_iter().map(Into::into).collect(),
+            ..default()
         }
     }
+
+    /// Turns close buttons on or off
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    /// Turns drag reordering on or off
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+}
+
+/// A button in a [`TabbedContainer`]'s tab bar
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct TabButton {
+    /// The container this tab switches
+    pub container: Entity,
+    /// Index into [`TabbedContainer::tabs`]
+    pub index: usize,
+}
+
+/// A content pane shown while its tab is active
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct TabPane {
+    /// The container this pane belongs to
+    pub container: Entity,
+    /// Index into [`TabbedContainer::tabs`]
+    pub index: usize,
 }
 
 /// Component for collapsible panels
@@ -239,6 +579,18 @@ pub enum TooltipPosition {
     Right,
 }
 
+impl TooltipPosition {
+    /// The position on the other side of the target
+    pub fn opposite(self) -> Self {
+        match self {
+            TooltipPosition::Top => TooltipPosition::Bottom,
+            TooltipPosition::Bottom => TooltipPosition::Top,
+            TooltipPosition::Left => TooltipPosition::Right,
+            TooltipPosition::Right => TooltipPosition::Left,
+        }
+    }
+}
+
 /// Tooltip with positioning
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -270,6 +622,35 @@ impl Default for ScrollPane {
     }
 }
 
+/// Marker for the content container inside a [`ScrollPane`]
+///
+/// Scroll bounds are measured from this node's laid-out size. When no child is
+/// marked, the pane's first child is used.
+#[derive(Component, Debug, Clone, Copy, Reflect, Default)]
+#[reflect(Component)]
+pub struct ScrollContent;
+
+/// Vertical scroll bar track attached to a [`ScrollPane`]
+///
+/// Spawn it as an absolutely positioned child of the pane with a single
+/// [`ScrollThumb`] child; see [`spawn_scroll_bar`](crate::utils::spawn_scroll_bar).
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct ScrollBar {
+    /// The pane this scroll bar controls
+    pub pane: Entity,
+}
+
+/// Draggable thumb inside a [`ScrollBar`]
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct ScrollThumb {
+    /// The pane this thumb controls
+    pub pane: Entity,
+    /// Cursor offset from the thumb's top edge while dragging
+    pub grab_offset: f32,
+}
+
 /// Component for displaying numeric values with min/max indicators
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -0,0 +1,263 @@
+//! Mouse cursor management
+//!
+//! [`UiCursor`] picks the cursor every frame from what the pointer is over:
+//!
+//! - a pointer hand over buttons and other interactive nodes,
+//! - a text beam over number entries,
+//! - a "not allowed" sign over disabled, read-only or cooling-down widgets,
+//! - whatever a [`HoverCursor`] on the node or one of its ancestors asks for.
+//!
+//! Away from the UI the game's contextual cursor applies: the game pushes one
+//! per context, such as "attack" while a unit would attack the hovered target,
+//! and the most recently pushed one is shown until it is popped. A
+//! [`UiCursorIcon::Image`] hides the hardware cursor and draws the image at
+//! the pointer instead. The cursor is picked after `Update`, so contexts
+//! pushed during a frame show the same frame.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy::window::CursorIcon;
+//! # use sf_ui_common::{UiCursor, UiCursorIcon};
+//! fn enter_attack_mode(mut cursor: ResMut<UiCursor>, asset_server: Res<AssetServer>) {
+//!     let sword = asset_server.load("cursors/attack.png");
+//!     cursor.push("attack", UiCursorIcon::image(sword, Vec2::splat(32.0)));
+//! }
+//!
+//! fn enter_build_mode(mut cursor: ResMut<UiCursor>) {
+//!     cursor.push("build", CursorIcon::Crosshair);
+//! }
+//!
+//! fn leave_modes(mut cursor: ResMut<UiCursor>) {
+//!     cursor.pop("attack");
+//!     cursor.pop("build");
+//! }
+//! ```
+
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use bevy::window::{CursorIcon, PrimaryWindow};
+
+use crate::click_cooldown::ClickCoolingDown;
+use crate::components::{is_disabled, WidgetDisabled};
+use crate::interaction::UiInteractionState;
+use crate::read_only::{locked_by, ReadOnly};
+use crate::slider_entry::SliderValueLabel;
+
+/// A cursor shown by [`UiCursor`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub enum UiCursorIcon {
+    /// One of the system's cursors
+    System(CursorIcon),
+    /// An image drawn in place of the hidden hardware cursor
+    Image {
+        /// The cursor image
+        image: Handle<Image>,
+        /// Drawn size in logical pixels
+        size: Vec2,
+        /// Point of the image placed on the pointer, from its top-left corner
+        hotspot: Vec2,
+    },
+}
+
+impl UiCursorIcon {
+    /// An image cursor of `size` whose top-left corner is the hotspot
+    pub fn image(image: Handle<Image>, size: Vec2) -> Self {
+        Self::Image {
+            image,
+            size,
+            hotspot: Vec2::ZERO,
+        }
+    }
+
+    /// Moves the hotspot of an image cursor; system cursors are unchanged
+    pub fn with_hotspot(mut self, point: Vec2) -> Self {
+        if let Self::Image { hotspot, .. } = &mut self {
+            *hotspot = point;
+        }
+        self
+    }
+}
+
+impl Default for UiCursorIcon {
+    fn default() -> Self {
+        Self::System(CursorIcon::Default)
+    }
+}
+
+impl From<CursorIcon> for UiCursorIcon {
+    fn from(icon: CursorIcon) -> Self {
+        Self::System(icon)
+    }
+}
+
+/// The cursor shown and the game's contextual cursors
+#[derive(Resource, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct UiCursor {
+    /// Contextual cursors by name, most recently pushed last
+    contexts: Vec<(String, UiCursorIcon)>,
+    /// The cursor resolved for this frame
+    current: UiCursorIcon,
+}
+
+impl UiCursor {
+    /// Shows `icon` away from the UI until `context` is popped
+    ///
+    /// Pushing a context that is already there moves it to the top with the
+    /// new icon.
+    pub fn push(&mut self, context: impl Into<String>, icon: impl Into<UiCursorIcon>) {
+        let context = context.into();
+        let icon = icon.into();
+        if self.contexts.last() == Some(&(context.clone(), icon.clone())) {
+            return;
+        }
+        self.contexts.retain(|(name, _)| *name != context);
+        self.contexts.push((context, icon));
+    }
+
+    /// Removes a contextual cursor, returning whether it was pushed
+    pub fn pop(&mut self, context: &str) -> bool {
+        let count = self.contexts.len();
+        self.contexts.retain(|(name, _)| name != context);
+        self.contexts.len() != count
+    }
+
+    /// Removes every contextual cursor
+    pub fn clear(&mut self) {
+        self.contexts.clear();
+    }
+
+    /// The contextual cursor shown away from the UI, if any
+    pub fn context(&self) -> Option<&UiCursorIcon> {
+        self.contexts.last().map(|(_, icon)| icon)
+    }
+
+    /// The cursor shown this frame
+    pub fn current(&self) -> &UiCursorIcon {
+        &self.current
+    }
+}
+
+/// Overrides the cursor over a node and everything inside it
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct HoverCursor(pub UiCursorIcon);
+
+/// Marks the node drawing an image cursor
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SoftwareCursor;
+
+/// System to pick the cursor from the hovered node or the game's context
+pub fn ui_cursor_resolve_system(
+    mut cursor: ResMut<UiCursor>,
+    interaction_state: Res<UiInteractionState>,
+    hover_cursors: Query<&HoverCursor>,
+    widgets: Query<(WidgetDisabled, Has<SliderValueLabel>, Has<ClickCoolingDown>)>,
+    read_only: Query<(), With<ReadOnly>>,
+    parents: Query<&Parent>,
+) {
+    let icon = match interaction_state.winner() {
+        Some(entity) => {
+            let hover_cursor = std::iter::once(entity)
+                .chain(parents.iter_ancestors(entity))
+                .find_map(|node| hover_cursors.get(node).ok());
+            let (disabled, text, cooling_down) = widgets
+                .get(entity)
+                .map(|(disabled, text, cooling_down)| (is_disabled(disabled), text, cooling_down))
+                .unwrap_or_default();
+            if let Some(HoverCursor(icon)) = hover_cursor {
+                icon.clone()
+            } else if disabled || cooling_down || locked_by(entity, &read_only, &parents).is_some()
+            {
+                CursorIcon::NotAllowed.into()
+            } else if text {
+                CursorIcon::Text.into()
+            } else {
+                CursorIcon::Pointer.into()
+            }
+        }
+        None => cursor.context().cloned().unwrap_or_default(),
+    };
+    if cursor.current != icon {
+        cursor.current = icon;
+    }
+}
+
+/// System to show the resolved cursor, as a system cursor or a drawn image
+pub fn ui_cursor_apply_system(
+    mut commands: Commands,
+    cursor: Res<UiCursor>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut software: Query<(&mut Style, &mut UiImage, &mut Visibility), With<SoftwareCursor>>,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    let position = window.cursor_position();
+
+    let UiCursorIcon::Image {
+        image,
+        size,
+        hotspot,
+    } = cursor.current()
+    else {
+        if let UiCursorIcon::System(icon) = cursor.current() {
+            if window.cursor.icon != *icon || !window.cursor.visible {
+                window.cursor.icon = *icon;
+                window.cursor.visible = true;
+            }
+        }
+        for (_, _, mut visibility) in &mut software {
+            visibility.set_if_neq(Visibility::Hidden);
+        }
+        return;
+    };
+
+    if window.cursor.visible {
+        window.cursor.visible = false;
+    }
+    let Ok((mut style, mut ui_image, mut visibility)) = software.get_single_mut() else {
+        commands.spawn((
+            ImageBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(i32::MAX),
+                focus_policy: bevy::ui::FocusPolicy::Pass,
+                ..default()
+            },
+            SoftwareCursor,
+        ));
+        return;
+    };
+    let Some(position) = position else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    let top_left = position / ui_scale.0 - *hotspot;
+    style.left = Val::Px(top_left.x);
+    style.top = Val::Px(top_left.y);
+    style.width = Val::Px(size.x);
+    style.height = Val::Px(size.y);
+    if ui_image.texture != *image {
+        ui_image.texture = image.clone();
+    }
+    visibility.set_if_neq(Visibility::Visible);
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiCursor>()
+        .register_type::<HoverCursor>()
+        .register_type::<SoftwareCursor>()
+        .init_resource::<UiCursor>()
+        .add_systems(
+            PostUpdate,
+            (ui_cursor_resolve_system, ui_cursor_apply_system)
+                .chain()
+                .before(UiSystem::Layout),
+        );
+}
@@ -0,0 +1,429 @@
+//! Drag and drop between UI elements
+//!
+//! Pressing a [`Draggable`] and moving the cursor past its
+//! [`threshold`](Draggable::threshold) starts a drag: a translucent ghost of
+//! the element follows the cursor and [`DragStarted`] is sent. The topmost
+//! [`DropTarget`] under the cursor that accepts the element's kind gets the
+//! focus border color, and releasing the mouse over it sends [`DroppedOn`]
+//! with both entities. Releasing anywhere else, or pressing Escape, ends the
+//! drag without a drop; moving the element is up to the game.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{Draggable, DropTarget, DroppedOn};
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn((ButtonBundle::default(), Draggable::new("queue_item")));
+//!     commands.spawn((NodeBundle::default(), DropTarget::accepting(["queue_item"])));
+//! }
+//!
+//! fn reorder(mut drops: EventReader<DroppedOn>) {
+//!     for drop in drops.read() {
+//!         info!("{:?} dropped on {:?}", drop.source, drop.target);
+//!     }
+//! }
+//! ```
+//!
+//! Draggables need an [`Interaction`], as on a `ButtonBundle`, to be pressed.
+
+use bevy::prelude::*;
+use bevy::ui::{FocusPolicy, UiStack};
+use bevy::utils::HashMap;
+use bevy::window::PrimaryWindow;
+
+use crate::interaction::{PointerCapture, UiInteractionState};
+use crate::state_machine::{UiState, UiStateMachine};
+use crate::theme::Theme;
+
+/// An element that can be picked up and dropped on a [`DropTarget`]
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Draggable {
+    /// What the element is, matched against [`DropTarget::accepts`]
+    pub kind: String,
+    /// Pixels the cursor moves after the press before the drag starts
+    pub threshold: f32,
+}
+
+impl Draggable {
+    /// Creates a draggable of the given kind
+    pub fn new(kind: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            threshold: 4.0,
+        }
+    }
+}
+
+/// An element that [`Draggable`]s can be dropped on
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct DropTarget {
+    /// Kinds of draggable this target takes; empty takes any
+    pub accepts: Vec<String>,
+    /// Whether an accepted draggable is over this target
+    pub hovered: bool,
+}
+
+impl DropTarget {
+    /// Creates a target that takes any draggable
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Creates a target that takes the given kinds
+    pub fn accepting<S: Into<String>>(kinds: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            accepts: kinds.into_iter().map(Into::into).collect(),
+            hovered: false,
+        }
+    }
+
+    /// Whether this target takes draggables of `kind`
+    pub fn accepts(&self, kind: &str) -> bool {
+        self.accepts.is_empty() || self.accepts.iter().any(|accepted| accepted == kind)
+    }
+}
+
+/// Marks the ghost that follows the cursor during a drag
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct DragGhost {
+    /// The element being dragged
+    pub source: Entity,
+}
+
+/// Sent when a drag starts
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DragStarted {
+    /// The element being dragged
+    pub source: Entity,
+    /// The ghost following the cursor
+    pub ghost: Entity,
+}
+
+/// Sent when a draggable is released over a target that accepts it
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DroppedOn {
+    /// The element that was dragged
+    pub source: Entity,
+    /// The target it was dropped on
+    pub target: Entity,
+}
+
+/// Where a drag is: nothing held, a draggable pressed, or a drag under way
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum DragPhase {
+    /// No draggable is held
+    #[default]
+    Idle,
+    /// A draggable is pressed but the cursor hasn't passed its threshold
+    Pressed,
+    /// The ghost follows the cursor
+    Dragging,
+}
+
+/// A press either becomes a drag or is let go; a drag always ends idle
+impl UiState for DragPhase {
+    const TRANSITIONS: &'static [(Self, Self)] = &[
+        (DragPhase::Idle, DragPhase::Pressed),
+        (DragPhase::Pressed, DragPhase::Dragging),
+        (DragPhase::Pressed, DragPhase::Idle),
+        (DragPhase::Dragging, DragPhase::Idle),
+    ];
+}
+
+impl DragPhase {
+    /// The machine a drag starts in, idle
+    pub const MACHINE: UiStateMachine<DragPhase> = UiStateMachine::new(DragPhase::Idle);
+}
+
+/// A press on a draggable, and the drag once it has started
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ActiveDrag {
+    source: Entity,
+    /// Cursor position at the press
+    origin: Vec2,
+    /// Cursor position relative to the element's top-left corner
+    grab_offset: Vec2,
+    ghost: Option<Entity>,
+    target: Option<Entity>,
+}
+
+/// The drag in progress, if any
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DragState {
+    phase: UiStateMachine<DragPhase>,
+    active: Option<ActiveDrag>,
+}
+
+impl Default for DragState {
+    fn default() -> Self {
+        Self {
+            phase: DragPhase::MACHINE,
+            active: None,
+        }
+    }
+}
+
+impl DragState {
+    /// Whether a draggable is held, and whether its drag has started
+    pub fn phase(&self) -> DragPhase {
+        self.phase.state()
+    }
+
+    /// The element being dragged, once the drag has started
+    pub fn source(&self) -> Option<Entity> {
+        self.active
+            .filter(|_| self.phase.is(DragPhase::Dragging))
+            .map(|drag| drag.source)
+    }
+
+    /// The accepting target under the cursor
+    pub fn target(&self) -> Option<Entity> {
+        self.active.and_then(|drag| drag.target)
+    }
+
+    /// Whether a drag has started
+    pub fn is_dragging(&self) -> bool {
+        self.source().is_some()
+    }
+}
+
+/// The parts of a draggable its ghost copies
+type GhostLook<'a> = (
+    Option<&'a BackgroundColor>,
+    Option<&'a UiImage>,
+    Option<&'a Children>,
+);
+
+/// System to pick up draggables and spawn the ghost once the cursor moves
+pub fn drag_start_system(
+    mut commands: Commands,
+    mut state: ResMut<DragState>,
+    draggables: Query<(Entity, &Draggable, &Node, &GlobalTransform)>,
+    (looks, texts): (Query<GhostLook>, Query<&Text>),
+    windows: Query<&Window, With<PrimaryWindow>>,
+    (interaction_state, mut capture): (Res<UiInteractionState>, ResMut<PointerCapture>),
+    mut started_events: EventWriter<DragStarted>,
+) {
+    let Some(cursor) = windows.get_single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    for (entity, _, node, transform) in &draggables {
+        if interaction_state.just_pointer_pressed(entity) && state.phase.go(DragPhase::Pressed) {
+            capture.capture(entity);
+            state.active = Some(ActiveDrag {
+                source: entity,
+                origin: cursor,
+                grab_offset: cursor - node.logical_rect(transform).min,
+                ghost: None,
+                target: None,
+            });
+        }
+    }
+
+    if !state.phase.is(DragPhase::Pressed) {
+        return;
+    }
+    let Some(drag) = state.active.as_mut() else {
+        return;
+    };
+    let Ok((source, draggable, node, _)) = draggables.get(drag.source) else {
+        return;
+    };
+    if cursor.distance(drag.origin) < draggable.threshold {
+        return;
+    }
+
+    // The ghost is a see-through copy of the element's box, image and texts
+    let (background, image, children) = looks.get(source).unwrap_or_default();
+    let faded = |color: Color| color.with_alpha(color.alpha() * 0.6);
+    let position = cursor - drag.grab_offset;
+    let mut ghost = commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(position.x),
+                top: Val::Px(position.y),
+                width: Val::Px(node.size().x),
+                height: Val::Px(node.size().y),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: background
+                .map_or(Color::NONE, |color| faded(color.0))
+                .into(),
+            focus_policy: FocusPolicy::Pass,
+            z_index: ZIndex::Global(400),
+            ..default()
+        },
+        DragGhost { source },
+        Name::new("DragGhost"),
+    ));
+    if let Some(image) = image {
+        ghost.insert(UiImage {
+            color: faded(image.color),
+            ..image.clone()
+        });
+    }
+    ghost.with_children(|ghost| {
+        for text in children
+            .into_iter()
+            .flatten()
+            .filter_map(|&child| texts.get(child).ok())
+        {
+            let mut text = text.clone();
+            for section in &mut text.sections {
+                section.style.color = faded(section.style.color);
+            }
+            ghost.spawn(TextBundle {
+                text,
+                focus_policy: FocusPolicy::Pass,
+                ..default()
+            });
+        }
+    });
+    let ghost = ghost.id();
+    drag.ghost = Some(ghost);
+    state.phase.go(DragPhase::Dragging);
+    started_events.send(DragStarted { source, ghost });
+}
+
+/// System to move the ghost and find the accepting target under the cursor
+///
+/// Targets are hit-tested front to back, so the topmost one wins.
+pub fn drag_move_system(
+    mut state: ResMut<DragState>,
+    draggables: Query<&Draggable>,
+    mut ghosts: Query<&mut Style, With<DragGhost>>,
+    mut targets: Query<(
+        Entity,
+        &mut DropTarget,
+        &Node,
+        &GlobalTransform,
+        &ViewVisibility,
+    )>,
+    ui_stack: Res<UiStack>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let cursor = windows.get_single().ok().and_then(Window::cursor_position);
+    let drag = state.active.filter(|_| state.phase.is(DragPhase::Dragging));
+    let mut over = None;
+
+    if let (Some(drag), Some(cursor)) = (drag, cursor) {
+        if let Some(mut style) = drag.ghost.and_then(|ghost| ghosts.get_mut(ghost).ok()) {
+            let position = cursor - drag.grab_offset;
+            style.left = Val::Px(position.x);
+            style.top = Val::Px(position.y);
+        }
+        if let Ok(draggable) = draggables.get(drag.source) {
+            over = ui_stack.uinodes.iter().rev().copied().find(|&entity| {
+                entity != drag.source
+                    && targets
+                        .get(entity)
+                        .is_ok_and(|(_, target, node, transform, visibility)| {
+                            visibility.get()
+                                && target.accepts(&draggable.kind)
+                                && node.logical_rect(transform).contains(cursor)
+                        })
+            });
+        }
+    }
+
+    if let Some(drag) = state.active.as_mut() {
+        if drag.target != over {
+            drag.target = over;
+        }
+    }
+    for (entity, mut target, ..) in &mut targets {
+        let is_over = over == Some(entity);
+        if target.hovered != is_over {
+            target.hovered = is_over;
+        }
+    }
+}
+
+/// System to drop on mouse-up, or cancel on Escape or when the element is gone
+pub fn drag_end_system(
+    mut commands: Commands,
+    mut state: ResMut<DragState>,
+    mut targets: Query<&mut DropTarget>,
+    draggables: Query<(), With<Draggable>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    (keyboard_input, mut capture): (Res<ButtonInput<KeyCode>>, ResMut<PointerCapture>),
+    mut dropped_events: EventWriter<DroppedOn>,
+) {
+    let Some(drag) = state.active else {
+        return;
+    };
+    let released = !mouse_buttons.pressed(MouseButton::Left);
+    let cancelled = keyboard_input.just_pressed(KeyCode::Escape);
+    let gone = !draggables.contains(drag.source);
+    if !released && !cancelled && !gone {
+        return;
+    }
+
+    let dragging = state.phase.is(DragPhase::Dragging);
+    if let (true, false, true, Some(target)) = (released, gone, dragging, drag.target) {
+        dropped_events.send(DroppedOn {
+            source: drag.source,
+            target,
+        });
+    }
+    if let Some(ghost) = drag.ghost {
+        commands.entity(ghost).despawn_recursive();
+    }
+    if let Some(mut target) = drag.target.and_then(|target| targets.get_mut(target).ok()) {
+        target.hovered = false;
+    }
+    if capture.is_captured(drag.source) {
+        capture.release();
+    }
+    state.active = None;
+    state.phase.go(DragPhase::Idle);
+}
+
+/// System to give hovered drop targets the focus border color
+///
+/// The target's own border color is put back when the drag leaves it.
+pub fn drop_target_visual_system(
+    theme: Res<Theme>,
+    mut targets: Query<(Entity, &DropTarget, &mut BorderColor), Changed<DropTarget>>,
+    mut saved: Local<HashMap<Entity, Color>>,
+) {
+    for (entity, target, mut border) in &mut targets {
+        match (target.hovered, saved.contains_key(&entity)) {
+            (true, false) => {
+                saved.insert(entity, border.0);
+                border.0 = theme.focus.border;
+            }
+            (false, true) => {
+                if let Some(color) = saved.remove(&entity) {
+                    border.0 = color;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Draggable>()
+        .register_type::<DragPhase>()
+        .register_type::<DropTarget>()
+        .register_type::<DragGhost>()
+        .init_resource::<DragState>()
+        .add_event::<DragStarted>()
+        .add_event::<DroppedOn>()
+        .add_systems(
+            Update,
+            (
+                drag_start_system,
+                drag_move_system,
+                drag_end_system,
+                drop_target_visual_system,
+            )
+                .chain(),
+        );
+}
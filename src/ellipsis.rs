@@ -0,0 +1,168 @@
+//! Automatic text ellipsis with a full-text tooltip
+//!
+//! Text entities tagged with [`AutoEllipsis`] are shortened with a trailing
+//! `…` when they don't fit their available width. While truncated, a
+//! [`Tooltip`] carrying the full string is attached so long map and player
+//! names stay discoverable; it is removed again once the text fits.
+
+use bevy::prelude::*;
+use bevy::text::{BreakLineOn, TextLayoutInfo};
+
+use crate::components::{Tooltip, TooltipPosition};
+
+const ELLIPSIS: char = '…';
+
+/// Truncates a text entity's first section to fit its available width
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct AutoEllipsis {
+    /// The untruncated text
+    pub full_text: String,
+    /// Width limit in pixels; defaults to the parent node's width
+    pub max_width: Option<f32>,
+    /// Whether the displayed text is currently truncated
+    truncated: bool,
+    /// Available width the current truncation was computed for
+    available: f32,
+}
+
+impl AutoEllipsis {
+    /// Truncates `full_text` to the parent node's width
+    pub fn new(full_text: impl Into<String>) -> Self {
+        Self {
+            full_text: full_text.into(),
+            max_width: None,
+            truncated: false,
+            available: 0.0,
+        }
+    }
+
+    /// Truncates `full_text` to a fixed width in pixels
+    pub fn with_max_width(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Whether the displayed text is currently truncated
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+/// Marks a [`Tooltip`] that was attached by the ellipsis system
+#[derive(Component, Debug, Clone, Copy, Reflect, Default)]
+#[reflect(Component)]
+pub struct EllipsisTooltip;
+
+/// System to shorten overflowing text and restore it when space grows
+///
+/// Text is measured after layout, so truncation converges over a frame or two
+/// as the shortened string is re-measured.
+pub fn auto_ellipsis_system(
+    mut texts: Query<(&mut AutoEllipsis, &mut Text, &TextLayoutInfo, Option<&Parent>)>,
+    nodes: Query<&Node>,
+) {
+    for (mut ellipsis, mut text, layout, parent) in &mut texts {
+        if text.linebreak_behavior != BreakLineOn::NoWrap {
+            text.linebreak_behavior = BreakLineOn::NoWrap;
+        }
+        let Some(section) = text.sections.first() else {
+            continue;
+        };
+
+        let available = ellipsis
+            .max_width
+            .or_else(|| {
+                parent
+                    .and_then(|parent| nodes.get(parent.get()).ok())
+                    .map(|node| node.size().x)
+            })
+            .unwrap_or(f32::INFINITY);
+
+        // Start over from the full text when the text or the space changes
+        let grew = available > ellipsis.available + 0.5;
+        let stale = if ellipsis.truncated {
+            !ellipsis
+                .full_text
+                .starts_with(section.value.trim_end_matches(ELLIPSIS))
+        } else {
+            section.value != ellipsis.full_text
+        };
+        if ellipsis.available != available {
+            ellipsis.available = available;
+        }
+        if grew || stale {
+            if section.value != ellipsis.full_text {
+                text.sections[0].value.clone_from(&ellipsis.full_text);
+                ellipsis.truncated = false;
+            }
+            continue;
+        }
+
+        let measured = layout.logical_size.x;
+        if measured <= available + 0.5 || measured <= 0.0 {
+            continue;
+        }
+
+        let shown = section.value.trim_end_matches(ELLIPSIS).chars().count();
+        let keep = ((shown as f32 * available / measured).floor() as usize)
+            .saturating_sub(1)
+            .min(shown.saturating_sub(1));
+        let mut truncated: String = ellipsis.full_text.chars().take(keep).collect();
+        truncated.push(ELLIPSIS);
+        text.sections[0].value = truncated;
+        ellipsis.truncated = true;
+    }
+}
+
+/// System to attach or remove the full-text tooltip as truncation changes
+pub fn ellipsis_tooltip_system(
+    mut commands: Commands,
+    mut texts: Query<
+        (
+            Entity,
+            &AutoEllipsis,
+            Option<&mut Tooltip>,
+            Has<EllipsisTooltip>,
+            Has<Interaction>,
+        ),
+        Changed<AutoEllipsis>,
+    >,
+) {
+    for (entity, ellipsis, tooltip, ours, has_interaction) in &mut texts {
+        match (ellipsis.truncated, tooltip) {
+            (true, Some(mut tooltip)) if ours && tooltip.text != ellipsis.full_text => {
+                tooltip.text.clone_from(&ellipsis.full_text);
+            }
+            (true, None) => {
+                let mut entity_commands = commands.entity(entity);
+                entity_commands.insert((
+                    Tooltip {
+                        text: ellipsis.full_text.clone(),
+                        position: TooltipPosition::Top,
+                        offset: 4.0,
+                    },
+                    EllipsisTooltip,
+                ));
+                if !has_interaction {
+                    entity_commands.insert(Interaction::default());
+                }
+            }
+            (false, Some(_)) if ours => {
+                commands.entity(entity).remove::<(Tooltip, EllipsisTooltip)>();
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<AutoEllipsis>()
+        .register_type::<EllipsisTooltip>()
+        .add_systems(
+            PostUpdate,
+            (auto_ellipsis_system, ellipsis_tooltip_system)
+                .chain()
+                .after(bevy::ui::widget::text_system),
+        );
+}
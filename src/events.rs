@@ -0,0 +1,88 @@
+//! Typed widget events
+//!
+//! Widget systems emit these so downstream crates can read an `EventReader`
+//! instead of re-querying [`Interaction`] themselves.
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+
+use crate::systems::{disabled_visual_system, set_enabled_system, slider_changed_event_system};
+
+/// Sent when a button is clicked
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiButtonClicked(pub Entity);
+
+/// Sent when a checkbox is toggled by the user
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiCheckboxToggled {
+    /// The checkbox entity
+    pub entity: Entity,
+    /// The new checked state
+    pub checked: bool,
+}
+
+/// Sent when a slider's value changes
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct UiSliderChanged {
+    /// The slider entity
+    pub entity: Entity,
+    /// The new slider value
+    pub value: f32,
+}
+
+/// Sent when the user picks a different option in a dropdown
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct DropdownSelectionChanged {
+    /// The dropdown entity
+    pub entity: Entity,
+    /// Index of the newly selected option
+    pub index: usize,
+    /// Text of the newly selected option
+    pub value: String,
+}
+
+/// Sent when the user switches a [`TabbedContainer`](crate::components::TabbedContainer) to another tab
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabChanged {
+    /// The container entity
+    pub container: Entity,
+    /// Index of the previously active tab
+    pub previous: usize,
+    /// Index of the newly active tab
+    pub index: usize,
+}
+
+/// Enables or disables a button, checkbox or slider at runtime
+///
+/// Send it as an event, or queue it with `commands.add(SetEnabled { .. })`.
+/// Disabled widgets ignore presses and drags, are drawn in the disabled
+/// colors and are skipped by focus navigation.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetEnabled {
+    /// The widget entity
+    pub entity: Entity,
+    /// Whether the widget should accept input
+    pub enabled: bool,
+}
+
+impl Command for SetEnabled {
+    fn apply(self, world: &mut World) {
+        world.send_event(self);
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.add_event::<UiButtonClicked>()
+        .add_event::<UiCheckboxToggled>()
+        .add_event::<UiSliderChanged>()
+        .add_event::<DropdownSelectionChanged>()
+        .add_event::<TabChanged>()
+        .add_event::<SetEnabled>()
+        .add_systems(
+            Update,
+            (set_enabled_system, disabled_visual_system)
+                .chain()
+                .after(crate::button_interaction_system),
+        )
+        .add_systems(PostUpdate, slider_changed_event_system);
+}
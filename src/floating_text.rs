@@ -0,0 +1,280 @@
+//! Floating feedback text
+//!
+//! [`spawn_floating_text`] puts a short-lived label on screen, such as a
+//! damage number over a unit or "+50 gold" next to the resource bar. The
+//! label rises from where it was spawned, fades out over the second half of
+//! its lifetime and despawns itself. World positions are projected through
+//! the game camera every frame, so the text stays over the spot it was
+//! spawned at while the camera moves. When [`UiMotion`] turns animations
+//! off, the label stays where it was spawned and only fades.
+//!
+//! Texts marked as damage or healing numbers with
+//! [`FloatingText::with_kind`] are dropped while [`CombatTextSettings`]
+//! turns their kind off.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{spawn_floating_text, FloatingText, FloatingTextKind};
+//! fn show_damage(mut commands: Commands) {
+//!     let hit = FloatingText::world("-12", Vec3::new(4.0, 1.5, -2.0))
+//!         .with_color(Color::srgb(1.0, 0.3, 0.2))
+//!         .with_kind(FloatingTextKind::Damage);
+//!     spawn_floating_text(&mut commands, hit);
+//!
+//!     let gold = FloatingText::screen("+50 gold", Vec2::new(120.0, 40.0));
+//!     spawn_floating_text(&mut commands, gold);
+//! }
+//! ```
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::render::camera::CameraUpdateSystem;
+use bevy::ui::UiSystem;
+
+use crate::assets::UiFonts;
+use crate::clock::UiClock;
+use crate::quality::UiMotion;
+use crate::world_bar::{default_camera, world_to_ui};
+
+/// Where a [`FloatingText`] starts
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum FloatingAnchor {
+    /// A point on screen in logical pixels
+    Screen(Vec2),
+    /// A point in the game world, projected through the active camera
+    World(Vec3),
+}
+
+/// What a [`FloatingText`] reports, for [`CombatTextSettings`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum FloatingTextKind {
+    /// Anything else, always shown
+    #[default]
+    General,
+    /// Damage dealt or taken
+    Damage,
+    /// Health restored
+    Healing,
+}
+
+/// Which kinds of combat text are shown
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct CombatTextSettings {
+    /// Whether damage numbers are shown
+    pub damage: bool,
+    /// Whether healing numbers are shown
+    pub healing: bool,
+}
+
+impl Default for CombatTextSettings {
+    fn default() -> Self {
+        Self {
+            damage: true,
+            healing: true,
+        }
+    }
+}
+
+impl CombatTextSettings {
+    /// Whether texts of `kind` are shown
+    pub fn shows(&self, kind: FloatingTextKind) -> bool {
+        match kind {
+            FloatingTextKind::General => true,
+            FloatingTextKind::Damage => self.damage,
+            FloatingTextKind::Healing => self.healing,
+        }
+    }
+}
+
+/// A label that rises, fades and despawns itself
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct FloatingText {
+    /// The text shown
+    pub text: String,
+    /// Where the text starts, centered on this point
+    pub anchor: FloatingAnchor,
+    /// Text color at full opacity
+    pub color: Color,
+    /// Font size in pixels
+    pub font_size: f32,
+    /// How long the text stays up
+    pub lifetime: Duration,
+    /// How far the text rises over its lifetime, in pixels
+    pub rise: f32,
+    /// Time since the text was spawned
+    pub elapsed: Duration,
+    /// What the text reports
+    pub kind: FloatingTextKind,
+}
+
+impl FloatingText {
+    /// Creates white text at `anchor`, up for one second
+    pub fn new(text: impl Into<String>, anchor: FloatingAnchor) -> Self {
+        Self {
+            text: text.into(),
+            anchor,
+            color: Color::WHITE,
+            font_size: 18.0,
+            lifetime: Duration::from_secs(1),
+            rise: 40.0,
+            elapsed: Duration::ZERO,
+            kind: FloatingTextKind::General,
+        }
+    }
+
+    /// Creates text at a point on screen
+    pub fn screen(text: impl Into<String>, position: Vec2) -> Self {
+        Self::new(text, FloatingAnchor::Screen(position))
+    }
+
+    /// Creates text over a point in the game world
+    pub fn world(text: impl Into<String>, position: Vec3) -> Self {
+        Self::new(text, FloatingAnchor::World(position))
+    }
+
+    /// Sets the text color
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the font size
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets how long the text stays up
+    pub fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Sets how far the text rises, in pixels
+    pub fn with_rise(mut self, rise: f32) -> Self {
+        self.rise = rise;
+        self
+    }
+
+    /// Marks what the text reports
+    pub fn with_kind(mut self, kind: FloatingTextKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// How far through its lifetime the text is, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        if self.lifetime.is_zero() {
+            return 1.0;
+        }
+        (self.elapsed.as_secs_f32() / self.lifetime.as_secs_f32()).min(1.0)
+    }
+
+    /// Opacity of the text, fading out over the second half of its lifetime
+    pub fn opacity(&self) -> f32 {
+        ((1.0 - self.progress()) * 2.0).min(1.0)
+    }
+}
+
+/// Spawns a floating text and returns its entity
+pub fn spawn_floating_text(commands: &mut Commands, floating: FloatingText) -> Entity {
+    let text = Text::from_section(
+        floating.text.clone(),
+        TextStyle {
+            font: UiFonts::BOLD,
+            font_size: floating.font_size,
+            color: floating.color,
+        },
+    );
+    commands
+        .spawn((
+            TextBundle {
+                text,
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                // Placed once projected, so it never shows at the origin
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(80),
+                ..default()
+            },
+            floating,
+            Name::new("FloatingText"),
+        ))
+        .id()
+}
+
+/// System to move, fade and despawn floating texts
+///
+/// Runs just before UI layout, like [`WorldAnchoredBar`](crate::WorldAnchoredBar).
+pub fn floating_text_system(
+    mut commands: Commands,
+    mut texts: Query<(
+        Entity,
+        &mut FloatingText,
+        &Node,
+        &mut Style,
+        &mut Text,
+        &mut Visibility,
+    )>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    ui_scale: Res<UiScale>,
+    clock: Res<UiClock>,
+    combat_text: Res<CombatTextSettings>,
+    motion: UiMotion,
+) {
+    let camera = default_camera(&cameras).and_then(|camera| cameras.get(camera).ok());
+    for (entity, mut floating, node, mut style, mut text, mut visibility) in &mut texts {
+        floating.elapsed += clock.delta();
+        if floating.elapsed >= floating.lifetime || !combat_text.shows(floating.kind) {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let anchor = match floating.anchor {
+            FloatingAnchor::Screen(position) => Some(position),
+            FloatingAnchor::World(position) => camera.and_then(|(_, camera, transform)| {
+                world_to_ui(camera, transform, position, ui_scale.0)
+            }),
+        };
+        let Some(anchor) = anchor else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        // Wait for the first layout so the text can be centered
+        if node.size() != Vec2::ZERO {
+            visibility.set_if_neq(Visibility::Inherited);
+        }
+        let rise = if motion.animations() {
+            floating.rise * floating.progress()
+        } else {
+            0.0
+        };
+        let position = anchor - node.size() / 2.0 - Vec2::Y * rise;
+        style.left = Val::Px(position.x);
+        style.top = Val::Px(position.y);
+
+        let color = floating
+            .color
+            .with_alpha(floating.color.alpha() * floating.opacity());
+        for section in &mut text.sections {
+            section.style.color = color;
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<FloatingText>()
+        .register_type::<CombatTextSettings>()
+        .init_resource::<CombatTextSettings>()
+        .add_systems(
+            PostUpdate,
+            floating_text_system
+                .after(CameraUpdateSystem)
+                .before(UiSystem::Layout),
+        );
+}
@@ -0,0 +1,344 @@
+//! Focus tracking, spatial navigation and activation
+//!
+//! [`UiFocus`] holds the element that keyboard and gamepad input act on.
+//! Directional input moves focus to the nearest [`Focusable`] in that direction,
+//! and activating the focused element injects a press into
+//! [`UiInteractionState`] so every widget reacts exactly as it would to a click.
+//!
+//! Tab follows [`FocusOrder`]: reading order from the layout, cached and
+//! rebuilt only when focusable elements move, appear or disappear, with
+//! elements the game pins placed first or right after another element:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::FocusOrder;
+//! fn login_form(mut order: ResMut<FocusOrder>, name: Entity, password: Entity) {
+//!     order.pin_first(name);
+//!     order.pin_after(password, name);
+//! }
+//! ```
+//!
+//! While a [`UiTransition`] or [`UiTween`] plays, the cached order is kept,
+//! so elements sliding past each other don't reshuffle it.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::components::Focusable;
+use crate::interaction::{InteractionArbitrationSet, UiInteractionState};
+use crate::split_screen::{HudRoot, HudScope};
+use crate::transition::UiTransition;
+use crate::tween::UiTween;
+
+/// Stick deflection needed to register a navigation step
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// The element that currently has keyboard/gamepad focus
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct UiFocus {
+    /// The focused entity, if any
+    pub focused: Option<Entity>,
+}
+
+/// Tab order of the focusable elements outside split-screen HUD roots
+#[derive(Resource, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct FocusOrder {
+    /// Cached order, rebuilt by [`focus_order_system`]
+    order: Vec<Entity>,
+    /// Elements placed first, in this order
+    first: Vec<Entity>,
+    /// Elements placed right after another, as `(entity, anchor)`
+    after: Vec<(Entity, Entity)>,
+    /// Whether the pins changed since the last rebuild
+    #[reflect(ignore)]
+    dirty: bool,
+}
+
+impl FocusOrder {
+    /// The cached tab order
+    pub fn order(&self) -> &[Entity] {
+        &self.order
+    }
+
+    /// Puts `entity` first, after elements pinned first before it
+    pub fn pin_first(&mut self, entity: Entity) {
+        self.unpin(entity);
+        self.first.push(entity);
+    }
+
+    /// Puts `entity` right after `anchor`
+    pub fn pin_after(&mut self, entity: Entity, anchor: Entity) {
+        self.unpin(entity);
+        self.after.push((entity, anchor));
+    }
+
+    /// Returns `entity` to its place in reading order
+    pub fn unpin(&mut self, entity: Entity) {
+        self.first.retain(|&pinned| pinned != entity);
+        self.after.retain(|&(pinned, _)| pinned != entity);
+        self.dirty = true;
+    }
+
+    /// The element after `current` in tab order accepted by `enabled`,
+    /// wrapping around; the first accepted one when `current` isn't in the order
+    pub fn next(
+        &self,
+        current: Option<Entity>,
+        enabled: impl Fn(Entity) -> bool,
+    ) -> Option<Entity> {
+        let start = current
+            .and_then(|current| self.order.iter().position(|&entity| entity == current))
+            .map_or(0, |index| index + 1);
+        let len = self.order.len();
+        (0..len)
+            .map(|offset| self.order[(start + offset) % len])
+            .find(|&entity| enabled(entity))
+    }
+
+    /// Sorts `positions` into reading order and applies the pins
+    fn rebuild(&mut self, mut positions: Vec<(Entity, Vec3)>) {
+        // Sort by vertical then horizontal position
+        positions.sort_by(|a, b| a.1.y.total_cmp(&b.1.y).then(a.1.x.total_cmp(&b.1.x)));
+        let mut order: Vec<Entity> = positions.into_iter().map(|(entity, _)| entity).collect();
+
+        let pinned_first: Vec<Entity> = self
+            .first
+            .iter()
+            .copied()
+            .filter(|entity| order.contains(entity))
+            .collect();
+        order.retain(|entity| !pinned_first.contains(entity));
+        order.splice(0..0, pinned_first);
+        for &(entity, anchor) in &self.after {
+            if entity == anchor || !order.contains(&entity) {
+                continue;
+            }
+            order.retain(|&other| other != entity);
+            if let Some(index) = order.iter().position(|&other| other == anchor) {
+                order.insert(index + 1, entity);
+            } else {
+                order.push(entity);
+            }
+        }
+        self.order = order;
+        self.dirty = false;
+    }
+}
+
+/// A direction for spatial focus navigation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum NavDirection {
+    /// Towards the top of the screen
+    Up,
+    /// Towards the bottom of the screen
+    Down,
+    /// Towards the left of the screen
+    Left,
+    /// Towards the right of the screen
+    Right,
+}
+
+impl NavDirection {
+    /// Unit vector in UI space (y grows downwards)
+    pub fn as_vec2(self) -> Vec2 {
+        match self {
+            NavDirection::Up => Vec2::NEG_Y,
+            NavDirection::Down => Vec2::Y,
+            NavDirection::Left => Vec2::NEG_X,
+            NavDirection::Right => Vec2::X,
+        }
+    }
+}
+
+/// Picks the nearest candidate from `from` in `direction`
+///
+/// Candidates behind or level with `from` are skipped. Distance along the
+/// direction counts once and sideways offset counts double, so elements in
+/// the same row or column are preferred over diagonal ones.
+pub fn spatial_neighbor(
+    from: Vec2,
+    direction: NavDirection,
+    candidates: impl IntoIterator<Item = (Entity, Vec2)>,
+) -> Option<Entity> {
+    let axis = direction.as_vec2();
+    candidates
+        .into_iter()
+        .filter_map(|(entity, position)| {
+            let offset = position - from;
+            let along = offset.dot(axis);
+            if along <= 0.5 {
+                return None;
+            }
+            let across = (offset - axis * along).length();
+            Some((entity, along + across * 2.0))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// Gamepad input used for UI navigation
+///
+/// The plain methods read every gamepad that isn't bound to a split-screen
+/// [`HudRoot`]; the `_on` variants read a single gamepad.
+#[derive(SystemParam)]
+pub struct GamepadNavInput<'w, 's> {
+    gamepads: Res<'w, Gamepads>,
+    buttons: Res<'w, ButtonInput<GamepadButton>>,
+    axes: Res<'w, Axis<GamepadAxis>>,
+    roots: Query<'w, 's, &'static HudRoot>,
+}
+
+impl<'w, 's> GamepadNavInput<'w, 's> {
+    /// Gamepads not bound to any [`HudRoot`]
+    fn unclaimed(&self) -> impl Iterator<Item = Gamepad> + '_ {
+        self.gamepads
+            .iter()
+            .filter(|&gamepad| !self.roots.iter().any(|root| root.gamepad == Some(gamepad)))
+    }
+
+    /// Whether `button_type` was just pressed on any unclaimed gamepad
+    pub fn just_pressed(&self, button_type: GamepadButtonType) -> bool {
+        self.unclaimed()
+            .any(|gamepad| self.just_pressed_on(gamepad, button_type))
+    }
+
+    /// Whether `button_type` was just pressed on `gamepad`
+    pub fn just_pressed_on(&self, gamepad: Gamepad, button_type: GamepadButtonType) -> bool {
+        self.buttons.just_pressed(GamepadButton::new(gamepad, button_type))
+    }
+
+    /// Reads a navigation step from the D-pad or left stick of any unclaimed gamepad
+    ///
+    /// The stick has to return to center (tracked in `stick_engaged`) before it
+    /// produces another step.
+    pub fn direction(&self, stick_engaged: &mut bool) -> Option<NavDirection> {
+        let gamepads: Vec<_> = self.unclaimed().collect();
+        self.read_direction(&gamepads, stick_engaged)
+    }
+
+    /// Reads a navigation step from the D-pad or left stick of `gamepad`
+    pub fn direction_on(&self, gamepad: Gamepad, stick_engaged: &mut bool) -> Option<NavDirection> {
+        self.read_direction(&[gamepad], stick_engaged)
+    }
+
+    fn read_direction(&self, gamepads: &[Gamepad], stick_engaged: &mut bool) -> Option<NavDirection> {
+        for (button_type, direction) in [
+            (GamepadButtonType::DPadUp, NavDirection::Up),
+            (GamepadButtonType::DPadDown, NavDirection::Down),
+            (GamepadButtonType::DPadLeft, NavDirection::Left),
+            (GamepadButtonType::DPadRight, NavDirection::Right),
+        ] {
+            if gamepads
+                .iter()
+                .any(|&gamepad| self.just_pressed_on(gamepad, button_type))
+            {
+                return Some(direction);
+            }
+        }
+
+        let mut stick = Vec2::ZERO;
+        for &gamepad in gamepads {
+            let axis = |axis_type| {
+                self.axes
+                    .get(GamepadAxis::new(gamepad, axis_type))
+                    .unwrap_or(0.0)
+            };
+            let value = Vec2::new(
+                axis(GamepadAxisType::LeftStickX),
+                axis(GamepadAxisType::LeftStickY),
+            );
+            if value.length() > stick.length() {
+                stick = value;
+            }
+        }
+
+        if stick.length() < STICK_THRESHOLD {
+            *stick_engaged = false;
+            return None;
+        }
+        if *stick_engaged {
+            return None;
+        }
+        *stick_engaged = true;
+
+        // Stick Y points up, UI Y points down
+        Some(if stick.x.abs() > stick.y.abs() {
+            if stick.x > 0.0 {
+                NavDirection::Right
+            } else {
+                NavDirection::Left
+            }
+        } else if stick.y > 0.0 {
+            NavDirection::Up
+        } else {
+            NavDirection::Down
+        })
+    }
+}
+
+/// System to activate the focused element with Enter, Space or the gamepad A (south) button
+///
+/// Runs right after arbitration so widget systems see the activation as a
+/// press in the same frame: buttons send [`UiButtonClicked`](crate::UiButtonClicked),
+/// checkboxes toggle and dropdowns open, just as they would on click.
+pub fn focus_activation_system(
+    focus: Res<UiFocus>,
+    focusables: Query<(), (With<Focusable>, Without<HudScope>)>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad: GamepadNavInput,
+    mut interaction_state: ResMut<UiInteractionState>,
+) {
+    let Some(focused) = focus.focused.filter(|&entity| focusables.contains(entity)) else {
+        return;
+    };
+    let keyboard = keyboard_input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Space]);
+    if keyboard || gamepad.just_pressed(GamepadButtonType::South) {
+        interaction_state.activate(focused);
+    }
+}
+
+/// System to rebuild [`FocusOrder`] when focusable elements move, appear or
+/// disappear, or the pins change
+///
+/// Waits while any [`UiTransition`] or [`UiTween`] plays.
+pub fn focus_order_system(
+    mut order: ResMut<FocusOrder>,
+    focusables: Query<(Entity, &GlobalTransform), (With<Focusable>, Without<HudScope>)>,
+    moved: Query<
+        (),
+        (
+            With<Focusable>,
+            Or<(Added<Focusable>, Changed<GlobalTransform>, Added<HudScope>)>,
+        ),
+    >,
+    mut removed: RemovedComponents<Focusable>,
+    animating: Query<(), Or<(With<UiTransition>, With<UiTween>)>>,
+) {
+    let removed = removed.read().count() > 0;
+    if !order.dirty && !removed && moved.is_empty() {
+        return;
+    }
+    if !animating.is_empty() {
+        // Rebuilt once the animations settle, which moves the elements again
+        order.bypass_change_detection().dirty = true;
+        return;
+    }
+    let positions = focusables
+        .iter()
+        .map(|(entity, transform)| (entity, transform.translation()))
+        .collect();
+    order.rebuild(positions);
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiFocus>()
+        .register_type::<FocusOrder>()
+        .init_resource::<UiFocus>()
+        .init_resource::<FocusOrder>()
+        .add_systems(
+            PreUpdate,
+            focus_activation_system.after(InteractionArbitrationSet),
+        );
+}
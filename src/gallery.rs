@@ -0,0 +1,549 @@
+//! Widget gallery for auditing themes
+//!
+//! [`WidgetGallery`] spawns one of every widget in each of its states and
+//! variants, styled from the current [`Theme`]. The gallery is rebuilt
+//! whenever the theme changes, so artists can tweak palettes live and we can
+//! eyeball regressions after refactors. Enable the `gallery` feature, or run
+//! `cargo run --example widget_gallery --features gallery`.
+
+use bevy::ecs::system::SystemId;
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::bars::{
+    comparison_bar_bundle, spawn_progress_bar, stacked_bar_bundle, BarSegment, ComparisonBar,
+    DeltaLabel, StackedBar,
+};
+use crate::builders::{CheckboxBuilder, SliderBuilder, UiButtonBuilder};
+use crate::chip::{status_chip_bundle, StatusChip, StatusKind};
+use crate::components::{Dropdown, DropdownEntry, DropdownLabel, ProgressBar};
+use crate::ellipsis::AutoEllipsis;
+use crate::modal::{spawn_modal, ModalConfig};
+use crate::list_view::{list_view_bundle, UiListView};
+use crate::menu::{menu_bar_bundle, MenuEntry};
+use crate::nameplate::{nameplate_bundle, ConnectionQuality, Nameplate};
+use crate::steps::{step_indicator_bundle, StepIndicator};
+use crate::table::{table_bundle, SortDirection, TableCell, UiTable, UiTableColumn};
+use crate::theme::Theme;
+use crate::toolbar::{toolbar_bundle, Toolbar};
+
+/// Plugin that shows the widget gallery
+///
+/// Add it alongside [`UiCommonPlugin`](crate::UiCommonPlugin) and
+/// [`systems::update`](crate::systems::update) so every widget is live.
+pub struct WidgetGallery;
+
+/// Root node of the spawned gallery
+#[derive(Component, Debug, Clone, Copy, Reflect, Default)]
+#[reflect(Component)]
+pub struct GalleryRoot;
+
+/// One-shot systems wired to gallery buttons
+#[derive(Resource, Debug, Clone, Copy)]
+struct GalleryActions {
+    open_modal: SystemId,
+}
+
+impl Plugin for WidgetGallery {
+    fn build(&self, app: &mut App) {
+        let open_modal = app.world_mut().register_system(open_modal_system);
+        app.register_type::<GalleryRoot>()
+            .insert_resource(GalleryActions { open_modal })
+            .add_systems(Update, gallery_rebuild_system);
+    }
+}
+
+fn open_modal_system(mut commands: Commands, theme: Res<Theme>) {
+    spawn_modal(
+        &mut commands,
+        &theme,
+        ModalConfig::confirm("Modal dialog", "Escape or Cancel closes this dialog."),
+    );
+}
+
+/// System to (re)build the gallery when the theme changes
+fn gallery_rebuild_system(
+    mut commands: Commands,
+    roots: Query<Entity, With<GalleryRoot>>,
+    theme: Res<Theme>,
+    actions: Res<GalleryActions>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+    for root in &roots {
+        commands.entity(root).despawn_recursive();
+    }
+    spawn_gallery(&mut commands, &theme, *actions);
+}
+
+/// Builds captioned rows of widgets
+struct GalleryBuilder<'a, 'w, 's> {
+    commands: &'a mut Commands<'w, 's>,
+    theme: &'a Theme,
+    font: Handle<Font>,
+}
+
+impl GalleryBuilder<'_, '_, '_> {
+    fn text(&mut self, text: &str, font_size: f32, color: Color) -> Entity {
+        self.commands
+            .spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: self.font.clone(),
+                    font_size,
+                    color,
+                },
+            ))
+            .id()
+    }
+
+    /// Stacks `widget` above a small caption
+    fn item(&mut self, caption: &str, widget: Entity) -> Entity {
+        let caption = self.text(caption, 12.0, self.theme.text.disabled);
+        self.commands
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .push_children(&[widget, caption])
+            .id()
+    }
+
+    /// A titled row of items
+    fn section(&mut self, title: &str, items: &[Entity]) -> Entity {
+        let title = self.text(title, 20.0, self.theme.text.normal);
+        let row = self
+            .commands
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(16.0),
+                    row_gap: Val::Px(8.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .push_children(items)
+            .id();
+        self.commands
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .push_children(&[title, row])
+            .id()
+    }
+
+    /// A non-interactive box showing a fixed background color
+    fn swatch(&mut self, label: &str, background: Color, border: Option<Color>, text: Color) -> Entity {
+        let label = self.text(label, 18.0, text);
+        self.commands
+            .spawn(NodeBundle {
+                style: Style {
+                    padding: UiRect::all(Val::Px(10.0)),
+                    border: UiRect::all(Val::Px(if border.is_some() { 2.0 } else { 0.0 })),
+                    ..default()
+                },
+                background_color: background.into(),
+                border_color: border.unwrap_or(Color::NONE).into(),
+                ..default()
+            })
+            .add_child(label)
+            .id()
+    }
+
+    fn button(&mut self, label: &str) -> UiButtonBuilder {
+        UiButtonBuilder::new(self.theme)
+            .label(label)
+            .font_size(18.0)
+    }
+
+    fn checkbox(&mut self) -> CheckboxBuilder {
+        CheckboxBuilder::new(self.theme)
+    }
+
+    fn slider(&mut self) -> SliderBuilder {
+        SliderBuilder::new(self.theme).width(140.0)
+    }
+
+    fn dropdown(&mut self, opened: bool) -> Entity {
+        let label = self
+            .commands
+            .spawn((TextBundle::from_section("", TextStyle {
+                font: self.font.clone(),
+                font_size: 18.0,
+                color: self.theme.text.normal,
+            }), DropdownLabel))
+            .id();
+        let mut dropdown = Dropdown {
+            options: vec![
+                DropdownEntry::header("Presets"),
+                "Low".into(),
+                "Medium".into(),
+                "High".into(),
+                DropdownEntry::Separator,
+                DropdownEntry::item("Custom")
+                    .disabled()
+                    .with_tooltip("Edit individual settings to use a custom preset"),
+            ],
+            selected_index: 2,
+            ..default()
+        };
+        dropdown.set_open(opened);
+        self.commands
+            .spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(140.0),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: self.theme.button.normal.into(),
+                    ..default()
+                },
+                dropdown,
+            ))
+            .add_child(label)
+            .id()
+    }
+
+    fn progress_bar(&mut self, value: f32) -> Entity {
+        let bar = ProgressBar {
+            value,
+            background_color: self.theme.slider.background,
+            fill_color: self.theme.slider.foreground,
+            show_text: true,
+        };
+        let style = Style {
+            width: Val::Px(140.0),
+            height: Val::Px(16.0),
+            ..default()
+        };
+        spawn_progress_bar(self.commands, bar, style)
+    }
+
+    fn bar_style() -> Style {
+        Style {
+            width: Val::Px(160.0),
+            height: Val::Px(14.0),
+            ..default()
+        }
+    }
+}
+
+/// Spawns the gallery and returns its root entity
+fn spawn_gallery(commands: &mut Commands, theme: &Theme, actions: GalleryActions) -> Entity {
+    let mut gallery = GalleryBuilder {
+        commands,
+        theme,
+        font: UiFonts::MEDIUM,
+    };
+    let mut sections = Vec::new();
+
+    // Buttons: static swatches for states the pointer can't hold still, plus live ones
+    let states = [
+        ("Normal", Interaction::None),
+        ("Hovered", Interaction::Hovered),
+        ("Pressed", Interaction::Pressed),
+    ];
+    let mut items: Vec<Entity> = states
+        .into_iter()
+        .map(|(label, interaction)| {
+            let swatch = gallery.swatch("Button", theme.button_color(interaction), None, theme.text.normal);
+            gallery.item(label, swatch)
+        })
+        .collect();
+    let swatch = gallery.swatch("Button", theme.button.disabled, None, theme.text.disabled);
+    items.push(gallery.item("Disabled", swatch));
+    let live = gallery.button("Hover me").tooltip("Tooltip text").spawn(gallery.commands);
+    items.push(gallery.item("Live + tooltip", live));
+    let disabled = gallery.button("Can't touch").disabled(true).spawn(gallery.commands);
+    items.push(gallery.item("Live disabled", disabled));
+    let modal = gallery
+        .button("Open modal")
+        .on_click(actions.open_modal)
+        .spawn(gallery.commands);
+    items.push(gallery.item("Modal", modal));
+    sections.push(gallery.section("Buttons", &items));
+
+    // Checkboxes
+    let items = [
+        ("Unchecked", false, false),
+        ("Checked", true, false),
+        ("Disabled", false, true),
+        ("Disabled checked", true, true),
+    ]
+    .map(|(caption, checked, disabled)| {
+        let checkbox = gallery.checkbox().checked(checked).disabled(disabled).spawn(gallery.commands);
+        gallery.item(caption, checkbox)
+    });
+    let labeled = gallery.checkbox().label("Fullscreen").spawn(gallery.commands);
+    let labeled = gallery.item("Labeled", labeled);
+    sections.push(gallery.section("Checkboxes", &[items.as_slice(), &[labeled]].concat()));
+
+    // Sliders
+    let mut items: Vec<Entity> = [("Empty", 0.0), ("Half", 0.5), ("Full", 1.0)]
+        .into_iter()
+        .map(|(caption, value)| {
+            let slider = gallery.slider().value(value).spawn(gallery.commands);
+            gallery.item(caption, slider)
+        })
+        .collect();
+    let stepped = gallery.slider().range(0.0, 10.0).step(1.0).value(3.0).spawn(gallery.commands);
+    items.push(gallery.item("Stepped 0-10", stepped));
+    let disabled = gallery.slider().value(0.3).disabled(true).spawn(gallery.commands);
+    items.push(gallery.item("Disabled", disabled));
+    let labeled = gallery.slider().value(0.7).label("Volume").spawn(gallery.commands);
+    items.push(gallery.item("Labeled", labeled));
+    let with_value = gallery
+        .slider()
+        .range(0.0, 100.0)
+        .value(65.0)
+        .format("{:.0}%")
+        .show_value()
+        .spawn(gallery.commands);
+    items.push(gallery.item("Typed value", with_value));
+    sections.push(gallery.section("Sliders", &items));
+
+    // Dropdowns
+    let closed = gallery.dropdown(false);
+    let closed = gallery.item("Closed", closed);
+    let opened = gallery.dropdown(true);
+    let opened = gallery.item("Open", opened);
+    sections.push(gallery.section("Dropdowns", &[closed, opened]));
+
+    // Lists
+    let saves = ["Autosave", "Mission 3", "Mission 2", "Sandbox", "Mission 1"]
+        .map(String::from)
+        .to_vec();
+    let single = gallery
+        .commands
+        .spawn(list_view_bundle(
+            UiListView::new(saves.clone()).with_selected(0),
+            120.0,
+        ))
+        .id();
+    let single = gallery.item("Single", single);
+    let multi = gallery
+        .commands
+        .spawn(list_view_bundle(UiListView::new(saves).multi(), 120.0))
+        .id();
+    let multi = gallery.item("Multi", multi);
+    let replays = (1..=5000).map(|n| format!("Replay {n:04}")).collect();
+    let virtualized = gallery
+        .commands
+        .spawn(list_view_bundle(
+            UiListView::new(replays).virtualized(4),
+            120.0,
+        ))
+        .id();
+    let virtualized = gallery.item("5000 rows", virtualized);
+    sections.push(gallery.section("Lists", &[single, multi, virtualized]));
+
+    // Tables
+    let table = UiTable::new(vec![
+        UiTableColumn::new("Player").width(Val::Px(140.0)),
+        UiTableColumn::new("Score").width(Val::Px(80.0)),
+        UiTableColumn::new("Ping").width(Val::Px(80.0)),
+    ])
+    .row(vec![TableCell::text("Ada"), TableCell::number(1200.0), TableCell::text("32 ms").sort_by(32.0)])
+    .row(vec![TableCell::text("Brin"), TableCell::number(950.0), TableCell::text("110 ms").sort_by(110.0)])
+    .row(vec![TableCell::text("Cato"), TableCell::number(1430.0), TableCell::text("58 ms").sort_by(58.0)])
+    .sorted_by(1, SortDirection::Descending);
+    let table = gallery.commands.spawn(table_bundle(table)).id();
+    let table = gallery.item("Scoreboard", table);
+    sections.push(gallery.section("Tables", &[table]));
+
+    // Bars
+    let mut items = Vec::new();
+    for value in [0.25, 0.75] {
+        let bar = gallery.progress_bar(value);
+        items.push(gallery.item(&format!("Progress {:.0}%", value * 100.0), bar));
+    }
+    let stacked = gallery
+        .commands
+        .spawn((
+            stacked_bar_bundle(GalleryBuilder::bar_style()),
+            StackedBar {
+                segments: vec![
+                    BarSegment::new(0.4, theme.slider.foreground).with_tooltip("Food 40%"),
+                    BarSegment::new(0.25, theme.focus.border).with_tooltip("Wood 25%"),
+                    BarSegment::new(0.15, theme.button.pressed).with_tooltip("Stone 15%"),
+                ],
+                ..default()
+            },
+        ))
+        .id();
+    items.push(gallery.item("Stacked", stacked));
+    for (caption, projected, label) in [
+        ("Increase", 0.8, DeltaLabel::Percent),
+        ("Decrease", 0.35, DeltaLabel::Absolute),
+        ("Unlabeled", 0.6, DeltaLabel::Hidden),
+    ] {
+        let bar = gallery
+            .commands
+            .spawn((
+                comparison_bar_bundle(GalleryBuilder::bar_style()),
+                ComparisonBar {
+                    base_color: theme.slider.foreground,
+                    label,
+                    ..ComparisonBar::new(0.5, projected, 1.0)
+                },
+            ))
+            .id();
+        items.push(gallery.item(caption, bar));
+    }
+    sections.push(gallery.section("Bars", &items));
+
+    // Status chips
+    let mut items = Vec::new();
+    for (caption, chip) in [
+        ("Success", StatusChip::new("Ready", StatusKind::Success)),
+        ("Warning", StatusChip::new("Outdated", StatusKind::Warning)),
+        ("Error", StatusChip::new("Full", StatusKind::Error)),
+        ("Info dismissible", StatusChip::new("New", StatusKind::Info).dismissible()),
+    ] {
+        let chip = gallery.commands.spawn(status_chip_bundle(chip)).id();
+        items.push(gallery.item(caption, chip));
+    }
+    sections.push(gallery.section("Status chips", &items));
+
+    // Nameplates
+    let mut items = Vec::new();
+    for (caption, connection, compact) in [
+        ("Full", ConnectionQuality::Good, false),
+        ("Full, poor connection", ConnectionQuality::Poor, false),
+        ("Compact", ConnectionQuality::Fair, true),
+        ("Compact, disconnected", ConnectionQuality::Disconnected, true),
+    ] {
+        let mut nameplate = Nameplate::new("Player", theme.status.info);
+        nameplate.connection = connection;
+        if compact {
+            nameplate = nameplate.compact();
+        }
+        let nameplate = gallery.commands.spawn(nameplate_bundle(nameplate)).id();
+        items.push(gallery.item(caption, nameplate));
+    }
+    sections.push(gallery.section("Nameplates", &items));
+
+    // Step indicators
+    let phases = ["Lobby", "Loadout", "Deploy", "Battle"];
+    let in_progress = StepIndicator::new(phases).with_current(2);
+    let mut failed = StepIndicator::new(phases).with_current(1);
+    failed.set_error(1);
+    let mut items = Vec::new();
+    for (caption, indicator) in [("In progress", in_progress), ("Failed", failed)] {
+        let indicator = gallery.commands.spawn(step_indicator_bundle(indicator)).id();
+        items.push(gallery.item(caption, indicator));
+    }
+    sections.push(gallery.section("Step indicators", &items));
+
+    // Toolbars
+    let icon = Handle::<Image>::default();
+    let toolbar = Toolbar::new()
+        .button("new", icon.clone(), "New")
+        .button("open", icon.clone(), "Open")
+        .button("save", icon.clone(), "Save")
+        .separator()
+        .button("undo", icon.clone(), "Undo")
+        .button("redo", icon.clone(), "Redo")
+        .separator()
+        .button("settings", icon, "Settings");
+    let mut items = Vec::new();
+    for (caption, width) in [("Wide", 320.0), ("Overflowing", 160.0)] {
+        let (mut node, toolbar) = toolbar_bundle(toolbar.clone());
+        node.style.width = Val::Px(width);
+        let toolbar = gallery.commands.spawn((node, toolbar)).id();
+        items.push(gallery.item(caption, toolbar));
+    }
+    sections.push(gallery.section("Toolbars", &items));
+
+    // Menus
+    let menus = vec![
+        MenuEntry::submenu(
+            "File",
+            vec![
+                MenuEntry::item("new", "New").hotkey("Ctrl+N"),
+                MenuEntry::submenu(
+                    "Open Recent",
+                    vec![
+                        MenuEntry::item("recent_1", "campaign.sav"),
+                        MenuEntry::item("recent_2", "skirmish.sav"),
+                    ],
+                ),
+                MenuEntry::Separator,
+                MenuEntry::item("quit", "Quit").hotkey("Alt+F4"),
+            ],
+        ),
+        MenuEntry::submenu(
+            "Edit",
+            vec![
+                MenuEntry::item("undo", "Undo").hotkey("Ctrl+Z"),
+                MenuEntry::item("redo", "Redo").hotkey("Ctrl+Y").disabled(),
+            ],
+        ),
+    ];
+    let mut items = Vec::new();
+    for (caption, open_path) in [("Closed", vec![]), ("Submenu open", vec![0, 1])] {
+        let (mut node, mut menu, bar) = menu_bar_bundle(menus.clone());
+        node.style.width = Val::Px(320.0);
+        let alt = open_path.is_empty();
+        menu.open_path = open_path;
+        let menu = gallery.commands.spawn((node, menu)).id();
+        // Only the closed bar opens with Alt
+        if alt {
+            gallery.commands.entity(menu).insert(bar);
+        }
+        items.push(gallery.item(caption, menu));
+    }
+    sections.push(gallery.section("Menus", &items));
+
+    // Text
+    let normal = gallery.text("Regular text", 18.0, theme.text.normal);
+    let normal = gallery.item("Normal", normal);
+    let disabled = gallery.text("Disabled text", 18.0, theme.text.disabled);
+    let disabled = gallery.item("Disabled", disabled);
+    let focus = gallery.swatch("Focused", theme.focus.highlight, Some(theme.focus.border), theme.focus.text);
+    let focus = gallery.item("Focus highlight", focus);
+    let ellipsis = gallery.text("", 18.0, theme.text.normal);
+    gallery
+        .commands
+        .entity(ellipsis)
+        .insert(AutoEllipsis::new("An extremely long map name that cannot fit").with_max_width(140.0));
+    let ellipsis = gallery.item("Auto ellipsis", ellipsis);
+    sections.push(gallery.section("Text", &[normal, disabled, focus, ellipsis]));
+
+    gallery
+        .commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(20.0)),
+                    row_gap: Val::Px(18.0),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                background_color: Color::srgb(0.06, 0.06, 0.08).into(),
+                ..default()
+            },
+            GalleryRoot,
+            Name::new("WidgetGallery"),
+        ))
+        .push_children(&sections)
+        .id()
+}
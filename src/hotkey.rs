@@ -0,0 +1,237 @@
+//! Scoped keyboard shortcuts
+//!
+//! A [`Hotkey`] on an entity sends [`HotkeyTriggered`] when its [`KeyChord`]
+//! is pressed, within the limits of its [`HotkeyScope`]:
+//!
+//! - [`Global`](HotkeyScope::Global) hotkeys always fire, even behind a modal.
+//! - [`CurrentScreen`](HotkeyScope::CurrentScreen) hotkeys fire while their
+//!   entity is visible and not behind an open modal, so an editor's Ctrl+S
+//!   waits while a dialog asks a question.
+//! - [`FocusedPanel`](HotkeyScope::FocusedPanel) hotkeys fire only while the
+//!   entity, typically a panel, holds the [`UiFocus`] or contains it.
+//!
+//! When several hotkeys share a chord only one fires, picked in that order
+//! from most to least specific: the focused panel nearest the focus, then the
+//! current screen, then global. Nothing fires while a
+//! [`KeyBindButton`](crate::KeyBindButton) is listening for a new binding.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{Hotkey, HotkeyScope, HotkeyTriggered, KeyChord};
+//! fn setup(mut commands: Commands, editor: Entity, layers_panel: Entity) {
+//!     commands
+//!         .entity(editor)
+//!         .insert(Hotkey::new("save", KeyChord::new(KeyCode::KeyS).ctrl()));
+//!     commands.entity(layers_panel).insert(
+//!         Hotkey::new("delete_layer", KeyChord::new(KeyCode::Delete))
+//!             .scope(HotkeyScope::FocusedPanel),
+//!     );
+//! }
+//!
+//! fn run(mut triggered: EventReader<HotkeyTriggered>) {
+//!     for event in triggered.read() {
+//!         info!("{} pressed", event.action);
+//!     }
+//! }
+//! ```
+
+use std::cmp::Reverse;
+
+use bevy::prelude::*;
+
+use crate::focus::UiFocus;
+use crate::keybind::{InputBinding, KeyBindButton};
+use crate::modal::{top_modal, ModalDialog};
+
+/// A key with the modifiers that must be held with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub struct KeyChord {
+    /// The key that triggers the chord
+    pub key: KeyCode,
+    /// Whether either Ctrl key must be held
+    pub ctrl: bool,
+    /// Whether either Shift key must be held
+    pub shift: bool,
+    /// Whether either Alt key must be held
+    pub alt: bool,
+}
+
+impl KeyChord {
+    /// A chord of `key` alone
+    pub fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    /// Requires Ctrl
+    pub fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    /// Requires Shift
+    pub fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// Requires Alt
+    pub fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// Whether the chord was pressed this frame, with exactly its modifiers held
+    pub fn just_pressed(&self, keyboard_input: &ButtonInput<KeyCode>) -> bool {
+        let held = |keys| keyboard_input.any_pressed(keys);
+        keyboard_input.just_pressed(self.key)
+            && held([KeyCode::ControlLeft, KeyCode::ControlRight]) == self.ctrl
+            && held([KeyCode::ShiftLeft, KeyCode::ShiftRight]) == self.shift
+            && held([KeyCode::AltLeft, KeyCode::AltRight]) == self.alt
+    }
+
+    /// Human-readable name, such as "Ctrl+Shift+S"
+    pub fn name(&self) -> String {
+        let modifiers = [
+            (self.ctrl, "Ctrl+"),
+            (self.shift, "Shift+"),
+            (self.alt, "Alt+"),
+        ];
+        let mut name: String = modifiers
+            .iter()
+            .filter(|(held, _)| *held)
+            .map(|(_, text)| *text)
+            .collect();
+        name.push_str(&InputBinding::Key(self.key).name());
+        name
+    }
+}
+
+/// Where a [`Hotkey`] is active, from least to most specific
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum HotkeyScope {
+    /// Always, even behind a modal
+    Global,
+    /// While the entity is visible and not behind a modal
+    #[default]
+    CurrentScreen,
+    /// While the entity holds or contains the focus
+    FocusedPanel,
+}
+
+/// A keyboard shortcut for an action
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Hotkey {
+    /// The action sent with [`HotkeyTriggered`], such as "save"
+    pub action: String,
+    /// The keys that trigger it
+    pub chord: KeyChord,
+    /// Where it is active
+    pub scope: HotkeyScope,
+}
+
+impl Hotkey {
+    /// Creates a hotkey for `action`, active on the current screen
+    pub fn new(action: impl Into<String>, chord: KeyChord) -> Self {
+        Self {
+            action: action.into(),
+            chord,
+            scope: HotkeyScope::default(),
+        }
+    }
+
+    /// Sets where the hotkey is active
+    pub fn scope(mut self, scope: HotkeyScope) -> Self {
+        self.scope = scope;
+        self
+    }
+}
+
+/// Sent when a [`Hotkey`] fires
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct HotkeyTriggered {
+    /// The entity carrying the hotkey
+    pub entity: Entity,
+    /// The hotkey's action
+    pub action: String,
+    /// The scope it fired in
+    pub scope: HotkeyScope,
+}
+
+/// System to fire the most specific active hotkey of each pressed chord
+pub fn hotkey_system(
+    hotkeys: Query<(Entity, &Hotkey, &InheritedVisibility)>,
+    key_bind_buttons: Query<&KeyBindButton>,
+    modals: Query<(Entity, &ModalDialog)>,
+    parents: Query<&Parent>,
+    focus: Res<UiFocus>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut triggered_events: EventWriter<HotkeyTriggered>,
+) {
+    if keyboard_input.get_just_pressed().next().is_none()
+        || key_bind_buttons.iter().any(|button| button.listening)
+    {
+        return;
+    }
+    let modal = top_modal(&modals);
+    let inside = |entity: Entity, root: Entity| {
+        entity == root
+            || parents
+                .iter_ancestors(entity)
+                .any(|ancestor| ancestor == root)
+    };
+    // How many levels the focus sits below `entity`, if it is inside it
+    let focus_depth = |entity: Entity| {
+        let focused = focus.focused?;
+        std::iter::once(focused)
+            .chain(parents.iter_ancestors(focused))
+            .position(|node| node == entity)
+    };
+
+    // Best candidate per chord: scope first, then nearness to the focus
+    let mut winners: Vec<(KeyChord, (HotkeyScope, Reverse<usize>), Entity)> = Vec::new();
+    for (entity, hotkey, visibility) in &hotkeys {
+        if !hotkey.chord.just_pressed(&keyboard_input) {
+            continue;
+        }
+        let unblocked = modal.is_none_or(|modal| inside(entity, modal));
+        let rank = match hotkey.scope {
+            HotkeyScope::Global => Some(0),
+            HotkeyScope::CurrentScreen => (visibility.get() && unblocked).then_some(0),
+            HotkeyScope::FocusedPanel => focus_depth(entity).filter(|_| unblocked),
+        };
+        let Some(depth) = rank else {
+            continue;
+        };
+        let key = (hotkey.scope, Reverse(depth));
+        match winners
+            .iter_mut()
+            .find(|(chord, ..)| *chord == hotkey.chord)
+        {
+            Some(winner) if key > winner.1 => *winner = (hotkey.chord, key, entity),
+            Some(_) => {}
+            None => winners.push((hotkey.chord, key, entity)),
+        }
+    }
+
+    for (_, (scope, _), entity) in winners {
+        if let Ok((_, hotkey, _)) = hotkeys.get(entity) {
+            triggered_events.send(HotkeyTriggered {
+                entity,
+                action: hotkey.action.clone(),
+                scope,
+            });
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Hotkey>()
+        .add_event::<HotkeyTriggered>()
+        .add_systems(Update, hotkey_system);
+}
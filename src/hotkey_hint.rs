@@ -0,0 +1,279 @@
+//! Hotkey hints over widgets
+//!
+//! While the [`HotkeyHints`] overlay is visible, every widget with a
+//! [`Hotkey`] gets a small label at its top-right corner naming the keys, as
+//! RTS games do while Alt is held. By default the hints show while left Alt
+//! is held; [`HotkeyHints::mode`] can make the key toggle them instead, and
+//! [`HotkeyHints::show`] and [`HotkeyHints::hide`] control them from code,
+//! for example from a help button.
+//!
+//! Widgets whose key is handled elsewhere register a [`HotkeyHint`]: either
+//! the entity carrying the [`Hotkey`], such as a panel that owns the
+//! shortcut for one of its buttons, whose hint then moves to the widget, or
+//! a fixed text for keys the game reads itself:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{Hotkey, HotkeyHint, HotkeyHints, KeyChord};
+//! fn setup(mut commands: Commands, panel: Entity, attack_button: Entity, stop_button: Entity) {
+//!     commands
+//!         .entity(panel)
+//!         .insert(Hotkey::new("attack", KeyChord::new(KeyCode::KeyA)));
+//!     commands
+//!         .entity(attack_button)
+//!         .insert(HotkeyHint::hotkey(panel));
+//!     commands.entity(stop_button).insert(HotkeyHint::text("S"));
+//! }
+//!
+//! fn show_help(mut hints: ResMut<HotkeyHints>) {
+//!     hints.show();
+//! }
+//! ```
+//!
+//! Games with a [`MenuBar`](crate::MenuBar), which opens on a tap of Alt,
+//! may want another [`HotkeyHints::key`].
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::assets::UiFonts;
+use crate::hotkey::Hotkey;
+use crate::placement::{clamp_to_area, PopupBounds};
+use crate::theme::Theme;
+use crate::utils::{first_section, first_section_is};
+
+/// Z-index of hotkey hint labels, above widgets and below tooltips
+const HOTKEY_HINT_Z_INDEX: i32 = 900;
+
+/// How [`HotkeyHints::key`] shows the hints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum HotkeyHintMode {
+    /// While the key is held
+    #[default]
+    Hold,
+    /// Each press shows or hides them
+    Toggle,
+}
+
+/// Whether hotkey hints are shown, and the key that shows them
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct HotkeyHints {
+    /// Key that shows the hints; none leaves them to [`show`](Self::show)
+    /// and [`hide`](Self::hide)
+    pub key: Option<KeyCode>,
+    /// How the key shows the hints
+    pub mode: HotkeyHintMode,
+    held: bool,
+    shown: bool,
+}
+
+impl Default for HotkeyHints {
+    fn default() -> Self {
+        Self {
+            key: Some(KeyCode::AltLeft),
+            mode: HotkeyHintMode::default(),
+            held: false,
+            shown: false,
+        }
+    }
+}
+
+impl HotkeyHints {
+    /// Shows the hints until [`hide`](Self::hide)
+    pub fn show(&mut self) {
+        self.shown = true;
+    }
+
+    /// Hides hints shown by [`show`](Self::show) or a toggle
+    pub fn hide(&mut self) {
+        self.shown = false;
+    }
+
+    /// Shows the hints if hidden, and hides them otherwise
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    /// Whether the hints are shown
+    pub fn is_visible(&self) -> bool {
+        self.held || self.shown
+    }
+}
+
+/// Shows a hotkey hint on a widget whose hotkey is handled elsewhere
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum HotkeyHint {
+    /// The keys of the [`Hotkey`] on another entity
+    Hotkey(Entity),
+    /// Fixed text
+    Text(String),
+}
+
+impl HotkeyHint {
+    /// Hints the keys of the [`Hotkey`] on `entity`
+    pub fn hotkey(entity: Entity) -> Self {
+        Self::Hotkey(entity)
+    }
+
+    /// Hints `text`
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+/// A hint label spawned while the overlay is shown
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct HotkeyHintLabel {
+    /// The widget the label is for
+    pub widget: Entity,
+}
+
+/// System to show the hints while [`HotkeyHints::key`] is held, or toggle
+/// them when it is pressed
+pub fn hotkey_hints_input_system(
+    mut hints: ResMut<HotkeyHints>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    let Some(key) = hints.key else {
+        if hints.held {
+            hints.held = false;
+        }
+        return;
+    };
+    match hints.mode {
+        HotkeyHintMode::Hold => {
+            let held = keyboard_input.pressed(key);
+            if hints.held != held {
+                hints.held = held;
+            }
+        }
+        HotkeyHintMode::Toggle => {
+            if keyboard_input.just_pressed(key) {
+                hints.toggle();
+            }
+        }
+    }
+}
+
+/// System to spawn, place and despawn hint labels for visible widgets with a
+/// [`Hotkey`] or [`HotkeyHint`]
+pub fn hotkey_hint_system(
+    mut commands: Commands,
+    hints: Res<HotkeyHints>,
+    widgets: Query<
+        (
+            Entity,
+            Option<&Hotkey>,
+            Option<&HotkeyHint>,
+            &Node,
+            &GlobalTransform,
+            &ViewVisibility,
+        ),
+        Or<(With<Hotkey>, With<HotkeyHint>)>,
+    >,
+    hotkeys: Query<&Hotkey>,
+    mut labels: Query<(
+        Entity,
+        &HotkeyHintLabel,
+        &Node,
+        &mut Style,
+        &mut Text,
+        &mut Visibility,
+    )>,
+    (theme, bounds): (Res<Theme>, Res<PopupBounds>),
+) {
+    let mut wanted = HashMap::default();
+    if hints.is_visible() {
+        // Hotkeys whose hint a registered widget shows instead
+        let moved: Vec<Entity> = widgets
+            .iter()
+            .filter_map(|(_, _, hint, ..)| match hint {
+                Some(HotkeyHint::Hotkey(owner)) => Some(*owner),
+                _ => None,
+            })
+            .collect();
+        for (entity, hotkey, hint, node, transform, visibility) in &widgets {
+            if hint.is_none() && moved.contains(&entity) {
+                continue;
+            }
+            let text = match hint {
+                Some(HotkeyHint::Hotkey(owner)) => {
+                    hotkeys.get(*owner).ok().map(|hotkey| hotkey.chord.name())
+                }
+                Some(HotkeyHint::Text(text)) => Some(text.clone()),
+                None => hotkey.map(|hotkey| hotkey.chord.name()),
+            };
+            if let Some(text) = text.filter(|_| visibility.get()) {
+                wanted.insert(entity, (text, node.logical_rect(transform)));
+            }
+        }
+    }
+
+    for (label, HotkeyHintLabel { widget }, node, mut style, mut text, mut visibility) in
+        &mut labels
+    {
+        let Some((hint, rect)) = wanted.remove(widget) else {
+            commands.entity(label).despawn_recursive();
+            continue;
+        };
+        if !first_section_is(&text, &hint) {
+            first_section(&mut text).value = hint;
+        }
+        // Centered on the widget's top-right corner, kept on its monitor
+        let size = node.size();
+        if size == Vec2::ZERO {
+            continue;
+        }
+        let mut position = Vec2::new(rect.max.x - size.x / 2.0, rect.min.y - size.y / 2.0);
+        if let Some(area) = bounds.area_at(rect.center()) {
+            position = clamp_to_area(position, size, area, 0.0);
+        }
+        if style.left != Val::Px(position.x) || style.top != Val::Px(position.y) {
+            style.left = Val::Px(position.x);
+            style.top = Val::Px(position.y);
+        }
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+
+    // Labels start hidden and are placed once their size is known
+    for (widget, (hint, _)) in wanted {
+        commands.spawn((
+            TextBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    padding: UiRect::axes(Val::Px(4.0), Val::Px(1.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.85).into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(HOTKEY_HINT_Z_INDEX),
+                ..TextBundle::from_section(
+                    hint,
+                    TextStyle {
+                        font: UiFonts::BOLD,
+                        font_size: 12.0,
+                        color: theme.text.normal,
+                    },
+                )
+            },
+            BorderColor(theme.focus.border),
+            HotkeyHintLabel { widget },
+            Name::new("HotkeyHintLabel"),
+        ));
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<HotkeyHints>()
+        .register_type::<HotkeyHint>()
+        .register_type::<HotkeyHintLabel>()
+        .init_resource::<HotkeyHints>()
+        .add_systems(
+            Update,
+            (hotkey_hints_input_system, hotkey_hint_system).chain(),
+        );
+}
@@ -0,0 +1,286 @@
+//! Pointer interaction arbitration for UI widgets
+//!
+//! Bevy sets [`Interaction`] on every node under the cursor that isn't blocked,
+//! so two widgets can both see `Pressed` in the same frame (for example a freshly
+//! opened dropdown and the button underneath it). The arbitration pass resolves a
+//! single winning target per frame and publishes it in [`UiInteractionState`],
+//! which widget systems read instead of racing on each other's mutations.
+//!
+//! Small targets such as close buttons and slider handles can take a
+//! [`HitboxPadding`] to react to the pointer a little outside their drawn
+//! bounds. The padding is applied to [`Interaction`] before arbitration, so
+//! every widget system sees the larger hitbox without any layout change.
+
+use bevy::prelude::*;
+use bevy::ui::{UiStack, UiSystem};
+use bevy::utils::HashMap;
+use bevy::window::PrimaryWindow;
+
+/// System set containing the interaction arbitration pass.
+///
+/// Runs in `PreUpdate` after Bevy's UI focus system, so every `Update` system
+/// sees the same resolved state for the whole frame.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InteractionArbitrationSet;
+
+/// Resolved pointer targets for a single frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub struct InteractionFrame {
+    /// Topmost entity under the pointer, if any
+    pub hovered: Option<Entity>,
+    /// Topmost entity being pressed, if any
+    pub pressed: Option<Entity>,
+    /// Entity activated without the pointer (keyboard or gamepad), if any
+    pub activated: Option<Entity>,
+}
+
+/// Double-buffered interaction state shared by all widget systems
+///
+/// `current` is written once per frame by the arbitration pass; `previous`
+/// holds the last frame's result so edge transitions can be detected without
+/// depending on system ordering.
+#[derive(Resource, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Resource)]
+pub struct UiInteractionState {
+    /// Targets resolved for the current frame
+    pub current: InteractionFrame,
+    /// Targets resolved for the previous frame
+    pub previous: InteractionFrame,
+}
+
+impl UiInteractionState {
+    /// Returns the entity that won the pointer this frame (pressed before hovered)
+    pub fn winner(&self) -> Option<Entity> {
+        self.current.pressed.or(self.current.hovered)
+    }
+
+    /// Whether `entity` is the topmost hovered target
+    pub fn is_hovered(&self, entity: Entity) -> bool {
+        self.current.hovered == Some(entity)
+    }
+
+    /// Whether `entity` is the topmost pressed target
+    pub fn is_pressed(&self, entity: Entity) -> bool {
+        self.current.pressed == Some(entity)
+    }
+
+    /// Whether `entity` became the pressed target or was activated this frame
+    pub fn just_pressed(&self, entity: Entity) -> bool {
+        self.just_pointer_pressed(entity) || self.current.activated == Some(entity)
+    }
+
+    /// Whether `entity` became the pressed target this frame through the pointer
+    ///
+    /// Use this instead of [`just_pressed`](Self::just_pressed) for gestures
+    /// that track the cursor, such as drags.
+    pub fn just_pointer_pressed(&self, entity: Entity) -> bool {
+        self.is_pressed(entity) && self.previous.pressed != Some(entity)
+    }
+
+    /// Injects a non-pointer activation of `entity` for the current frame
+    ///
+    /// Widget systems see it through [`just_pressed`](Self::just_pressed) exactly
+    /// like a click. Call this after [`InteractionArbitrationSet`].
+    pub fn activate(&mut self, entity: Entity) {
+        self.current.activated = Some(entity);
+    }
+
+    /// Whether `entity` stopped being the pressed target this frame
+    pub fn just_released(&self, entity: Entity) -> bool {
+        self.previous.pressed == Some(entity) && self.current.pressed != Some(entity)
+    }
+}
+
+/// Entity that currently owns the pointer for a drag gesture
+///
+/// Widgets capture the pointer on press so they keep receiving movement after
+/// the cursor leaves their bounds. The capture is released automatically when
+/// the left mouse button goes up.
+#[derive(Resource, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PointerCapture {
+    captured: Option<Entity>,
+}
+
+impl PointerCapture {
+    /// Captures the pointer for `entity`, replacing any previous capture
+    pub fn capture(&mut self, entity: Entity) {
+        self.captured = Some(entity);
+    }
+
+    /// Releases the pointer capture
+    pub fn release(&mut self) {
+        self.captured = None;
+    }
+
+    /// Returns the entity holding the capture, if any
+    pub fn captured(&self) -> Option<Entity> {
+        self.captured
+    }
+
+    /// Whether `entity` holds the capture
+    pub fn is_captured(&self, entity: Entity) -> bool {
+        self.captured == Some(entity)
+    }
+}
+
+/// Enlarges a node's interactive area past its visual bounds
+///
+/// Each side of the rect extends the hitbox outward by that much; percentages
+/// are of the node's own width or height. A node in front of the padding
+/// still takes the pointer first.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct HitboxPadding(pub UiRect);
+
+impl HitboxPadding {
+    /// The same padding on every side
+    pub fn all(padding: Val) -> Self {
+        Self(UiRect::all(padding))
+    }
+}
+
+/// System to hover and press padded nodes when the pointer is in their padding
+///
+/// Bevy's focus pass only tests drawn bounds; this runs right after it and
+/// turns `Interaction::None` into `Hovered` or `Pressed` for the front-most
+/// node whose [`HitboxPadding`] holds the cursor, unless a node in front of
+/// it already has the pointer.
+pub fn hitbox_padding_system(
+    mut nodes: Query<(
+        Entity,
+        &mut Interaction,
+        Option<(&HitboxPadding, &Node, &GlobalTransform)>,
+    )>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_stack: Res<UiStack>,
+    ui_scale: Res<UiScale>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+) {
+    let Some((cursor, viewport)) = windows.get_single().ok().and_then(|window| {
+        let cursor = window.cursor_position()? / ui_scale.0;
+        Some((cursor, window.size() / ui_scale.0))
+    }) else {
+        return;
+    };
+    if nodes.iter().all(|(.., padded)| padded.is_none()) {
+        return;
+    }
+    let depths: HashMap<Entity, usize> = ui_stack
+        .uinodes
+        .iter()
+        .enumerate()
+        .map(|(depth, &node)| (node, depth))
+        .collect();
+
+    // The front-most node Bevy already gave the pointer to
+    let mut front = None;
+    // The front-most padded node with the cursor in its padding
+    let mut candidate = None;
+    for (entity, interaction, padded) in &nodes {
+        let Some(&depth) = depths.get(&entity) else {
+            continue;
+        };
+        if *interaction != Interaction::None {
+            front = front.max(Some(depth));
+            continue;
+        }
+        let Some((padding, node, transform)) = padded else {
+            continue;
+        };
+        let rect = node.logical_rect(transform);
+        let resolve = |val: Val, size: f32| val.resolve(size, viewport).unwrap_or(0.0);
+        let padding = padding.0;
+        let hitbox = Rect {
+            min: rect.min
+                - Vec2::new(
+                    resolve(padding.left, rect.width()),
+                    resolve(padding.top, rect.height()),
+                ),
+            max: rect.max
+                + Vec2::new(
+                    resolve(padding.right, rect.width()),
+                    resolve(padding.bottom, rect.height()),
+                ),
+        };
+        if hitbox.contains(cursor) && candidate.is_none_or(|(front, _)| depth > front) {
+            candidate = Some((depth, entity));
+        }
+    }
+
+    let Some((depth, entity)) = candidate else {
+        return;
+    };
+    if front.is_some_and(|front| front > depth) {
+        return;
+    }
+    if let Ok((_, mut interaction, _)) = nodes.get_mut(entity) {
+        *interaction = if mouse_buttons.just_pressed(MouseButton::Left) {
+            Interaction::Pressed
+        } else {
+            Interaction::Hovered
+        };
+    }
+}
+
+/// Releases the pointer capture on mouse-up
+pub fn pointer_capture_release_system(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut capture: ResMut<PointerCapture>,
+) {
+    if capture.captured.is_some() && !mouse_buttons.pressed(MouseButton::Left) {
+        capture.release();
+    }
+}
+
+/// Resolves one winning hovered/pressed target per frame
+///
+/// Candidates are ranked by [`UiStack`] order, so the front-most node wins.
+pub fn interaction_arbitration_system(
+    ui_stack: Res<UiStack>,
+    interactions: Query<&Interaction>,
+    mut state: ResMut<UiInteractionState>,
+) {
+    let mut frame = InteractionFrame::default();
+
+    // UiStack is ordered back-to-front, so walk it in reverse
+    for &entity in ui_stack.uinodes.iter().rev() {
+        let Ok(interaction) = interactions.get(entity) else {
+            continue;
+        };
+        match interaction {
+            Interaction::Pressed if frame.pressed.is_none() => {
+                frame.pressed = Some(entity);
+                frame.hovered.get_or_insert(entity);
+            }
+            Interaction::Hovered if frame.hovered.is_none() => {
+                frame.hovered = Some(entity);
+            }
+            _ => {}
+        }
+        if frame.pressed.is_some() && frame.hovered.is_some() {
+            break;
+        }
+    }
+
+    state.previous = state.current;
+    state.current = frame;
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiInteractionState>()
+        .register_type::<PointerCapture>()
+        .register_type::<HitboxPadding>()
+        .init_resource::<UiInteractionState>()
+        .init_resource::<PointerCapture>()
+        .configure_sets(PreUpdate, InteractionArbitrationSet.after(UiSystem::Focus))
+        .add_systems(
+            PreUpdate,
+            (
+                hitbox_padding_system.before(interaction_arbitration_system),
+                interaction_arbitration_system,
+                pointer_capture_release_system,
+            )
+                .in_set(InteractionArbitrationSet),
+        );
+}
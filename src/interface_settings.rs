@@ -0,0 +1,247 @@
+//! Player-facing interface settings
+//!
+//! [`InterfaceSettings`] gathers every option this crate's widgets expose to
+//! players in one resource: interface scale, tooltip delay, combat text,
+//! world UI distance, colorblind status colors, reduced motion and panel
+//! snapping. Changes are pushed into the resources the widgets read
+//! ([`WidgetScale`], [`TooltipSettings`], [`CombatTextSettings`],
+//! [`ZoomVisibilitySettings`], [`ReducedMotion`], the theme's [`StatusPalette`]
+//! and [`PanelSnapSettings`]), so set those through this resource rather
+//! than directly.
+//!
+//! The resource is bound with [`BindPlugin`](crate::BindPlugin) already, so a
+//! generated Interface tab is all a settings screen needs:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{InterfaceSettings, SettingsMenuBuilder, Theme};
+//! fn open_interface_settings(
+//!     mut commands: Commands,
+//!     theme: Res<Theme>,
+//!     settings: Res<InterfaceSettings>,
+//! ) {
+//!     SettingsMenuBuilder::new(&theme)
+//!         .interface_tab(&settings)
+//!         .spawn_bound::<InterfaceSettings>(&mut commands);
+//! }
+//! ```
+//!
+//! Edge-scroll hints have no widget in this crate; the game reads
+//! [`InterfaceSettings::edge_scroll_hints`] itself.
+
+use bevy::prelude::*;
+
+use crate::binding::BindPlugin;
+use crate::floating_text::CombatTextSettings;
+use crate::panel_snap::PanelSnapSettings;
+use crate::quality::ReducedMotion;
+use crate::scale::WidgetScale;
+use crate::settings_menu::{SettingDefinition, SettingsMenuBuilder};
+use crate::theme::{StatusPalette, Theme};
+use crate::tooltip::TooltipSettings;
+use crate::types::SettingsTab;
+use crate::zoom_visibility::ZoomVisibilitySettings;
+
+/// Status colors adapted to a color vision deficiency
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum ColorblindMode {
+    /// The theme's own colors
+    #[default]
+    Off,
+    /// Red-blind
+    Protanopia,
+    /// Green-blind
+    Deuteranopia,
+    /// Blue-blind
+    Tritanopia,
+}
+
+impl ColorblindMode {
+    /// Every mode, in the order the settings dropdown lists them
+    pub const ALL: [Self; 4] = [
+        Self::Off,
+        Self::Protanopia,
+        Self::Deuteranopia,
+        Self::Tritanopia,
+    ];
+
+    /// Status colors that stay distinct under this mode, `None` when off
+    pub fn status_palette(self) -> Option<StatusPalette> {
+        match self {
+            Self::Off => None,
+            // Blue and orange in place of green and red
+            Self::Protanopia | Self::Deuteranopia => Some(StatusPalette {
+                success: Color::srgb(0.0, 0.45, 0.7),
+                warning: Color::srgb(0.94, 0.89, 0.26),
+                error: Color::srgb(0.84, 0.37, 0.0),
+                info: Color::srgb(0.34, 0.71, 0.91),
+            }),
+            // Keeps green against red and moves warning away from yellow
+            Self::Tritanopia => Some(StatusPalette {
+                success: Color::srgb(0.0, 0.62, 0.45),
+                warning: Color::srgb(0.8, 0.47, 0.65),
+                error: Color::srgb(0.84, 0.2, 0.2),
+                info: Color::srgb(0.6, 0.6, 0.6),
+            }),
+        }
+    }
+}
+
+/// Interface options the player can change
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct InterfaceSettings {
+    /// Size of every widget, written to [`WidgetScale`]
+    pub hud_scale: f32,
+    /// Seconds before tooltips show
+    pub tooltip_delay: f32,
+    /// Whether damage numbers float over units
+    pub damage_numbers: bool,
+    /// Whether healing numbers float over units
+    pub healing_numbers: bool,
+    /// Whether arrows hint at edge scrolling when the pointer nears a window edge
+    pub edge_scroll_hints: bool,
+    /// Camera distance beyond which health bars and nameplates are hidden
+    pub world_ui_distance: f32,
+    /// Status colors for color vision deficiencies
+    pub colorblind_mode: ColorblindMode,
+    /// Whether widget animations are skipped, written to [`ReducedMotion`]
+    pub reduced_motion: bool,
+    /// Whether dragged panels snap to edges, other panels and the grid
+    pub panel_snapping: bool,
+}
+
+impl Default for InterfaceSettings {
+    fn default() -> Self {
+        Self {
+            hud_scale: 1.0,
+            tooltip_delay: 0.5,
+            damage_numbers: true,
+            healing_numbers: true,
+            edge_scroll_hints: true,
+            world_ui_distance: 90.0,
+            colorblind_mode: ColorblindMode::Off,
+            reduced_motion: false,
+            panel_snapping: true,
+        }
+    }
+}
+
+impl InterfaceSettings {
+    /// Settings rows for every field, with ids matching the field names
+    pub fn setting_definitions(&self) -> Vec<SettingDefinition> {
+        let colorblind_modes = ColorblindMode::ALL.map(|mode| format!("{mode:?}"));
+        let colorblind_index = ColorblindMode::ALL
+            .iter()
+            .position(|mode| *mode == self.colorblind_mode)
+            .unwrap_or_default();
+        vec![
+            SettingDefinition::slider("hud_scale", "Interface scale", 0.5..=2.0, self.hud_scale)
+                .step(0.05)
+                .help("Size of menus and the HUD"),
+            SettingDefinition::slider(
+                "tooltip_delay",
+                "Tooltip delay",
+                0.0..=2.0,
+                self.tooltip_delay,
+            )
+            .step(0.1)
+            .help("Seconds before a tooltip shows"),
+            SettingDefinition::checkbox("damage_numbers", "Damage numbers", self.damage_numbers),
+            SettingDefinition::checkbox("healing_numbers", "Healing numbers", self.healing_numbers),
+            SettingDefinition::checkbox(
+                "edge_scroll_hints",
+                "Edge scroll hints",
+                self.edge_scroll_hints,
+            )
+            .help("Show arrows when the pointer nears a window edge"),
+            SettingDefinition::slider(
+                "world_ui_distance",
+                "Health bar distance",
+                20.0..=200.0,
+                self.world_ui_distance,
+            )
+            .step(5.0)
+            .help("Camera distance beyond which health bars and nameplates hide"),
+            SettingDefinition::dropdown(
+                "colorblind_mode",
+                "Colorblind mode",
+                colorblind_modes,
+                colorblind_index,
+            ),
+            SettingDefinition::checkbox("reduced_motion", "Reduced motion", self.reduced_motion)
+                .help("Skip widget animations"),
+            SettingDefinition::checkbox("panel_snapping", "Panel snapping", self.panel_snapping)
+                .help("Snap dragged panels to screen edges and other panels"),
+        ]
+    }
+}
+
+impl SettingsMenuBuilder {
+    /// Adds an Interface tab holding [`InterfaceSettings::setting_definitions`]
+    pub fn interface_tab(self, settings: &InterfaceSettings) -> Self {
+        self.tab(SettingsTab::Interface)
+            .settings(settings.setting_definitions())
+    }
+}
+
+/// Values the settings replaced, restored when the player turns them off
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OverriddenValues {
+    status: Option<StatusPalette>,
+}
+
+/// System to push changed interface settings into the resources widgets read
+pub fn interface_settings_apply_system(
+    settings: Res<InterfaceSettings>,
+    mut widget_scale: ResMut<WidgetScale>,
+    mut tooltips: ResMut<TooltipSettings>,
+    mut combat_text: ResMut<CombatTextSettings>,
+    (mut zoom, mut snapping): (ResMut<ZoomVisibilitySettings>, ResMut<PanelSnapSettings>),
+    (mut reduced_motion, mut theme): (ResMut<ReducedMotion>, ResMut<Theme>),
+    mut overridden: Local<OverriddenValues>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let scale = settings.hud_scale.max(0.1);
+    if widget_scale.0 != scale {
+        widget_scale.0 = scale;
+    }
+    tooltips.delay = settings.tooltip_delay.max(0.0);
+    combat_text.set_if_neq(CombatTextSettings {
+        damage: settings.damage_numbers,
+        healing: settings.healing_numbers,
+    });
+    // Keep the default proportion of fading to hidden distance
+    zoom.set_if_neq(ZoomVisibilitySettings {
+        fade_start: settings.world_ui_distance * 2.0 / 3.0,
+        cutoff: settings.world_ui_distance,
+    });
+    if snapping.enabled != settings.panel_snapping {
+        snapping.enabled = settings.panel_snapping;
+    }
+
+    reduced_motion.set_if_neq(ReducedMotion(settings.reduced_motion));
+
+    match settings.colorblind_mode.status_palette() {
+        Some(palette) => {
+            overridden.status.get_or_insert(theme.status);
+            if theme.status != palette {
+                theme.status = palette;
+            }
+        }
+        None => {
+            if let Some(previous) = overridden.status.take() {
+                theme.status = previous;
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<InterfaceSettings>()
+        .init_resource::<InterfaceSettings>()
+        .add_plugins(BindPlugin::<InterfaceSettings>::default())
+        .add_systems(Update, interface_settings_apply_system);
+}
@@ -0,0 +1,274 @@
+//! Key binding buttons for the Controls settings tab
+//!
+//! A [`KeyBindButton`] shows the input bound to an action. Pressing it
+//! starts listening: the next key, mouse button or gamepad button becomes
+//! the new binding and [`KeyBindingChanged`] is sent, listing any other
+//! actions already bound to that input. Escape cancels listening. Buttons
+//! whose binding clashes with another button are drawn in the error color.
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::interaction::UiInteractionState;
+use crate::theme::Theme;
+use crate::utils::first_section;
+
+/// An input that can be bound to an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum InputBinding {
+    /// A keyboard key
+    Key(KeyCode),
+    /// A mouse button
+    Mouse(MouseButton),
+    /// A button on any gamepad
+    Gamepad(GamepadButtonType),
+}
+
+impl InputBinding {
+    /// Human-readable name, such as "W", "Left Mouse" or "Gamepad South"
+    pub fn name(&self) -> String {
+        match self {
+            InputBinding::Key(key) => {
+                let name = format!("{key:?}");
+                ["Key", "Digit"]
+                    .iter()
+                    .find_map(|prefix| name.strip_prefix(prefix))
+                    .filter(|rest| !rest.is_empty())
+                    .map_or_else(|| name.clone(), str::to_string)
+            }
+            InputBinding::Mouse(MouseButton::Other(index)) => format!("Mouse {index}"),
+            InputBinding::Mouse(button) => format!("{button:?} Mouse"),
+            InputBinding::Gamepad(button) => format!("Gamepad {button:?}"),
+        }
+    }
+}
+
+/// A button that rebinds an action when pressed
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct KeyBindButton {
+    /// The action this button binds, such as "move_forward"
+    pub action: String,
+    /// The current binding, if any
+    pub binding: Option<InputBinding>,
+    /// Whether the button is waiting for an input
+    pub listening: bool,
+}
+
+impl KeyBindButton {
+    /// Creates a button for `action` showing `binding`
+    pub fn new(action: impl Into<String>, binding: Option<InputBinding>) -> Self {
+        Self {
+            action: action.into(),
+            binding,
+            listening: false,
+        }
+    }
+}
+
+/// The text node inside a [`KeyBindButton`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct KeyBindLabel {
+    /// The button this label belongs to
+    pub button: Entity,
+}
+
+/// Sent when a [`KeyBindButton`] captures a new input
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct KeyBindingChanged {
+    /// The button entity
+    pub button: Entity,
+    /// The rebound action
+    pub action: String,
+    /// The binding before the change
+    pub previous: Option<InputBinding>,
+    /// The new binding
+    pub binding: InputBinding,
+    /// Other actions already bound to the same input
+    pub conflicts: Vec<String>,
+}
+
+/// Creates a key binding button
+pub fn key_bind_button_bundle(button: KeyBindButton) -> (ButtonBundle, KeyBindButton) {
+    (
+        ButtonBundle {
+            style: Style {
+                min_width: Val::Px(120.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                padding: UiRect::axes(Val::Px(10.0), Val::Px(6.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            border_color: Color::NONE.into(),
+            ..default()
+        },
+        button,
+    )
+}
+
+/// System to spawn the label of new key binding buttons
+pub fn key_bind_spawn_system(mut commands: Commands, buttons: Query<Entity, Added<KeyBindButton>>) {
+    for entity in &buttons {
+        commands.entity(entity).with_children(|button| {
+            button.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: UiFonts::BOLD,
+                        font_size: 18.0,
+                        ..default()
+                    },
+                ),
+                KeyBindLabel { button: entity },
+            ));
+        });
+    }
+}
+
+/// System to start listening on press and capture the next input
+///
+/// Inputs are captured before presses are handled, so the press that starts
+/// listening isn't taken as the new binding.
+pub fn key_bind_capture_system(
+    mut buttons: Query<(Entity, &mut KeyBindButton)>,
+    interaction_state: Res<UiInteractionState>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut changed_events: EventWriter<KeyBindingChanged>,
+) {
+    let captured = keys
+        .get_just_pressed()
+        .next()
+        .map(|&key| InputBinding::Key(key))
+        .or_else(|| {
+            mouse_buttons
+                .get_just_pressed()
+                .next()
+                .map(|&button| InputBinding::Mouse(button))
+        })
+        .or_else(|| {
+            gamepad_buttons
+                .get_just_pressed()
+                .next()
+                .map(|button| InputBinding::Gamepad(button.button_type))
+        });
+
+    let mut rebound = None;
+    for (entity, mut button) in &mut buttons {
+        if !button.listening {
+            continue;
+        }
+        match captured {
+            Some(InputBinding::Key(KeyCode::Escape)) => button.listening = false,
+            Some(binding) => {
+                let previous = button.binding.replace(binding);
+                button.listening = false;
+                rebound = Some((entity, previous, binding));
+            }
+            None => {}
+        }
+    }
+
+    if let Some((entity, previous, binding)) = rebound {
+        let mut action = String::new();
+        let mut conflicts = Vec::new();
+        for (other, button) in &buttons {
+            if other == entity {
+                action.clone_from(&button.action);
+            } else if button.binding == Some(binding) {
+                conflicts.push(button.action.clone());
+            }
+        }
+        changed_events.send(KeyBindingChanged {
+            button: entity,
+            action,
+            previous,
+            binding,
+            conflicts,
+        });
+        return;
+    }
+
+    let pressed = buttons
+        .iter()
+        .find(|(entity, button)| !button.listening && interaction_state.just_pressed(*entity))
+        .map(|(entity, _)| entity);
+    if let Some(pressed) = pressed {
+        // Only one button listens at a time
+        for (entity, mut button) in &mut buttons {
+            let listening = entity == pressed;
+            if button.listening != listening {
+                button.listening = listening;
+            }
+        }
+    }
+}
+
+/// System to refresh labels and conflict highlighting
+pub fn key_bind_update_system(
+    theme: Res<Theme>,
+    buttons: Query<Ref<KeyBindButton>>,
+    mut border_colors: Query<(&KeyBindButton, &mut BorderColor)>,
+    mut labels: Query<(&KeyBindLabel, &mut Text)>,
+) {
+    if !theme.is_changed() && !buttons.iter().any(|button| button.is_changed()) {
+        return;
+    }
+    let conflicting = |button: &KeyBindButton| {
+        button.binding.is_some()
+            && buttons
+                .iter()
+                .filter(|other| other.binding == button.binding)
+                .count()
+                > 1
+    };
+
+    for (button, mut border) in &mut border_colors {
+        border.0 = if button.listening {
+            theme.focus.border
+        } else if conflicting(button) {
+            theme.status.error
+        } else {
+            Color::NONE
+        };
+    }
+    for (label, mut text) in &mut labels {
+        let Ok(button) = buttons.get(label.button) else {
+            continue;
+        };
+        let section = first_section(&mut text);
+        if button.listening {
+            section.value = "Press a key…".to_string();
+            section.style.color = theme.focus.text;
+        } else {
+            section.value = button
+                .binding
+                .map_or_else(|| "Unbound".to_string(), |binding| binding.name());
+            section.style.color = if button.binding.is_none() {
+                theme.text.disabled
+            } else if conflicting(&button) {
+                theme.status.error
+            } else {
+                theme.text.normal
+            };
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<KeyBindButton>()
+        .register_type::<KeyBindLabel>()
+        .add_event::<KeyBindingChanged>()
+        .add_systems(
+            Update,
+            (
+                key_bind_spawn_system,
+                key_bind_capture_system,
+                key_bind_update_system,
+            )
+                .chain(),
+        );
+}
@@ -0,0 +1,483 @@
+//! Data-driven UI layouts
+//!
+//! A [`UiLayout`] describes a widget tree of panels, text, buttons,
+//! checkboxes and sliders in a `.layout.ron` or `.layout.json` file, so menus
+//! can be changed without recompiling. Put a [`UiLayoutRoot`] on a node and
+//! the described widgets are spawned under it, through the same
+//! [builders](crate::builders) code would use, once the asset has loaded:
+//!
+//! ```ron
+//! Panel(
+//!     direction: Column,
+//!     gap: 12.0,
+//!     children: [
+//!         Text(text: "Settings", size: 32.0),
+//!         Checkbox(label: "Fullscreen", id: "fullscreen", checked: true),
+//!         Slider(label: "Volume", id: "volume", value: 0.8),
+//!         Button(label: "Back", id: "back"),
+//!     ],
+//! )
+//! ```
+//!
+//! Optional fields such as `id` take a plain value; `Some(..)` is not needed.
+//! Give interactive widgets an `id` and look them up through
+//! [`Buttons`](crate::params::Buttons) and friends.
+//!
+//! With asset hot reloading enabled (Bevy's `file_watcher` feature), editing a layout file respawns the trees
+//! built from it in place. Checkboxes and sliders keep their current values
+//! across the reload when their `id` is unchanged.
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+use crate::assets::UiFonts;
+use crate::builders::{CheckboxBuilder, SliderBuilder, UiButtonBuilder};
+use crate::components::{Panel, UiCheckbox, UiId, UiSlider};
+use crate::theme::Theme;
+
+/// Direction a layout panel places its children
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Default)]
+pub enum LayoutDirection {
+    /// Top to bottom
+    #[default]
+    Column,
+    /// Left to right
+    Row,
+}
+
+/// One node of a [`UiLayout`] widget tree
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum LayoutNode {
+    /// A container laying out its children in a row or column
+    Panel {
+        /// Layout direction
+        #[serde(default)]
+        direction: LayoutDirection,
+        /// Space between children in pixels
+        #[serde(default = "default_gap")]
+        gap: f32,
+        /// Padding on every side in pixels
+        #[serde(default)]
+        padding: f32,
+        /// Fixed width in pixels
+        #[serde(default)]
+        width: Option<f32>,
+        /// Fixed height in pixels
+        #[serde(default)]
+        height: Option<f32>,
+        /// Title shown above the children
+        #[serde(default)]
+        title: Option<String>,
+        /// Widget identifier
+        #[serde(default)]
+        id: Option<String>,
+        /// Child nodes
+        #[serde(default)]
+        children: Vec<LayoutNode>,
+    },
+    /// A text label
+    Text {
+        /// The text
+        text: String,
+        /// Font size in pixels
+        #[serde(default = "default_font_size")]
+        size: f32,
+    },
+    /// A button
+    Button {
+        /// Button text
+        label: String,
+        /// Widget identifier
+        #[serde(default)]
+        id: Option<String>,
+        /// Tooltip text
+        #[serde(default)]
+        tooltip: Option<String>,
+        /// Whether the button ignores input
+        #[serde(default)]
+        disabled: bool,
+    },
+    /// A checkbox with a label
+    Checkbox {
+        /// Label text
+        label: String,
+        /// Widget identifier
+        #[serde(default)]
+        id: Option<String>,
+        /// Tooltip text
+        #[serde(default)]
+        tooltip: Option<String>,
+        /// Whether the checkbox ignores input
+        #[serde(default)]
+        disabled: bool,
+        /// Initial state
+        #[serde(default)]
+        checked: bool,
+    },
+    /// A slider with a label
+    Slider {
+        /// Label text
+        label: String,
+        /// Widget identifier
+        #[serde(default)]
+        id: Option<String>,
+        /// Tooltip text
+        #[serde(default)]
+        tooltip: Option<String>,
+        /// Whether the slider ignores input
+        #[serde(default)]
+        disabled: bool,
+        /// Minimum value
+        #[serde(default)]
+        min: f32,
+        /// Maximum value
+        #[serde(default = "default_max")]
+        max: f32,
+        /// Initial value
+        #[serde(default)]
+        value: f32,
+        /// Snap increment; continuous when absent
+        #[serde(default)]
+        step: Option<f32>,
+    },
+}
+
+fn default_gap() -> f32 {
+    8.0
+}
+
+fn default_font_size() -> f32 {
+    18.0
+}
+
+fn default_max() -> f32 {
+    1.0
+}
+
+/// A widget tree loaded from a `.layout.ron` or `.layout.json` file
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct UiLayout {
+    /// The top node of the tree
+    pub root: LayoutNode,
+}
+
+/// Spawns the widgets of a [`UiLayout`] as children of this node
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct UiLayoutRoot(pub Handle<UiLayout>);
+
+/// Marks a [`UiLayoutRoot`] whose layout has been spawned
+#[derive(Component, Debug, Clone, Copy, Reflect, Default)]
+#[reflect(Component)]
+pub struct UiLayoutSpawned;
+
+/// Widget values carried over a layout reload, keyed by [`UiId`]
+///
+/// Added to a [`UiLayoutRoot`] when its layout is modified and consumed when
+/// the tree is respawned.
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub struct UiLayoutPreserved {
+    /// Checkbox states
+    pub checked: HashMap<String, bool>,
+    /// Slider values
+    pub values: HashMap<String, f32>,
+}
+
+impl UiLayoutPreserved {
+    /// Overwrites the initial state of widgets in `node` whose id was preserved
+    fn apply(&self, node: &mut LayoutNode) {
+        match node {
+            LayoutNode::Panel { children, .. } => {
+                for child in children {
+                    self.apply(child);
+                }
+            }
+            LayoutNode::Checkbox {
+                id: Some(id),
+                checked,
+                ..
+            } => {
+                if let Some(preserved) = self.checked.get(id) {
+                    *checked = *preserved;
+                }
+            }
+            LayoutNode::Slider {
+                id: Some(id),
+                value,
+                ..
+            } => {
+                if let Some(preserved) = self.values.get(id) {
+                    *value = *preserved;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Error loading a layout file
+#[derive(Debug)]
+pub enum UiLayoutError {
+    /// The file couldn't be read
+    Io(std::io::Error),
+    /// The file isn't a valid RON layout
+    Ron(ron::error::SpannedError),
+    /// The file isn't a valid JSON layout
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for UiLayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UiLayoutError::Io(error) => write!(f, "could not read layout: {error}"),
+            UiLayoutError::Ron(error) => write!(f, "invalid layout: {error}"),
+            UiLayoutError::Json(error) => write!(f, "invalid layout: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for UiLayoutError {}
+
+/// Loads [`UiLayout`]s from `.layout.ron` and `.layout.json` files
+#[derive(Default)]
+pub struct UiLayoutLoader;
+
+impl AssetLoader for UiLayoutLoader {
+    type Asset = UiLayout;
+    type Settings = ();
+    type Error = UiLayoutError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<UiLayout, UiLayoutError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.map_err(UiLayoutError::Io)?;
+
+        let is_json = load_context
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "json");
+        if is_json {
+            serde_json::from_slice(&bytes).map_err(UiLayoutError::Json)
+        } else {
+            ron::Options::default()
+                .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+                .from_bytes(&bytes)
+                .map_err(UiLayoutError::Ron)
+        }
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["layout.ron", "layout.json"]
+    }
+}
+
+/// Spawns the widgets described by `node` and returns the top entity
+pub fn spawn_layout_node(commands: &mut Commands, theme: &Theme, node: &LayoutNode) -> Entity {
+    match node {
+        LayoutNode::Panel {
+            direction,
+            gap,
+            padding,
+            width,
+            height,
+            title,
+            id,
+            children,
+        } => {
+            let mut entities = Vec::with_capacity(children.len() + 1);
+            if let Some(title) = title {
+                let title = LayoutNode::Text {
+                    text: title.clone(),
+                    size: 24.0,
+                };
+                entities.push(spawn_layout_node(commands, theme, &title));
+            }
+            for child in children {
+                entities.push(spawn_layout_node(commands, theme, child));
+            }
+
+            let mut panel = commands.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: match direction {
+                            LayoutDirection::Column => FlexDirection::Column,
+                            LayoutDirection::Row => FlexDirection::Row,
+                        },
+                        row_gap: Val::Px(*gap),
+                        column_gap: Val::Px(*gap),
+                        padding: UiRect::all(Val::Px(*padding)),
+                        width: width.map_or(Val::Auto, Val::Px),
+                        height: height.map_or(Val::Auto, Val::Px),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Panel {
+                    title: title.clone(),
+                    collapsible: false,
+                    collapsed: false,
+                },
+            ));
+            if let Some(id) = id {
+                panel.insert(UiId::new(id.clone()));
+            }
+            panel.push_children(&entities).id()
+        }
+        LayoutNode::Text { text, size } => commands
+            .spawn(TextBundle::from_section(
+                text.clone(),
+                TextStyle {
+                    font: UiFonts::MEDIUM,
+                    font_size: *size,
+                    color: theme.text.normal,
+                },
+            ))
+            .id(),
+        LayoutNode::Button {
+            label,
+            id,
+            tooltip,
+            disabled,
+        } => {
+            let mut builder = UiButtonBuilder::new(theme)
+                .label(label.clone())
+                .disabled(*disabled);
+            if let Some(id) = id {
+                builder = builder.id(id.clone());
+            }
+            if let Some(tooltip) = tooltip {
+                builder = builder.tooltip(tooltip.clone());
+            }
+            builder.spawn(commands)
+        }
+        LayoutNode::Checkbox {
+            label,
+            id,
+            tooltip,
+            disabled,
+            checked,
+        } => {
+            let mut builder = CheckboxBuilder::new(theme)
+                .label(label.clone())
+                .disabled(*disabled)
+                .checked(*checked);
+            if let Some(id) = id {
+                builder = builder.id(id.clone());
+            }
+            if let Some(tooltip) = tooltip {
+                builder = builder.tooltip(tooltip.clone());
+            }
+            builder.spawn(commands)
+        }
+        LayoutNode::Slider {
+            label,
+            id,
+            tooltip,
+            disabled,
+            min,
+            max,
+            value,
+            step,
+        } => {
+            let mut builder = SliderBuilder::new(theme)
+                .label(label.clone())
+                .disabled(*disabled)
+                .range(*min, *max)
+                .value(*value);
+            if let Some(id) = id {
+                builder = builder.id(id.clone());
+            }
+            if let Some(tooltip) = tooltip {
+                builder = builder.tooltip(tooltip.clone());
+            }
+            if let Some(step) = step {
+                builder = builder.step(*step);
+            }
+            builder.spawn(commands)
+        }
+    }
+}
+
+/// System to tear down layout trees whose asset was modified
+///
+/// Records the values of identified checkboxes and sliders in
+/// [`UiLayoutPreserved`] and despawns the tree, so
+/// [`ui_layout_spawn_system`] rebuilds it from the new asset.
+pub fn ui_layout_reload_system(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<UiLayout>>,
+    roots: Query<(Entity, &UiLayoutRoot), With<UiLayoutSpawned>>,
+    children: Query<&Children>,
+    widgets: Query<(&UiId, Option<&UiCheckbox>, Option<&UiSlider>)>,
+) {
+    for event in asset_events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        for (entity, root) in &roots {
+            if root.0.id() != *id {
+                continue;
+            }
+            let mut preserved = UiLayoutPreserved::default();
+            for (ui_id, checkbox, slider) in widgets.iter_many(children.iter_descendants(entity)) {
+                if let Some(checkbox) = checkbox {
+                    preserved.checked.insert(ui_id.0.clone(), checkbox.checked);
+                }
+                if let Some(slider) = slider {
+                    preserved.values.insert(ui_id.0.clone(), slider.value);
+                }
+            }
+            commands
+                .entity(entity)
+                .despawn_descendants()
+                .remove::<UiLayoutSpawned>()
+                .insert(preserved);
+        }
+    }
+}
+
+/// System to spawn the widgets of layout roots whose asset has loaded
+pub fn ui_layout_spawn_system(
+    mut commands: Commands,
+    roots: Query<(Entity, &UiLayoutRoot, Option<&UiLayoutPreserved>), Without<UiLayoutSpawned>>,
+    layouts: Res<Assets<UiLayout>>,
+    theme: Res<Theme>,
+) {
+    for (entity, root, preserved) in &roots {
+        let Some(layout) = layouts.get(&root.0) else {
+            continue;
+        };
+        let tree = match preserved {
+            Some(preserved) => {
+                let mut node = layout.root.clone();
+                preserved.apply(&mut node);
+                spawn_layout_node(&mut commands, &theme, &node)
+            }
+            None => spawn_layout_node(&mut commands, &theme, &layout.root),
+        };
+        commands
+            .entity(entity)
+            .add_child(tree)
+            .insert(UiLayoutSpawned)
+            .remove::<UiLayoutPreserved>();
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiLayoutRoot>()
+        .register_type::<UiLayoutSpawned>()
+        .init_asset::<UiLayout>()
+        .init_asset_loader::<UiLayoutLoader>()
+        .add_systems(
+            Update,
+            (ui_layout_reload_system, ui_layout_spawn_system).chain(),
+        );
+}
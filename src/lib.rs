@@ -7,6 +7,7 @@
 
 #![warn(missing_docs)]
 #![warn(rustdoc::missing_crate_level_docs)]
+#![allow(clippy::type_complexity)]
 
 use bevy::prelude::*;
 
@@ -17,37 +18,340 @@ use bevy::prelude::*;
 /// This module provides functionality for making UI elements accessible,
 /// including screen reader support and keyboard navigation.
 pub mod accessibility;
+pub mod assets;
+pub mod accordion;
+pub mod attention;
+pub mod bars;
+pub mod binding;
+pub mod build_queue;
+pub mod builders;
+pub mod chip;
+pub mod click_cooldown;
+pub mod clock;
+pub mod command_card;
+pub mod drag;
+pub mod ellipsis;
+pub mod events;
+pub mod floating_text;
+pub mod focus;
+pub mod hotkey;
+pub mod hotkey_hint;
+#[cfg(feature = "gallery")]
+pub mod gallery;
+pub mod interaction;
+pub mod interface_settings;
+pub mod keybind;
+pub mod layout;
+pub mod list_view;
+pub mod localization;
+pub mod menu;
 pub mod menu_components;
+pub mod message_box;
+pub mod minimap;
+pub mod modal;
+pub mod nameplate;
+pub mod notifications;
+pub mod numeric_input;
+pub mod palette;
+pub mod panel;
+pub mod panel_snap;
+pub mod params;
+pub mod pending_changes;
+pub mod persistence;
+pub mod placement;
+pub mod quality;
+pub mod read_only;
+pub mod resource_display;
+pub mod scale;
+pub mod scrub;
+pub mod secondary_action;
+pub mod selection_box;
+pub mod selection_panel;
+pub mod selection_summary;
+pub mod settings_menu;
+pub mod skin;
+pub mod slider_entry;
+pub mod sound;
+pub mod sparkline;
+pub mod split_button;
+pub mod split_screen;
+pub mod state_machine;
+pub mod steps;
+pub mod table;
+pub mod tabs;
 pub mod components;
+pub mod cursor;
 pub mod styles;
 pub mod systems;
+pub mod text_match;
+pub mod theme;
+pub mod toolbar;
+pub mod tooltip;
+pub mod transition;
+pub mod tween;
 /// Common type definitions used throughout the UI
 /// 
 /// This module contains enums, structs, and other type definitions that are
 /// used across different parts of the UI system.
 pub mod types;
 pub mod utils;
+pub mod world_bar;
+pub mod zoom_visibility;
 
 // Re-export commonly used items
 pub use accessibility::*;
-pub use components::{UiSlider, UiCheckbox};
+pub use accordion::{
+    accordion_bundle, accordion_section, Accordion, AccordionOpenness, AccordionPart,
+    AccordionSection, AccordionSectionBuilder,
+};
+pub use assets::{
+    ui_asset_audit_system, ui_font_fallback_system, ui_font_paths_system, ui_font_weights_system,
+    UiAssetAudit, UiFontPaths, UiFonts,
+};
+#[cfg(feature = "embedded_assets")]
+pub use assets::{UiIcon, UiIcons};
+pub use attention::{attention_system, Flash, Pulse, Shake};
+pub use bars::{
+    comparison_bar_bundle, progress_bar_parts_system, spawn_progress_bar, stacked_bar_bundle,
+    update_progress_bars, BarSegment, ComparisonBar, ComparisonBarPart, DeltaLabel,
+    ProgressBarPart, StackedBar, StackedBarSegment,
+};
+pub use build_queue::{
+    build_queue_bundle, build_queue_input_system, build_queue_progress_system,
+    build_queue_spawn_system, BuildQueue, BuildQueuePart, BuildQueuePartKind, QueueItem,
+    QueueItemCancelled, QueueItemMoved, QUEUE_ITEM_DRAG_KIND,
+};
+pub use binding::{
+    bind_resource_to_widget_system, bind_widget_to_resource_system, Bind, BindPlugin,
+};
+pub use builders::{
+    spawn_tabbed_container, spawn_tabbed_container_with, widget_callback_system,
+    CheckboxBuilder, OnChange, OnClick, SliderBuilder, UiButtonBuilder,
+};
+pub use chip::{
+    status_chip_bundle, StatusChip, StatusChipDismissed, StatusChipPart, StatusKind,
+};
+pub use click_cooldown::{
+    click_cooldown_system, click_cooldown_visual_system, ClickCooldown, ClickCoolingDown,
+};
+pub use clock::UiClock;
+pub use ellipsis::{AutoEllipsis, EllipsisTooltip};
+#[cfg(feature = "gallery")]
+pub use gallery::{GalleryRoot, WidgetGallery};
+pub use floating_text::{
+    floating_text_system, spawn_floating_text, CombatTextSettings, FloatingAnchor, FloatingText,
+    FloatingTextKind,
+};
+pub use focus::{spatial_neighbor, FocusOrder, GamepadNavInput, NavDirection, UiFocus};
+pub use drag::{
+    drag_end_system, drag_move_system, drag_start_system, drop_target_visual_system, DragGhost,
+    DragPhase, DragStarted, DragState, Draggable, DropTarget, DroppedOn,
+};
+pub use events::{
+    DropdownSelectionChanged, SetEnabled, TabChanged, UiButtonClicked, UiCheckboxToggled,
+    UiSliderChanged,
+};
+pub use hotkey::{hotkey_system, Hotkey, HotkeyScope, HotkeyTriggered, KeyChord};
+pub use hotkey_hint::{
+    hotkey_hint_system, hotkey_hints_input_system, HotkeyHint, HotkeyHintLabel, HotkeyHintMode,
+    HotkeyHints,
+};
+pub use interaction::{
+    hitbox_padding_system, HitboxPadding, InteractionArbitrationSet, InteractionFrame,
+    PointerCapture, UiInteractionState,
+};
+pub use interface_settings::{
+    interface_settings_apply_system, ColorblindMode, InterfaceSettings, OverriddenValues,
+};
+pub use command_card::{
+    command_card_bundle, command_card_input_system, command_card_spawn_system,
+    command_card_visual_system, CommandCard, CommandCardPart, CommandCardPartKind, CommandIssued,
+    CommandSlot,
+};
+pub use components::{is_disabled, WidgetDisabled, UiSlider, UiCheckbox, CheckmarkNode, SliderFill, SliderHandle, UiId, ProgressBar, ScrollBar, ScrollThumb, TabButton, TabPane, TabbedContainer};
+pub use cursor::{
+    ui_cursor_apply_system, ui_cursor_resolve_system, HoverCursor, SoftwareCursor, UiCursor,
+    UiCursorIcon,
+};
+pub use keybind::{
+    key_bind_button_bundle, InputBinding, KeyBindButton, KeyBindLabel, KeyBindingChanged,
+};
+pub use layout::{
+    spawn_layout_node, ui_layout_reload_system, ui_layout_spawn_system, LayoutDirection,
+    LayoutNode, UiLayout, UiLayoutError, UiLayoutLoader, UiLayoutPreserved, UiLayoutRoot,
+    UiLayoutSpawned,
+};
+pub use list_view::{
+    list_view_bundle, list_view_keyboard_system, list_view_pointer_system, list_view_spawn_system,
+    list_view_virtual_system, list_view_visual_system, ListContent, ListRow, ListSelectionChanged,
+    ListSelectionMode, UiListView,
+};
+pub use localization::{
+    localized_text_system, pseudo_localize, LocalizedSettingRow, LocalizedText, LocalizedTooltip,
+    Localizer, StringTableLocalizer, UiLocalizer,
+};
+pub use menu::{
+    context_menu_bundle, context_menu_open_system, context_menu_selected_system,
+    menu_anchor_system, menu_bar_alt_system, menu_bar_bundle, menu_dismiss_system,
+    menu_keyboard_system, menu_pointer_system, menu_popup_system, menu_spawn_system,
+    menu_visual_system, open_context_menu, ContextMenu, ContextMenuSelected, Menu, MenuBar,
+    MenuEntry, MenuItemSelected, MenuKind, MenuPart, MenuPartKind,
+};
+pub use message_box::{
+    message_box_bundle, message_box_button_system, message_box_spawn_system, MessageBox,
+    MessageBoxAction, MessageBoxActionPressed, MessageBoxButton,
+};
+pub use minimap::{
+    minimap_frame_bundle, minimap_pointer_system, minimap_spawn_system,
+    minimap_viewport_visual_system, MinimapClicked, MinimapFrame, MinimapViewport,
+    MinimapViewportDragged,
+};
+pub use modal::{
+    spawn_modal, ModalBody, ModalButton, ModalButtonAction, ModalConfig, ModalDialog,
+    ModalResponse, ModalResult, ModalState,
+};
+pub use nameplate::{
+    nameplate_bundle, ConnectionQuality, Nameplate, NameplatePart, NameplateVariant,
+};
+pub use notifications::{
+    notification_center_bundle, notification_toasts_bundle, notification_toasts_bundle_at,
+    Notification, NotificationCenter, NotificationPart, NotificationPartKind,
+    NotificationPriority, NotificationRules, NotificationToasts, ToastCorner, ToastState,
+    UiNotifications,
+};
+pub use numeric_input::{
+    numeric_input_bundle, numeric_input_spawn_system, numeric_input_system,
+    numeric_input_visual_system, NumericInput, NumericInputPart, NumericInputPartKind,
+    ValueCommitted,
+};
+pub use panel::{
+    panel_title_bar, PanelAction, PanelActionKind, PanelCloseRequested, PanelOpenness, PanelPinned,
+    PanelTitleBar, PanelTitleBarBuilder, PanelToggled,
+};
+pub use panel_snap::{
+    floating_panel_title_bar_system, panel_drag_end_system, panel_drag_move_system,
+    panel_drag_start_system, panel_snap_guide_system, FloatingPanel, PanelDragState, PanelMoved,
+    PanelSnapGuide, PanelSnapSettings,
+};
+pub use palette::{
+    command_palette_input_system, command_palette_pointer_system,
+    command_palette_row_visual_system, command_palette_view_system, CommandExecuted,
+    CommandPalette, CommandPaletteList, CommandPalettePlugin, CommandPaletteQuery,
+    CommandPaletteRoot, CommandPaletteRow, CommandRegistry, RegisteredCommand,
+};
+pub use params::{Buttons, Checkboxes, Sliders};
+pub use pending_changes::{
+    pending_changes_banner_bundle, pending_changes_banner_spawn_system,
+    pending_changes_banner_system, pending_changes_input_system, pending_changes_system,
+    pending_confirmation_system, AppliedSettings, ConfirmApply, PendingChangesBanner,
+    PendingChangesPart, PendingChangesPartKind, PendingConfirmation, SettingsApplied,
+    SettingsConfirmed, SettingsReverted,
+};
+pub use persistence::{PersistentId, PersistentWidgets, UiStateSnapshot, WidgetValue};
+pub use placement::{area_overflow, clamp_to_area, popup_bounds_system, PopupBounds};
+pub use quality::{ReducedMotion, UiMotion, UiQuality};
+pub use read_only::{read_only_block_system, read_only_lock_system, ReadOnly, ReadOnlyLock};
+pub use resource_display::{
+    format_compact, resource_display_bundle, resource_display_spawn_system,
+    resource_display_update_system, ResourceDisplay, ResourceDisplayPart,
+};
+pub use scale::WidgetScale;
+pub use scrub::{scrub_system, Scrubber};
+pub use secondary_action::{secondary_action_system, SecondaryAction, SecondaryActionPressed};
+pub use selection_box::{
+    selection_box_input_system, selection_box_visual_system, SelectionBox, SelectionBoxCompleted,
+    SelectionBoxOverlay,
+};
+pub use selection_panel::{
+    selection_panel_bundle, selection_panel_pointer_system, selection_panel_spawn_system,
+    PortraitAction, PortraitClicked, SelectedUnit, SelectionPanel, SelectionPanelPart,
+    SelectionPanelPartKind,
+};
+pub use selection_summary::{
+    selection_summary_tooltip_system, SelectionSummary, SelectionSummaryPopup,
+    SelectionSummaryTooltip, UnitTypeCount,
+};
+pub use settings_menu::{SettingControl, SettingDefinition, SettingsMenuBuilder};
+pub use skin::{SkinDefinition, SkinDefinitionLoader, SkinLoadError, SkinState, SkinStateKind, WidgetSkin};
+pub use slider_entry::{
+    slider_value_entry_system, slider_value_label_bundle, slider_value_label_system,
+    SliderValueLabel,
+};
+pub use sound::{ui_sound_system, UiSoundKind, UiSoundOverride, UiSounds};
+pub use sparkline::{sparkline_bundle, sparkline_system, Sparkline, SparklineSegment};
+pub use split_button::{
+    split_button_bundle, split_button_input_system, split_button_selected_system,
+    split_button_spawn_system, SplitButton, SplitButtonActionSelected, SplitButtonPart,
+    SplitButtonPartKind, SplitButtonPressed,
+};
+pub use split_screen::{hud_root_bundle, HudFocus, HudRoot, HudScaleBase, HudScope};
+pub use state_machine::{UiState, UiStateMachine};
+pub use steps::{
+    step_indicator_bundle, Step, StepIndicator, StepIndicatorPart, StepPartKind, StepState,
+};
 pub use styles::*;
 pub use systems::*;
+pub use table::{
+    table_bundle, table_header_system, table_spawn_system, SortDirection, TableCell, TablePart,
+    TablePartKind, TableSort, TableSorted, UiTable, UiTableColumn,
+};
+pub use tabs::{
+    add_tab, tab_close_system, tab_drop_system, tab_pane_bundle, tab_strip_scroll_system,
+    tab_strip_system, AddTab, RemoveTab, TabCloseButton, TabCloseRequested, TabMoved, TabStrip,
+    TAB_DRAG_KIND,
+};
+pub use text_match::{fuzzy_match, rank_matches, FuzzyMatch};
+pub use theme::{
+    ButtonPalette, FocusPalette, SliderPalette, SpacingMetrics, StatusPalette, TextPalette, Theme,
+    ThemeRole,
+};
+pub use toolbar::{
+    toolbar_bundle, Toolbar, ToolbarButtonPressed, ToolbarItem, ToolbarPart, ToolbarPartKind,
+};
+pub use tooltip::{
+    clamp_tooltip, place_at_cursor, place_tooltip, tooltip_anchor, tooltip_system, PooledTooltip,
+    RichTooltip, TooltipDelay, TooltipEnvironment, TooltipFollowCursor, TooltipLayer, TooltipPool,
+    TooltipSettings,
+};
+pub use transition::{
+    ui_transition_system, Edge, TransitionFinished, TransitionKind, UiTransition,
+};
+pub use tween::{
+    ui_hover_transition_record_system, ui_hover_transition_system, ui_tween_system, Easing,
+    TweenRepeat, TweenTarget, UiHoverTransition, UiTween, UiTweenCompleted,
+};
 pub use types::*;
 pub use utils::*;
+pub use world_bar::{world_anchored_bar_system, WorldAnchoredBar};
+pub use zoom_visibility::{zoom_visibility_system, ZoomVisibility, ZoomVisibilitySettings};
 pub use bevy::window::PrimaryWindow;
 /// Re-export egui menu UI wrappers for use in menus and plugins.
 /// These provide styled, ergonomic access to common egui widgets.
 pub use menu_components::egui_wrappers::{
     menu_scroll_area,
     menu_button,
+    menu_button_with,
     menu_label,
     menu_text_edit_singleline,
+    menu_text_edit_singleline_with,
     menu_checkbox,
+    menu_checkbox_with,
     menu_table,
+    menu_virtual_table,
+    MenuOptions,
+    MenuVariant,
+    TableColumn,
 };
+pub use menu_components::egui_modal::{menu_alert, menu_confirm, menu_modal, ConfirmResult};
+pub use menu_components::egui_state::{
+    menu_collapsing, menu_scroll_area_stateful, menu_selectable_list, MenuState,
+};
+pub use menu_components::egui_theme::{apply_theme, MenuPalette};
 
 /// Standard color definitions for UI elements
+///
+/// These constants make up the default [`Theme`]; read the `Theme` resource
+/// instead when colors should follow runtime palette changes.
 pub mod colors {
     use bevy::prelude::Color;
     
@@ -126,12 +430,15 @@ pub mod colors {
 /// Common UI component bundles
 pub mod bundles {
     use bevy::prelude::*;
+    use crate::assets::UiFonts;
     use crate::colors;
 
     /// Creates a standard button bundle with the given text
+    ///
+    /// The asset server is no longer used: the text is drawn with [`UiFonts`].
     pub fn button_bundle(
         text: &str,
-        asset_server: &Res<AssetServer>,
+        _asset_server: &Res<AssetServer>,
         style: Style,
     ) -> (ButtonBundle, TextBundle) {
         let button = ButtonBundle {
@@ -149,7 +456,7 @@ pub mod bundles {
         let text = TextBundle::from_section(
             text,
             TextStyle {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font: UiFonts::BOLD,
                 font_size: 24.0,
                 color: colors::text::NORMAL,
             },
@@ -159,9 +466,11 @@ pub mod bundles {
     }
 
     /// Creates a checkbox bundle with the given state
+    ///
+    /// The asset server is no longer used: the text is drawn with [`UiFonts`].
     pub fn checkbox_bundle(
         checked: bool,
-        asset_server: &Res<AssetServer>,
+        _asset_server: &Res<AssetServer>,
     ) -> (NodeBundle, TextBundle) {
         let checkbox = NodeBundle {
             style: Style {
@@ -181,7 +490,7 @@ pub mod bundles {
         let check = TextBundle::from_section(
             if checked { "X" } else { "" },
             TextStyle {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font: UiFonts::BOLD,
                 font_size: 20.0,
                 color: colors::text::NORMAL,
             },
@@ -198,14 +507,17 @@ impl Plugin for UiCommonPlugin {
     fn build(&self, app: &mut App) {
         // Register components
         app.register_type::<UiSlider>()
+           .register_type::<SliderFill>()
+           .register_type::<SliderHandle>()
            .register_type::<UiCheckbox>()
+           .register_type::<UiId>()
            // No need to register ECS types for egui menu components
            ;
+        add_core_plugins(app);
         // Add UI system set
         app.add_systems(Update, (
             button_interaction_system,
             checkbox_interaction_system,
-            slider_interaction_system,
             // egui systems are initialized in your app entrypoint (see bevy_egui docs)
         ));
         // Note: egui menu components are available via menu_components::egui_wrappers
@@ -213,50 +525,134 @@ impl Plugin for UiCommonPlugin {
     }
 }
 
+/// Adds the shared state every widget system depends on, once per app
+///
+/// Both [`UiCommonPlugin`] and [`systems::update`] call this, so either entry
+/// point can be used (or both) without registering anything twice.
+pub(crate) fn add_core_plugins(app: &mut App) {
+    if app.world().contains_resource::<UiInteractionState>() {
+        return;
+    }
+    app.add_plugins(assets::plugin);
+    app.add_plugins((
+        clock::plugin,
+        quality::plugin,
+        interaction::plugin,
+        focus::plugin,
+        theme::plugin,
+        events::plugin,
+        tooltip::plugin,
+        bars::plugin,
+        modal::plugin,
+        ellipsis::plugin,
+        builders::plugin,
+        split_screen::plugin,
+        drag::plugin,
+        slider_entry::plugin,
+        list_view::plugin,
+    ))
+    .add_plugins((
+        attention::plugin,
+        table::plugin,
+        minimap::plugin,
+        resource_display::plugin,
+        systems::plugin,
+        selection_panel::plugin,
+        command_card::plugin,
+        build_queue::plugin,
+        click_cooldown::plugin,
+        world_bar::plugin,
+        read_only::plugin,
+        floating_text::plugin,
+        tabs::plugin,
+        split_button::plugin,
+        hotkey::plugin,
+    ))
+    .add_plugins((
+        panel::plugin,
+        skin::plugin,
+        chip::plugin,
+        nameplate::plugin,
+        sound::plugin,
+        accordion::plugin,
+        steps::plugin,
+        layout::plugin,
+        toolbar::plugin,
+        notifications::plugin,
+        localization::plugin,
+        keybind::plugin,
+        tween::plugin,
+        menu::plugin,
+        transition::plugin,
+    ))
+    .add_plugins((
+        selection_box::plugin,
+        cursor::plugin,
+        numeric_input::plugin,
+        message_box::plugin,
+        scrub::plugin,
+        sparkline::plugin,
+        zoom_visibility::plugin,
+        selection_summary::plugin,
+        interface_settings::plugin,
+        utils::plugin,
+        menu_components::egui_state::plugin,
+        scale::plugin,
+        panel_snap::plugin,
+        persistence::plugin,
+        placement::plugin,
+    ))
+    .add_plugins((secondary_action::plugin, hotkey_hint::plugin));
+}
+
 /// System to handle button interactions
+///
+/// Only [`Button`] nodes are clicked and repainted, so other nodes that take
+/// an [`Interaction`] for hovers, such as modal backdrops or bar segments with
+/// tooltips, keep their own colors. Disabled widgets send no clicks and keep
+/// their disabled colors.
 pub fn button_interaction_system(
-    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), Changed<Interaction>>,
+    mut interaction_query: Query<
+        (Entity, Ref<Interaction>, &mut BackgroundColor, WidgetDisabled),
+        With<Button>,
+    >,
+    interaction_state: Res<UiInteractionState>,
+    theme: Res<Theme>,
+    mut clicked_events: EventWriter<UiButtonClicked>,
 ) {
-    for (interaction, mut bg_color) in &mut interaction_query {
-        *bg_color = match interaction {
-            Interaction::Pressed => colors::button::PRESSED.into(),
-            Interaction::Hovered => colors::button::HOVERED.into(),
-            Interaction::None => colors::button::NORMAL.into(),
+    for (entity, interaction, mut bg_color, widget) in &mut interaction_query {
+        if is_disabled(widget) {
+            continue;
+        }
+        if interaction_state.just_pressed(entity) {
+            clicked_events.send(UiButtonClicked(entity));
+        }
+        if !interaction.is_changed() {
+            continue;
+        }
+        *bg_color = match *interaction {
+            Interaction::Pressed if interaction_state.is_pressed(entity) => theme.button.pressed.into(),
+            Interaction::Pressed | Interaction::Hovered => theme.button.hovered.into(),
+            Interaction::None => theme.button.normal.into(),
         };
     }
 }
 
 /// System to handle checkbox interactions
+///
+/// The check mark itself is updated by [`systems::checkmark_system`].
 pub fn checkbox_interaction_system(
-    mut query: Query<(&Interaction, &mut UiCheckbox, &Children), (Changed<Interaction>, With<UiCheckbox>)>,
-    mut _text_query: Query<&mut Text>,
+    mut query: Query<(Entity, &mut UiCheckbox)>,
+    interaction_state: Res<UiInteractionState>,
+    mut toggled_events: EventWriter<UiCheckboxToggled>,
 ) {
-    for (interaction, mut checkbox, children) in &mut query {
-        if *interaction == Interaction::Pressed {
+    for (entity, mut checkbox) in &mut query {
+        if interaction_state.just_pressed(entity) && !checkbox.disabled {
             checkbox.checked = !checkbox.checked;
-            if let Ok(mut text) = _text_query.get_mut(children[0]) {
-                text.sections[0].value = if checkbox.checked { "☑" } else { "☐" }.to_string();
-            }
-        }
-    }
-}
-
-/// System to handle slider interactions
-pub fn slider_interaction_system(
-    mut query: Query<(&Interaction, &mut UiSlider, &mut Style), (Changed<Interaction>, With<UiSlider>)>,
-) {
-    for (interaction, mut slider, mut style) in &mut query {
-        match interaction {
-            Interaction::Pressed => {
-                slider.value = slider.value.clamp(0.0, 1.0);
-                style.width = Val::Px(slider.value * 100.0);
-            }
-            Interaction::Hovered => {
-                style.width = Val::Px(slider.value * 100.0);
-            }
-            Interaction::None => {
-                style.width = Val::Px(slider.value * 100.0);
-            }
+            toggled_events.send(UiCheckboxToggled {
+                entity,
+                checked: checkbox.checked,
+            });
         }
     }
 }
@@ -0,0 +1,570 @@
+//! Scrollable list views with selection
+//!
+//! A [`UiListView`] shows its items as a column of equal-height rows in a
+//! clipped, scrollable box, for save-game lists or lobby player lists.
+//! Clicking a row selects it; in a [`ListSelectionMode::Multi`] list,
+//! Ctrl-click toggles a row and Shift-click selects a range. While the list
+//! has focus, Up/Down/Home/End move the selection (with Shift extending it
+//! in a multi-select list), Space toggles the current row and Ctrl+A selects
+//! everything. Every change sends [`ListSelectionChanged`].
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{list_view_bundle, ListSelectionChanged, UiListView};
+//! fn setup(mut commands: Commands) {
+//!     let saves = vec!["Autosave".to_string(), "Mission 3".to_string()];
+//!     commands.spawn(list_view_bundle(UiListView::new(saves).multi(), 240.0));
+//! }
+//!
+//! fn load(mut changes: EventReader<ListSelectionChanged>) {
+//!     for change in changes.read() {
+//!         info!("selected rows {:?}", change.selected);
+//!     }
+//! }
+//! ```
+//!
+//! Replacing [`UiListView::items`] rebuilds the rows, dropping selected
+//! indices that no longer exist.
+//!
+//! Lists with thousands of entries should be
+//! [`virtualized`](UiListView::virtualized): only the rows in view, plus a
+//! buffer above and below, are spawned, and scrolling moves rows that leave
+//! the view to the items coming into it, so the entity count stays bounded
+//! however long the list is.
+
+use std::ops::Range;
+
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::assets::UiFonts;
+use crate::components::{FocusState, Focusable, FocusableType};
+use crate::focus::UiFocus;
+use crate::interaction::UiInteractionState;
+use crate::theme::Theme;
+
+/// How many rows of a [`UiListView`] can be selected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum ListSelectionMode {
+    /// At most one row
+    #[default]
+    Single,
+    /// Any number of rows
+    Multi,
+}
+
+/// A scrollable list of text rows
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct UiListView {
+    /// Row texts, top to bottom
+    pub items: Vec<String>,
+    /// How many rows can be selected
+    pub mode: ListSelectionMode,
+    /// Selected row indices, ascending
+    pub selected: Vec<usize>,
+    /// Row the keyboard moves from, and where Shift ranges start
+    pub cursor: Option<usize>,
+    /// Height of each row in pixels
+    pub row_height: f32,
+    /// Pixels scrolled from the top
+    pub scroll: f32,
+    /// Spawn only the rows in view and this many on each side, when set
+    pub virtual_buffer: Option<usize>,
+    /// Items the spawned rows show
+    #[reflect(ignore)]
+    shown: Option<Vec<String>>,
+}
+
+impl UiListView {
+    /// Creates a single-select list
+    pub fn new(items: Vec<String>) -> Self {
+        Self {
+            items,
+            mode: ListSelectionMode::Single,
+            selected: Vec::new(),
+            cursor: None,
+            row_height: 28.0,
+            scroll: 0.0,
+            virtual_buffer: None,
+            shown: None,
+        }
+    }
+
+    /// Allows selecting any number of rows
+    pub fn multi(mut self) -> Self {
+        self.mode = ListSelectionMode::Multi;
+        self
+    }
+
+    /// Spawns only the rows in view, plus `buffer` rows above and below
+    pub fn virtualized(mut self, buffer: usize) -> Self {
+        self.virtual_buffer = Some(buffer);
+        self
+    }
+
+    /// Selects the row at `index`
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.select_only(index);
+        self
+    }
+
+    /// Whether the row at `index` is selected
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.binary_search(&index).is_ok()
+    }
+
+    /// Texts of the selected rows
+    pub fn selected_items(&self) -> impl Iterator<Item = &str> {
+        self.selected
+            .iter()
+            .filter_map(|&index| self.items.get(index).map(String::as_str))
+    }
+
+    /// Selects only the row at `index` and moves the cursor there
+    fn select_only(&mut self, index: usize) {
+        self.selected = vec![index];
+        self.cursor = Some(index);
+    }
+
+    /// Adds or removes the row at `index`
+    fn toggle(&mut self, index: usize) {
+        match self.selected.binary_search(&index) {
+            Ok(position) => {
+                self.selected.remove(position);
+            }
+            Err(position) => self.selected.insert(position, index),
+        }
+        self.cursor = Some(index);
+    }
+
+    /// Selects the rows from the cursor to `index`, keeping the cursor
+    fn select_range(&mut self, index: usize) {
+        let anchor = self.cursor.unwrap_or(index);
+        self.selected = (anchor.min(index)..=anchor.max(index)).collect();
+        self.cursor = Some(anchor);
+    }
+}
+
+/// A row spawned for a [`UiListView`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ListRow {
+    /// The list this row belongs to
+    pub list: Entity,
+    /// Index of the row's item
+    pub index: usize,
+}
+
+/// Marks the column holding a list's rows
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ListContent;
+
+/// Sent when the selection of a [`UiListView`] changes
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ListSelectionChanged {
+    /// The list entity
+    pub list: Entity,
+    /// Selected row indices, ascending
+    pub selected: Vec<usize>,
+}
+
+/// Creates a focusable list view `height` pixels tall
+pub fn list_view_bundle(list: UiListView, height: f32) -> (NodeBundle, Focusable, UiListView) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                height: Val::Px(height),
+                border: UiRect::all(Val::Px(1.0)),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            ..default()
+        },
+        Focusable {
+            state: FocusState::NotFocused,
+            focus_type: FocusableType::List,
+        },
+        list,
+    )
+}
+
+/// Scrolls `list` so the row at `index` is fully visible in a box `view` pixels tall
+fn scroll_into_view(list: &mut UiListView, index: usize, view: f32) {
+    let top = index as f32 * list.row_height;
+    let bottom = top + list.row_height;
+    let scroll = if top < list.scroll {
+        top
+    } else if bottom > list.scroll + view {
+        bottom - view
+    } else {
+        return;
+    };
+    list.scroll = scroll.max(0.0);
+}
+
+/// Indices of the rows a virtualized list keeps spawned in a box `view` pixels tall
+fn virtual_range(list: &UiListView, buffer: usize, view: f32) -> Range<usize> {
+    let count = list.items.len();
+    if list.row_height <= 0.0 {
+        return 0..count;
+    }
+    let first = (list.scroll / list.row_height).floor() as usize;
+    let last = ((list.scroll + view) / list.row_height).ceil() as usize;
+    first.saturating_sub(buffer).min(count)..(last + buffer).min(count)
+}
+
+/// Spawns the row for `index` under `content`
+fn spawn_row(
+    commands: &mut Commands,
+    content: Entity,
+    row: ListRow,
+    list: &UiListView,
+    text_style: &TextStyle,
+) {
+    let mut style = Style {
+        height: Val::Px(list.row_height),
+        flex_shrink: 0.0,
+        align_items: AlignItems::Center,
+        padding: UiRect::horizontal(Val::Px(8.0)),
+        ..default()
+    };
+    // Virtualized rows are placed by index, since most rows above are missing
+    if list.virtual_buffer.is_some() {
+        style.position_type = PositionType::Absolute;
+        style.top = Val::Px(row.index as f32 * list.row_height);
+        style.width = Val::Percent(100.0);
+    }
+    commands
+        .spawn((
+            ButtonBundle {
+                style,
+                background_color: Color::NONE.into(),
+                ..default()
+            },
+            row,
+        ))
+        .with_children(|row_node| {
+            row_node.spawn(TextBundle::from_section(
+                list.items[row.index].clone(),
+                text_style.clone(),
+            ));
+        })
+        .set_parent(content);
+}
+
+fn row_text_style(theme: &Theme) -> TextStyle {
+    TextStyle {
+        font: UiFonts::MEDIUM,
+        font_size: 16.0,
+        color: theme.text.normal,
+    }
+}
+
+/// System to spawn the rows of new lists, and rebuild them when the items change
+///
+/// Virtualized lists only get their content column here; their rows come
+/// from [`list_view_virtual_system`].
+pub fn list_view_spawn_system(
+    mut commands: Commands,
+    mut lists: Query<(Entity, &mut UiListView), Changed<UiListView>>,
+    theme: Res<Theme>,
+) {
+    let text_style = row_text_style(&theme);
+    for (entity, mut list) in &mut lists {
+        if list.shown.as_ref() == Some(&list.items) {
+            continue;
+        }
+        let count = list.items.len();
+        let list = list.bypass_change_detection();
+        list.selected.retain(|&index| index < count);
+        list.cursor = list.cursor.filter(|&index| index < count);
+        list.shown = Some(list.items.clone());
+
+        // A virtualized column keeps the full height so scrolling works
+        let height = match list.virtual_buffer {
+            Some(_) => Val::Px(count as f32 * list.row_height),
+            None => Val::Auto,
+        };
+        commands.entity(entity).despawn_descendants();
+        let content = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        flex_shrink: 0.0,
+                        width: Val::Percent(100.0),
+                        height,
+                        ..default()
+                    },
+                    ..default()
+                },
+                ListContent,
+            ))
+            .set_parent(entity)
+            .id();
+        if list.virtual_buffer.is_some() {
+            continue;
+        }
+        for index in 0..count {
+            let row = ListRow {
+                list: entity,
+                index,
+            };
+            spawn_row(&mut commands, content, row, list, &text_style);
+        }
+    }
+}
+
+/// System to keep the rows of virtualized lists matching the scrolled view
+///
+/// Rows still in range keep their item; rows that left it are moved to the
+/// items that came into view, and rows are only spawned or despawned when the
+/// view grows or shrinks.
+pub fn list_view_virtual_system(
+    mut commands: Commands,
+    lists: Query<(Entity, &UiListView, &Node, &Children)>,
+    contents: Query<(Entity, Option<&Children>), With<ListContent>>,
+    mut rows: Query<(Entity, &mut ListRow, &mut Style, &Children)>,
+    mut texts: Query<&mut Text>,
+    theme: Res<Theme>,
+) {
+    for (entity, list, node, children) in &lists {
+        let Some(buffer) = list.virtual_buffer else {
+            continue;
+        };
+        if list.shown.as_ref() != Some(&list.items) {
+            continue;
+        }
+        let Some((content, content_children)) = contents.iter_many(children).next() else {
+            continue;
+        };
+        let range = virtual_range(list, buffer, node.size().y);
+
+        let mut missing: Vec<bool> = vec![true; range.len()];
+        let mut free = Vec::new();
+        for (row_entity, row, ..) in rows.iter_many(content_children.into_iter().flatten()) {
+            if range.contains(&row.index) && missing[row.index - range.start] {
+                missing[row.index - range.start] = false;
+            } else {
+                free.push(row_entity);
+            }
+        }
+        let mut free = free.into_iter();
+        let text_style = row_text_style(&theme);
+        for index in range.clone().filter(|&index| missing[index - range.start]) {
+            let Some(row_entity) = free.next() else {
+                let row = ListRow {
+                    list: entity,
+                    index,
+                };
+                spawn_row(&mut commands, content, row, list, &text_style);
+                continue;
+            };
+            let Ok((_, mut row, mut style, row_children)) = rows.get_mut(row_entity) else {
+                continue;
+            };
+            row.index = index;
+            style.top = Val::Px(index as f32 * list.row_height);
+            for &child in row_children {
+                if let Ok(mut text) = texts.get_mut(child) {
+                    if let Some(section) = text.sections.first_mut() {
+                        section.value.clone_from(&list.items[index]);
+                    }
+                }
+            }
+        }
+        for row_entity in free {
+            commands.entity(row_entity).despawn_recursive();
+        }
+    }
+}
+
+/// System to select rows on click and scroll lists with the mouse wheel
+///
+/// Clicking a row also focuses its list.
+pub fn list_view_pointer_system(
+    mut lists: Query<(Entity, &mut UiListView, &Node, &GlobalTransform)>,
+    rows: Query<(Entity, &ListRow)>,
+    interaction_state: Res<UiInteractionState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut focus: ResMut<UiFocus>,
+    (mut wheel_events, windows): (EventReader<MouseWheel>, Query<&Window, With<PrimaryWindow>>),
+    mut changed_events: EventWriter<ListSelectionChanged>,
+) {
+    let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    for (row, part) in &rows {
+        if !interaction_state.just_pointer_pressed(row) {
+            continue;
+        }
+        let Ok((entity, mut list, ..)) = lists.get_mut(part.list) else {
+            continue;
+        };
+        if focus.focused != Some(entity) {
+            focus.focused = Some(entity);
+        }
+        let before = list.selected.clone();
+        match list.mode {
+            ListSelectionMode::Multi if ctrl => list.toggle(part.index),
+            ListSelectionMode::Multi if shift => list.select_range(part.index),
+            _ => list.select_only(part.index),
+        }
+        if list.selected != before {
+            changed_events.send(ListSelectionChanged {
+                list: entity,
+                selected: list.selected.clone(),
+            });
+        }
+    }
+
+    let cursor = windows.get_single().ok().and_then(Window::cursor_position);
+    for event in wheel_events.read() {
+        let Some(cursor) = cursor else {
+            continue;
+        };
+        for (_, mut list, node, transform) in &mut lists {
+            let rect = node.logical_rect(transform);
+            if !rect.contains(cursor) {
+                continue;
+            }
+            let delta = match event.unit {
+                MouseScrollUnit::Line => event.y * 20.0,
+                MouseScrollUnit::Pixel => event.y,
+            };
+            let content = list.items.len() as f32 * list.row_height;
+            let max_scroll = (content - rect.height()).max(0.0);
+            let scroll = (list.scroll - delta).clamp(0.0, max_scroll);
+            if list.scroll != scroll {
+                list.scroll = scroll;
+            }
+        }
+    }
+}
+
+/// System to move the selection of the focused list with the keyboard
+pub fn list_view_keyboard_system(
+    mut lists: Query<(Entity, &mut UiListView, &Node)>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    focus: Res<UiFocus>,
+    mut changed_events: EventWriter<ListSelectionChanged>,
+) {
+    let Some((entity, mut list, node)) = focus
+        .focused
+        .and_then(|focused| lists.get_mut(focused).ok())
+    else {
+        return;
+    };
+    let count = list.items.len();
+    if count == 0 {
+        return;
+    }
+    let pressed = |key| keyboard_input.just_pressed(key);
+    let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let multi = list.mode == ListSelectionMode::Multi;
+
+    // Shift ranges grow from the cursor, so move whichever end is not the cursor
+    let current = match (list.selected.first(), list.selected.last()) {
+        (Some(&first), Some(&last)) if multi && shift => Some(if list.cursor == Some(first) {
+            last
+        } else {
+            first
+        }),
+        _ => list.cursor,
+    };
+    let target = if pressed(KeyCode::ArrowDown) {
+        Some(current.map_or(0, |index| (index + 1).min(count - 1)))
+    } else if pressed(KeyCode::ArrowUp) {
+        Some(current.map_or(0, |index| index.saturating_sub(1)))
+    } else if pressed(KeyCode::Home) {
+        Some(0)
+    } else if pressed(KeyCode::End) {
+        Some(count - 1)
+    } else {
+        None
+    };
+
+    let before = list.selected.clone();
+    if let Some(target) = target {
+        if multi && shift {
+            list.select_range(target);
+        } else {
+            list.select_only(target);
+        }
+        scroll_into_view(&mut list, target, node.size().y);
+    } else if multi && pressed(KeyCode::Space) {
+        let index = list.cursor.unwrap_or(0);
+        list.toggle(index);
+    } else if multi && ctrl && pressed(KeyCode::KeyA) {
+        list.selected = (0..count).collect();
+    }
+    if list.selected != before {
+        changed_events.send(ListSelectionChanged {
+            list: entity,
+            selected: list.selected.clone(),
+        });
+    }
+}
+
+/// System to color selected and hovered rows and apply the scroll offset
+pub fn list_view_visual_system(
+    theme: Res<Theme>,
+    lists: Query<(Ref<UiListView>, &Children)>,
+    mut rows: Query<(Ref<ListRow>, Ref<Interaction>, &mut BackgroundColor)>,
+    mut contents: Query<&mut Style, With<ListContent>>,
+) {
+    for (list, children) in &lists {
+        if !list.is_changed() {
+            continue;
+        }
+        for &child in children {
+            if let Ok(mut style) = contents.get_mut(child) {
+                let top = Val::Px(-list.scroll);
+                if style.top != top {
+                    style.top = top;
+                }
+            }
+        }
+    }
+    for (row, interaction, mut background) in &mut rows {
+        let Ok((list, _)) = lists.get(row.list) else {
+            continue;
+        };
+        if !list.is_changed()
+            && !row.is_changed()
+            && !interaction.is_changed()
+            && !theme.is_changed()
+        {
+            continue;
+        }
+        background.0 = if list.is_selected(row.index) {
+            theme.button.pressed
+        } else if *interaction != Interaction::None {
+            theme.button.hovered
+        } else {
+            Color::NONE
+        };
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiListView>()
+        .register_type::<ListRow>()
+        .register_type::<ListContent>()
+        .add_event::<ListSelectionChanged>()
+        .add_systems(
+            Update,
+            (
+                list_view_spawn_system,
+                list_view_pointer_system,
+                list_view_keyboard_system,
+                list_view_virtual_system,
+                list_view_visual_system.after(crate::button_interaction_system),
+            )
+                .chain(),
+        );
+}
@@ -0,0 +1,243 @@
+//! Translating widget text
+//!
+//! Widgets hold keys instead of literal strings: [`LocalizedText`] on a text
+//! node, [`LocalizedTooltip`] next to a [`Tooltip`] and
+//! [`LocalizedSettingRow`] next to a [`SettingRow`]. The [`UiLocalizer`]
+//! resource resolves the keys through a [`Localizer`] and they are resolved
+//! again whenever it changes, for example on [`UiLocalizer::set_language`].
+//! Keys without a translation are shown as-is.
+//!
+//! The builders in [`builders`](crate::builders) attach these components when
+//! `.localized()` is called, treating the label and tooltip as keys.
+//!
+//! [`UiLocalizer::set_pseudo_locale`] runs every resolved string through
+//! [`pseudo_localize`], which swaps letters for accented lookalikes, pads the
+//! text by 30% and brackets it, so "Example" reads `[Ẽẋâḿṕĺẽ··]`. Text
+//! that overflows or gets clipped, and strings that skipped localization,
+//! stand out during development without waiting for real translations.
+
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use bevy::utils::HashMap;
+
+use crate::components::{SettingRow, Tooltip};
+
+/// Source of translated strings
+pub trait Localizer: Send + Sync + 'static {
+    /// The current language code, such as "en" or "de"
+    fn language(&self) -> &str;
+
+    /// Switches to another language
+    fn set_language(&mut self, language: &str);
+
+    /// The translation of `key` in the current language, if there is one
+    fn localize(&self, key: &str) -> Option<String>;
+}
+
+/// A [`Localizer`] backed by in-memory string tables, one per language
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringTableLocalizer {
+    language: String,
+    tables: HashMap<String, HashMap<String, String>>,
+}
+
+impl Default for StringTableLocalizer {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl StringTableLocalizer {
+    /// Creates a localizer with no strings, set to `language`
+    pub fn new(language: impl Into<String>) -> Self {
+        Self {
+            language: language.into(),
+            tables: HashMap::new(),
+        }
+    }
+
+    /// Adds translations for `language`, replacing existing ones with the same key
+    pub fn with_table(
+        mut self,
+        language: impl Into<String>,
+        entries: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.tables
+            .entry(language.into())
+            .or_default()
+            .extend(entries.into_iter().map(|(key, text)| (key.into(), text.into())));
+        self
+    }
+
+    /// Languages that have a string table
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.tables.keys().map(String::as_str)
+    }
+}
+
+impl Localizer for StringTableLocalizer {
+    fn language(&self) -> &str {
+        &self.language
+    }
+
+    fn set_language(&mut self, language: &str) {
+        self.language = language.to_string();
+    }
+
+    fn localize(&self, key: &str) -> Option<String> {
+        self.tables.get(&self.language)?.get(key).cloned()
+    }
+}
+
+/// The active [`Localizer`]
+///
+/// Defaults to an empty [`StringTableLocalizer`], which shows every key as-is.
+#[derive(Resource)]
+pub struct UiLocalizer {
+    localizer: Box<dyn Localizer>,
+    pseudo_locale: bool,
+}
+
+impl Default for UiLocalizer {
+    fn default() -> Self {
+        Self::new(StringTableLocalizer::default())
+    }
+}
+
+impl UiLocalizer {
+    /// Wraps a localizer
+    pub fn new(localizer: impl Localizer) -> Self {
+        Self {
+            localizer: Box::new(localizer),
+            pseudo_locale: false,
+        }
+    }
+
+    /// The current language code
+    pub fn language(&self) -> &str {
+        self.localizer.language()
+    }
+
+    /// Switches language; every localized widget is updated
+    pub fn set_language(&mut self, language: &str) {
+        self.localizer.set_language(language);
+    }
+
+    /// Whether resolved strings are pseudo-localized
+    pub fn pseudo_locale(&self) -> bool {
+        self.pseudo_locale
+    }
+
+    /// Turns pseudo-localization of every resolved string on or off; every
+    /// localized widget is updated
+    pub fn set_pseudo_locale(&mut self, enabled: bool) {
+        self.pseudo_locale = enabled;
+    }
+
+    /// The translation of `key`, or `key` itself when there is none
+    pub fn resolve(&self, key: &str) -> String {
+        let text = self
+            .localizer
+            .localize(key)
+            .unwrap_or_else(|| key.to_string());
+        if self.pseudo_locale {
+            pseudo_localize(&text)
+        } else {
+            text
+        }
+    }
+}
+
+/// Accented lookalikes of `a`-`z` and `A`-`Z`
+const PSEUDO_LOWER: [char; 26] = [
+    'â', 'ḃ', 'ċ', 'ḋ', 'ẽ', 'ḟ', 'ġ', 'ḣ', 'î', 'ĵ', 'ķ', 'ĺ', 'ḿ', 'ñ', 'õ', 'ṕ', 'ʠ', 'ŕ', 'ś',
+    'ţ', 'û', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ź',
+];
+const PSEUDO_UPPER: [char; 26] = [
+    'Â', 'Ḃ', 'Ċ', 'Ḋ', 'Ẽ', 'Ḟ', 'Ġ', 'Ḣ', 'Î', 'Ĵ', 'Ķ', 'Ĺ', 'Ḿ', 'Ñ', 'Õ', 'Ṕ', 'Ǫ', 'Ŕ', 'Ś',
+    'Ţ', 'Û', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ź',
+];
+
+/// Pseudo-localizes `text`: accents its letters, pads it by 30% and brackets it
+///
+/// Placeholders in braces, such as `{count}`, are kept as they are so
+/// formatting still finds them.
+pub fn pseudo_localize(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() * 2 + 2);
+    result.push('[');
+    let mut in_placeholder = false;
+    for character in text.chars() {
+        match character {
+            '{' => in_placeholder = true,
+            '}' => in_placeholder = false,
+            _ => {}
+        }
+        let accented = match character {
+            _ if in_placeholder => character,
+            'a'..='z' => PSEUDO_LOWER[(character as u8 - b'a') as usize],
+            'A'..='Z' => PSEUDO_UPPER[(character as u8 - b'A') as usize],
+            _ => character,
+        };
+        result.push(accented);
+    }
+    let padding = (text.chars().count() as f32 * 0.3).round() as usize;
+    result.extend(std::iter::repeat_n('·', padding));
+    result.push(']');
+    result
+}
+
+/// Shows the translation of this key in the first section of the node's [`Text`]
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct LocalizedText(pub String);
+
+/// Sets the sibling [`Tooltip`]'s text to the translation of this key
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct LocalizedTooltip(pub String);
+
+/// Sets the sibling [`SettingRow`]'s label and help text from these keys
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct LocalizedSettingRow {
+    /// Key of the label
+    pub label: String,
+    /// Key of the help text
+    pub help_text: Option<String>,
+}
+
+/// System to resolve localized text when the key or the localizer changes
+pub fn localized_text_system(
+    localizer: Res<UiLocalizer>,
+    mut texts: Query<(Ref<LocalizedText>, &mut Text)>,
+    mut tooltips: Query<(Ref<LocalizedTooltip>, &mut Tooltip)>,
+    mut rows: Query<(Ref<LocalizedSettingRow>, &mut SettingRow)>,
+) {
+    let all = localizer.is_changed();
+    for (key, mut text) in &mut texts {
+        if all || key.is_changed() {
+            if let Some(section) = text.sections.first_mut() {
+                section.value = localizer.resolve(&key.0);
+            }
+        }
+    }
+    for (key, mut tooltip) in &mut tooltips {
+        if all || key.is_changed() {
+            tooltip.text = localizer.resolve(&key.0);
+        }
+    }
+    for (keys, mut row) in &mut rows {
+        if all || keys.is_changed() {
+            row.label = localizer.resolve(&keys.label);
+            row.help_text = keys.help_text.as_deref().map(|key| localizer.resolve(key));
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<LocalizedText>()
+        .register_type::<LocalizedTooltip>()
+        .register_type::<LocalizedSettingRow>()
+        .init_resource::<UiLocalizer>()
+        .add_systems(PostUpdate, localized_text_system.before(UiSystem::Layout));
+}
@@ -0,0 +1,1026 @@
+//! Context menus and menu bars with cascading submenus
+//!
+//! A [`Menu`] holds a tree of [`MenuEntry`] items, submenus and separators.
+//! [`context_menu_bundle`] shows it as a popup at a screen position, and
+//! [`menu_bar_bundle`] as a row of titles whose submenus drop down below
+//! them. Both share the same submenu handling:
+//!
+//! - Resting the pointer on a submenu entry opens it to the side after
+//!   [`Menu::submenu_delay`]; resting on another entry closes it again, so
+//!   the pointer can cross a sibling on its way into an open submenu.
+//! - Up/Down move the highlight, Right or Enter open the highlighted
+//!   submenu, Left closes the innermost one, and Escape closes the menu.
+//! - Popups that would leave the window open on the other side instead.
+//!
+//! Picking an item sends [`MenuItemSelected`] with the item's [`UiId`] and
+//! closes the menu. A closed context menu is despawned; a menu bar just folds
+//! its submenus away. Items can show a hotkey hint, such as `Ctrl+S`,
+//! right-aligned after their label; the hint is only text, so binding the key
+//! is up to the game.
+//!
+//! To open a menu on right-click, put a [`ContextMenu`] on the entity: the
+//! menu opens at the cursor, closes on a click elsewhere or Escape, and
+//! picking an item also sends [`ContextMenuSelected`] naming the entity.
+//! [`open_context_menu`] does the same from code.
+//!
+//! Menu bars also answer to Alt, as in desktop editors: tapping Alt opens the
+//! first menu (and tapping it again closes it), and Alt plus a letter opens
+//! the menu whose title starts with that letter.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{context_menu_bundle, MenuEntry};
+//! fn open(mut commands: Commands, cursor: Vec2) {
+//!     commands.spawn(context_menu_bundle(
+//!         vec![
+//!             MenuEntry::item("rename", "Rename"),
+//!             MenuEntry::submenu(
+//!                 "Move to",
+//!                 vec![MenuEntry::item("group_1", "Group 1"), MenuEntry::item("group_2", "Group 2")],
+//!             ),
+//!             MenuEntry::Separator,
+//!             MenuEntry::item("delete", "Delete").disabled(),
+//!         ],
+//!         cursor,
+//!     ));
+//! }
+//! ```
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use bevy::ui::{UiStack, UiSystem};
+use bevy::window::PrimaryWindow;
+
+use crate::assets::UiFonts;
+use crate::clock::UiClock;
+use crate::components::UiId;
+use crate::interaction::UiInteractionState;
+use crate::placement::PopupBounds;
+use crate::theme::Theme;
+
+/// An entry in a [`Menu`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+// Recursive through `Submenu::entries`
+#[reflect(no_field_bounds)]
+pub enum MenuEntry {
+    /// A selectable item
+    Item {
+        /// Identifier sent with [`MenuItemSelected`] and set as the row's [`UiId`]
+        id: String,
+        /// Text shown in the menu
+        label: String,
+        /// Disabled items are dimmed and can't be picked
+        disabled: bool,
+        /// Shortcut hint shown right-aligned, such as `Ctrl+S`
+        hotkey: Option<String>,
+    },
+    /// An entry that opens a nested menu
+    Submenu {
+        /// Text shown in the menu
+        label: String,
+        /// Entries of the nested menu
+        entries: Vec<MenuEntry>,
+    },
+    /// A thin line between groups
+    Separator,
+}
+
+impl MenuEntry {
+    /// Creates an enabled item
+    pub fn item(id: impl Into<String>, label: impl Into<String>) -> Self {
+        MenuEntry::Item {
+            id: id.into(),
+            label: label.into(),
+            disabled: false,
+            hotkey: None,
+        }
+    }
+
+    /// Creates a submenu
+    pub fn submenu(label: impl Into<String>, entries: Vec<MenuEntry>) -> Self {
+        MenuEntry::Submenu {
+            label: label.into(),
+            entries,
+        }
+    }
+
+    /// Marks an item as disabled
+    pub fn disabled(mut self) -> Self {
+        if let MenuEntry::Item { disabled, .. } = &mut self {
+            *disabled = true;
+        }
+        self
+    }
+
+    /// Sets an item's shortcut hint
+    pub fn hotkey(mut self, text: impl Into<String>) -> Self {
+        if let MenuEntry::Item { hotkey, .. } = &mut self {
+            *hotkey = Some(text.into());
+        }
+        self
+    }
+
+    /// Text shown for the entry; none for separators
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            MenuEntry::Item { label, .. } | MenuEntry::Submenu { label, .. } => Some(label),
+            MenuEntry::Separator => None,
+        }
+    }
+
+    /// Whether keyboard navigation stops on this entry
+    fn is_selectable(&self) -> bool {
+        matches!(
+            self,
+            MenuEntry::Item {
+                disabled: false,
+                ..
+            } | MenuEntry::Submenu { .. }
+        )
+    }
+}
+
+/// How a [`Menu`] is shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum MenuKind {
+    /// A popup column at a screen position
+    #[default]
+    Context,
+    /// A row of titles, each expected to be a [`MenuEntry::Submenu`]
+    Bar,
+}
+
+/// A context menu or menu bar
+///
+/// Paths address entries by index at each level, so `[1, 0]` is the first
+/// entry of the submenu at index 1. Entries are read when the menu is
+/// spawned.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Menu {
+    /// Top-level entries
+    pub entries: Vec<MenuEntry>,
+    /// How the menu is shown
+    pub kind: MenuKind,
+    /// Path of the innermost open submenu; empty when none is open
+    pub open_path: Vec<usize>,
+    /// Path of the highlighted entry
+    pub highlighted: Option<Vec<usize>>,
+    /// Seconds the pointer rests on an entry before submenus open or close
+    pub submenu_delay: f32,
+    /// Entity the menu was opened for, such as a right-clicked [`ContextMenu`]
+    pub owner: Option<Entity>,
+    /// Submenu the pointer is heading for, and for how long
+    pending: Option<(Vec<usize>, f32)>,
+    /// `open_path` the spawned popups match
+    shown_path: Vec<usize>,
+}
+
+impl Menu {
+    /// Creates a menu of the given kind
+    pub fn new(kind: MenuKind, entries: Vec<MenuEntry>) -> Self {
+        Self {
+            entries,
+            kind,
+            open_path: Vec::new(),
+            highlighted: None,
+            submenu_delay: 0.25,
+            owner: None,
+            pending: None,
+            shown_path: Vec::new(),
+        }
+    }
+
+    /// The entries of the level at `path`; the top level for an empty path
+    pub fn entries_at(&self, path: &[usize]) -> Option<&[MenuEntry]> {
+        path.iter()
+            .try_fold(self.entries.as_slice(), |entries, &index| {
+                match entries.get(index)? {
+                    MenuEntry::Submenu { entries, .. } => Some(entries.as_slice()),
+                    _ => None,
+                }
+            })
+    }
+
+    /// The entry at `path`
+    pub fn entry(&self, path: &[usize]) -> Option<&MenuEntry> {
+        let (index, parent) = path.split_last()?;
+        self.entries_at(parent)?.get(*index)
+    }
+
+    /// Opens the submenu at `path` and highlights its first entry
+    fn enter(&mut self, path: &[usize]) {
+        let first = self
+            .entries_at(path)
+            .and_then(|entries| next_selectable(entries, None, true));
+        self.open_path = path.to_vec();
+        self.highlighted = Some(first.map_or_else(|| path.to_vec(), |first| child(path, first)));
+    }
+}
+
+/// Marks a menu bar that opens with Alt
+///
+/// Added by [`menu_bar_bundle`]; remove it from bars that shouldn't take Alt,
+/// such as all but one when several are on screen.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct MenuBar;
+
+/// What a [`MenuPart`] is
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub enum MenuPartKind {
+    /// The row of the entry at this path
+    Row(Vec<usize>),
+    /// The popup listing the submenu at this path
+    Popup(Vec<usize>),
+}
+
+/// A node spawned for a [`Menu`]
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct MenuPart {
+    /// The menu this node belongs to
+    pub menu: Entity,
+    /// What this node is
+    pub kind: MenuPartKind,
+}
+
+/// Sent when a menu item is picked
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct MenuItemSelected {
+    /// The menu entity
+    pub menu: Entity,
+    /// The item's [`MenuEntry::Item::id`]
+    pub id: UiId,
+    /// Position of the item among the entries of its level
+    pub index: usize,
+    /// The menu's [`Menu::owner`]
+    pub owner: Option<Entity>,
+}
+
+/// Opens a context menu of its entries when the entity is right-clicked
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ContextMenu {
+    /// Entries of the menu
+    pub entries: Vec<MenuEntry>,
+}
+
+impl ContextMenu {
+    /// Creates a context menu of plain items, using each label as its id
+    pub fn new<S: Into<String>>(options: impl IntoIterator<Item = S>) -> Self {
+        let entries = options
+            .into_iter()
+            .map(|label| {
+                let label = label.into();
+                MenuEntry::item(label.clone(), label)
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Creates a context menu with submenus, separators or disabled items
+    pub fn from_entries(entries: Vec<MenuEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+/// Sent when an item is picked from a [`ContextMenu`]
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ContextMenuSelected {
+    /// The entity carrying the [`ContextMenu`]
+    pub owner: Entity,
+    /// Position of the item among the entries of its level; for menus
+    /// without submenus, its index in [`ContextMenu::entries`]
+    pub index: usize,
+    /// The item's [`MenuEntry::Item::id`]
+    pub id: UiId,
+}
+
+/// Creates a context menu with its top-left corner at `position`
+pub fn context_menu_bundle(entries: Vec<MenuEntry>, position: Vec2) -> (NodeBundle, Menu) {
+    (
+        NodeBundle {
+            style: Style {
+                left: Val::Px(position.x),
+                top: Val::Px(position.y),
+                ..popup_style()
+            },
+            z_index: ZIndex::Global(200),
+            ..default()
+        },
+        Menu::new(MenuKind::Context, entries),
+    )
+}
+
+/// Opens a context menu for `owner` with its top-left corner at `position`
+///
+/// Picking an item sends [`ContextMenuSelected`] for `owner` as well as
+/// [`MenuItemSelected`].
+pub fn open_context_menu(
+    commands: &mut Commands,
+    owner: Entity,
+    entries: Vec<MenuEntry>,
+    position: Vec2,
+) -> Entity {
+    let (node, mut menu) = context_menu_bundle(entries, position);
+    menu.owner = Some(owner);
+    commands.spawn((node, menu)).id()
+}
+
+/// Creates a menu bar; each entry should be a [`MenuEntry::Submenu`]
+pub fn menu_bar_bundle(menus: Vec<MenuEntry>) -> (NodeBundle, Menu, MenuBar) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                width: Val::Percent(100.0),
+                padding: UiRect::horizontal(Val::Px(4.0)),
+                ..default()
+            },
+            ..default()
+        },
+        Menu::new(MenuKind::Bar, menus),
+        MenuBar,
+    )
+}
+
+fn popup_style() -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        flex_direction: FlexDirection::Column,
+        min_width: Val::Px(160.0),
+        padding: UiRect::all(Val::Px(4.0)),
+        ..default()
+    }
+}
+
+/// `path` extended by `index`
+fn child(path: &[usize], index: usize) -> Vec<usize> {
+    let mut child = path.to_vec();
+    child.push(index);
+    child
+}
+
+/// The next selectable index after `from`, wrapping around
+fn next_selectable(entries: &[MenuEntry], from: Option<usize>, forward: bool) -> Option<usize> {
+    let len = entries.len();
+    let mut index = from;
+    for _ in 0..len {
+        let next = match index {
+            None if forward => 0,
+            None => len - 1,
+            Some(index) if forward => (index + 1) % len,
+            Some(index) => (index + len - 1) % len,
+        };
+        if entries[next].is_selectable() {
+            return Some(next);
+        }
+        index = Some(next);
+    }
+    None
+}
+
+/// Spawns the rows of the level at `path`, and popups for its open submenus
+fn spawn_rows(
+    parent: &mut ChildBuilder,
+    entity: Entity,
+    menu: &Menu,
+    path: &[usize],
+    text_style: &TextStyle,
+    theme: &Theme,
+) {
+    let horizontal = menu.kind == MenuKind::Bar && path.is_empty();
+    for (index, entry) in menu.entries_at(path).unwrap_or_default().iter().enumerate() {
+        let row_path = child(path, index);
+        let (label, disabled, submenu, id, hotkey) = match entry {
+            MenuEntry::Item {
+                id,
+                label,
+                disabled,
+                hotkey,
+            } => (label, *disabled, false, Some(id), hotkey.as_ref()),
+            MenuEntry::Submenu { label, .. } => (label, false, true, None, None),
+            MenuEntry::Separator => {
+                let style = if horizontal {
+                    Style {
+                        width: Val::Px(1.0),
+                        height: Val::Px(16.0),
+                        margin: UiRect::horizontal(Val::Px(4.0)),
+                        ..default()
+                    }
+                } else {
+                    Style {
+                        height: Val::Px(1.0),
+                        margin: UiRect::vertical(Val::Px(3.0)),
+                        ..default()
+                    }
+                };
+                parent.spawn(NodeBundle {
+                    style,
+                    background_color: theme.text.disabled.into(),
+                    ..default()
+                });
+                continue;
+            }
+        };
+
+        let mut text_style = text_style.clone();
+        if disabled {
+            text_style.color = theme.text.disabled;
+        }
+        let mut row = parent.spawn((
+            ButtonBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(16.0),
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                    ..default()
+                },
+                background_color: Color::NONE.into(),
+                ..default()
+            },
+            MenuPart {
+                menu: entity,
+                kind: MenuPartKind::Row(row_path.clone()),
+            },
+        ));
+        if let Some(id) = id {
+            row.insert(UiId::new(id.clone()));
+        }
+        row.with_children(|row| {
+            row.spawn(TextBundle::from_section(label.clone(), text_style.clone()));
+            if let Some(hotkey) = hotkey {
+                let hint_style = TextStyle {
+                    color: theme.text.disabled,
+                    ..text_style.clone()
+                };
+                row.spawn(TextBundle::from_section(hotkey.clone(), hint_style));
+            }
+            if submenu && !horizontal {
+                row.spawn(TextBundle::from_section("›", text_style.clone()));
+            }
+            if submenu && menu.open_path.starts_with(&row_path) {
+                spawn_popup(row, entity, menu, &row_path, &text_style, theme);
+            }
+        });
+    }
+}
+
+/// Spawns the popup of the submenu at `path` under its row
+fn spawn_popup(
+    row: &mut ChildBuilder,
+    entity: Entity,
+    menu: &Menu,
+    path: &[usize],
+    text_style: &TextStyle,
+    theme: &Theme,
+) {
+    // Menu bar titles drop down; everything else opens to the side
+    let (left, top) = if menu.kind == MenuKind::Bar && path.len() == 1 {
+        (Val::Px(0.0), Val::Percent(100.0))
+    } else {
+        (Val::Percent(100.0), Val::Px(0.0))
+    };
+    row.spawn((
+        NodeBundle {
+            style: Style {
+                left,
+                top,
+                ..popup_style()
+            },
+            background_color: theme.button.normal.into(),
+            z_index: ZIndex::Global(200),
+            ..default()
+        },
+        MenuPart {
+            menu: entity,
+            kind: MenuPartKind::Popup(path.to_vec()),
+        },
+    ))
+    .with_children(|popup| spawn_rows(popup, entity, menu, path, text_style, theme));
+}
+
+fn text_style(theme: &Theme) -> TextStyle {
+    TextStyle {
+        font: UiFonts::MEDIUM,
+        font_size: 16.0,
+        color: theme.text.normal,
+    }
+}
+
+/// Closes a menu: context menus are despawned, menu bars fold up
+fn close_menu(commands: &mut Commands, entity: Entity, menu: &mut Menu) {
+    match menu.kind {
+        MenuKind::Context => commands.entity(entity).despawn_recursive(),
+        MenuKind::Bar => {
+            menu.open_path.clear();
+            menu.highlighted = None;
+            menu.pending = None;
+        }
+    }
+}
+
+/// Picks the item at `path`, or opens the submenu there
+fn activate(
+    commands: &mut Commands,
+    entity: Entity,
+    menu: &mut Menu,
+    path: &[usize],
+    selected_events: &mut EventWriter<MenuItemSelected>,
+) {
+    match menu.entry(path) {
+        Some(MenuEntry::Item {
+            id,
+            disabled: false,
+            ..
+        }) => {
+            selected_events.send(MenuItemSelected {
+                menu: entity,
+                id: UiId::new(id.clone()),
+                index: path.last().copied().unwrap_or_default(),
+                owner: menu.owner,
+            });
+            close_menu(commands, entity, menu);
+        }
+        Some(MenuEntry::Submenu { .. }) => menu.enter(path),
+        _ => {}
+    }
+}
+
+/// System to spawn the top level of new menus
+pub fn menu_spawn_system(
+    mut commands: Commands,
+    mut menus: Query<(Entity, &mut Menu), Added<Menu>>,
+    theme: Res<Theme>,
+) {
+    let text_style = text_style(&theme);
+    for (entity, mut menu) in &mut menus {
+        commands
+            .entity(entity)
+            .insert(BackgroundColor(theme.button.normal))
+            .with_children(|root| spawn_rows(root, entity, &menu, &[], &text_style, &theme));
+        let open_path = menu.open_path.clone();
+        menu.bypass_change_detection().shown_path = open_path;
+    }
+}
+
+/// System to track the hovered entry and open submenus on hover and click
+///
+/// Submenus follow the pointer after [`Menu::submenu_delay`]. In an open menu
+/// bar, hovering another title switches to its menu at once.
+pub fn menu_pointer_system(
+    mut commands: Commands,
+    mut menus: Query<(Entity, &mut Menu)>,
+    rows: Query<(Entity, &MenuPart, &Interaction)>,
+    interaction_state: Res<UiInteractionState>,
+    clock: Res<UiClock>,
+    mut selected_events: EventWriter<MenuItemSelected>,
+) {
+    let mut hovered = Vec::new();
+    let mut pressed = Vec::new();
+    for (row, part, interaction) in &rows {
+        let MenuPartKind::Row(path) = &part.kind else {
+            continue;
+        };
+        if *interaction != Interaction::None {
+            hovered.push((part.menu, path));
+        }
+        if interaction_state.just_pressed(row) {
+            pressed.push((part.menu, path));
+        }
+    }
+
+    for (entity, path) in hovered {
+        let Ok((_, mut menu)) = menus.get_mut(entity) else {
+            continue;
+        };
+        if menu.highlighted.as_ref() != Some(path) {
+            menu.highlighted = Some(path.clone());
+        }
+        let target = match menu.entry(path) {
+            Some(MenuEntry::Submenu { .. }) => path.clone(),
+            _ => path[..path.len() - 1].to_vec(),
+        };
+        if menu.kind == MenuKind::Bar && path.len() == 1 {
+            // Titles only react to hover while the bar is open
+            if !menu.open_path.is_empty() && menu.open_path != target && !target.is_empty() {
+                menu.open_path = target;
+            }
+            continue;
+        }
+
+        let waited = match &mut menu.bypass_change_detection().pending {
+            Some((pending, waited)) if *pending == target => {
+                *waited += clock.delta_seconds();
+                *waited
+            }
+            pending => {
+                *pending = Some((target.clone(), 0.0));
+                0.0
+            }
+        };
+        if waited >= menu.submenu_delay && menu.open_path != target {
+            menu.open_path = target;
+        }
+    }
+
+    for (entity, path) in pressed {
+        let Ok((_, mut menu)) = menus.get_mut(entity) else {
+            continue;
+        };
+        match menu.entry(path) {
+            Some(MenuEntry::Submenu { .. }) if menu.kind == MenuKind::Bar && path.len() == 1 => {
+                if menu.open_path.first() == path.first() {
+                    menu.open_path.clear();
+                } else {
+                    menu.open_path = path.clone();
+                }
+            }
+            Some(MenuEntry::Submenu { .. }) => menu.open_path = path.clone(),
+            _ => activate(&mut commands, entity, &mut menu, path, &mut selected_events),
+        }
+    }
+}
+
+/// System to navigate open menus with the keyboard
+///
+/// Context menus always take keys; menu bars only while a menu is open.
+pub fn menu_keyboard_system(
+    mut commands: Commands,
+    mut menus: Query<(Entity, &mut Menu)>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut selected_events: EventWriter<MenuItemSelected>,
+) {
+    let pressed = |key| keyboard_input.just_pressed(key);
+    for (entity, mut menu) in &mut menus {
+        let bar = menu.kind == MenuKind::Bar;
+        if bar && menu.open_path.is_empty() {
+            continue;
+        }
+        if pressed(KeyCode::Escape) {
+            close_menu(&mut commands, entity, &mut menu);
+            continue;
+        }
+
+        // The level being navigated: the highlighted entry's, or the innermost open one
+        let level = match &menu.highlighted {
+            Some(highlighted) => highlighted[..highlighted.len() - 1].to_vec(),
+            None => menu.open_path.clone(),
+        };
+        let current = menu
+            .highlighted
+            .as_ref()
+            .and_then(|path| path.last().copied());
+        let titles = menu.entries.len();
+        let switch_title = |menu: &mut Menu, forward: bool| {
+            let title = menu.open_path.first().copied().unwrap_or_default();
+            let title = if forward {
+                (title + 1) % titles
+            } else {
+                (title + titles - 1) % titles
+            };
+            menu.open_path = vec![title];
+            menu.highlighted = Some(vec![title]);
+        };
+
+        if bar && level.is_empty() && pressed(KeyCode::ArrowDown) {
+            // From a title down into its menu
+            let open_path = menu.open_path.clone();
+            menu.enter(&open_path);
+        } else if pressed(KeyCode::ArrowDown) || pressed(KeyCode::ArrowUp) {
+            let forward = pressed(KeyCode::ArrowDown);
+            let next = menu
+                .entries_at(&level)
+                .and_then(|entries| next_selectable(entries, current, forward));
+            if let Some(next) = next {
+                menu.highlighted = Some(child(&level, next));
+            }
+        } else if pressed(KeyCode::ArrowRight) {
+            let highlighted = menu.highlighted.clone().unwrap_or_default();
+            if let Some(MenuEntry::Submenu { .. }) = menu.entry(&highlighted) {
+                menu.enter(&highlighted);
+            } else if bar {
+                switch_title(&mut menu, true);
+            }
+        } else if pressed(KeyCode::ArrowLeft) {
+            if bar && level.len() <= 1 {
+                switch_title(&mut menu, false);
+            } else if !level.is_empty() {
+                menu.open_path = level[..level.len() - 1].to_vec();
+                menu.highlighted = Some(level);
+            }
+        } else if pressed(KeyCode::Enter) || pressed(KeyCode::Space) {
+            if let Some(highlighted) = menu.highlighted.clone() {
+                activate(
+                    &mut commands,
+                    entity,
+                    &mut menu,
+                    &highlighted,
+                    &mut selected_events,
+                );
+            }
+        }
+    }
+}
+
+/// System to open and close menu bars with Alt
+///
+/// A tap of Alt with no other key in between toggles the first menu; Alt
+/// plus a letter opens the menu whose title starts with it.
+pub fn menu_bar_alt_system(
+    mut menus: Query<&mut Menu, With<MenuBar>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut key_events: EventReader<KeyboardInput>,
+    mut tapping: Local<bool>,
+) {
+    let alt_keys = [KeyCode::AltLeft, KeyCode::AltRight];
+    let letter = key_events
+        .read()
+        .filter(|event| event.state.is_pressed())
+        .find_map(|event| match &event.logical_key {
+            Key::Character(text) => text.chars().next(),
+            _ => None,
+        });
+
+    if keyboard_input.any_just_pressed(alt_keys) {
+        *tapping = true;
+    } else if keyboard_input
+        .get_just_pressed()
+        .any(|key| !alt_keys.contains(key))
+    {
+        // Alt is a modifier for this press, not a tap
+        *tapping = false;
+    }
+    let tapped = *tapping && keyboard_input.any_just_released(alt_keys);
+    if tapped {
+        *tapping = false;
+    }
+    let letter = letter.filter(|_| keyboard_input.any_pressed(alt_keys));
+
+    for mut menu in &mut menus {
+        if tapped {
+            if menu.open_path.is_empty() {
+                if !menu.entries.is_empty() {
+                    menu.open_path = vec![0];
+                    menu.highlighted = Some(vec![0]);
+                }
+            } else {
+                menu.open_path.clear();
+                menu.highlighted = None;
+                menu.pending = None;
+            }
+        } else if let Some(letter) = letter {
+            let title = menu.entries.iter().position(|entry| {
+                entry
+                    .label()
+                    .and_then(|label| label.chars().next())
+                    .is_some_and(|first| first.to_lowercase().eq(letter.to_lowercase()))
+            });
+            if let Some(title) = title {
+                menu.enter(&[title]);
+            }
+        }
+    }
+}
+
+/// System to close menus on a click outside them
+///
+/// Any right-click closes open menus too, since UI presses only track the
+/// left button.
+pub fn menu_dismiss_system(
+    mut commands: Commands,
+    mut menus: Query<(Entity, &mut Menu)>,
+    parents: Query<&Parent>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    interaction_state: Res<UiInteractionState>,
+) {
+    if !mouse_buttons.any_just_pressed([MouseButton::Left, MouseButton::Right]) {
+        return;
+    }
+    let pressed = interaction_state.current.pressed;
+    for (entity, mut menu) in &mut menus {
+        if menu.kind == MenuKind::Bar && menu.open_path.is_empty() {
+            continue;
+        }
+        let inside = pressed.is_some_and(|target| {
+            target == entity
+                || parents
+                    .iter_ancestors(target)
+                    .any(|ancestor| ancestor == entity)
+        });
+        if !inside {
+            close_menu(&mut commands, entity, &mut menu);
+        }
+    }
+}
+
+/// System to open a [`ContextMenu`] at the cursor when its entity is right-clicked
+///
+/// The topmost visible entity with a `ContextMenu` under the cursor wins.
+pub fn context_menu_open_system(
+    mut commands: Commands,
+    owners: Query<(&ContextMenu, &Node, &GlobalTransform, &ViewVisibility)>,
+    ui_stack: Res<UiStack>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    if !mouse_buttons.just_pressed(MouseButton::Right) {
+        return;
+    }
+    let Some(cursor) = windows.get_single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    let clicked = ui_stack.uinodes.iter().rev().find_map(|&entity| {
+        let (context_menu, node, transform, visibility) = owners.get(entity).ok()?;
+        (visibility.get() && node.logical_rect(transform).contains(cursor))
+            .then_some((entity, context_menu))
+    });
+    if let Some((owner, context_menu)) = clicked {
+        open_context_menu(&mut commands, owner, context_menu.entries.clone(), cursor);
+    }
+}
+
+/// System to send [`ContextMenuSelected`] for items picked from owned menus
+pub fn context_menu_selected_system(
+    mut selected_events: EventReader<MenuItemSelected>,
+    mut context_events: EventWriter<ContextMenuSelected>,
+) {
+    for event in selected_events.read() {
+        if let Some(owner) = event.owner {
+            context_events.send(ContextMenuSelected {
+                owner,
+                index: event.index,
+                id: event.id.clone(),
+            });
+        }
+    }
+}
+
+/// System to spawn and despawn submenu popups as [`Menu::open_path`] changes
+///
+/// Popups shared by the old and new paths are kept; the rest are rebuilt.
+pub fn menu_popup_system(
+    mut commands: Commands,
+    mut menus: Query<(Entity, &mut Menu), Changed<Menu>>,
+    parts: Query<(Entity, &MenuPart)>,
+    theme: Res<Theme>,
+) {
+    let text_style = text_style(&theme);
+    for (entity, mut menu) in &mut menus {
+        if menu.shown_path == menu.open_path {
+            continue;
+        }
+        let common = menu
+            .shown_path
+            .iter()
+            .zip(&menu.open_path)
+            .take_while(|(shown, open)| shown == open)
+            .count();
+        let find = |kind: MenuPartKind| {
+            parts
+                .iter()
+                .find(|(_, part)| part.menu == entity && part.kind == kind)
+                .map(|(part, _)| part)
+        };
+
+        if menu.shown_path.len() > common {
+            let closed = MenuPartKind::Popup(menu.shown_path[..=common].to_vec());
+            if let Some(popup) = find(closed) {
+                commands.entity(popup).despawn_recursive();
+            }
+        }
+        if menu.open_path.len() > common {
+            let path = &menu.open_path[..=common];
+            let row = find(MenuPartKind::Row(path.to_vec()));
+            if let (Some(row), Some(MenuEntry::Submenu { .. })) = (row, menu.entry(path)) {
+                commands.entity(row).with_children(|row| {
+                    spawn_popup(row, entity, &menu, path, &text_style, &theme);
+                });
+            }
+        }
+        let open_path = menu.open_path.clone();
+        menu.bypass_change_detection().shown_path = open_path;
+    }
+}
+
+/// System to highlight the hovered entry and the rows of open submenus
+pub fn menu_visual_system(
+    theme: Res<Theme>,
+    menus: Query<Ref<Menu>>,
+    mut rows: Query<(Ref<Interaction>, &MenuPart, &mut BackgroundColor)>,
+) {
+    for (interaction, part, mut background) in &mut rows {
+        let (Ok(menu), MenuPartKind::Row(path)) = (menus.get(part.menu), &part.kind) else {
+            continue;
+        };
+        if !menu.is_changed() && !theme.is_changed() && !interaction.is_changed() {
+            continue;
+        }
+        let active = menu.highlighted.as_ref() == Some(path) || menu.open_path.starts_with(path);
+        background.0 = if active {
+            theme.button.hovered
+        } else {
+            Color::NONE
+        };
+    }
+}
+
+/// System to keep popups on the monitor they open on, opening them on the
+/// other side if needed
+pub fn menu_anchor_system(
+    menus: Query<&Menu>,
+    mut popups: Query<
+        (
+            Ref<Node>,
+            &GlobalTransform,
+            &mut Style,
+            Option<&MenuPart>,
+            Option<&Menu>,
+        ),
+        Or<(With<Menu>, With<MenuPart>)>,
+    >,
+    bounds: Res<PopupBounds>,
+) {
+    for (node, transform, mut style, part, context) in &mut popups {
+        if !node.is_changed() {
+            continue;
+        }
+        let rect = node.logical_rect(transform);
+        let Some(area) = bounds.area_at(rect.min) else {
+            continue;
+        };
+        let overflow = (rect.max - area.max).max(Vec2::ZERO);
+        if overflow == Vec2::ZERO {
+            continue;
+        }
+
+        match (part, context) {
+            // A context menu's root: slide it back onto the monitor
+            (_, Some(menu)) if menu.kind == MenuKind::Context => {
+                if let (Val::Px(left), Val::Px(top)) = (style.left, style.top) {
+                    style.left = Val::Px((left - overflow.x).max(area.min.x));
+                    style.top = Val::Px((top - overflow.y).max(area.min.y));
+                }
+            }
+            (
+                Some(MenuPart {
+                    menu,
+                    kind: MenuPartKind::Popup(path),
+                }),
+                _,
+            ) => {
+                let drops_down = menus
+                    .get(*menu)
+                    .is_ok_and(|menu| menu.kind == MenuKind::Bar && path.len() == 1);
+                if overflow.x > 0.0 {
+                    if drops_down {
+                        style.left = Val::Auto;
+                        style.right = Val::Px(0.0);
+                    } else {
+                        // Open to the left of the row instead
+                        style.left = Val::Auto;
+                        style.right = Val::Percent(100.0);
+                    }
+                }
+                if overflow.y > 0.0 && !drops_down {
+                    style.top = Val::Px(-overflow.y);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Menu>()
+        .register_type::<ContextMenu>()
+        .register_type::<MenuBar>()
+        .register_type::<MenuPart>()
+        .add_event::<MenuItemSelected>()
+        .add_event::<ContextMenuSelected>()
+        .add_systems(
+            Update,
+            (
+                menu_spawn_system,
+                menu_pointer_system,
+                menu_keyboard_system,
+                menu_bar_alt_system,
+                menu_dismiss_system,
+                context_menu_open_system,
+                menu_popup_system,
+                menu_visual_system.after(crate::button_interaction_system),
+                context_menu_selected_system,
+            )
+                .chain(),
+        )
+        .add_systems(
+            PostUpdate,
+            menu_anchor_system
+                .after(UiSystem::Layout)
+                .after(bevy::transform::TransformSystem::TransformPropagate),
+        );
+}
@@ -0,0 +1,116 @@
+//! Modal dialogs for egui menus
+//!
+//! These mirror [`spawn_modal`](crate::modal::spawn_modal) for screens drawn
+//! with egui: the same [`ModalConfig`] and [`ModalResponse`], a dimmed
+//! backdrop that swallows clicks, buttons laid out left to right, Escape to
+//! cancel and Enter to confirm. Being immediate mode, the dialog is shown for
+//! as long as the caller keeps calling the helper, and the response is
+//! returned on the frame a choice is made:
+//!
+//! ```rust
+//! # use sf_ui_common::{menu_confirm, ConfirmResult};
+//! # fn show(ctx: &bevy_egui::egui::Context, quitting: &mut bool) {
+//! if *quitting {
+//!     match menu_confirm(ctx, "Quit", "Leave the match?") {
+//!         ConfirmResult::Confirmed => { /* leave */ *quitting = false }
+//!         ConfirmResult::Cancelled => *quitting = false,
+//!         ConfirmResult::Pending => {}
+//!     }
+//! }
+//! # }
+//! ```
+
+use bevy_egui::egui;
+
+use super::egui_wrappers::{menu_button_with, MenuOptions};
+use crate::modal::{ModalConfig, ModalResponse};
+
+/// Outcome of [`menu_confirm`] for one frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfirmResult {
+    /// No choice yet; keep showing the dialog
+    Pending,
+    /// OK or Enter
+    Confirmed,
+    /// Cancel or Escape
+    Cancelled,
+}
+
+/// Shows a modal dialog and returns the response chosen this frame, if any
+///
+/// The button with [`ModalResponse::Ok`] is drawn as the primary action and
+/// also answers Enter; Escape answers [`ModalResponse::Cancel`].
+pub fn menu_modal(ctx: &egui::Context, config: &ModalConfig) -> Option<ModalResponse> {
+    let id = egui::Id::new(("menu_modal", &config.title));
+
+    // Backdrop: dims the screen and swallows clicks outside the dialog
+    let screen = ctx.screen_rect();
+    egui::Area::new(id.with("backdrop"))
+        .order(egui::Order::Foreground)
+        .fixed_pos(screen.min)
+        .show(ctx, |ui| {
+            ui.painter()
+                .rect_filled(screen, 0.0, egui::Color32::from_black_alpha(160));
+            ui.allocate_rect(screen, egui::Sense::click_and_drag());
+        });
+
+    let mut response = None;
+    egui::Area::new(id)
+        .order(egui::Order::Foreground)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            egui::Frame::window(ui.style()).show(ui, |ui| {
+                ui.set_max_width(400.0);
+                ui.heading(&config.title);
+                ui.add_space(8.0);
+                ui.label(&config.body);
+                ui.add_space(12.0);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    // Right to left, so the last button is placed first
+                    for button in config.buttons.iter().rev() {
+                        let options = if button.response == ModalResponse::Ok {
+                            MenuOptions::new().primary()
+                        } else {
+                            MenuOptions::new()
+                        };
+                        if menu_button_with(ui, &button.label, options) {
+                            response = Some(button.response.clone());
+                        }
+                    }
+                });
+            });
+        });
+    ctx.move_to_top(egui::LayerId::new(egui::Order::Foreground, id));
+
+    let (escape, enter) = ctx.input(|input| {
+        (
+            input.key_pressed(egui::Key::Escape),
+            input.key_pressed(egui::Key::Enter),
+        )
+    });
+    let has_ok = config
+        .buttons
+        .iter()
+        .any(|button| button.response == ModalResponse::Ok);
+    response.or(if escape {
+        Some(ModalResponse::Cancel)
+    } else if enter && has_ok {
+        Some(ModalResponse::Ok)
+    } else {
+        None
+    })
+}
+
+/// Shows an OK/Cancel confirmation dialog
+pub fn menu_confirm(ctx: &egui::Context, title: &str, body: &str) -> ConfirmResult {
+    match menu_modal(ctx, &ModalConfig::confirm(title, body)) {
+        None => ConfirmResult::Pending,
+        Some(ModalResponse::Ok) => ConfirmResult::Confirmed,
+        Some(_) => ConfirmResult::Cancelled,
+    }
+}
+
+/// Shows an informational dialog; returns true once it is dismissed
+pub fn menu_alert(ctx: &egui::Context, title: &str, body: &str) -> bool {
+    menu_modal(ctx, &ModalConfig::alert(title, body)).is_some()
+}
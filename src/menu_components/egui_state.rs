@@ -0,0 +1,160 @@
+//! Shared state for egui menus
+//!
+//! The egui wrappers are stateless, so open sections, selected rows and
+//! scroll positions would otherwise live in each caller. [`MenuState`] keeps
+//! them in one resource under string keys, where any system can read or
+//! reset them and a save system can persist them through reflection. The
+//! wrappers here take the resource and a key:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{menu_collapsing, menu_scroll_area_stateful, menu_selectable_list, MenuState};
+//! # fn show(ui: &mut bevy_egui::egui::Ui, mut state: ResMut<MenuState>) {
+//! let state = &mut *state;
+//! menu_scroll_area_stateful(ui, state, "lobby.scroll", |ui, state| {
+//!     menu_collapsing(ui, state, "lobby.maps", "Maps", |ui, state| {
+//!         menu_selectable_list(ui, state, "lobby.map", &["Dunes", "Glacier", "Delta"]);
+//!     });
+//! });
+//! # }
+//! ```
+//!
+//! Other systems react to the same keys, for instance to open the section
+//! holding a newly selected map with [`MenuState::set_open`].
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_egui::egui;
+
+/// Open sections, selected rows and scroll offsets of egui menus, by key
+#[derive(Resource, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct MenuState {
+    open: HashMap<String, bool>,
+    selected: HashMap<String, usize>,
+    scroll: HashMap<String, f32>,
+}
+
+impl MenuState {
+    /// Whether the section at `key` is open, `None` if it was never toggled
+    pub fn is_open(&self, key: &str) -> Option<bool> {
+        self.open.get(key).copied()
+    }
+
+    /// Opens or closes the section at `key`
+    pub fn set_open(&mut self, key: impl Into<String>, open: bool) {
+        self.open.insert(key.into(), open);
+    }
+
+    /// The selected row of the list at `key`
+    pub fn selected(&self, key: &str) -> Option<usize> {
+        self.selected.get(key).copied()
+    }
+
+    /// Selects a row of the list at `key`, or clears its selection
+    pub fn select(&mut self, key: impl Into<String>, row: Option<usize>) {
+        let key = key.into();
+        match row {
+            Some(row) => self.selected.insert(key, row),
+            None => self.selected.remove(&key),
+        };
+    }
+
+    /// Vertical scroll offset of the scroll area at `key`, in points
+    pub fn scroll_offset(&self, key: &str) -> f32 {
+        self.scroll.get(key).copied().unwrap_or_default()
+    }
+
+    /// Scrolls the scroll area at `key` to `offset` points
+    pub fn set_scroll_offset(&mut self, key: impl Into<String>, offset: f32) {
+        self.scroll.insert(key.into(), offset.max(0.0));
+    }
+
+    /// Forgets everything stored under `key`
+    pub fn remove(&mut self, key: &str) {
+        self.open.remove(key);
+        self.selected.remove(key);
+        self.scroll.remove(key);
+    }
+
+    /// Forgets everything stored under keys starting with `prefix`, such as
+    /// every key of one screen
+    pub fn remove_prefixed(&mut self, prefix: &str) {
+        self.open.retain(|key, _| !key.starts_with(prefix));
+        self.selected.retain(|key, _| !key.starts_with(prefix));
+        self.scroll.retain(|key, _| !key.starts_with(prefix));
+    }
+}
+
+/// Variant of [`menu_scroll_area`](super::egui_wrappers::menu_scroll_area)
+/// keeping its scroll offset in [`MenuState`] under `key`
+///
+/// The state is handed on to `add_contents` for the wrappers inside.
+pub fn menu_scroll_area_stateful<R>(
+    ui: &mut egui::Ui,
+    state: &mut MenuState,
+    key: &str,
+    add_contents: impl FnOnce(&mut egui::Ui, &mut MenuState) -> R,
+) -> R {
+    let output = egui::ScrollArea::vertical()
+        .id_source(key)
+        .auto_shrink([false; 2])
+        .vertical_scroll_offset(state.scroll_offset(key))
+        .show(ui, |ui| add_contents(ui, state));
+    if state.scroll_offset(key) != output.state.offset.y {
+        state.set_scroll_offset(key, output.state.offset.y);
+    }
+    output.inner
+}
+
+/// A collapsing section whose open state is kept in [`MenuState`] under `key`
+///
+/// Sections start closed. Returns what `add_contents` returned, or `None`
+/// while closed.
+pub fn menu_collapsing<R>(
+    ui: &mut egui::Ui,
+    state: &mut MenuState,
+    key: &str,
+    title: &str,
+    add_contents: impl FnOnce(&mut egui::Ui, &mut MenuState) -> R,
+) -> Option<R> {
+    let open = state.is_open(key).unwrap_or(false);
+    let response = egui::CollapsingHeader::new(title)
+        .id_source(key)
+        .open(Some(open))
+        .show(ui, |ui| add_contents(ui, state));
+    if response.header_response.clicked() {
+        state.set_open(key, !open);
+    }
+    response.body_returned
+}
+
+/// A list of selectable rows whose selection is kept in [`MenuState`] under `key`
+///
+/// Returns the row clicked this frame.
+pub fn menu_selectable_list(
+    ui: &mut egui::Ui,
+    state: &mut MenuState,
+    key: &str,
+    rows: &[impl AsRef<str>],
+) -> Option<usize> {
+    let selected = state.selected(key);
+    let mut clicked = None;
+    for (index, row) in rows.iter().enumerate() {
+        if ui
+            .selectable_label(selected == Some(index), row.as_ref())
+            .clicked()
+        {
+            clicked = Some(index);
+        }
+    }
+    if clicked.is_some() && clicked != selected {
+        state.select(key, clicked);
+    }
+    clicked
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<MenuState>()
+        .init_resource::<MenuState>();
+}
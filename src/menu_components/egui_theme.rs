@@ -0,0 +1,100 @@
+//! Bridge from the Bevy UI [`Theme`] to egui's visuals
+//!
+//! Call [`apply_theme`] on an egui context whenever the [`Theme`] resource
+//! changes so egui menus match the Bevy UI widgets:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{apply_theme, Theme};
+//! # fn show(ctx: &bevy_egui::egui::Context, theme: Res<Theme>) {
+//! if theme.is_changed() {
+//!     apply_theme(ctx, &theme);
+//! }
+//! # }
+//! ```
+//!
+//! Besides egui's visuals this stores a [`MenuPalette`] in the context with
+//! the primary and danger colors used by the button variants in
+//! [`egui_wrappers`](super::egui_wrappers).
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::theme::Theme;
+
+/// Converts a Bevy color to an egui color
+pub fn to_color32(color: Color) -> egui::Color32 {
+    let [r, g, b, a] = color.to_srgba().to_u8_array();
+    egui::Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
+/// Theme colors the egui wrappers need beyond egui's own visuals
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MenuPalette {
+    /// Fill of primary buttons
+    pub primary: egui::Color32,
+    /// Fill of danger buttons
+    pub danger: egui::Color32,
+    /// Text on primary and danger buttons
+    pub text: egui::Color32,
+}
+
+impl Default for MenuPalette {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl MenuPalette {
+    /// Picks the palette colors from a theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            primary: to_color32(theme.status.info),
+            danger: to_color32(theme.status.error),
+            text: to_color32(theme.text.normal),
+        }
+    }
+
+    /// The palette stored in `ctx`, or the default theme's when there is none
+    pub fn get(ctx: &egui::Context) -> Self {
+        ctx.data(|data| data.get_temp(egui::Id::NULL))
+            .unwrap_or_default()
+    }
+
+    /// Stores the palette in `ctx`
+    pub fn store(self, ctx: &egui::Context) {
+        ctx.data_mut(|data| data.insert_temp(egui::Id::NULL, self));
+    }
+}
+
+/// Builds egui visuals matching a theme
+pub fn theme_visuals(theme: &Theme) -> egui::Visuals {
+    let mut visuals = egui::Visuals::dark();
+    let text = to_color32(theme.text.normal);
+    let widgets = &mut visuals.widgets;
+
+    widgets.noninteractive.fg_stroke.color = text;
+    for (state, fill) in [
+        (&mut widgets.inactive, theme.button.normal),
+        (&mut widgets.hovered, theme.button.hovered),
+        (&mut widgets.active, theme.button.pressed),
+        (&mut widgets.open, theme.button.pressed),
+    ] {
+        state.weak_bg_fill = to_color32(fill);
+        state.bg_fill = to_color32(fill);
+        state.fg_stroke.color = text;
+    }
+    widgets.hovered.bg_stroke.color = to_color32(theme.focus.border);
+
+    visuals.selection.bg_fill = to_color32(theme.focus.highlight);
+    visuals.selection.stroke.color = to_color32(theme.focus.text);
+    visuals.error_fg_color = to_color32(theme.status.error);
+    visuals.warn_fg_color = to_color32(theme.status.warning);
+    visuals
+}
+
+/// Restyles an egui context from a theme
+pub fn apply_theme(ctx: &egui::Context, theme: &Theme) {
+    ctx.set_visuals(theme_visuals(theme));
+    MenuPalette::from_theme(theme).store(ctx);
+}
@@ -1,14 +1,97 @@
 //! Wrapper for bevy_egui's ScrollArea for use in menu UIs.
+//!
+//! The `_with` variants take [`MenuOptions`] to disable a widget, attach a
+//! tooltip or, for buttons, pick a primary or danger style. Their colors
+//! come from the theme bridge in [`egui_theme`](super::egui_theme).
 
 use bevy_egui::egui;
 
+use super::egui_theme::MenuPalette;
+
+/// Visual emphasis of a menu button
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MenuVariant {
+    /// Regular button
+    #[default]
+    Normal,
+    /// The main action of a menu, such as "Apply"
+    Primary,
+    /// A destructive action, such as "Delete save"
+    Danger,
+}
+
+/// Options shared by the `_with` menu wrappers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MenuOptions<'a> {
+    /// Whether the widget accepts input
+    pub enabled: bool,
+    /// Text shown while the widget is hovered
+    pub tooltip: Option<&'a str>,
+    /// Button emphasis; ignored by other widgets
+    pub variant: MenuVariant,
+}
+
+impl Default for MenuOptions<'_> {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            tooltip: None,
+            variant: MenuVariant::Normal,
+        }
+    }
+}
+
+impl<'a> MenuOptions<'a> {
+    /// Enabled, without tooltip, normal variant
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the widget accepts input
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Shows `tooltip` while the widget is hovered, enabled or not
+    pub fn tooltip(mut self, tooltip: &'a str) -> Self {
+        self.tooltip = Some(tooltip);
+        self
+    }
+
+    /// Styles a button as the menu's main action
+    pub fn primary(mut self) -> Self {
+        self.variant = MenuVariant::Primary;
+        self
+    }
+
+    /// Styles a button as a destructive action
+    pub fn danger(mut self) -> Self {
+        self.variant = MenuVariant::Danger;
+        self
+    }
+
+    /// Adds `widget` honoring the enabled flag and tooltip
+    fn add(&self, ui: &mut egui::Ui, widget: impl egui::Widget) -> egui::Response {
+        let response = ui.add_enabled(self.enabled, widget);
+        match self.tooltip {
+            Some(tooltip) if self.enabled => response.on_hover_text(tooltip),
+            Some(tooltip) => response.on_disabled_hover_text(tooltip),
+            None => response,
+        }
+    }
+}
+
 /// Wraps egui's ScrollArea for consistent use in menu UIs.
 /// Example usage in an egui context:
 ///
 /// ```rust
-/// menu_scroll_area(|ui| {
+/// # use sf_ui_common::menu_scroll_area;
+/// # fn show(ui: &mut bevy_egui::egui::Ui) {
+/// menu_scroll_area(ui, |ui| {
 ///     ui.label("Lots of content...");
 /// });
+/// # }
 /// ```
 pub fn menu_scroll_area<R>(ui: &mut egui::Ui, add_contents: impl FnOnce(&mut egui::Ui) -> R) -> R {
     egui::ScrollArea::vertical()
@@ -22,19 +105,55 @@ pub fn menu_scroll_area<R>(ui: &mut egui::Ui, add_contents: impl FnOnce(&mut egu
 ///
 /// Example:
 /// ```rust
+/// # use sf_ui_common::menu_button;
+/// # fn show(ui: &mut bevy_egui::egui::Ui) {
 /// if menu_button(ui, "Click me") {
 ///     // handle click
 /// }
+/// # }
 /// ```
 pub fn menu_button(ui: &mut egui::Ui, text: &str) -> bool {
-    ui.add(egui::Button::new(text).wrap(true)).clicked()
+    menu_button_with(ui, text, MenuOptions::default())
+}
+
+/// Wrapper for an egui button with an enabled flag, tooltip and variant.
+/// Returns true if the button was clicked.
+///
+/// Example:
+/// ```rust
+/// # use sf_ui_common::{menu_button_with, MenuOptions};
+/// # fn show(ui: &mut bevy_egui::egui::Ui, has_save: bool) {
+/// let options = MenuOptions::new()
+///     .danger()
+///     .enabled(has_save)
+///     .tooltip("Permanently delete this save");
+/// if menu_button_with(ui, "Delete", options) {
+///     // handle click
+/// }
+/// # }
+/// ```
+pub fn menu_button_with(ui: &mut egui::Ui, text: &str, options: MenuOptions) -> bool {
+    let palette = MenuPalette::get(ui.ctx());
+    let button = match options.variant {
+        MenuVariant::Normal => egui::Button::new(text),
+        MenuVariant::Primary => {
+            egui::Button::new(egui::RichText::new(text).color(palette.text)).fill(palette.primary)
+        }
+        MenuVariant::Danger => {
+            egui::Button::new(egui::RichText::new(text).color(palette.text)).fill(palette.danger)
+        }
+    };
+    options.add(ui, button.wrap(true)).clicked()
 }
 
 /// Wrapper for an egui label with menu styling.
 ///
 /// Example:
 /// ```rust
+/// # use sf_ui_common::menu_label;
+/// # fn show(ui: &mut bevy_egui::egui::Ui) {
 /// menu_label(ui, "This is a label");
+/// # }
 /// ```
 pub fn menu_label(ui: &mut egui::Ui, text: &str) {
     ui.label(text);
@@ -45,13 +164,26 @@ pub fn menu_label(ui: &mut egui::Ui, text: &str) {
 ///
 /// Example:
 /// ```rust
+/// # use sf_ui_common::menu_text_edit_singleline;
+/// # fn show(ui: &mut bevy_egui::egui::Ui) {
 /// let mut value = String::new();
 /// if menu_text_edit_singleline(ui, &mut value) {
 ///     // value changed
 /// }
+/// # }
 /// ```
 pub fn menu_text_edit_singleline(ui: &mut egui::Ui, value: &mut String) -> bool {
-    ui.text_edit_singleline(value).changed()
+    menu_text_edit_singleline_with(ui, value, MenuOptions::default())
+}
+
+/// Wrapper for a single-line text edit field with an enabled flag and tooltip.
+/// Returns true if the value was changed.
+pub fn menu_text_edit_singleline_with(
+    ui: &mut egui::Ui,
+    value: &mut String,
+    options: MenuOptions,
+) -> bool {
+    options.add(ui, egui::TextEdit::singleline(value)).changed()
 }
 
 /// Wrapper for a menu checkbox.
@@ -59,19 +191,45 @@ pub fn menu_text_edit_singleline(ui: &mut egui::Ui, value: &mut String) -> bool
 ///
 /// Example:
 /// ```rust
+/// # use sf_ui_common::menu_checkbox;
+/// # fn show(ui: &mut bevy_egui::egui::Ui) {
 /// let mut checked = false;
 /// if menu_checkbox(ui, &mut checked, "Enable feature") {
 ///     // toggled
 /// }
+/// # }
 /// ```
 pub fn menu_checkbox(ui: &mut egui::Ui, checked: &mut bool, label: &str) -> bool {
-    ui.checkbox(checked, label).changed()
+    menu_checkbox_with(ui, checked, label, MenuOptions::default())
+}
+
+/// Wrapper for a menu checkbox with an enabled flag and tooltip.
+/// Returns true if the checkbox was toggled.
+///
+/// Example:
+/// ```rust
+/// # use sf_ui_common::{menu_checkbox_with, MenuOptions};
+/// # fn show(ui: &mut bevy_egui::egui::Ui) {
+/// let mut vsync = true;
+/// let options = MenuOptions::new().enabled(false).tooltip("Not supported by this display");
+/// menu_checkbox_with(ui, &mut vsync, "VSync", options);
+/// # }
+/// ```
+pub fn menu_checkbox_with(
+    ui: &mut egui::Ui,
+    checked: &mut bool,
+    label: &str,
+    options: MenuOptions,
+) -> bool {
+    options.add(ui, egui::Checkbox::new(checked, label)).changed()
 }
 
 /// Wrapper for a simple egui table (Grid).
 ///
 /// Example:
 /// ```rust
+/// # use sf_ui_common::menu_table;
+/// # fn show(ui: &mut bevy_egui::egui::Ui) {
 /// menu_table(ui, &["Header 1", "Header 2"], |ui| {
 ///     ui.label("Row 1, Col 1");
 ///     ui.label("Row 1, Col 2");
@@ -80,6 +238,7 @@ pub fn menu_checkbox(ui: &mut egui::Ui, checked: &mut bool, label: &str) -> bool
 ///     ui.label("Row 2, Col 2");
 ///     ui.end_row();
 /// });
+/// # }
 /// ```
 pub fn menu_table<R>(ui: &mut egui::Ui, headers: &[&str], add_rows: impl FnOnce(&mut egui::Ui) -> R) -> R {
     egui::Grid::new("menu_table")
@@ -94,3 +253,143 @@ pub fn menu_table<R>(ui: &mut egui::Ui, headers: &[&str], add_rows: impl FnOnce(
         .inner
 }
 
+
+/// A typed column of a [`menu_virtual_table`]
+pub struct TableColumn<T> {
+    /// Header text
+    pub header: String,
+    /// Column width in points
+    pub width: f32,
+    /// Text shown in this column for a row
+    pub cell: Box<dyn Fn(&T) -> String + Send + Sync>,
+    /// Ordering used when the header is clicked; the column isn't sortable without one
+    pub compare: Option<Box<dyn Fn(&T, &T) -> std::cmp::Ordering + Send + Sync>>,
+}
+
+impl<T> TableColumn<T> {
+    /// Creates an unsortable column showing `cell` for each row
+    pub fn new(
+        header: impl Into<String>,
+        width: f32,
+        cell: impl Fn(&T) -> String + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            header: header.into(),
+            width,
+            cell: Box::new(cell),
+            compare: None,
+        }
+    }
+
+    /// Makes the column sortable with `compare`
+    pub fn sort_by(
+        mut self,
+        compare: impl Fn(&T, &T) -> std::cmp::Ordering + Send + Sync + 'static,
+    ) -> Self {
+        self.compare = Some(Box::new(compare));
+        self
+    }
+
+    /// Makes the column sortable by a key
+    pub fn sort_by_key<K: Ord>(self, key: impl Fn(&T) -> K + Send + Sync + 'static) -> Self {
+        self.sort_by(move |a, b| key(a).cmp(&key(b)))
+    }
+}
+
+/// Sort state of a [`menu_virtual_table`], kept in egui memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct TableSort {
+    column: Option<usize>,
+    descending: bool,
+}
+
+/// Wrapper for a table that only lays out the rows in view, for lists with
+/// thousands of entries.
+///
+/// Clicking a sortable header sorts `rows` in place, and clicking it again
+/// reverses the order. Returns the index of the row clicked this frame.
+///
+/// Example:
+/// ```rust
+/// # use sf_ui_common::{menu_virtual_table, TableColumn};
+/// struct Replay { map: String, duration: u32 }
+///
+/// # fn show(ui: &mut bevy_egui::egui::Ui, replays: &mut [Replay]) {
+/// let columns = [
+///     TableColumn::new("Map", 200.0, |r: &Replay| r.map.clone()).sort_by_key(|r| r.map.clone()),
+///     TableColumn::new("Length", 80.0, |r: &Replay| format!("{}s", r.duration))
+///         .sort_by_key(|r| r.duration),
+/// ];
+/// if let Some(index) = menu_virtual_table(ui, "replays", &columns, replays) {
+///     // open replays[index]
+/// }
+/// # }
+/// ```
+pub fn menu_virtual_table<T>(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    columns: &[TableColumn<T>],
+    rows: &mut [T],
+) -> Option<usize> {
+    let id = ui.make_persistent_id(id_source);
+    let mut sort: TableSort = ui.data(|data| data.get_temp(id)).unwrap_or_default();
+    let row_height = ui.spacing().interact_size.y;
+
+    ui.horizontal(|ui| {
+        for (index, column) in columns.iter().enumerate() {
+            let arrow = match sort {
+                TableSort { column: Some(sorted), descending } if sorted == index => {
+                    if descending { " ⏷" } else { " ⏶" }
+                }
+                _ => "",
+            };
+            let header = egui::RichText::new(format!("{}{arrow}", column.header)).strong();
+            let Some(compare) = &column.compare else {
+                ui.add_sized([column.width, row_height], egui::Label::new(header).truncate(true));
+                continue;
+            };
+            let clicked = ui
+                .add_sized(
+                    [column.width, row_height],
+                    egui::Label::new(header).truncate(true).sense(egui::Sense::click()),
+                )
+                .clicked();
+            if clicked {
+                sort.descending = sort.column == Some(index) && !sort.descending;
+                sort.column = Some(index);
+                rows.sort_by(|a, b| {
+                    let ordering = compare(a, b);
+                    if sort.descending { ordering.reverse() } else { ordering }
+                });
+                ui.data_mut(|data| data.insert_temp(id, sort));
+            }
+        }
+    });
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .id_source(id)
+        .auto_shrink([false; 2])
+        .show_rows(ui, row_height, rows.len(), |ui, range| {
+            let mut clicked = None;
+            for index in range {
+                let row = &rows[index];
+                let response = ui
+                    .horizontal(|ui| {
+                        for column in columns {
+                            ui.add_sized(
+                                [column.width, row_height],
+                                egui::Label::new((column.cell)(row)).truncate(true),
+                            );
+                        }
+                    })
+                    .response
+                    .interact(egui::Sense::click());
+                if response.clicked() {
+                    clicked = Some(index);
+                }
+            }
+            clicked
+        })
+        .inner
+}
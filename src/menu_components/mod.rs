@@ -1,9 +1,26 @@
 //! Advanced UI components that require more complex implementations
 
+pub mod egui_modal;
+pub mod egui_state;
+pub mod egui_theme;
 pub mod egui_wrappers;
 
+pub use egui_modal::{menu_alert, menu_confirm, menu_modal, ConfirmResult};
+pub use egui_state::{
+    menu_collapsing, menu_scroll_area_stateful, menu_selectable_list, MenuState,
+};
+pub use egui_theme::{
+    apply_theme, theme_visuals, to_color32, MenuPalette,
+};
+
 pub use egui_wrappers::{
     menu_button,
+    menu_button_with,
     menu_checkbox,
+    menu_checkbox_with,
     menu_table,
+    menu_virtual_table,
+    MenuOptions,
+    MenuVariant,
+    TableColumn,
 };
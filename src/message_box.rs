@@ -0,0 +1,316 @@
+//! Inline message boxes
+//!
+//! A [`MessageBox`] is a tinted box placed inside a panel or form, such as
+//! "Restart required to apply display changes" under the video settings. Its
+//! [`StatusKind`] picks the accent color and icon; it shows a bold title, a
+//! body in which `**double asterisks**` mark bold runs, and optional action
+//! buttons that send [`MessageBoxActionPressed`].
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{message_box_bundle, MessageBox, MessageBoxActionPressed, StatusKind};
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn(message_box_bundle(
+//!         MessageBox::new(
+//!             StatusKind::Warning,
+//!             "Restart required",
+//!             "Display changes apply after a **restart**.",
+//!         )
+//!         .with_action("restart", "Restart now"),
+//!     ));
+//! }
+//!
+//! fn restart(mut pressed: EventReader<MessageBoxActionPressed>) {
+//!     for event in pressed.read() {
+//!         info!("{} pressed", event.id.0);
+//!     }
+//! }
+//! ```
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::chip::StatusKind;
+use crate::components::UiId;
+use crate::interaction::UiInteractionState;
+use crate::theme::Theme;
+
+/// A button shown at the bottom of a [`MessageBox`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct MessageBoxAction {
+    /// Identifier sent with [`MessageBoxActionPressed`]
+    pub id: UiId,
+    /// Button label
+    pub label: String,
+}
+
+/// An inline info, warning, error or success message
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct MessageBox {
+    /// Severity, which picks the accent color and icon
+    pub kind: StatusKind,
+    /// Bold first line
+    pub title: String,
+    /// Message text; `**text**` is shown in bold
+    pub body: String,
+    /// Buttons shown under the body
+    pub actions: Vec<MessageBoxAction>,
+    /// Icon replacing the default glyph
+    pub icon: Option<Handle<Image>>,
+}
+
+impl MessageBox {
+    /// Creates a message box without actions
+    pub fn new(kind: StatusKind, title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            kind,
+            title: title.into(),
+            body: body.into(),
+            actions: Vec::new(),
+            icon: None,
+        }
+    }
+
+    /// Adds an action button
+    pub fn with_action(mut self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.actions.push(MessageBoxAction {
+            id: UiId::new(id),
+            label: label.into(),
+        });
+        self
+    }
+
+    /// Shows an image instead of the default glyph
+    pub fn with_icon(mut self, icon: Handle<Image>) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+}
+
+/// An action button spawned for a [`MessageBox`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct MessageBoxButton {
+    /// The message box this button belongs to
+    pub message_box: Entity,
+    /// Position of the action in [`MessageBox::actions`]
+    pub index: usize,
+}
+
+/// Sent when an action button of a [`MessageBox`] is pressed
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct MessageBoxActionPressed {
+    /// The message box entity
+    pub message_box: Entity,
+    /// The action's [`MessageBoxAction::id`]
+    pub id: UiId,
+    /// Position of the action in [`MessageBox::actions`]
+    pub index: usize,
+}
+
+/// Creates the container bundle for a message box
+pub fn message_box_bundle(message_box: MessageBox) -> (NodeBundle, MessageBox) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::FlexStart,
+                column_gap: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                border: UiRect::left(Val::Px(4.0)),
+                ..default()
+            },
+            border_radius: BorderRadius::all(Val::Px(4.0)),
+            ..default()
+        },
+        message_box,
+    )
+}
+
+/// The glyph drawn in the icon badge of each kind
+fn glyph(kind: StatusKind) -> &'static str {
+    match kind {
+        StatusKind::Success => "+",
+        StatusKind::Warning => "!",
+        StatusKind::Error => "x",
+        StatusKind::Info => "i",
+    }
+}
+
+/// Splits `text` into sections, bold between pairs of `**`
+fn rich_sections(text: &str, regular: TextStyle, bold: TextStyle) -> Vec<TextSection> {
+    text.split("**")
+        .enumerate()
+        .filter(|(_, run)| !run.is_empty())
+        .map(|(index, run)| {
+            let style = if index % 2 == 1 { &bold } else { &regular };
+            TextSection::new(run, style.clone())
+        })
+        .collect()
+}
+
+/// System to spawn the content of message boxes, and rebuild it when the box
+/// or the theme changes
+pub fn message_box_spawn_system(
+    mut commands: Commands,
+    mut boxes: Query<(
+        Entity,
+        Ref<MessageBox>,
+        &mut BackgroundColor,
+        &mut BorderColor,
+    )>,
+    theme: Res<Theme>,
+) {
+    for (entity, message_box, mut background, mut border) in &mut boxes {
+        if !message_box.is_changed() && !theme.is_changed() {
+            continue;
+        }
+        let accent = message_box.kind.color(&theme);
+        background.0 = accent.with_alpha(0.12);
+        border.0 = accent;
+        commands.entity(entity).despawn_descendants();
+
+        let style = |font, font_size| TextStyle {
+            font,
+            font_size,
+            color: theme.text.normal,
+        };
+        commands.entity(entity).with_children(|parent| {
+            let badge = NodeBundle {
+                style: Style {
+                    width: Val::Px(22.0),
+                    height: Val::Px(22.0),
+                    flex_shrink: 0.0,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            };
+            match &message_box.icon {
+                Some(icon) => {
+                    parent.spawn(ImageBundle {
+                        style: badge.style,
+                        image: UiImage::new(icon.clone()).with_color(accent),
+                        ..default()
+                    });
+                }
+                None => {
+                    parent
+                        .spawn(NodeBundle {
+                            background_color: accent.into(),
+                            border_radius: BorderRadius::MAX,
+                            ..badge
+                        })
+                        .with_children(|badge| {
+                            badge.spawn(TextBundle::from_section(
+                                glyph(message_box.kind),
+                                TextStyle {
+                                    color: Color::WHITE,
+                                    ..style(UiFonts::BOLD, 14.0)
+                                },
+                            ));
+                        });
+                }
+            }
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        flex_grow: 1.0,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|column| {
+                    if !message_box.title.is_empty() {
+                        column.spawn(TextBundle::from_section(
+                            message_box.title.clone(),
+                            style(UiFonts::BOLD, 16.0),
+                        ));
+                    }
+                    column.spawn(TextBundle::from_sections(rich_sections(
+                        &message_box.body,
+                        style(UiFonts::MEDIUM, 14.0),
+                        style(UiFonts::BOLD, 14.0),
+                    )));
+                    if message_box.actions.is_empty() {
+                        return;
+                    }
+                    column
+                        .spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Row,
+                                column_gap: Val::Px(6.0),
+                                margin: UiRect::top(Val::Px(4.0)),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|row| {
+                            for (index, action) in message_box.actions.iter().enumerate() {
+                                row.spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                                            ..default()
+                                        },
+                                        background_color: theme.button.normal.into(),
+                                        ..default()
+                                    },
+                                    MessageBoxButton {
+                                        message_box: entity,
+                                        index,
+                                    },
+                                ))
+                                .with_children(|button| {
+                                    button.spawn(TextBundle::from_section(
+                                        action.label.clone(),
+                                        style(UiFonts::MEDIUM, 14.0),
+                                    ));
+                                });
+                            }
+                        });
+                });
+        });
+    }
+}
+
+/// System to send [`MessageBoxActionPressed`] for pressed action buttons
+pub fn message_box_button_system(
+    buttons: Query<(Entity, &MessageBoxButton)>,
+    boxes: Query<&MessageBox>,
+    interaction_state: Res<UiInteractionState>,
+    mut pressed_events: EventWriter<MessageBoxActionPressed>,
+) {
+    for (entity, button) in &buttons {
+        if !interaction_state.just_pressed(entity) {
+            continue;
+        }
+        let Some(action) = boxes
+            .get(button.message_box)
+            .ok()
+            .and_then(|message_box| message_box.actions.get(button.index))
+        else {
+            continue;
+        };
+        pressed_events.send(MessageBoxActionPressed {
+            message_box: button.message_box,
+            id: action.id.clone(),
+            index: button.index,
+        });
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<MessageBox>()
+        .register_type::<MessageBoxButton>()
+        .add_event::<MessageBoxActionPressed>()
+        .add_systems(
+            Update,
+            (message_box_button_system, message_box_spawn_system).chain(),
+        );
+}
@@ -0,0 +1,250 @@
+//! Minimap frames with a viewport indicator
+//!
+//! A [`MinimapFrame`] shows a map texture supplied by the game, typically a
+//! render-to-texture image of a top-down camera, with a rectangle marking the
+//! part of the map in view. Clicking the map around the rectangle sends
+//! [`MinimapClicked`] with the normalized map position, and dragging the
+//! rectangle moves [`MinimapFrame::viewport`] and sends
+//! [`MinimapViewportDragged`] so the game can follow with its camera.
+//!
+//! Map positions are normalized: `(0, 0)` is the top-left corner of the
+//! texture and `(1, 1)` the bottom-right.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{minimap_frame_bundle, MinimapClicked};
+//! fn setup(mut commands: Commands, map_texture: Handle<Image>) {
+//!     commands.spawn(minimap_frame_bundle(map_texture, 200.0));
+//! }
+//!
+//! fn jump(mut clicks: EventReader<MinimapClicked>) {
+//!     for click in clicks.read() {
+//!         info!("move the camera to {}", click.position);
+//!     }
+//! }
+//! ```
+//!
+//! The game keeps the rectangle in sync by setting the viewport from its
+//! camera each frame; while the player drags it, the frame's own value wins.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::interaction::{PointerCapture, UiInteractionState};
+use crate::theme::Theme;
+
+/// A map texture with a draggable rectangle marking the area in view
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct MinimapFrame {
+    /// Area in view, in normalized map coordinates
+    pub viewport: Rect,
+}
+
+impl Default for MinimapFrame {
+    fn default() -> Self {
+        Self {
+            viewport: Rect::new(0.4, 0.4, 0.6, 0.6),
+        }
+    }
+}
+
+impl MinimapFrame {
+    /// Centers the viewport on `center`, keeping its size and keeping it on the map
+    pub fn center_on(&mut self, center: Vec2) {
+        let half = self.viewport.half_size().min(Vec2::splat(0.5));
+        let center = center.clamp(half, Vec2::ONE - half);
+        self.viewport = Rect::from_center_half_size(center, half);
+    }
+}
+
+/// The viewport rectangle drawn over a [`MinimapFrame`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct MinimapViewport {
+    /// The frame this rectangle belongs to
+    pub minimap: Entity,
+    /// Cursor position relative to the viewport center while dragging
+    grab: Vec2,
+}
+
+/// Sent when the map is clicked outside the viewport rectangle
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct MinimapClicked {
+    /// The minimap frame
+    pub minimap: Entity,
+    /// Clicked point in normalized map coordinates
+    pub position: Vec2,
+}
+
+/// Sent every frame the viewport rectangle is dragged to a new place
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct MinimapViewportDragged {
+    /// The minimap frame
+    pub minimap: Entity,
+    /// New viewport center in normalized map coordinates
+    pub center: Vec2,
+}
+
+/// Creates a square minimap frame `size` pixels wide showing `image`
+pub fn minimap_frame_bundle(
+    image: Handle<Image>,
+    size: f32,
+) -> (ImageBundle, Interaction, MinimapFrame) {
+    (
+        ImageBundle {
+            style: Style {
+                width: Val::Px(size),
+                height: Val::Px(size),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            image: UiImage::new(image),
+            ..default()
+        },
+        Interaction::default(),
+        MinimapFrame::default(),
+    )
+}
+
+/// Normalized map position of `cursor` over a frame node
+fn map_position(cursor: Vec2, node: &Node, transform: &GlobalTransform) -> Vec2 {
+    let rect = node.logical_rect(transform);
+    ((cursor - rect.min) / rect.size().max(Vec2::ONE)).clamp(Vec2::ZERO, Vec2::ONE)
+}
+
+/// System to add the viewport rectangle to new minimap frames
+pub fn minimap_spawn_system(
+    mut commands: Commands,
+    frames: Query<Entity, Added<MinimapFrame>>,
+    theme: Res<Theme>,
+) {
+    for entity in &frames {
+        let viewport = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    border_color: theme.focus.border.into(),
+                    ..default()
+                },
+                Interaction::default(),
+                MinimapViewport {
+                    minimap: entity,
+                    grab: Vec2::ZERO,
+                },
+                Name::new("MinimapViewport"),
+            ))
+            .id();
+        commands.entity(entity).add_child(viewport);
+    }
+}
+
+/// System to report map clicks and drag the viewport rectangle
+pub fn minimap_pointer_system(
+    mut frames: Query<(Entity, &mut MinimapFrame, &Node, &GlobalTransform)>,
+    mut viewports: Query<(Entity, &mut MinimapViewport)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    interaction_state: Res<UiInteractionState>,
+    mut capture: ResMut<PointerCapture>,
+    mut clicked_events: EventWriter<MinimapClicked>,
+    mut dragged_events: EventWriter<MinimapViewportDragged>,
+) {
+    let Some(cursor) = windows.get_single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    for (entity, _, node, transform) in &frames {
+        if interaction_state.just_pointer_pressed(entity) {
+            clicked_events.send(MinimapClicked {
+                minimap: entity,
+                position: map_position(cursor, node, transform),
+            });
+        }
+    }
+
+    for (entity, mut viewport) in &mut viewports {
+        let Ok((minimap, mut frame, node, transform)) = frames.get_mut(viewport.minimap) else {
+            continue;
+        };
+        let position = map_position(cursor, node, transform);
+        if interaction_state.just_pointer_pressed(entity) {
+            capture.capture(entity);
+            viewport.grab = position - frame.viewport.center();
+            continue;
+        }
+        if !capture.is_captured(entity) {
+            continue;
+        }
+        let before = frame.viewport;
+        frame.center_on(position - viewport.grab);
+        if frame.viewport != before {
+            dragged_events.send(MinimapViewportDragged {
+                minimap,
+                center: frame.viewport.center(),
+            });
+        }
+    }
+}
+
+/// The parts of a viewport rectangle its visuals change
+type ViewportLook<'a> = (
+    &'a MinimapViewport,
+    &'a mut Style,
+    Ref<'a, Interaction>,
+    &'a mut BorderColor,
+    &'a mut BackgroundColor,
+);
+
+/// System to place viewport rectangles over their frames
+///
+/// Runs after the generic button colors, which would otherwise fill the frame
+/// and rectangle on hover.
+pub fn minimap_viewport_visual_system(
+    mut frames: Query<(Ref<MinimapFrame>, Ref<Interaction>, &mut BackgroundColor)>,
+    mut viewports: Query<ViewportLook, Without<MinimapFrame>>,
+    theme: Res<Theme>,
+) {
+    for (_, interaction, mut background) in &mut frames {
+        if interaction.is_changed() {
+            background.0 = Color::NONE;
+        }
+    }
+    for (viewport, mut style, interaction, mut border, mut background) in &mut viewports {
+        let Ok((frame, ..)) = frames.get(viewport.minimap) else {
+            continue;
+        };
+        if frame.is_changed() || style.is_added() {
+            let rect = frame.viewport;
+            style.left = Val::Percent(rect.min.x * 100.0);
+            style.top = Val::Percent(rect.min.y * 100.0);
+            style.width = Val::Percent(rect.width() * 100.0);
+            style.height = Val::Percent(rect.height() * 100.0);
+        }
+        if interaction.is_changed() || theme.is_changed() {
+            background.0 = Color::NONE;
+            border.0 = match *interaction {
+                Interaction::None => theme.focus.border,
+                Interaction::Hovered | Interaction::Pressed => theme.text.normal,
+            };
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<MinimapFrame>()
+        .register_type::<MinimapViewport>()
+        .add_event::<MinimapClicked>()
+        .add_event::<MinimapViewportDragged>()
+        .add_systems(
+            Update,
+            (
+                minimap_spawn_system,
+                minimap_pointer_system,
+                minimap_viewport_visual_system.after(crate::button_interaction_system),
+            )
+                .chain(),
+        );
+}
@@ -0,0 +1,375 @@
+//! Modal dialogs with a dimmed backdrop
+//!
+//! [`spawn_modal`] builds a full-screen backdrop, a centered panel with title and
+//! body text, and a row of buttons. While a modal is open, interaction with UI
+//! outside it is blocked, and pressing a button emits a [`ModalResult`] and
+//! closes the dialog.
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::assets::UiFonts;
+use crate::interaction::{InteractionArbitrationSet, UiInteractionState};
+use crate::state_machine::{UiState, UiStateMachine};
+use crate::theme::Theme;
+
+/// Base z-index for modal backdrops; stacked modals are placed above it
+const MODAL_Z_INDEX: i32 = 500;
+
+/// The response chosen in a modal dialog
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub enum ModalResponse {
+    /// The confirm button
+    Ok,
+    /// The cancel button, or Escape
+    Cancel,
+    /// A custom button identified by name
+    Custom(String),
+}
+
+/// A button shown in a modal dialog
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct ModalButton {
+    /// Button label
+    pub label: String,
+    /// Response sent when the button is pressed
+    pub response: ModalResponse,
+}
+
+impl ModalButton {
+    /// Creates a button with the given label and response
+    pub fn new(label: impl Into<String>, response: ModalResponse) -> Self {
+        Self {
+            label: label.into(),
+            response,
+        }
+    }
+
+    /// A standard "OK" button
+    pub fn ok() -> Self {
+        Self::new("OK", ModalResponse::Ok)
+    }
+
+    /// A standard "Cancel" button
+    pub fn cancel() -> Self {
+        Self::new("Cancel", ModalResponse::Cancel)
+    }
+}
+
+/// Content and buttons for [`spawn_modal`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct ModalConfig {
+    /// Dialog title
+    pub title: String,
+    /// Dialog body text
+    pub body: String,
+    /// Buttons, left to right
+    pub buttons: Vec<ModalButton>,
+}
+
+impl ModalConfig {
+    /// An informational dialog with a single OK button
+    pub fn alert(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            buttons: vec![ModalButton::ok()],
+        }
+    }
+
+    /// A confirmation dialog with OK and Cancel buttons
+    pub fn confirm(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            buttons: vec![ModalButton::ok(), ModalButton::cancel()],
+        }
+    }
+}
+
+/// Where a modal dialog is in its life
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum ModalState {
+    /// Spawned, but not yet stacked above the other modals
+    #[default]
+    Opening,
+    /// Stacked and waiting for a response
+    Open,
+    /// Answered and about to be despawned
+    Closed,
+}
+
+/// Modals are stacked once, then answered once
+impl UiState for ModalState {
+    const TRANSITIONS: &'static [(Self, Self)] = &[
+        (ModalState::Opening, ModalState::Open),
+        (ModalState::Opening, ModalState::Closed),
+        (ModalState::Open, ModalState::Closed),
+    ];
+}
+
+impl ModalState {
+    /// The machine a modal starts in, opening
+    pub const MACHINE: UiStateMachine<ModalState> = UiStateMachine::new(ModalState::Opening);
+}
+
+/// Root component of an open modal dialog (the backdrop entity)
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct ModalDialog {
+    /// Stacking order; higher values are in front
+    pub depth: u32,
+    /// Whether the modal has been stacked or answered
+    pub state: UiStateMachine<ModalState>,
+}
+
+/// A button inside a modal dialog
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ModalButtonAction {
+    /// The modal this button belongs to
+    pub modal: Entity,
+    /// Response sent when pressed
+    pub response: ModalResponse,
+}
+
+/// The body text of a modal dialog, for changing it while the modal is open
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ModalBody {
+    /// The modal this text belongs to
+    pub modal: Entity,
+}
+
+/// Sent when a modal dialog is closed
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ModalResult {
+    /// The modal root entity (already despawned when this is read)
+    pub modal: Entity,
+    /// The response chosen
+    pub response: ModalResponse,
+}
+
+/// Spawns a modal dialog and returns its root entity
+pub fn spawn_modal(commands: &mut Commands, theme: &Theme, config: ModalConfig) -> Entity {
+    let modal = commands.spawn_empty().id();
+    commands
+        .entity(modal)
+        .insert((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(MODAL_Z_INDEX),
+                ..default()
+            },
+            Interaction::default(),
+            ModalDialog {
+                depth: 0,
+                state: ModalState::MACHINE,
+            },
+            Name::new("ModalDialog"),
+        ))
+        .with_children(|backdrop| {
+            backdrop
+                .spawn(NodeBundle {
+                    style: Style {
+                        min_width: Val::Px(320.0),
+                        max_width: Val::Percent(60.0),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(20.0)),
+                        row_gap: Val::Px(12.0),
+                        ..default()
+                    },
+                    background_color: Color::srgba(0.1, 0.1, 0.1, 0.95).into(),
+                    focus_policy: FocusPolicy::Block,
+                    ..default()
+                })
+                .with_children(|panel| {
+                    panel.spawn(TextBundle::from_section(
+                        config.title,
+                        TextStyle {
+                            font: UiFonts::BOLD,
+                            font_size: 24.0,
+                            color: theme.text.normal,
+                        },
+                    ));
+                    panel.spawn((
+                        TextBundle::from_section(
+                            config.body,
+                            TextStyle {
+                                font: UiFonts::REGULAR,
+                                font_size: 16.0,
+                                color: theme.text.normal,
+                            },
+                        ),
+                        ModalBody { modal },
+                    ));
+                    panel
+                        .spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Row,
+                                justify_content: JustifyContent::FlexEnd,
+                                column_gap: Val::Px(10.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|row| {
+                            for button in config.buttons {
+                                row.spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..default()
+                                        },
+                                        background_color: theme.button.normal.into(),
+                                        ..default()
+                                    },
+                                    ModalButtonAction {
+                                        modal,
+                                        response: button.response,
+                                    },
+                                ))
+                                .with_children(|button_node| {
+                                    button_node.spawn(TextBundle::from_section(
+                                        button.label,
+                                        TextStyle {
+                                            font: UiFonts::BOLD,
+                                            font_size: 18.0,
+                                            color: theme.text.normal,
+                                        },
+                                    ));
+                                });
+                            }
+                        });
+                });
+        });
+    modal
+}
+
+/// Returns the front-most modal that hasn't been answered, if any
+pub(crate) fn top_modal(modals: &Query<(Entity, &ModalDialog)>) -> Option<Entity> {
+    modals
+        .iter()
+        .filter(|(_, dialog)| !dialog.state.is(ModalState::Closed))
+        .max_by_key(|(_, dialog)| dialog.depth)
+        .map(|(entity, _)| entity)
+}
+
+/// System to stack newly opened modals above existing ones
+pub fn modal_stack_system(mut modals: Query<(&mut ModalDialog, &mut ZIndex)>) {
+    let opening = |dialog: &ModalDialog| dialog.state.is(ModalState::Opening);
+    if !modals.iter().any(|(dialog, _)| opening(dialog)) {
+        return;
+    }
+    let mut top_depth = modals
+        .iter()
+        .filter(|(dialog, _)| !opening(dialog))
+        .map(|(dialog, _)| dialog.depth)
+        .max()
+        .unwrap_or(0);
+
+    for (mut dialog, mut z_index) in &mut modals {
+        if dialog.state.go(ModalState::Open) {
+            top_depth += 1;
+            dialog.depth = top_depth;
+            *z_index = ZIndex::Global(MODAL_Z_INDEX + top_depth as i32);
+        }
+    }
+}
+
+/// System to block interaction with UI behind the front-most modal
+///
+/// Runs right after arbitration and clears any resolved target that isn't
+/// inside the top modal, so widgets behind it never see hovers or presses.
+pub fn modal_block_system(
+    modals: Query<(Entity, &ModalDialog)>,
+    parents: Query<&Parent>,
+    mut interaction_state: ResMut<UiInteractionState>,
+) {
+    let Some(modal) = top_modal(&modals) else {
+        return;
+    };
+    let inside = |entity: Entity| {
+        entity == modal
+            || parents
+                .iter_ancestors(entity)
+                .any(|ancestor| ancestor == modal)
+    };
+
+    let frame = &mut interaction_state.current;
+    if frame.hovered.is_some_and(|entity| !inside(entity)) {
+        frame.hovered = None;
+    }
+    if frame.pressed.is_some_and(|entity| !inside(entity)) {
+        frame.pressed = None;
+    }
+    if frame.activated.is_some_and(|entity| !inside(entity)) {
+        frame.activated = None;
+    }
+}
+
+/// System to handle modal button presses and Escape to cancel
+///
+/// A modal is answered once, even if several of its buttons see a press.
+pub fn modal_button_system(
+    mut commands: Commands,
+    buttons: Query<(Entity, &ModalButtonAction)>,
+    mut modals: Query<(Entity, &mut ModalDialog)>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    interaction_state: Res<UiInteractionState>,
+    mut results: EventWriter<ModalResult>,
+) {
+    let mut close = |modals: &mut Query<(Entity, &mut ModalDialog)>, modal, response| {
+        let Ok((_, mut dialog)) = modals.get_mut(modal) else {
+            return;
+        };
+        if dialog.state.go(ModalState::Closed) {
+            commands.entity(modal).despawn_recursive();
+            results.send(ModalResult { modal, response });
+        }
+    };
+
+    for (entity, action) in &buttons {
+        if interaction_state.just_pressed(entity) {
+            close(&mut modals, action.modal, action.response.clone());
+            return;
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        let top = modals
+            .iter()
+            .filter(|(_, dialog)| !dialog.state.is(ModalState::Closed))
+            .max_by_key(|(_, dialog)| dialog.depth)
+            .map(|(entity, _)| entity);
+        if let Some(modal) = top {
+            close(&mut modals, modal, ModalResponse::Cancel);
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<ModalDialog>()
+        .register_type::<ModalState>()
+        .register_type::<ModalButtonAction>()
+        .register_type::<ModalBody>()
+        .add_event::<ModalResult>()
+        .add_systems(
+            PreUpdate,
+            modal_block_system
+                .after(InteractionArbitrationSet)
+                .after(crate::focus::focus_activation_system),
+        )
+        .add_systems(Update, (modal_stack_system, modal_button_system));
+}
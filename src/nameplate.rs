@@ -0,0 +1,257 @@
+//! Player nameplates
+//!
+//! A [`Nameplate`] shows a player's avatar, name in their team color, rank
+//! icon and a connection-quality dot. The same widget is used in the lobby
+//! list, scoreboard and chat; [`NameplateVariant::Compact`] drops the rank
+//! icon and shrinks everything to fit a single chat line. Spawn one with
+//! [`nameplate_bundle`]; the parts are added as children and follow later
+//! edits to the component.
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::theme::Theme;
+use crate::utils::first_section;
+
+/// Size variant of a [`Nameplate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum NameplateVariant {
+    /// Large avatar and rank icon, for lobby lists and scoreboards
+    #[default]
+    Full,
+    /// Small avatar without rank, for chat lines
+    Compact,
+}
+
+impl NameplateVariant {
+    /// Returns `(avatar size, font size)` in pixels
+    fn metrics(self) -> (f32, f32) {
+        match self {
+            NameplateVariant::Full => (40.0, 18.0),
+            NameplateVariant::Compact => (20.0, 14.0),
+        }
+    }
+}
+
+/// Network connection quality shown as a colored dot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum ConnectionQuality {
+    /// Low latency
+    #[default]
+    Good,
+    /// Noticeable latency
+    Fair,
+    /// High latency or packet loss
+    Poor,
+    /// Not connected
+    Disconnected,
+}
+
+impl ConnectionQuality {
+    /// Classifies a round-trip time in milliseconds
+    pub fn from_ping_ms(ping: u32) -> Self {
+        match ping {
+            0..=80 => ConnectionQuality::Good,
+            81..=180 => ConnectionQuality::Fair,
+            _ => ConnectionQuality::Poor,
+        }
+    }
+
+    /// Returns the dot color from the theme's status palette
+    pub fn color(self, theme: &Theme) -> Color {
+        match self {
+            ConnectionQuality::Good => theme.status.success,
+            ConnectionQuality::Fair => theme.status.warning,
+            ConnectionQuality::Poor => theme.status.error,
+            ConnectionQuality::Disconnected => theme.text.disabled,
+        }
+    }
+}
+
+/// Avatar, name, rank and connection indicator for a player
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Nameplate {
+    /// Player name
+    pub name: String,
+    /// Team color used for the name
+    pub team_color: Color,
+    /// Avatar image; a placeholder square is shown when `None`
+    pub avatar: Option<Handle<Image>>,
+    /// Rank icon, shown in the full variant only
+    pub rank_icon: Option<Handle<Image>>,
+    /// Connection quality
+    pub connection: ConnectionQuality,
+    /// Size variant
+    pub variant: NameplateVariant,
+}
+
+impl Nameplate {
+    /// Creates a full nameplate with no avatar or rank
+    pub fn new(name: impl Into<String>, team_color: Color) -> Self {
+        Self {
+            name: name.into(),
+            team_color,
+            avatar: None,
+            rank_icon: None,
+            connection: ConnectionQuality::default(),
+            variant: NameplateVariant::default(),
+        }
+    }
+
+    /// Sets the avatar image
+    pub fn with_avatar(mut self, avatar: Handle<Image>) -> Self {
+        self.avatar = Some(avatar);
+        self
+    }
+
+    /// Sets the rank icon
+    pub fn with_rank(mut self, rank_icon: Handle<Image>) -> Self {
+        self.rank_icon = Some(rank_icon);
+        self
+    }
+
+    /// Switches to the compact variant
+    pub fn compact(mut self) -> Self {
+        self.variant = NameplateVariant::Compact;
+        self
+    }
+}
+
+/// Child nodes of a [`Nameplate`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum NameplatePart {
+    /// The avatar image
+    Avatar,
+    /// The player name
+    Name,
+    /// The rank icon
+    Rank,
+    /// The connection-quality dot
+    Connection,
+}
+
+/// Creates the container bundle for a nameplate
+pub fn nameplate_bundle(nameplate: Nameplate) -> (NodeBundle, Nameplate) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(6.0),
+                ..default()
+            },
+            ..default()
+        },
+        nameplate,
+    )
+}
+
+/// System to spawn the parts of new nameplates
+pub fn nameplate_spawn_system(mut commands: Commands, nameplates: Query<Entity, Added<Nameplate>>) {
+    for entity in &nameplates {
+        commands.entity(entity).with_children(|plate| {
+            plate.spawn((ImageBundle::default(), NameplatePart::Avatar));
+            plate.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: UiFonts::BOLD,
+                        ..default()
+                    },
+                ),
+                NameplatePart::Name,
+            ));
+            plate.spawn((ImageBundle::default(), NameplatePart::Rank));
+            plate.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Px(8.0),
+                        height: Val::Px(8.0),
+                        ..default()
+                    },
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                },
+                NameplatePart::Connection,
+            ));
+        });
+    }
+}
+
+/// System to refresh nameplate parts when the nameplate or theme changes
+pub fn nameplate_update_system(
+    theme: Res<Theme>,
+    nameplates: Query<(Ref<Nameplate>, &Children)>,
+    mut parts: Query<(
+        &NameplatePart,
+        &mut Style,
+        Option<&mut UiImage>,
+        Option<&mut BackgroundColor>,
+        Option<&mut Text>,
+    )>,
+) {
+    for (nameplate, children) in &nameplates {
+        if !nameplate.is_changed() && !theme.is_changed() {
+            continue;
+        }
+        let (avatar_size, font_size) = nameplate.variant.metrics();
+
+        for &child in children.iter() {
+            let Ok((part, mut style, image, background, text)) = parts.get_mut(child) else {
+                continue;
+            };
+            match part {
+                NameplatePart::Avatar => {
+                    style.width = Val::Px(avatar_size);
+                    style.height = Val::Px(avatar_size);
+                    if let Some(mut image) = image {
+                        // The default texture is solid white, tinted into a placeholder
+                        match &nameplate.avatar {
+                            Some(avatar) => {
+                                image.texture = avatar.clone();
+                                image.color = Color::WHITE;
+                            }
+                            None => {
+                                image.texture = Handle::default();
+                                image.color = theme.button.normal;
+                            }
+                        }
+                    }
+                }
+                NameplatePart::Name => {
+                    if let Some(mut text) = text {
+                        let section = first_section(&mut text);
+                        section.value.clone_from(&nameplate.name);
+                        section.style.color = nameplate.team_color;
+                        section.style.font_size = font_size;
+                    }
+                }
+                NameplatePart::Rank => {
+                    let shown = nameplate.variant == NameplateVariant::Full && nameplate.rank_icon.is_some();
+                    style.display = if shown { Display::Flex } else { Display::None };
+                    style.width = Val::Px(font_size);
+                    style.height = Val::Px(font_size);
+                    if let (Some(mut image), Some(rank)) = (image, &nameplate.rank_icon) {
+                        image.texture = rank.clone();
+                    }
+                }
+                NameplatePart::Connection => {
+                    if let Some(mut background) = background {
+                        background.0 = nameplate.connection.color(&theme);
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Nameplate>()
+        .register_type::<NameplatePart>()
+        .add_systems(
+            Update,
+            (nameplate_spawn_system, nameplate_update_system).chain(),
+        );
+}
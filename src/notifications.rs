@@ -0,0 +1,1071 @@
+//! Toast notifications and the notification center
+//!
+//! Game code posts messages through the [`UiNotifications`] resource. Each
+//! one shows as a toast in every [`NotificationToasts`] stack for
+//! [`UiNotifications::toast_duration`] seconds, or its own
+//! [`duration`](Notification::duration), then moves to the history. Toasts
+//! slide in from the side of the stack's [`ToastCorner`] and slide out when
+//! they expire or are clicked, or just appear and vanish when [`UiMotion`]
+//! turns animations off. At most [`UiNotifications::max_toasts`] show
+//! at once; the rest wait in a queue.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{Notification, StatusKind, UiNotifications};
+//! fn on_research_done(mut notifications: ResMut<UiNotifications>) {
+//!     notifications.push(
+//!         Notification::new("Research complete", StatusKind::Success)
+//!             .with_category("Research")
+//!             .with_duration(6.0),
+//!     );
+//! }
+//! ```
+//! A [`NotificationCenter`] is a toggle button with an unread badge that
+//! opens a panel listing that history with timestamps, per-category filters
+//! and a "Clear all" button.
+//!
+//! [`NotificationRules`] decide which notifications interrupt the player.
+//! Muted categories, cinematic mode and per-category rate limits send
+//! notifications straight to the history without a toast, and repeats of a
+//! recent message are coalesced into one entry ("Unit under attack x4").
+//! Notifications at or above [`NotificationRules::bypass_priority`] always
+//! show.
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::attention::Pulse;
+use crate::chip::{status_chip_bundle, StatusChip, StatusKind};
+use crate::clock::UiClock;
+use crate::interaction::UiInteractionState;
+use crate::quality::UiMotion;
+use crate::theme::Theme;
+use crate::tween::{Easing, UiTween, UiTweenCompleted};
+
+/// How urgent a notification is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect, Default)]
+pub enum NotificationPriority {
+    /// Background information
+    Low,
+    /// Ordinary messages
+    #[default]
+    Normal,
+    /// Needs attention soon
+    High,
+    /// Needs attention now; bypasses mutes by default
+    Critical,
+}
+
+/// Routing rules deciding which notifications show as toasts
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct NotificationRules {
+    /// Categories whose notifications skip the toast
+    pub muted_categories: Vec<String>,
+    /// Whether a cinematic is playing; only bypassing notifications show
+    pub cinematic: bool,
+    /// Seconds within which an identical message is coalesced into the earlier one
+    pub coalesce_window: f32,
+    /// Most toasts per category within [`NotificationRules::coalesce_window`]
+    pub rate_limit: usize,
+    /// Lowest priority that ignores mutes, cinematic mode and rate limits
+    pub bypass_priority: NotificationPriority,
+}
+
+impl Default for NotificationRules {
+    fn default() -> Self {
+        Self {
+            muted_categories: Vec::new(),
+            cinematic: false,
+            coalesce_window: 5.0,
+            rate_limit: 3,
+            bypass_priority: NotificationPriority::Critical,
+        }
+    }
+}
+
+impl NotificationRules {
+    /// Whether toasts of `category` are muted
+    pub fn is_muted(&self, category: &str) -> bool {
+        self.muted_categories.iter().any(|muted| muted == category)
+    }
+
+    /// Mutes or unmutes toasts of `category`
+    pub fn set_muted(&mut self, category: &str, muted: bool) {
+        if muted && !self.is_muted(category) {
+            self.muted_categories.push(category.to_string());
+        } else if !muted {
+            self.muted_categories.retain(|m| m != category);
+        }
+    }
+}
+
+/// A message posted through [`UiNotifications`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Notification {
+    /// Unique identifier, returned by [`UiNotifications::notify`]
+    pub id: u64,
+    /// Category used for filtering, such as "Combat" or "Economy"
+    pub category: String,
+    /// The message text
+    pub message: String,
+    /// Severity, which picks the accent color
+    pub kind: StatusKind,
+    /// Urgency, checked against [`NotificationRules`]
+    pub priority: NotificationPriority,
+    /// How many identical notifications were coalesced into this one
+    pub count: u32,
+    /// [`UiClock`] time the notification was last posted, in seconds
+    pub time: f32,
+    /// Whether the player has seen it in the notification center
+    pub read: bool,
+    /// Seconds the toast stays on screen; [`UiNotifications::toast_duration`] when `None`
+    pub duration: Option<f32>,
+    /// [`UiClock`] time the toast appeared, in seconds
+    pub shown: f32,
+}
+
+impl Notification {
+    /// Creates a notification in the "General" category with normal priority
+    ///
+    /// Post it with [`UiNotifications::push`], which fills in the id and times.
+    pub fn new(message: impl Into<String>, kind: StatusKind) -> Self {
+        Self {
+            id: 0,
+            category: "General".to_string(),
+            message: message.into(),
+            kind,
+            priority: NotificationPriority::Normal,
+            count: 1,
+            time: 0.0,
+            read: false,
+            duration: None,
+            shown: 0.0,
+        }
+    }
+
+    /// Sets the category
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = category.into();
+        self
+    }
+
+    /// Sets the priority
+    pub fn with_priority(mut self, priority: NotificationPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets how many seconds the toast stays on screen
+    pub fn with_duration(mut self, seconds: f32) -> Self {
+        self.duration = Some(seconds);
+        self
+    }
+
+    /// The message with the coalesced count appended, such as "Unit under attack x4"
+    pub fn text(&self) -> String {
+        if self.count > 1 {
+            format!("{} x{}", self.message, self.count)
+        } else {
+            self.message.clone()
+        }
+    }
+}
+
+/// Active toasts and notification history
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct UiNotifications {
+    /// Seconds a toast stays on screen
+    pub toast_duration: f32,
+    /// Most toasts shown at once; later ones wait in a queue until there is room
+    pub max_toasts: usize,
+    /// Most notifications kept in the history; the oldest are dropped
+    pub history_limit: usize,
+    /// Mutes, cinematic mode, rate limiting and priority escalation
+    pub rules: NotificationRules,
+    active: Vec<Notification>,
+    queued: Vec<Notification>,
+    history: Vec<Notification>,
+    next_id: u64,
+    now: f32,
+}
+
+impl Default for UiNotifications {
+    fn default() -> Self {
+        Self {
+            toast_duration: 4.0,
+            max_toasts: 4,
+            history_limit: 100,
+            rules: NotificationRules::default(),
+            active: Vec::new(),
+            queued: Vec::new(),
+            history: Vec::new(),
+            next_id: 0,
+            now: 0.0,
+        }
+    }
+}
+
+impl UiNotifications {
+    /// Posts a notification with normal priority and returns its id
+    pub fn notify(
+        &mut self,
+        category: impl Into<String>,
+        message: impl Into<String>,
+        kind: StatusKind,
+    ) -> u64 {
+        self.notify_with_priority(category, message, kind, NotificationPriority::Normal)
+    }
+
+    /// Posts a notification and returns its id
+    pub fn notify_with_priority(
+        &mut self,
+        category: impl Into<String>,
+        message: impl Into<String>,
+        kind: StatusKind,
+        priority: NotificationPriority,
+    ) -> u64 {
+        self.push(
+            Notification::new(message, kind)
+                .with_category(category)
+                .with_priority(priority),
+        )
+    }
+
+    /// Posts a prepared notification and returns its id
+    ///
+    /// A repeat of a message posted within the coalesce window bumps the
+    /// earlier notification's count and returns its id instead. Muted,
+    /// cinematic-suppressed and rate-limited notifications go straight to the
+    /// history unless their priority reaches
+    /// [`NotificationRules::bypass_priority`]. While
+    /// [`max_toasts`](Self::max_toasts) are on screen new toasts are queued,
+    /// except bypassing ones, which push the oldest toast out.
+    pub fn push(&mut self, notification: Notification) -> u64 {
+        let Notification {
+            category,
+            message,
+            kind,
+            priority,
+            duration,
+            ..
+        } = notification;
+        let now = self.now;
+        let window = self.rules.coalesce_window;
+        let recent = |n: &Notification| now - n.time <= window;
+
+        let repeat =
+            |n: &&mut Notification| n.category == category && n.message == message && recent(n);
+        if let Some(earlier) = self.active.iter_mut().rev().find(repeat) {
+            earlier.count += 1;
+            earlier.time = now;
+            earlier.shown = now;
+            earlier.priority = earlier.priority.max(priority);
+            return earlier.id;
+        }
+        if let Some(earlier) = self.queued.iter_mut().rev().find(repeat) {
+            earlier.count += 1;
+            earlier.time = now;
+            earlier.priority = earlier.priority.max(priority);
+            return earlier.id;
+        }
+        // A bypassing repeat of a message that already left the screen shows again
+        let bypass = priority >= self.rules.bypass_priority;
+        if !bypass {
+            if let Some(earlier) = self.history.iter_mut().rev().find(repeat) {
+                earlier.count += 1;
+                earlier.time = now;
+                earlier.read = false;
+                return earlier.id;
+            }
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let notification = Notification {
+            id,
+            category,
+            message,
+            kind,
+            priority,
+            count: 1,
+            time: now,
+            read: false,
+            duration,
+            shown: now,
+        };
+
+        let recent_in_category = self
+            .active
+            .iter()
+            .chain(&self.queued)
+            .chain(&self.history)
+            .filter(|n| n.category == notification.category && recent(n))
+            .count();
+        let suppressed = self.rules.is_muted(&notification.category)
+            || self.rules.cinematic
+            || recent_in_category >= self.rules.rate_limit;
+        if suppressed && !bypass {
+            self.archive(notification);
+            return id;
+        }
+
+        if self.active.len() < self.max_toasts {
+            self.active.push(notification);
+        } else if bypass {
+            self.active.push(notification);
+            while self.active.len() > self.max_toasts {
+                let oldest = self.active.remove(0);
+                self.archive(oldest);
+            }
+        } else {
+            self.queued.push(notification);
+        }
+        id
+    }
+
+    /// Closes a toast, or drops a queued one, moving it to the history as read
+    pub fn dismiss(&mut self, id: u64) {
+        let notification = if let Some(index) = self.active.iter().position(|n| n.id == id) {
+            self.active.remove(index)
+        } else if let Some(index) = self.queued.iter().position(|n| n.id == id) {
+            self.queued.remove(index)
+        } else {
+            return;
+        };
+        self.archive(Notification {
+            read: true,
+            ..notification
+        });
+    }
+
+    /// Toasts currently on screen, oldest first
+    pub fn active(&self) -> &[Notification] {
+        &self.active
+    }
+
+    /// Toasts waiting for room on screen, oldest first
+    pub fn queued(&self) -> &[Notification] {
+        &self.queued
+    }
+
+    /// Past notifications, oldest first
+    pub fn history(&self) -> &[Notification] {
+        &self.history
+    }
+
+    /// Number of history entries the player hasn't seen
+    pub fn unread_count(&self) -> usize {
+        self.history.iter().filter(|n| !n.read).count()
+    }
+
+    /// Marks every history entry as seen
+    pub fn mark_all_read(&mut self) {
+        for notification in &mut self.history {
+            notification.read = true;
+        }
+    }
+
+    /// Empties the history
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Categories present in the history, sorted
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = self.history.iter().map(|n| n.category.clone()).collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+
+    fn archive(&mut self, notification: Notification) {
+        self.history.push(notification);
+        if self.history.len() > self.history_limit {
+            let excess = self.history.len() - self.history_limit;
+            self.history.drain(..excess);
+        }
+    }
+}
+
+/// A stack of toasts for the active notifications
+#[derive(Component, Debug, Clone, Copy, Reflect, Default)]
+#[reflect(Component)]
+pub struct NotificationToasts;
+
+/// The screen corner a [`NotificationToasts`] stack sits in
+///
+/// Toasts slide in from, and out to, the nearer side of the screen.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub enum ToastCorner {
+    /// Top left
+    TopLeft,
+    /// Top right
+    #[default]
+    TopRight,
+    /// Bottom left
+    BottomLeft,
+    /// Bottom right
+    BottomRight,
+}
+
+impl ToastCorner {
+    /// Horizontal distance toasts slide over, towards the nearer side
+    fn slide(self) -> f32 {
+        match self {
+            Self::TopLeft | Self::BottomLeft => -TOAST_WIDTH,
+            Self::TopRight | Self::BottomRight => TOAST_WIDTH,
+        }
+    }
+}
+
+/// A toast in a [`NotificationToasts`] stack
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ToastState {
+    /// Coalesced count the text was built for
+    pub count: u32,
+    /// Whether the toast is sliding out, to be despawned
+    pub leaving: bool,
+}
+
+/// Width of a toast stack in pixels
+const TOAST_WIDTH: f32 = 300.0;
+
+/// A toggle button and history panel for past notifications
+#[derive(Component, Debug, Clone, PartialEq, Reflect, Default)]
+#[reflect(Component)]
+pub struct NotificationCenter {
+    /// Whether the history panel is shown
+    pub open: bool,
+    /// Categories filtered out of the history list
+    pub hidden_categories: Vec<String>,
+}
+
+impl NotificationCenter {
+    /// Whether history entries of `category` are listed
+    pub fn shows(&self, category: &str) -> bool {
+        !self
+            .hidden_categories
+            .iter()
+            .any(|hidden| hidden == category)
+    }
+
+    /// Shows or hides history entries of `category`
+    pub fn toggle_category(&mut self, category: &str) {
+        if self.shows(category) {
+            self.hidden_categories.push(category.to_string());
+        } else {
+            self.hidden_categories.retain(|hidden| hidden != category);
+        }
+    }
+}
+
+/// What a [`NotificationPart`] is
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub enum NotificationPartKind {
+    /// A toast; pressing it dismisses the notification
+    Toast(u64),
+    /// The notification center's toggle button
+    Toggle,
+    /// The unread count on the toggle button
+    Badge,
+    /// The history panel
+    Panel,
+    /// The "Clear all" button
+    ClearAll,
+    /// The row of category filters
+    Filters,
+    /// A category filter button
+    Filter(String),
+    /// The list of history entries
+    List,
+}
+
+/// A node spawned for a [`NotificationToasts`] stack or [`NotificationCenter`]
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct NotificationPart {
+    /// The stack or center this node belongs to
+    pub owner: Entity,
+    /// What this node is
+    pub kind: NotificationPartKind,
+}
+
+/// Creates a toast stack anchored to the top right of the screen
+pub fn notification_toasts_bundle() -> (NodeBundle, NotificationToasts) {
+    let (node, toasts, _) = notification_toasts_bundle_at(ToastCorner::TopRight);
+    (node, toasts)
+}
+
+/// Creates a toast stack anchored to `corner` of the screen
+pub fn notification_toasts_bundle_at(
+    corner: ToastCorner,
+) -> (NodeBundle, NotificationToasts, ToastCorner) {
+    let margin = Val::Px(16.0);
+    let mut style = Style {
+        position_type: PositionType::Absolute,
+        width: Val::Px(TOAST_WIDTH),
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(8.0),
+        ..default()
+    };
+    match corner {
+        ToastCorner::TopLeft => (style.top, style.left) = (margin, margin),
+        ToastCorner::TopRight => (style.top, style.right) = (margin, margin),
+        ToastCorner::BottomLeft => (style.bottom, style.left) = (margin, margin),
+        ToastCorner::BottomRight => (style.bottom, style.right) = (margin, margin),
+    }
+    (
+        NodeBundle {
+            style,
+            z_index: ZIndex::Global(90),
+            ..default()
+        },
+        NotificationToasts,
+        corner,
+    )
+}
+
+/// Creates the container bundle for a notification center
+pub fn notification_center_bundle() -> (NodeBundle, NotificationCenter) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
+                ..default()
+            },
+            ..default()
+        },
+        NotificationCenter::default(),
+    )
+}
+
+/// Formats a [`UiClock`] time as `h:mm:ss`
+fn format_time(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Spawns the category line and message of a notification
+fn spawn_notification_text(
+    parent: &mut ChildBuilder,
+    notification: &Notification,
+    theme: &Theme,
+    with_time: bool,
+) {
+    let caption = if with_time {
+        format!(
+            "{} · {}",
+            notification.category,
+            format_time(notification.time)
+        )
+    } else {
+        notification.category.clone()
+    };
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|text| {
+            text.spawn(TextBundle::from_section(
+                caption,
+                TextStyle {
+                    font: UiFonts::MEDIUM,
+                    font_size: 12.0,
+                    color: notification.kind.color(theme),
+                },
+            ));
+            text.spawn(TextBundle::from_section(
+                notification.text(),
+                TextStyle {
+                    font: UiFonts::MEDIUM,
+                    font_size: 16.0,
+                    color: theme.text.normal,
+                },
+            ));
+        });
+}
+
+/// System to keep notification timestamps current and expire old toasts
+pub fn notification_expiry_system(mut notifications: ResMut<UiNotifications>, clock: Res<UiClock>) {
+    // Advancing the clock alone shouldn't rebuild every toast and history list
+    let now = clock.elapsed_seconds();
+    notifications.bypass_change_detection().now = now;
+
+    let default_duration = notifications.toast_duration;
+    let expired = |notification: &Notification| {
+        now - notification.shown >= notification.duration.unwrap_or(default_duration)
+    };
+    if notifications.active.iter().any(expired) {
+        let (expired, active): (Vec<_>, Vec<_>) = std::mem::take(&mut notifications.active)
+            .into_iter()
+            .partition(expired);
+        notifications.active = active;
+        for notification in expired {
+            notifications.archive(notification);
+        }
+    }
+
+    // Queued toasts take the free places, timed from when they show
+    let free = notifications
+        .max_toasts
+        .saturating_sub(notifications.active.len())
+        .min(notifications.queued.len());
+    if free > 0 {
+        let shown: Vec<_> = notifications.queued.drain(..free).collect();
+        for mut notification in shown {
+            notification.shown = now;
+            notifications.active.push(notification);
+        }
+    }
+}
+
+/// The parts of a toast the stack system updates
+type ToastNode<'a> = (
+    &'a NotificationPart,
+    &'a mut ToastState,
+    &'a mut BackgroundColor,
+    &'a mut BorderColor,
+);
+
+/// System to keep toast stacks in step with the active notifications
+///
+/// New toasts slide in and closed or expired ones slide out, then get
+/// despawned by [`notification_toast_despawn_system`]. A toast's text is
+/// rebuilt when a repeat bumps its count or the theme changes.
+pub fn notification_toasts_system(
+    mut commands: Commands,
+    stacks: Query<(Entity, Option<&ToastCorner>, Option<&Children>), With<NotificationToasts>>,
+    mut toasts: Query<ToastNode>,
+    added: Query<(), Added<NotificationToasts>>,
+    notifications: Res<UiNotifications>,
+    (theme, motion): (Res<Theme>, UiMotion),
+) {
+    if !notifications.is_changed() && !theme.is_changed() && added.is_empty() {
+        return;
+    }
+    for (stack, corner, children) in &stacks {
+        let slide = corner.copied().unwrap_or_default().slide();
+        let animate = motion.animations();
+        let mut shown = Vec::new();
+        for &child in children.into_iter().flatten() {
+            let Ok((part, mut state, mut background, mut border)) = toasts.get_mut(child) else {
+                continue;
+            };
+            let NotificationPartKind::Toast(id) = part.kind else {
+                continue;
+            };
+            if state.leaving {
+                continue;
+            }
+            let Some(notification) = notifications.active().iter().find(|n| n.id == id) else {
+                state.leaving = true;
+                if !animate {
+                    commands.entity(child).despawn_recursive();
+                    continue;
+                }
+                commands.entity(child).insert(
+                    UiTween::new(0.2)
+                        .margin(UiRect::left(Val::Px(slide)))
+                        .scale(0.9)
+                        .easing(Easing::QuadIn),
+                );
+                continue;
+            };
+            shown.push(id);
+            if state.count == notification.count && !theme.is_changed() {
+                continue;
+            }
+            state.count = notification.count;
+            background.0 = theme.button.normal;
+            border.0 = notification.kind.color(&theme);
+            commands
+                .entity(child)
+                .despawn_descendants()
+                .with_children(|toast| {
+                    spawn_notification_text(toast, notification, &theme, false);
+                });
+        }
+
+        for notification in notifications.active() {
+            if shown.contains(&notification.id) {
+                continue;
+            }
+            // Off to the side and shrunk, to slide in from
+            let (margin, scale) = if animate { (slide, 0.9) } else { (0.0, 1.0) };
+            let toast = commands
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::axes(Val::Px(12.0), Val::Px(8.0)),
+                            margin: UiRect::left(Val::Px(margin)),
+                            border: UiRect::left(Val::Px(4.0)),
+                            ..default()
+                        },
+                        background_color: theme.button.normal.into(),
+                        border_color: notification.kind.color(&theme).into(),
+                        transform: Transform::from_scale(Vec3::splat(scale)),
+                        ..default()
+                    },
+                    NotificationPart {
+                        owner: stack,
+                        kind: NotificationPartKind::Toast(notification.id),
+                    },
+                    ToastState {
+                        count: notification.count,
+                        leaving: false,
+                    },
+                ))
+                .with_children(|toast| {
+                    spawn_notification_text(toast, notification, &theme, false);
+                })
+                .id();
+            if animate {
+                commands.entity(toast).insert(
+                    UiTween::new(0.25)
+                        .margin(UiRect::ZERO)
+                        .scale(1.0)
+                        .easing(Easing::CubicOut),
+                );
+            }
+            commands.entity(stack).add_child(toast);
+        }
+    }
+}
+
+/// System to despawn toasts once they have slid out
+pub fn notification_toast_despawn_system(
+    mut commands: Commands,
+    mut completed: EventReader<UiTweenCompleted>,
+    toasts: Query<&ToastState>,
+) {
+    for event in completed.read() {
+        if toasts.get(event.entity).is_ok_and(|state| state.leaving) {
+            commands.entity(event.entity).despawn_recursive();
+        }
+    }
+}
+
+/// System to spawn the toggle button and history panel of new notification centers
+pub fn notification_center_spawn_system(
+    mut commands: Commands,
+    centers: Query<Entity, Added<NotificationCenter>>,
+    theme: Res<Theme>,
+) {
+    for entity in &centers {
+        let part = |kind| NotificationPart {
+            owner: entity,
+            kind,
+        };
+        let text_style = TextStyle {
+            font: UiFonts::BOLD,
+            font_size: 16.0,
+            color: theme.text.normal,
+        };
+        let button = ButtonBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(6.0),
+                padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                ..default()
+            },
+            background_color: theme.button.normal.into(),
+            ..default()
+        };
+
+        commands.entity(entity).with_children(|center| {
+            center
+                .spawn((button.clone(), part(NotificationPartKind::Toggle)))
+                .with_children(|toggle| {
+                    toggle.spawn(TextBundle::from_section(
+                        "Notifications",
+                        text_style.clone(),
+                    ));
+                    toggle.spawn((
+                        status_chip_bundle(StatusChip::new("0", StatusKind::Error)),
+                        part(NotificationPartKind::Badge),
+                    ));
+                });
+
+            center
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            top: Val::Percent(100.0),
+                            right: Val::Px(0.0),
+                            width: Val::Px(320.0),
+                            max_height: Val::Px(400.0),
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(8.0),
+                            padding: UiRect::all(Val::Px(8.0)),
+                            overflow: Overflow::clip_y(),
+                            display: Display::None,
+                            ..default()
+                        },
+                        background_color: theme.button.normal.into(),
+                        z_index: ZIndex::Global(100),
+                        ..default()
+                    },
+                    part(NotificationPartKind::Panel),
+                ))
+                .with_children(|panel| {
+                    panel
+                        .spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Row,
+                                justify_content: JustifyContent::SpaceBetween,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|header| {
+                            header.spawn(TextBundle::from_section("History", text_style.clone()));
+                            header
+                                .spawn((button.clone(), part(NotificationPartKind::ClearAll)))
+                                .with_children(|clear| {
+                                    clear.spawn(TextBundle::from_section(
+                                        "Clear all",
+                                        text_style.clone(),
+                                    ));
+                                });
+                        });
+                    panel.spawn((
+                        NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Row,
+                                flex_wrap: FlexWrap::Wrap,
+                                column_gap: Val::Px(4.0),
+                                row_gap: Val::Px(4.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        part(NotificationPartKind::Filters),
+                    ));
+                    panel.spawn((
+                        NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(6.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        part(NotificationPartKind::List),
+                    ));
+                });
+        });
+    }
+}
+
+/// System to handle toast, toggle, filter and "Clear all" presses
+///
+/// Opening a notification center marks the history as read.
+pub fn notification_interaction_system(
+    parts: Query<(Entity, &NotificationPart)>,
+    mut centers: Query<&mut NotificationCenter>,
+    mut notifications: ResMut<UiNotifications>,
+    interaction_state: Res<UiInteractionState>,
+) {
+    for (entity, part) in &parts {
+        if !interaction_state.just_pressed(entity) {
+            continue;
+        }
+        match &part.kind {
+            NotificationPartKind::Toast(id) => notifications.dismiss(*id),
+            NotificationPartKind::Toggle => {
+                if let Ok(mut center) = centers.get_mut(part.owner) {
+                    center.open = !center.open;
+                    if center.open {
+                        notifications.mark_all_read();
+                    }
+                }
+            }
+            NotificationPartKind::ClearAll => notifications.clear_history(),
+            NotificationPartKind::Filter(category) => {
+                if let Ok(mut center) = centers.get_mut(part.owner) {
+                    center.toggle_category(category);
+                }
+            }
+            NotificationPartKind::Badge
+            | NotificationPartKind::Panel
+            | NotificationPartKind::Filters
+            | NotificationPartKind::List => {}
+        }
+    }
+}
+
+/// System to refresh notification centers when the history, filters or theme change
+///
+/// History arriving while a center is open counts as read, and the unread badge
+/// pulses when the unread count grows.
+pub fn notification_center_update_system(
+    mut commands: Commands,
+    centers: Query<Ref<NotificationCenter>>,
+    mut parts: Query<(
+        Entity,
+        &NotificationPart,
+        &mut Style,
+        Option<&mut StatusChip>,
+    )>,
+    mut notifications: ResMut<UiNotifications>,
+    theme: Res<Theme>,
+    mut last_unread: Local<usize>,
+) {
+    if centers.iter().any(|center| center.open) && notifications.unread_count() > 0 {
+        notifications.mark_all_read();
+    }
+    let notifications_changed = notifications.is_changed();
+    // The badge pulses when more notifications arrive unread
+    let unread = notifications.unread_count();
+    let more_unread = unread > *last_unread;
+    *last_unread = unread;
+
+    for (entity, part, mut style, chip) in &mut parts {
+        let Ok(center) = centers.get(part.owner) else {
+            continue;
+        };
+        if !center.is_changed() && !notifications_changed && !theme.is_changed() {
+            continue;
+        }
+        match part.kind {
+            NotificationPartKind::Panel => {
+                style.display = if center.open {
+                    Display::Flex
+                } else {
+                    Display::None
+                };
+            }
+            NotificationPartKind::Badge => {
+                if more_unread {
+                    commands.entity(entity).insert(Pulse::default());
+                }
+                style.display = if unread > 0 {
+                    Display::Flex
+                } else {
+                    Display::None
+                };
+                if let Some(mut chip) = chip {
+                    let text = unread.to_string();
+                    if chip.text != text {
+                        chip.text = text;
+                    }
+                }
+            }
+            NotificationPartKind::Filters => {
+                let text_style = TextStyle {
+                    font: UiFonts::MEDIUM,
+                    font_size: 14.0,
+                    color: theme.text.normal,
+                };
+                commands
+                    .entity(entity)
+                    .despawn_descendants()
+                    .with_children(|filters| {
+                        for category in notifications.categories() {
+                            let color = if center.shows(&category) {
+                                theme.text.normal
+                            } else {
+                                theme.text.disabled
+                            };
+                            filters
+                                .spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                                            ..default()
+                                        },
+                                        background_color: theme.button.normal.into(),
+                                        border_radius: BorderRadius::MAX,
+                                        ..default()
+                                    },
+                                    NotificationPart {
+                                        owner: part.owner,
+                                        kind: NotificationPartKind::Filter(category.clone()),
+                                    },
+                                ))
+                                .with_children(|filter| {
+                                    filter.spawn(TextBundle::from_section(
+                                        category,
+                                        TextStyle {
+                                            color,
+                                            ..text_style.clone()
+                                        },
+                                    ));
+                                });
+                        }
+                    });
+            }
+            NotificationPartKind::List => {
+                commands
+                    .entity(entity)
+                    .despawn_descendants()
+                    .with_children(|list| {
+                        // Newest first
+                        for notification in notifications
+                            .history()
+                            .iter()
+                            .rev()
+                            .filter(|notification| center.shows(&notification.category))
+                        {
+                            list.spawn(NodeBundle {
+                                style: Style {
+                                    padding: UiRect::left(Val::Px(8.0)),
+                                    border: UiRect::left(Val::Px(3.0)),
+                                    ..default()
+                                },
+                                border_color: notification.kind.color(&theme).into(),
+                                ..default()
+                            })
+                            .with_children(|entry| {
+                                spawn_notification_text(entry, notification, &theme, true);
+                            });
+                        }
+                    });
+            }
+            NotificationPartKind::Toast(_)
+            | NotificationPartKind::Toggle
+            | NotificationPartKind::ClearAll
+            | NotificationPartKind::Filter(_) => {}
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiNotifications>()
+        .register_type::<NotificationPriority>()
+        .register_type::<NotificationToasts>()
+        .register_type::<ToastCorner>()
+        .register_type::<ToastState>()
+        .register_type::<NotificationCenter>()
+        .register_type::<NotificationPart>()
+        .init_resource::<UiNotifications>()
+        .add_systems(
+            Update,
+            (
+                notification_interaction_system,
+                notification_expiry_system,
+                notification_toasts_system,
+                notification_toast_despawn_system,
+                notification_center_spawn_system,
+                notification_center_update_system,
+            )
+                .chain(),
+        );
+}
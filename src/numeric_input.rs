@@ -0,0 +1,391 @@
+//! Number inputs with stepper buttons
+//!
+//! A [`NumericInput`] shows its value in a small field between "-" and "+"
+//! buttons. The buttons and the mouse wheel, while the input is hovered, move
+//! the value by [`step`](NumericInput::step); clicking the field lets the
+//! player type a number, committed with Enter and cancelled with Escape or a
+//! click elsewhere. Values are clamped to the range, and every change sends
+//! [`ValueCommitted`].
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{numeric_input_bundle, NumericInput, ValueCommitted};
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn(numeric_input_bundle(
+//!         NumericInput::new(500.0).range(0.0, 10_000.0).step(100.0),
+//!     ));
+//! }
+//!
+//! fn starting_resources(mut committed: EventReader<ValueCommitted>) {
+//!     for event in committed.read() {
+//!         info!("starting resources: {}", event.value);
+//!     }
+//! }
+//! ```
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::attention::Shake;
+use crate::interaction::UiInteractionState;
+use crate::slider_entry::ValueEntry;
+use crate::theme::Theme;
+
+/// A number field with "-" and "+" stepper buttons
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct NumericInput {
+    /// Current value
+    pub value: f32,
+    /// Smallest allowed value
+    pub min: f32,
+    /// Largest allowed value
+    pub max: f32,
+    /// Amount the buttons and the mouse wheel move the value by
+    pub step: f32,
+    /// Digits shown after the decimal point
+    pub decimals: usize,
+    /// Whether the input ignores the player
+    pub disabled: bool,
+    #[reflect(ignore)]
+    entry: Option<ValueEntry>,
+    /// Whether the field and buttons were spawned
+    #[reflect(ignore)]
+    built: bool,
+}
+
+impl NumericInput {
+    /// Creates an input holding `value`, unbounded, stepping by 1
+    pub fn new(value: f32) -> Self {
+        Self {
+            value,
+            min: f32::MIN,
+            max: f32::MAX,
+            step: 1.0,
+            decimals: 0,
+            disabled: false,
+            entry: None,
+            built: false,
+        }
+    }
+
+    /// Limits the value to `min..=max`
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
+        self.value = self.clamp(self.value);
+        self
+    }
+
+    /// Sets the amount the buttons and the mouse wheel move the value by
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Shows `decimals` digits after the decimal point
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// Whether a number is being typed
+    pub fn is_editing(&self) -> bool {
+        self.entry.is_some()
+    }
+
+    /// Drops the number being typed, keeping the current value
+    pub fn cancel_entry(&mut self) {
+        self.entry = None;
+    }
+
+    /// The value as shown in the field
+    pub fn display_value(&self) -> String {
+        format!("{:.*}", self.decimals, self.value)
+    }
+
+    /// `value` limited to the range and rounded to the shown decimals
+    pub fn clamp(&self, value: f32) -> f32 {
+        let factor = 10f32.powi(self.decimals as i32);
+        let rounded = (value * factor).round() / factor;
+        rounded.clamp(self.min, self.max.max(self.min))
+    }
+}
+
+/// Which part of a [`NumericInput`] a [`NumericInputPart`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum NumericInputPartKind {
+    /// The value field
+    Field,
+    /// The "-" button
+    Decrement,
+    /// The "+" button
+    Increment,
+}
+
+/// A part spawned for a [`NumericInput`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct NumericInputPart {
+    /// The input this part belongs to
+    pub input: Entity,
+    /// Which part this is
+    pub kind: NumericInputPartKind,
+}
+
+/// Sent when a [`NumericInput`]'s value changes through its buttons, the
+/// mouse wheel or a typed number
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct ValueCommitted {
+    /// The input entity
+    pub input: Entity,
+    /// The new value
+    pub value: f32,
+}
+
+/// Creates the container bundle for a number input
+pub fn numeric_input_bundle(input: NumericInput) -> (NodeBundle, NumericInput) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Stretch,
+                column_gap: Val::Px(2.0),
+                ..default()
+            },
+            ..default()
+        },
+        input,
+    )
+}
+
+/// System to spawn the field and stepper buttons of new number inputs
+pub fn numeric_input_spawn_system(
+    mut commands: Commands,
+    mut inputs: Query<(Entity, &mut NumericInput), Changed<NumericInput>>,
+    theme: Res<Theme>,
+) {
+    for (entity, mut input) in &mut inputs {
+        if input.built {
+            continue;
+        }
+        input.bypass_change_detection().built = true;
+
+        let text_style = |font| TextStyle {
+            font,
+            font_size: 18.0,
+            color: theme.text.normal,
+        };
+        let stepper = |kind| {
+            (
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(24.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: theme.button.normal.into(),
+                    ..default()
+                },
+                NumericInputPart {
+                    input: entity,
+                    kind,
+                },
+            )
+        };
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn(stepper(NumericInputPartKind::Decrement))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section("-", text_style(UiFonts::BOLD)));
+                });
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(input.display_value(), text_style(UiFonts::MEDIUM)),
+                    style: Style {
+                        min_width: Val::Px(64.0),
+                        padding: UiRect::axes(Val::Px(6.0), Val::Px(4.0)),
+                        ..default()
+                    },
+                    ..default()
+                }
+                .with_text_justify(JustifyText::Center),
+                Interaction::default(),
+                NumericInputPart {
+                    input: entity,
+                    kind: NumericInputPartKind::Field,
+                },
+            ));
+            parent
+                .spawn(stepper(NumericInputPartKind::Increment))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section("+", text_style(UiFonts::BOLD)));
+                });
+        });
+    }
+}
+
+/// System to step, scroll, type and commit number input values
+pub fn numeric_input_system(
+    mut commands: Commands,
+    mut inputs: Query<(Entity, &mut NumericInput)>,
+    parts: Query<(Entity, &NumericInputPart)>,
+    interaction_state: Res<UiInteractionState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    (mouse_buttons, mut key_events, mut wheel_events): (
+        Res<ButtonInput<MouseButton>>,
+        EventReader<KeyboardInput>,
+        EventReader<MouseWheel>,
+    ),
+    mut committed_events: EventWriter<ValueCommitted>,
+) {
+    let typed: Vec<Key> = key_events
+        .read()
+        .filter(|event| event.state.is_pressed())
+        .map(|event| event.logical_key.clone())
+        .collect();
+    let scrolled: f32 = wheel_events.read().map(|event| event.y.signum()).sum();
+    let hovered = interaction_state
+        .winner()
+        .and_then(|entity| parts.get(entity).ok())
+        .map(|(_, part)| part.input);
+    let pressed = parts
+        .iter()
+        .find(|(entity, _)| interaction_state.just_pressed(*entity))
+        .map(|(_, part)| *part);
+
+    for (entity, mut input) in &mut inputs {
+        if input.disabled {
+            if input.is_editing() {
+                input.entry = None;
+            }
+            continue;
+        }
+        let mut steps = 0.0;
+        if let Some(part) = pressed.filter(|part| part.input == entity) {
+            match part.kind {
+                NumericInputPartKind::Decrement => steps -= 1.0,
+                NumericInputPartKind::Increment => steps += 1.0,
+                NumericInputPartKind::Field => {
+                    if !input.is_editing() {
+                        input.entry = Some(ValueEntry::new(input.display_value()));
+                    }
+                    continue;
+                }
+            }
+        }
+        if hovered == Some(entity) {
+            steps += scrolled;
+        }
+
+        let mut committed = None;
+        if steps != 0.0 {
+            committed = Some(input.value + steps * input.step);
+            input.entry = None;
+        } else if let Some(entry) = &input.entry {
+            // Clicking away cancels, like Escape
+            if mouse_buttons.just_pressed(MouseButton::Left)
+                || keyboard_input.just_pressed(KeyCode::Escape)
+            {
+                input.entry = None;
+                continue;
+            }
+            if keyboard_input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+                // Text that is not a number shakes and stays open for fixing
+                let Ok(value) = entry.text.parse::<f32>() else {
+                    commands.entity(entity).insert(Shake::default());
+                    continue;
+                };
+                committed = Some(value);
+                input.entry = None;
+            } else if !typed.is_empty() {
+                let mut entry = entry.clone();
+                entry.type_keys(&typed);
+                if input.entry.as_ref() != Some(&entry) {
+                    input.entry = Some(entry);
+                }
+            }
+        }
+
+        let Some(value) = committed.map(|value| input.clamp(value)) else {
+            continue;
+        };
+        if input.value != value {
+            input.value = value;
+            committed_events.send(ValueCommitted {
+                input: entity,
+                value,
+            });
+        }
+    }
+}
+
+/// System to show number input values, or the number being typed
+///
+/// Runs after the generic button colors, which would otherwise tint the field.
+pub fn numeric_input_visual_system(
+    theme: Res<Theme>,
+    inputs: Query<Ref<NumericInput>>,
+    mut parts: Query<(
+        &NumericInputPart,
+        Ref<Interaction>,
+        &mut BackgroundColor,
+        Option<&mut Text>,
+    )>,
+) {
+    for (part, interaction, mut background, text) in &mut parts {
+        let Ok(input) = inputs.get(part.input) else {
+            continue;
+        };
+        if !input.is_changed() && !interaction.is_changed() && !theme.is_changed() {
+            continue;
+        }
+        match (part.kind, text) {
+            (NumericInputPartKind::Field, Some(mut text)) => {
+                let Some(section) = text.sections.first_mut() else {
+                    continue;
+                };
+                match &input.entry {
+                    Some(entry) => {
+                        section.value = format!("{}|", entry.text);
+                        section.style.color = theme.focus.text;
+                        background.0 = theme.focus.highlight;
+                    }
+                    None => {
+                        section.value = input.display_value();
+                        section.style.color = if input.disabled {
+                            theme.text.disabled
+                        } else {
+                            theme.text.normal
+                        };
+                        background.0 = if *interaction != Interaction::None && !input.disabled {
+                            theme.button.hovered
+                        } else {
+                            theme.button.normal
+                        };
+                    }
+                }
+            }
+            _ if input.disabled => background.0 = theme.button.disabled,
+            _ => background.0 = theme.button_color(*interaction),
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<NumericInput>()
+        .register_type::<NumericInputPart>()
+        .add_event::<ValueCommitted>()
+        .add_systems(
+            Update,
+            (
+                numeric_input_spawn_system,
+                numeric_input_system,
+                numeric_input_visual_system.after(crate::button_interaction_system),
+            )
+                .chain(),
+        );
+}
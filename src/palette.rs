@@ -0,0 +1,462 @@
+//! Command palette overlay
+//!
+//! [`CommandPalettePlugin`] opens a search box over the screen on Ctrl+P.
+//! It lists the commands in the [`CommandRegistry`], filtered as the player
+//! types by [`fuzzy_match`](crate::text_match::fuzzy_match), with the matched letters highlighted. Up/Down
+//! move the selection, Enter or a click runs the selected command by sending
+//! [`CommandExecuted`], and Escape or Ctrl+P close the palette.
+//!
+//! The registry is a plain resource, so a developer console can list and run
+//! the same commands:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{CommandExecuted, CommandPalettePlugin, CommandRegistry};
+//! fn register(mut registry: ResMut<CommandRegistry>) {
+//!     registry
+//!         .register("editor.save", "Save Map")
+//!         .register("editor.toggle_grid", "Toggle Grid");
+//! }
+//!
+//! fn run(mut executed: EventReader<CommandExecuted>) {
+//!     for event in executed.read() {
+//!         info!("running {}", event.id);
+//!     }
+//! }
+//!
+//! # let mut app = App::new();
+//! app.add_plugins(CommandPalettePlugin)
+//!     .add_systems(Startup, register)
+//!     .add_systems(Update, run);
+//! ```
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::assets::UiFonts;
+use crate::interaction::UiInteractionState;
+use crate::text_match::{rank_matches, FuzzyMatch};
+use crate::theme::Theme;
+
+/// A command that can be run from the palette
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+pub struct RegisteredCommand {
+    /// Identifier sent with [`CommandExecuted`]
+    pub id: String,
+    /// Name shown and searched in the palette
+    pub label: String,
+}
+
+/// Commands known to the palette, in registration order
+#[derive(Resource, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct CommandRegistry {
+    commands: Vec<RegisteredCommand>,
+}
+
+impl CommandRegistry {
+    /// Adds a command, replacing any with the same `id`
+    pub fn register(&mut self, id: impl Into<String>, label: impl Into<String>) -> &mut Self {
+        let command = RegisteredCommand {
+            id: id.into(),
+            label: label.into(),
+        };
+        match self
+            .commands
+            .iter_mut()
+            .find(|other| other.id == command.id)
+        {
+            Some(other) => *other = command,
+            None => self.commands.push(command),
+        }
+        self
+    }
+
+    /// Removes the command with `id`, if registered
+    pub fn unregister(&mut self, id: &str) {
+        self.commands.retain(|command| command.id != id);
+    }
+
+    /// The command with `id`
+    pub fn get(&self, id: &str) -> Option<&RegisteredCommand> {
+        self.commands.iter().find(|command| command.id == id)
+    }
+
+    /// All commands, in registration order
+    pub fn commands(&self) -> &[RegisteredCommand] {
+        &self.commands
+    }
+}
+
+/// State of the command palette
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct CommandPalette {
+    /// Whether the overlay is shown
+    pub open: bool,
+    /// Text typed so far
+    pub query: String,
+    /// Index of the selected result
+    pub selected: usize,
+    /// Most results listed at once
+    pub max_results: usize,
+    /// Matching commands, best first, as registry indices
+    results: Vec<(usize, FuzzyMatch)>,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self {
+            open: false,
+            query: String::new(),
+            selected: 0,
+            max_results: 10,
+            results: Vec::new(),
+        }
+    }
+}
+
+impl CommandPalette {
+    /// Opens the palette with an empty query
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Closes the palette
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Recomputes the results for the current query
+    fn search(&mut self, registry: &CommandRegistry) {
+        let labels = registry
+            .commands()
+            .iter()
+            .map(|command| command.label.as_str());
+        let mut results = rank_matches(&self.query, labels);
+        results.truncate(self.max_results);
+        self.results = results;
+        self.selected = self.selected.min(self.results.len().saturating_sub(1));
+    }
+
+    /// Id of the selected command
+    fn selected_id(&self, registry: &CommandRegistry) -> Option<String> {
+        let (index, _) = self.results.get(self.selected)?;
+        Some(registry.commands().get(*index)?.id.clone())
+    }
+}
+
+/// Sent when a command is run from the palette
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct CommandExecuted {
+    /// The command's [`RegisteredCommand::id`]
+    pub id: String,
+}
+
+/// Marks the palette's full-screen overlay
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct CommandPaletteRoot;
+
+/// Marks the text showing the query
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct CommandPaletteQuery;
+
+/// Marks the column of results
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct CommandPaletteList;
+
+/// A result row, by its index in the results
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct CommandPaletteRow(pub usize);
+
+/// System to open, type into, navigate and run the palette from the keyboard
+pub fn command_palette_input_system(
+    mut palette: ResMut<CommandPalette>,
+    registry: Res<CommandRegistry>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut key_events: EventReader<KeyboardInput>,
+    mut executed_events: EventWriter<CommandExecuted>,
+) {
+    let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let alt = keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    if ctrl && keyboard_input.just_pressed(KeyCode::KeyP) {
+        key_events.clear();
+        if palette.open {
+            palette.close();
+        } else {
+            palette.open();
+            palette.search(&registry);
+        }
+        return;
+    }
+    if !palette.open {
+        key_events.clear();
+        return;
+    }
+
+    let mut query = palette.query.clone();
+    for event in key_events.read() {
+        if !event.state.is_pressed() || ctrl || alt {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => query.push_str(text),
+            Key::Space => query.push(' '),
+            Key::Backspace => {
+                query.pop();
+            }
+            _ => {}
+        }
+    }
+    if query != palette.query {
+        palette.query = query;
+        palette.selected = 0;
+        palette.search(&registry);
+    } else if registry.is_changed() {
+        palette.search(&registry);
+    }
+
+    let count = palette.results.len();
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        palette.close();
+    } else if keyboard_input.just_pressed(KeyCode::Enter) {
+        if let Some(id) = palette.selected_id(&registry) {
+            executed_events.send(CommandExecuted { id });
+            palette.close();
+        }
+    } else if count > 0 && keyboard_input.just_pressed(KeyCode::ArrowDown) {
+        palette.selected = (palette.selected + 1) % count;
+    } else if count > 0 && keyboard_input.just_pressed(KeyCode::ArrowUp) {
+        palette.selected = (palette.selected + count - 1) % count;
+    }
+}
+
+/// System to run a result when it is clicked, and select it when hovered
+pub fn command_palette_pointer_system(
+    mut palette: ResMut<CommandPalette>,
+    registry: Res<CommandRegistry>,
+    rows: Query<(Entity, &CommandPaletteRow, Ref<Interaction>)>,
+    interaction_state: Res<UiInteractionState>,
+    mut executed_events: EventWriter<CommandExecuted>,
+) {
+    for (entity, row, interaction) in &rows {
+        if interaction.is_changed()
+            && *interaction == Interaction::Hovered
+            && palette.selected != row.0
+        {
+            palette.selected = row.0;
+        }
+        if interaction_state.just_pressed(entity) {
+            palette.selected = row.0;
+            if let Some(id) = palette.selected_id(&registry) {
+                executed_events.send(CommandExecuted { id });
+                palette.close();
+            }
+        }
+    }
+}
+
+/// System to show, update and hide the overlay as the palette changes
+pub fn command_palette_view_system(
+    mut commands: Commands,
+    palette: Res<CommandPalette>,
+    registry: Res<CommandRegistry>,
+    roots: Query<Entity, With<CommandPaletteRoot>>,
+    lists: Query<Entity, With<CommandPaletteList>>,
+    mut query_texts: Query<&mut Text, With<CommandPaletteQuery>>,
+    theme: Res<Theme>,
+) {
+    if !palette.is_changed() && !theme.is_changed() {
+        return;
+    }
+    if !palette.open {
+        for root in &roots {
+            commands.entity(root).despawn_recursive();
+        }
+        return;
+    }
+
+    let text_style = TextStyle {
+        font: UiFonts::MEDIUM,
+        font_size: 18.0,
+        color: theme.text.normal,
+    };
+    let query = if palette.query.is_empty() {
+        TextSection::new(
+            "Type a command…",
+            TextStyle {
+                color: theme.text.disabled,
+                ..text_style.clone()
+            },
+        )
+    } else {
+        TextSection::new(palette.query.clone(), text_style.clone())
+    };
+
+    let list = match lists.get_single() {
+        Ok(list) => {
+            for mut text in &mut query_texts {
+                text.sections = vec![query.clone()];
+            }
+            commands.entity(list).despawn_descendants();
+            list
+        }
+        Err(_) => {
+            let mut list = Entity::PLACEHOLDER;
+            commands
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::FlexStart,
+                            padding: UiRect::top(Val::Percent(15.0)),
+                            ..default()
+                        },
+                        background_color: Color::srgba(0.0, 0.0, 0.0, 0.4).into(),
+                        focus_policy: FocusPolicy::Block,
+                        z_index: ZIndex::Global(300),
+                        ..default()
+                    },
+                    Interaction::default(),
+                    CommandPaletteRoot,
+                    Name::new("CommandPalette"),
+                ))
+                .with_children(|root| {
+                    root.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(480.0),
+                            max_width: Val::Percent(90.0),
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::all(Val::Px(8.0)),
+                            row_gap: Val::Px(6.0),
+                            ..default()
+                        },
+                        background_color: Color::srgba(0.1, 0.1, 0.1, 0.95).into(),
+                        focus_policy: FocusPolicy::Block,
+                        ..default()
+                    })
+                    .with_children(|panel| {
+                        panel
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    padding: UiRect::all(Val::Px(6.0)),
+                                    border: UiRect::bottom(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                border_color: theme.focus.border.into(),
+                                ..default()
+                            })
+                            .with_children(|field| {
+                                field.spawn((
+                                    TextBundle::from_sections([query.clone()]),
+                                    CommandPaletteQuery,
+                                ));
+                            });
+                        list = panel
+                            .spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        flex_direction: FlexDirection::Column,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                CommandPaletteList,
+                            ))
+                            .id();
+                    });
+                });
+            list
+        }
+    };
+
+    commands.entity(list).with_children(|list| {
+        if palette.results.is_empty() {
+            list.spawn(TextBundle::from_section(
+                "No matching commands",
+                TextStyle {
+                    color: theme.text.disabled,
+                    ..text_style.clone()
+                },
+            ));
+        }
+        for (row, (index, found)) in palette.results.iter().enumerate() {
+            let Some(command) = registry.commands().get(*index) else {
+                continue;
+            };
+            let background = if row == palette.selected {
+                theme.button.hovered
+            } else {
+                Color::NONE
+            };
+            list.spawn((
+                ButtonBundle {
+                    style: Style {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        ..default()
+                    },
+                    background_color: background.into(),
+                    ..default()
+                },
+                CommandPaletteRow(row),
+            ))
+            .with_children(|row| {
+                row.spawn(TextBundle::from_sections(found.sections(
+                    &command.label,
+                    &text_style,
+                    theme.focus.border,
+                )));
+            });
+        }
+    });
+}
+
+/// System to highlight the selected result and clear hover colors from the rest
+pub fn command_palette_row_visual_system(
+    palette: Res<CommandPalette>,
+    theme: Res<Theme>,
+    mut rows: Query<(&CommandPaletteRow, Ref<Interaction>, &mut BackgroundColor)>,
+) {
+    for (row, interaction, mut background) in &mut rows {
+        if !interaction.is_changed() && !palette.is_changed() {
+            continue;
+        }
+        background.0 = if row.0 == palette.selected {
+            theme.button.hovered
+        } else {
+            Color::NONE
+        };
+    }
+}
+
+/// Adds the Ctrl+P command palette and its [`CommandRegistry`]
+pub struct CommandPalettePlugin;
+
+impl Plugin for CommandPalettePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CommandRegistry>()
+            .init_resource::<CommandPalette>()
+            .register_type::<CommandRegistry>()
+            .register_type::<CommandPaletteRow>()
+            .add_event::<CommandExecuted>()
+            .add_systems(
+                Update,
+                (
+                    command_palette_input_system,
+                    command_palette_pointer_system,
+                    command_palette_view_system,
+                    command_palette_row_visual_system.after(crate::button_interaction_system),
+                )
+                    .chain(),
+            );
+    }
+}
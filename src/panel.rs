@@ -0,0 +1,508 @@
+//! Standard panel headers
+//!
+//! [`panel_title_bar`] builds the header every floating panel needs: an
+//! optional icon, the title, and collapse, pin and close buttons. Collapsing
+//! toggles the panel's [`Collapsible`] (or [`Panel`]) state, slides
+//! everything below the header shut and sends [`PanelToggled`]; clicking the
+//! header itself does the same, except on a
+//! [`FloatingPanel`](crate::FloatingPanel), which the header drags. Closing sends [`PanelCloseRequested`] so the
+//! owner decides what closing means.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{panel_title_bar, Theme};
+//! fn setup(mut commands: Commands, theme: Res<Theme>) {
+//!     commands
+//!         .spawn(NodeBundle::default())
+//!         .with_children(|panel| {
+//!             panel_title_bar("Army", &theme)
+//!                 .collapsible()
+//!                 .pinnable()
+//!                 .spawn(panel);
+//!             // Panel body follows the title bar
+//!         });
+//! }
+//! ```
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::accordion::AccordionSection;
+use crate::clock::UiClock;
+use crate::components::{Collapsible, Panel};
+use crate::interaction::{HitboxPadding, UiInteractionState};
+use crate::panel_snap::FloatingPanel;
+use crate::quality::UiMotion;
+use crate::theme::Theme;
+use crate::utils::first_section;
+
+/// Collapse animation speed (higher is faster)
+const PANEL_ANIMATION_SPEED: f32 = 12.0;
+
+/// Header row spawned by [`panel_title_bar`]
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct PanelTitleBar {
+    /// The panel this header belongs to
+    pub panel: Entity,
+}
+
+/// What a title bar button does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum PanelActionKind {
+    /// Toggle the panel's [`Collapsible`] state
+    Collapse,
+    /// Toggle [`PanelPinned`] on the panel
+    Pin,
+    /// Send [`PanelCloseRequested`]
+    Close,
+}
+
+/// A title bar button
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct PanelAction {
+    /// The panel the button acts on
+    pub panel: Entity,
+    /// What the button does
+    pub kind: PanelActionKind,
+}
+
+/// Marks a panel the player has pinned open
+#[derive(Component, Debug, Clone, Copy, Reflect, Default)]
+#[reflect(Component)]
+pub struct PanelPinned;
+
+/// Sent when a panel's close button is pressed
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelCloseRequested {
+    /// The panel to close
+    pub panel: Entity,
+}
+
+/// Sent when a panel is collapsed or expanded from its title bar
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelToggled {
+    /// The panel that was toggled
+    pub panel: Entity,
+    /// Whether the panel is now collapsed
+    pub collapsed: bool,
+}
+
+/// Open/close animation state of a collapsible panel, added automatically
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct PanelOpenness {
+    /// How far the body is open, from 0 (collapsed) to 1 (expanded)
+    pub openness: f32,
+    /// The panel's own `max_height`, restored once fully open
+    max_height: Val,
+    /// The panel's own `overflow`, restored once fully open
+    overflow: Overflow,
+}
+
+/// Builder for a panel header
+///
+/// Created by [`panel_title_bar`]. Only the close button is shown by default.
+#[derive(Debug, Clone)]
+pub struct PanelTitleBarBuilder {
+    title: String,
+    icon: Option<Handle<Image>>,
+    collapsible: bool,
+    pinnable: bool,
+    closable: bool,
+    font: Handle<Font>,
+    theme: Theme,
+}
+
+/// Starts a panel header using colors from `theme`
+pub fn panel_title_bar(title: impl Into<String>, theme: &Theme) -> PanelTitleBarBuilder {
+    PanelTitleBarBuilder {
+        title: title.into(),
+        icon: None,
+        collapsible: false,
+        pinnable: false,
+        closable: true,
+        font: UiFonts::BOLD,
+        theme: theme.clone(),
+    }
+}
+
+impl PanelTitleBarBuilder {
+    /// Shows an icon before the title
+    pub fn icon(mut self, icon: Handle<Image>) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Adds a collapse button
+    pub fn collapsible(mut self) -> Self {
+        self.collapsible = true;
+        self
+    }
+
+    /// Adds a pin button
+    pub fn pinnable(mut self) -> Self {
+        self.pinnable = true;
+        self
+    }
+
+    /// Sets whether the close button is shown
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    /// Spawns the header as the first child of the panel being built
+    ///
+    /// A collapsible header also gives the panel a [`Collapsible`] component
+    /// if it doesn't have one yet.
+    pub fn spawn(self, panel: &mut ChildBuilder) -> Entity {
+        let panel_entity = panel.parent_entity();
+        if self.collapsible {
+            let title = self.title.clone();
+            panel.add_command(move |world: &mut World| {
+                if let Some(mut panel) = world.get_entity_mut(panel_entity) {
+                    if !panel.contains::<Collapsible>() {
+                        panel.insert(Collapsible {
+                            title,
+                            collapsible: true,
+                            collapsed: false,
+                        });
+                    }
+                }
+            });
+        }
+        let text_style = |font_size| TextStyle {
+            font: self.font.clone(),
+            font_size,
+            color: self.theme.text.normal,
+        };
+
+        let mut title_bar = panel.spawn((
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(6.0),
+                    padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                    ..default()
+                },
+                background_color: self.theme.button.normal.into(),
+                ..default()
+            },
+            PanelTitleBar {
+                panel: panel_entity,
+            },
+            Name::new("PanelTitleBar"),
+        ));
+        if self.collapsible {
+            // Clicking the header outside its buttons collapses the panel too
+            title_bar.insert(Interaction::default());
+        }
+        title_bar
+            .with_children(|bar| {
+                if let Some(icon) = self.icon.clone() {
+                    bar.spawn(ImageBundle {
+                        style: Style {
+                            width: Val::Px(18.0),
+                            height: Val::Px(18.0),
+                            flex_shrink: 0.0,
+                            ..default()
+                        },
+                        image: UiImage::new(icon),
+                        ..default()
+                    });
+                }
+                bar.spawn(
+                    TextBundle::from_section(self.title.clone(), text_style(18.0)).with_style(
+                        Style {
+                            flex_grow: 1.0,
+                            ..default()
+                        },
+                    ),
+                );
+
+                for (enabled, kind) in [
+                    (self.collapsible, PanelActionKind::Collapse),
+                    (self.pinnable, PanelActionKind::Pin),
+                    (self.closable, PanelActionKind::Close),
+                ] {
+                    if !enabled {
+                        continue;
+                    }
+                    bar.spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(20.0),
+                                height: Val::Px(20.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: self.theme.button.normal.into(),
+                            ..default()
+                        },
+                        PanelAction {
+                            panel: panel_entity,
+                            kind,
+                        },
+                        HitboxPadding::all(Val::Px(3.0)),
+                    ))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section(
+                            glyph(kind, false),
+                            text_style(14.0),
+                        ));
+                    });
+                }
+            })
+            .id()
+    }
+}
+
+/// Button text for `kind` in its current state
+///
+/// The collapse chevron keeps one glyph and is rotated instead.
+fn glyph(kind: PanelActionKind, active: bool) -> &'static str {
+    match (kind, active) {
+        (PanelActionKind::Collapse, _) => "v",
+        (PanelActionKind::Pin, false) => "o",
+        (PanelActionKind::Pin, true) => "*",
+        (PanelActionKind::Close, _) => "x",
+    }
+}
+
+/// Flips a panel's collapsed state, returning the new state
+///
+/// Panels with both components keep them in step; [`Collapsible`] wins when
+/// they disagree.
+fn toggle_collapsed(
+    collapsible: Option<Mut<Collapsible>>,
+    panel: Option<Mut<Panel>>,
+) -> Option<bool> {
+    let collapsed = match (&collapsible, &panel) {
+        (Some(collapsible), _) if collapsible.collapsible => !collapsible.collapsed,
+        (None, Some(panel)) if panel.collapsible => !panel.collapsed,
+        _ => return None,
+    };
+    if let Some(mut collapsible) = collapsible {
+        collapsible.collapsed = collapsed;
+    }
+    if let Some(mut panel) = panel {
+        panel.collapsed = collapsed;
+    }
+    Some(collapsed)
+}
+
+/// Whether a panel is currently collapsed
+fn is_collapsed(collapsible: Option<&Collapsible>, panel: Option<&Panel>) -> bool {
+    match (collapsible, panel) {
+        (Some(collapsible), _) => collapsible.collapsible && collapsible.collapsed,
+        (None, Some(panel)) => panel.collapsible && panel.collapsed,
+        (None, None) => false,
+    }
+}
+
+/// System to apply title bar button and header presses
+///
+/// Pressing a collapsible title bar outside its buttons also toggles the
+/// panel, unless it is a [`FloatingPanel`], whose title bar drags it instead.
+pub fn panel_action_system(
+    mut commands: Commands,
+    actions: Query<(Entity, &PanelAction)>,
+    title_bars: Query<(Entity, &PanelTitleBar), With<Interaction>>,
+    mut panels: Query<
+        (Option<&mut Collapsible>, Option<&mut Panel>, Has<PanelPinned>),
+        Without<AccordionSection>,
+    >,
+    (interaction_state, floating): (Res<UiInteractionState>, Query<(), With<FloatingPanel>>),
+    mut close_events: EventWriter<PanelCloseRequested>,
+    mut toggled_events: EventWriter<PanelToggled>,
+) {
+    let toggles = title_bars
+        .iter()
+        .filter(|(_, title_bar)| !floating.contains(title_bar.panel))
+        .map(|(entity, title_bar)| (entity, title_bar.panel, PanelActionKind::Collapse));
+    let pressed = actions
+        .iter()
+        .map(|(entity, action)| (entity, action.panel, action.kind))
+        .chain(toggles)
+        .filter(|&(entity, ..)| interaction_state.just_pressed(entity));
+
+    for (_, panel, kind) in pressed {
+        let Ok((collapsible, panel_component, pinned)) = panels.get_mut(panel) else {
+            continue;
+        };
+        match kind {
+            PanelActionKind::Collapse => {
+                if let Some(collapsed) = toggle_collapsed(collapsible, panel_component) {
+                    toggled_events.send(PanelToggled { panel, collapsed });
+                }
+            }
+            PanelActionKind::Pin => {
+                if pinned {
+                    commands.entity(panel).remove::<PanelPinned>();
+                } else {
+                    commands.entity(panel).insert(PanelPinned);
+                }
+            }
+            PanelActionKind::Close => {
+                close_events.send(PanelCloseRequested { panel });
+            }
+        }
+    }
+}
+
+/// System to slide collapsible panel bodies open and closed
+///
+/// The panel's `max_height` is animated between its title bar and its full
+/// height, clipping the body on the way. Once closed, body children are
+/// removed from layout so they can't be focused.
+pub fn panel_collapse_system(
+    mut commands: Commands,
+    clock: Res<UiClock>,
+    motion: UiMotion,
+    mut panels: Query<
+        (
+            Entity,
+            Option<&Collapsible>,
+            Option<&Panel>,
+            &Children,
+            &mut Style,
+            Option<&mut PanelOpenness>,
+        ),
+        (
+            Or<(With<Collapsible>, With<Panel>)>,
+            Without<AccordionSection>,
+        ),
+    >,
+    nodes: Query<(&Node, Has<PanelTitleBar>)>,
+) {
+    let blend = if motion.animations() {
+        1.0 - (-PANEL_ANIMATION_SPEED * clock.delta_seconds()).exp()
+    } else {
+        1.0
+    };
+
+    for (entity, collapsible, panel, children, mut style, openness) in &mut panels {
+        let target = if is_collapsed(collapsible, panel) {
+            0.0
+        } else {
+            1.0
+        };
+        let Some(mut openness) = openness else {
+            commands.entity(entity).insert(PanelOpenness {
+                openness: target,
+                max_height: style.max_height,
+                overflow: style.overflow,
+            });
+            continue;
+        };
+        if openness.openness == target {
+            continue;
+        }
+
+        openness.openness += (target - openness.openness) * blend;
+        if (openness.openness - target).abs() < 0.01 {
+            openness.openness = target;
+        }
+        // At either end the body is fully shown or out of layout, so no clipping is needed
+        if openness.openness == target {
+            style.max_height = openness.max_height;
+            style.overflow = openness.overflow;
+            continue;
+        }
+
+        let (mut header, mut body) = (0.0, 0.0);
+        for (node, is_title) in nodes.iter_many(children) {
+            if is_title {
+                header += node.size().y;
+            } else {
+                body += node.size().y;
+            }
+        }
+        style.max_height = Val::Px(header + body * openness.openness);
+        style.overflow = Overflow::clip();
+    }
+}
+
+/// System to show and hide panel bodies and rotate collapse chevrons
+pub fn panel_body_system(
+    panels: Query<(Entity, &PanelOpenness, &Children), Changed<PanelOpenness>>,
+    mut styles: Query<&mut Style, Without<PanelTitleBar>>,
+    actions: Query<(&PanelAction, &Children)>,
+    mut transforms: Query<&mut Transform, With<Text>>,
+) {
+    for (panel, openness, children) in &panels {
+        let display = if openness.openness == 0.0 {
+            Display::None
+        } else {
+            Display::Flex
+        };
+        let mut panel_children = styles.iter_many_mut(children);
+        while let Some(mut style) = panel_children.fetch_next() {
+            if style.display != display {
+                style.display = display;
+            }
+        }
+
+        // The chevron points down when open and right when closed
+        let rotation = Quat::from_rotation_z(-FRAC_PI_2 * (1.0 - openness.openness));
+        for (action, button_children) in &actions {
+            if action.panel != panel || action.kind != PanelActionKind::Collapse {
+                continue;
+            }
+            let mut glyphs = transforms.iter_many_mut(button_children);
+            while let Some(mut transform) = glyphs.fetch_next() {
+                transform.rotation = rotation;
+            }
+        }
+    }
+}
+
+/// System to keep pin button glyphs in sync with [`PanelPinned`]
+pub fn panel_pin_system(
+    pinned: Query<Entity, Added<PanelPinned>>,
+    mut unpinned: RemovedComponents<PanelPinned>,
+    is_pinned: Query<(), With<PanelPinned>>,
+    actions: Query<(&PanelAction, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    let changed: Vec<_> = pinned.iter().chain(unpinned.read()).collect();
+    for panel in changed {
+        for (action, button_children) in &actions {
+            if action.panel != panel || action.kind != PanelActionKind::Pin {
+                continue;
+            }
+            let text = glyph(PanelActionKind::Pin, is_pinned.contains(panel));
+            let mut glyphs = texts.iter_many_mut(button_children);
+            while let Some(mut glyph) = glyphs.fetch_next() {
+                first_section(&mut glyph).value = text.to_string();
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<PanelTitleBar>()
+        .register_type::<PanelAction>()
+        .register_type::<PanelPinned>()
+        .register_type::<PanelOpenness>()
+        .register_type::<Collapsible>()
+        .register_type::<Panel>()
+        .add_event::<PanelCloseRequested>()
+        .add_event::<PanelToggled>()
+        .add_systems(
+            Update,
+            (
+                panel_action_system,
+                panel_collapse_system,
+                panel_body_system,
+                panel_pin_system,
+            )
+                .chain(),
+        );
+}
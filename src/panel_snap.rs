@@ -0,0 +1,491 @@
+//! Dragging floating panels, with snapping
+//!
+//! A [`FloatingPanel`] is moved by dragging its [`PanelTitleBar`], or the
+//! panel itself when it has none. While it moves, its edges snap to the
+//! screen's [`SafeArea`], to the edges of other floating panels and to an
+//! optional grid, all configured in [`PanelSnapSettings`]. A snapped edge
+//! holds on until the cursor pulls [`resistance`](PanelSnapSettings::resistance)
+//! pixels past the snap distance, and thin guide lines show which edges
+//! snapped. [`PanelMoved`] is sent when the panel is let go, for games that
+//! remember panel layouts.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{panel_title_bar, FloatingPanel, PanelSnapSettings, Theme};
+//! fn setup(mut commands: Commands, theme: Res<Theme>) {
+//!     commands
+//!         .spawn((
+//!             NodeBundle {
+//!                 style: Style {
+//!                     position_type: PositionType::Absolute,
+//!                     left: Val::Px(40.0),
+//!                     top: Val::Px(80.0),
+//!                     width: Val::Px(260.0),
+//!                     flex_direction: FlexDirection::Column,
+//!                     ..default()
+//!                 },
+//!                 ..default()
+//!             },
+//!             FloatingPanel::default(),
+//!         ))
+//!         .with_children(|panel| {
+//!             panel_title_bar("Army", &theme).spawn(panel);
+//!         });
+//! }
+//!
+//! fn snap_to_grid(mut settings: ResMut<PanelSnapSettings>) {
+//!     settings.grid = Some(16.0);
+//! }
+//! ```
+//!
+//! Panels are positioned with `left` and `top` in pixels and should be
+//! absolutely positioned in a full-screen parent, or be roots themselves.
+//! Pressing the title bar of a floating panel drags it rather than
+//! collapsing it; its collapse button still works. Snapping is turned off
+//! from the interface settings with
+//! [`InterfaceSettings::panel_snapping`](crate::InterfaceSettings::panel_snapping).
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::interaction::{PointerCapture, UiInteractionState};
+use crate::panel::PanelTitleBar;
+use crate::theme::Theme;
+use crate::utils::{SafeArea, ScreenAnchor};
+
+/// Thickness of the snap guide lines in pixels
+const GUIDE_THICKNESS: f32 = 1.0;
+
+/// Draws snap guides above the panels
+const GUIDE_Z_INDEX: i32 = 450;
+
+/// A panel the player can move by dragging its title bar
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct FloatingPanel {
+    /// Whether this panel snaps while dragged, and other panels snap to it
+    pub snap: bool,
+}
+
+impl Default for FloatingPanel {
+    fn default() -> Self {
+        Self { snap: true }
+    }
+}
+
+/// How dragged [`FloatingPanel`]s snap
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct PanelSnapSettings {
+    /// Whether panels snap at all
+    pub enabled: bool,
+    /// Pixels from an edge within which a panel edge snaps to it
+    pub distance: f32,
+    /// Extra pixels the cursor pulls past [`distance`](Self::distance)
+    /// before a snapped edge lets go
+    pub resistance: f32,
+    /// Whether panels snap to the edges of the [`SafeArea`]
+    pub screen_edges: bool,
+    /// Whether panels snap to the edges of other floating panels
+    pub panel_edges: bool,
+    /// Grid spacing the panel's top-left corner snaps to, if any
+    pub grid: Option<f32>,
+    /// Whether guide lines show the snapped edges
+    pub guides: bool,
+}
+
+impl Default for PanelSnapSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            distance: 10.0,
+            resistance: 12.0,
+            screen_edges: true,
+            panel_edges: true,
+            grid: None,
+            guides: true,
+        }
+    }
+}
+
+/// Sent when the player lets go of a dragged panel
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct PanelMoved {
+    /// The panel that moved
+    pub panel: Entity,
+    /// Its new `left` and `top` in pixels
+    pub position: Vec2,
+}
+
+/// A guide line showing an edge a dragged panel snapped to
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct PanelSnapGuide {
+    /// Whether the line is vertical, for a horizontal snap
+    pub vertical: bool,
+}
+
+/// An edge of the dragged panel held to a line on one axis
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AxisSnap {
+    /// Position of the line
+    line: f32,
+    /// Whether the panel's far edge (right or bottom) is on the line
+    far_edge: bool,
+    /// Whether the line gets a guide, which grid lines don't
+    guide: bool,
+}
+
+/// A panel being dragged
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PanelDrag {
+    panel: Entity,
+    /// The title bar or panel that was pressed, holding the pointer capture
+    handle: Entity,
+    /// Cursor position relative to the panel's top-left corner
+    grab_offset: Vec2,
+    /// The panel's top-left corner when picked up
+    rect_origin: Vec2,
+    /// Its `left` and `top` when picked up
+    style_origin: Vec2,
+    /// Current snaps on the horizontal and vertical axis
+    snaps: [Option<AxisSnap>; 2],
+}
+
+/// The panel drag in progress, if any
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct PanelDragState {
+    active: Option<PanelDrag>,
+}
+
+impl PanelDragState {
+    /// The panel being dragged
+    pub fn panel(&self) -> Option<Entity> {
+        self.active.map(|drag| drag.panel)
+    }
+
+    /// Positions of the lines the dragged panel snapped to, horizontally
+    /// and vertically, leaving out grid lines
+    pub fn guides(&self) -> [Option<f32>; 2] {
+        let snaps = self.active.map(|drag| drag.snaps).unwrap_or_default();
+        snaps.map(|snap| snap.filter(|snap| snap.guide).map(|snap| snap.line))
+    }
+}
+
+/// Moves a span starting at `min` onto the nearest line within reach
+///
+/// A `held` snap is kept while within `distance + resistance`; otherwise
+/// the nearest edge line, or grid line for the near edge, within `distance`
+/// wins. Returns the new start and the snap applied.
+fn snap_axis(
+    min: f32,
+    size: f32,
+    lines: &[f32],
+    held: Option<AxisSnap>,
+    settings: &PanelSnapSettings,
+) -> (f32, Option<AxisSnap>) {
+    let edge = |far_edge: bool| if far_edge { min + size } else { min };
+    let place = |snap: AxisSnap| {
+        if snap.far_edge {
+            snap.line - size
+        } else {
+            snap.line
+        }
+    };
+
+    if let Some(held) = held {
+        if (held.line - edge(held.far_edge)).abs() <= settings.distance + settings.resistance {
+            return (place(held), Some(held));
+        }
+    }
+    let edges = lines.iter().flat_map(|&line| {
+        [false, true].map(|far_edge| AxisSnap {
+            line,
+            far_edge,
+            guide: true,
+        })
+    });
+    let grid = settings
+        .grid
+        .filter(|&spacing| spacing > 0.0)
+        .map(|spacing| AxisSnap {
+            line: (min / spacing).round() * spacing,
+            far_edge: false,
+            guide: false,
+        });
+    let best = edges
+        .chain(grid)
+        .map(|snap| (snap, (snap.line - edge(snap.far_edge)).abs()))
+        .filter(|&(_, offset)| offset <= settings.distance)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(snap, _)| snap);
+    match best {
+        Some(snap) => (place(snap), Some(snap)),
+        None => (min, None),
+    }
+}
+
+/// Makes the title bars of floating panels pressable
+pub fn floating_panel_title_bar_system(
+    mut commands: Commands,
+    title_bars: Query<(Entity, &PanelTitleBar), Without<Interaction>>,
+    panels: Query<(), With<FloatingPanel>>,
+) {
+    for (entity, title_bar) in &title_bars {
+        if panels.contains(title_bar.panel) {
+            commands.entity(entity).insert(Interaction::default());
+        }
+    }
+}
+
+/// System to pick up a floating panel pressed on its title bar
+pub fn panel_drag_start_system(
+    mut commands: Commands,
+    mut state: ResMut<PanelDragState>,
+    mut panels: Query<(Entity, &Node, &GlobalTransform, &mut Style), With<FloatingPanel>>,
+    title_bars: Query<(Entity, &PanelTitleBar)>,
+    (interaction_state, mut capture): (Res<UiInteractionState>, ResMut<PointerCapture>),
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+) {
+    if state.active.is_some() {
+        return;
+    }
+    let Some(cursor) = windows
+        .get_single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .map(|cursor| cursor / ui_scale.0)
+    else {
+        return;
+    };
+    let handles = title_bars
+        .iter()
+        .map(|(entity, title_bar)| (entity, title_bar.panel))
+        .chain(panels.iter().map(|(entity, ..)| (entity, entity)));
+    let Some((handle, panel)) = handles
+        .filter(|&(handle, _)| interaction_state.just_pointer_pressed(handle))
+        .last()
+    else {
+        return;
+    };
+    let Ok((_, node, transform, mut style)) = panels.get_mut(panel) else {
+        return;
+    };
+
+    let rect = node.logical_rect(transform);
+    let px = |val: Val| if let Val::Px(px) = val { px } else { 0.0 };
+    // An anchored panel keeps the place it is dragged to
+    commands.entity(panel).remove::<ScreenAnchor>();
+    style.right = Val::Auto;
+    style.bottom = Val::Auto;
+    capture.capture(handle);
+    state.active = Some(PanelDrag {
+        panel,
+        handle,
+        grab_offset: cursor - rect.min,
+        rect_origin: rect.min,
+        style_origin: Vec2::new(px(style.left), px(style.top)),
+        snaps: [None; 2],
+    });
+}
+
+/// System to move the dragged panel with the cursor, snapping its edges
+pub fn panel_drag_move_system(
+    mut state: ResMut<PanelDragState>,
+    settings: Res<PanelSnapSettings>,
+    mut panels: Query<(
+        Entity,
+        &FloatingPanel,
+        &Node,
+        &GlobalTransform,
+        &mut Style,
+        &ViewVisibility,
+    )>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    (safe_area, ui_scale): (Res<SafeArea>, Res<UiScale>),
+) {
+    let Some(drag) = state.active else {
+        return;
+    };
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position().map(|cursor| cursor / ui_scale.0) else {
+        return;
+    };
+    let Ok((_, floating, node, ..)) = panels.get(drag.panel) else {
+        return;
+    };
+    let size = node.size();
+    let mut min = cursor - drag.grab_offset;
+    let mut snaps = [None; 2];
+
+    if settings.enabled && floating.snap {
+        let mut lines: [Vec<f32>; 2] = default();
+        if settings.screen_edges {
+            let bounds = Vec2::new(window.width(), window.height()) / ui_scale.0;
+            let safe = safe_area.rect(bounds);
+            lines[0].extend([safe.min.x, safe.max.x]);
+            lines[1].extend([safe.min.y, safe.max.y]);
+        }
+        if settings.panel_edges {
+            let others = panels
+                .iter()
+                .filter(|(entity, floating, .., visibility)| {
+                    *entity != drag.panel && floating.snap && visibility.get()
+                })
+                .map(|(_, _, node, transform, ..)| node.logical_rect(transform));
+            for other in others {
+                lines[0].extend([other.min.x, other.max.x]);
+                lines[1].extend([other.min.y, other.max.y]);
+            }
+        }
+        for axis in 0..2 {
+            (min[axis], snaps[axis]) = snap_axis(
+                min[axis],
+                size[axis],
+                &lines[axis],
+                drag.snaps[axis],
+                &settings,
+            );
+        }
+    }
+
+    let position = drag.style_origin + (min - drag.rect_origin);
+    if let Ok((.., mut style, _)) = panels.get_mut(drag.panel) {
+        if style.left != Val::Px(position.x) || style.top != Val::Px(position.y) {
+            style.left = Val::Px(position.x);
+            style.top = Val::Px(position.y);
+        }
+    }
+    if drag.snaps != snaps {
+        if let Some(drag) = state.active.as_mut() {
+            drag.snaps = snaps;
+        }
+    }
+}
+
+/// System to let go of the dragged panel on mouse-up, or when it is gone
+pub fn panel_drag_end_system(
+    mut state: ResMut<PanelDragState>,
+    panels: Query<&Style, With<FloatingPanel>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut capture: ResMut<PointerCapture>,
+    mut moved_events: EventWriter<PanelMoved>,
+) {
+    let Some(drag) = state.active else {
+        return;
+    };
+    let style = panels.get(drag.panel);
+    if mouse_buttons.pressed(MouseButton::Left) && style.is_ok() {
+        return;
+    }
+    if let Ok(style) = style {
+        if let (Val::Px(left), Val::Px(top)) = (style.left, style.top) {
+            moved_events.send(PanelMoved {
+                panel: drag.panel,
+                position: Vec2::new(left, top),
+            });
+        }
+    }
+    if capture.is_captured(drag.handle) {
+        capture.release();
+    }
+    state.active = None;
+}
+
+/// System to show guide lines along the edges the dragged panel snapped to
+pub fn panel_snap_guide_system(
+    mut commands: Commands,
+    state: Res<PanelDragState>,
+    settings: Res<PanelSnapSettings>,
+    theme: Res<Theme>,
+    mut guides: Query<(Entity, &PanelSnapGuide, &mut Style, &mut Visibility)>,
+) {
+    if !state.is_changed() && !settings.is_changed() {
+        return;
+    }
+    let lines = if settings.guides {
+        state.guides()
+    } else {
+        [None; 2]
+    };
+    if state.panel().is_none() {
+        for (entity, ..) in &guides {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    for (vertical, line) in [(true, lines[0]), (false, lines[1])] {
+        let existing = guides
+            .iter_mut()
+            .find(|(_, guide, ..)| guide.vertical == vertical);
+        let Some(line) = line else {
+            if let Some((.., mut visibility)) = existing {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+            continue;
+        };
+        let style = if vertical {
+            Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(line - GUIDE_THICKNESS / 2.0),
+                top: Val::Px(0.0),
+                width: Val::Px(GUIDE_THICKNESS),
+                height: Val::Percent(100.0),
+                ..default()
+            }
+        } else {
+            Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(line - GUIDE_THICKNESS / 2.0),
+                width: Val::Percent(100.0),
+                height: Val::Px(GUIDE_THICKNESS),
+                ..default()
+            }
+        };
+        match existing {
+            Some((_, _, mut guide_style, mut visibility)) => {
+                if *guide_style != style {
+                    *guide_style = style;
+                }
+                visibility.set_if_neq(Visibility::Visible);
+            }
+            None => {
+                commands.spawn((
+                    NodeBundle {
+                        style,
+                        background_color: theme.focus.border.into(),
+                        z_index: ZIndex::Global(GUIDE_Z_INDEX),
+                        ..default()
+                    },
+                    PanelSnapGuide { vertical },
+                    Name::new("PanelSnapGuide"),
+                ));
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<FloatingPanel>()
+        .register_type::<PanelSnapSettings>()
+        .register_type::<PanelSnapGuide>()
+        .init_resource::<PanelSnapSettings>()
+        .init_resource::<PanelDragState>()
+        .add_event::<PanelMoved>()
+        .add_systems(
+            Update,
+            (
+                floating_panel_title_bar_system,
+                panel_drag_start_system,
+                panel_drag_move_system,
+                panel_drag_end_system,
+                panel_snap_guide_system,
+            )
+                .chain(),
+        );
+}
@@ -0,0 +1,120 @@
+//! `SystemParam` helpers for reading and driving widgets by [`UiId`]
+//!
+//! These wrap the queries gameplay systems would otherwise write by hand:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::params::{Buttons, Sliders};
+//! fn apply_settings(buttons: Buttons, mut sliders: Sliders) {
+//!     if buttons.is_clicked("reset_audio") {
+//!         sliders.set_value("master_volume", 0.8);
+//!     }
+//! }
+//! ```
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::components::{UiButton, UiCheckbox, UiId, UiSlider};
+use crate::interaction::UiInteractionState;
+
+/// Read access to buttons by [`UiId`]
+#[derive(SystemParam)]
+pub struct Buttons<'w, 's> {
+    buttons: Query<'w, 's, (Entity, &'static UiId, Option<&'static UiButton>), With<Button>>,
+    interaction_state: Res<'w, UiInteractionState>,
+}
+
+impl<'w, 's> Buttons<'w, 's> {
+    /// Returns the entity of the button with the given id
+    pub fn entity(&self, id: &str) -> Option<Entity> {
+        self.buttons
+            .iter()
+            .find(|(_, ui_id, _)| ui_id.0 == id)
+            .map(|(entity, _, _)| entity)
+    }
+
+    /// Whether the button with the given id was clicked this frame
+    pub fn is_clicked(&self, id: &str) -> bool {
+        self.buttons.iter().any(|(entity, ui_id, button)| {
+            ui_id.0 == id
+                && !button.is_some_and(|button| button.disabled)
+                && self.interaction_state.just_pressed(entity)
+        })
+    }
+
+    /// Whether the button with the given id is hovered this frame
+    pub fn is_hovered(&self, id: &str) -> bool {
+        self.entity(id)
+            .is_some_and(|entity| self.interaction_state.is_hovered(entity))
+    }
+}
+
+/// Read/write access to sliders by [`UiId`]
+#[derive(SystemParam)]
+pub struct Sliders<'w, 's> {
+    sliders: Query<'w, 's, (Entity, &'static UiId, &'static mut UiSlider)>,
+}
+
+impl<'w, 's> Sliders<'w, 's> {
+    /// Returns the entity of the slider with the given id
+    pub fn entity(&self, id: &str) -> Option<Entity> {
+        self.sliders
+            .iter()
+            .find(|(_, ui_id, _)| ui_id.0 == id)
+            .map(|(entity, _, _)| entity)
+    }
+
+    /// Returns the current value of the slider with the given id
+    pub fn value(&self, id: &str) -> Option<f32> {
+        self.sliders
+            .iter()
+            .find(|(_, ui_id, _)| ui_id.0 == id)
+            .map(|(_, _, slider)| slider.value)
+    }
+
+    /// Sets the value of the slider with the given id, snapped to its range and step
+    ///
+    /// Returns `false` if no slider has that id.
+    pub fn set_value(&mut self, id: &str, value: f32) -> bool {
+        let Some((_, _, mut slider)) = self.sliders.iter_mut().find(|(_, ui_id, _)| ui_id.0 == id)
+        else {
+            return false;
+        };
+        let value = slider.snap(value);
+        if slider.value != value {
+            slider.value = value;
+        }
+        true
+    }
+}
+
+/// Read/write access to checkboxes by [`UiId`]
+#[derive(SystemParam)]
+pub struct Checkboxes<'w, 's> {
+    checkboxes: Query<'w, 's, (&'static UiId, &'static mut UiCheckbox)>,
+}
+
+impl<'w, 's> Checkboxes<'w, 's> {
+    /// Returns whether the checkbox with the given id is checked
+    pub fn is_checked(&self, id: &str) -> Option<bool> {
+        self.checkboxes
+            .iter()
+            .find(|(ui_id, _)| ui_id.0 == id)
+            .map(|(_, checkbox)| checkbox.checked)
+    }
+
+    /// Sets the checked state of the checkbox with the given id
+    ///
+    /// Returns `false` if no checkbox has that id.
+    pub fn set_checked(&mut self, id: &str, checked: bool) -> bool {
+        let Some((_, mut checkbox)) = self.checkboxes.iter_mut().find(|(ui_id, _)| ui_id.0 == id)
+        else {
+            return false;
+        };
+        if checkbox.checked != checked {
+            checkbox.checked = checked;
+        }
+        true
+    }
+}
@@ -0,0 +1,509 @@
+//! Apply and revert for bound settings
+//!
+//! Widgets with a [`Bind<R>`] write straight into the settings resource `R`,
+//! which is what a game previews while the player edits. [`AppliedSettings<R>`]
+//! keeps a copy of the values last applied, and lists the bound fields that
+//! differ from it. A [`PendingChangesBanner<R>`] shows while there are such
+//! fields, with their count and Apply and Revert buttons: Apply takes the
+//! current values as the applied ones and sends [`SettingsApplied<R>`], for
+//! example to save them; Revert restores the applied values, which moves the
+//! bound widgets back, and sends [`SettingsReverted<R>`].
+//!
+//! [`BindPlugin<R>`] runs all of this, and
+//! [`SettingsMenuBuilder::spawn_bound`](crate::SettingsMenuBuilder::spawn_bound)
+//! adds a banner to a settings screen. A banner can also go anywhere else:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{pending_changes_banner_bundle, BindPlugin};
+//! #[derive(Resource, Reflect, Default)]
+//! struct VideoSettings {
+//!     vsync: bool,
+//! }
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn(pending_changes_banner_bundle::<VideoSettings>());
+//! }
+//!
+//! # let mut app = App::new();
+//! app.init_resource::<VideoSettings>()
+//!     .add_plugins(BindPlugin::<VideoSettings>::default());
+//! ```
+//!
+//! Settings that can leave the game unusable, such as a display mode the
+//! monitor can't show, ask to be kept after Apply. With
+//! [`BindPlugin::confirm_apply`](crate::BindPlugin::confirm_apply), Apply
+//! sends [`SettingsApplied<R>`] so the game can switch to the new values,
+//! then opens a "Keep these settings?" modal that counts down. Keep sends
+//! [`SettingsConfirmed<R>`]; Revert, Escape or running out of time restore
+//! the values applied before and send [`SettingsReverted<R>`]:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{BindPlugin, SettingsConfirmed};
+//! # #[derive(Resource, Reflect, Default)]
+//! # struct VideoSettings {
+//! #     vsync: bool,
+//! # }
+//! fn save_video(mut confirmed: EventReader<SettingsConfirmed<VideoSettings>>) {
+//!     for _ in confirmed.read() {
+//!         // write the settings file
+//!     }
+//! }
+//!
+//! # let mut app = App::new();
+//! app.add_plugins(BindPlugin::<VideoSettings>::default().confirm_apply(10.0))
+//!     .add_systems(Update, save_video);
+//! ```
+//!
+//! A game applying settings without a banner can insert a
+//! [`PendingConfirmation<R>`] itself to get the same countdown.
+//!
+//! [`Bind<R>`]: crate::Bind
+//! [`BindPlugin<R>`]: crate::BindPlugin
+
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy::reflect::GetPath;
+
+use crate::assets::UiFonts;
+use crate::binding::Bind;
+use crate::builders::UiButtonBuilder;
+use crate::clock::UiClock;
+use crate::interaction::UiInteractionState;
+use crate::modal::{
+    spawn_modal, ModalBody, ModalButton, ModalConfig, ModalDialog, ModalResponse, ModalResult,
+    ModalState,
+};
+use crate::theme::Theme;
+
+/// The values of the settings resource `R` as last applied
+///
+/// Inserted by [`BindPlugin<R>`](crate::BindPlugin) from the resource's
+/// value on the first frame it exists.
+#[derive(Resource)]
+pub struct AppliedSettings<R: Resource + Reflect> {
+    value: Box<dyn Reflect>,
+    pending: Vec<String>,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<R: Resource + Reflect> AppliedSettings<R> {
+    /// Takes `settings` as the applied values
+    pub fn new(settings: &R) -> Self {
+        Self {
+            value: settings.clone_value(),
+            pending: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Paths of the bound fields whose value differs from the applied one
+    pub fn pending(&self) -> &[String] {
+        &self.pending
+    }
+
+    /// Whether any bound field differs from its applied value
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// The applied value of the field at `path`
+    pub fn field(&self, path: &str) -> Option<&dyn Reflect> {
+        self.value.reflect_path(path).ok()
+    }
+
+    /// Takes the current `settings` as the applied values
+    pub fn apply(&mut self, settings: &R) {
+        self.value = settings.clone_value();
+        self.pending.clear();
+    }
+
+    /// Restores the applied values into `settings`
+    pub fn revert(&self, settings: &mut R) {
+        settings.apply(self.value.as_ref());
+    }
+}
+
+/// Sent when the player applies pending changes to `R`
+#[derive(Event)]
+pub struct SettingsApplied<R: Resource>(PhantomData<fn() -> R>);
+
+impl<R: Resource> Default for SettingsApplied<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Sent when the player reverts pending changes to `R`
+#[derive(Event)]
+pub struct SettingsReverted<R: Resource>(PhantomData<fn() -> R>);
+
+impl<R: Resource> Default for SettingsReverted<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Sent when the player keeps values of `R` awaiting a
+/// [`PendingConfirmation<R>`]
+#[derive(Event)]
+pub struct SettingsConfirmed<R: Resource>(PhantomData<fn() -> R>);
+
+impl<R: Resource> Default for SettingsConfirmed<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Makes Apply on a [`PendingChangesBanner<R>`] wait for the player to keep
+/// the new values
+///
+/// Inserted by [`BindPlugin::confirm_apply`](crate::BindPlugin::confirm_apply);
+/// removing it applies without asking again.
+#[derive(Resource)]
+pub struct ConfirmApply<R: Resource> {
+    /// Seconds before the values are reverted
+    pub seconds: f32,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<R: Resource> ConfirmApply<R> {
+    /// Reverts after `seconds`
+    pub fn new(seconds: f32) -> Self {
+        Self {
+            seconds,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Newly applied values of `R` waiting for the player to keep them
+///
+/// While this resource exists, a modal counts down the seconds left. Keep
+/// removes it and sends [`SettingsConfirmed<R>`]; Revert, Escape, closing
+/// the modal or the countdown reaching zero restore the previous values into
+/// `R` and [`AppliedSettings<R>`] and send [`SettingsReverted<R>`].
+#[derive(Resource)]
+pub struct PendingConfirmation<R: Resource + Reflect> {
+    previous: Box<dyn Reflect>,
+    remaining: f32,
+    modal: Option<Entity>,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<R: Resource + Reflect> PendingConfirmation<R> {
+    /// Waits `seconds` for the player to keep the current values of `R`,
+    /// restoring `previous` otherwise
+    pub fn new(previous: &R, seconds: f32) -> Self {
+        Self::from_value(previous.clone_value(), seconds)
+    }
+
+    fn from_value(previous: Box<dyn Reflect>, seconds: f32) -> Self {
+        Self {
+            previous,
+            remaining: seconds,
+            modal: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Seconds left before the previous values are restored
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    /// The countdown modal, once spawned
+    pub fn modal(&self) -> Option<Entity> {
+        self.modal
+    }
+}
+
+/// A bar listing pending changes to `R`, hidden while there are none
+#[derive(Component)]
+pub struct PendingChangesBanner<R: Resource>(PhantomData<fn() -> R>);
+
+impl<R: Resource> Default for PendingChangesBanner<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// What a [`PendingChangesPart`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum PendingChangesPartKind {
+    /// The pending change count
+    Label,
+    /// The Apply button
+    Apply,
+    /// The Revert button
+    Revert,
+}
+
+/// A node spawned inside a [`PendingChangesBanner`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct PendingChangesPart {
+    /// The banner this node belongs to
+    pub banner: Entity,
+    /// What this node is
+    pub kind: PendingChangesPartKind,
+}
+
+/// Creates the container bundle for a pending changes banner
+pub fn pending_changes_banner_bundle<R: Resource>() -> (NodeBundle, PendingChangesBanner<R>) {
+    (
+        NodeBundle {
+            style: Style {
+                display: Display::None,
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            background_color: Color::srgba(0.2, 0.2, 0.2, 0.9).into(),
+            ..default()
+        },
+        PendingChangesBanner::default(),
+    )
+}
+
+/// Text of the banner label for `count` pending changes
+fn pending_label(count: usize) -> String {
+    match count {
+        1 => "1 pending change".to_string(),
+        count => format!("{count} pending changes"),
+    }
+}
+
+/// System to list the bound fields of `R` that differ from their applied values
+pub fn pending_changes_system<R: Resource + Reflect>(
+    mut commands: Commands,
+    settings: Res<R>,
+    applied: Option<ResMut<AppliedSettings<R>>>,
+    binds: Query<&Bind<R>>,
+) {
+    let Some(mut applied) = applied else {
+        commands.insert_resource(AppliedSettings::new(settings.as_ref()));
+        return;
+    };
+    let mut pending: Vec<String> = Vec::new();
+    for bind in &binds {
+        if pending.contains(&bind.path) {
+            continue;
+        }
+        let current = settings.reflect_path(bind.path.as_str()).ok();
+        let differs = match (current, applied.field(&bind.path)) {
+            (Some(current), Some(applied)) => current.reflect_partial_eq(applied) != Some(true),
+            _ => false,
+        };
+        if differs {
+            pending.push(bind.path.clone());
+        }
+    }
+    if applied.pending != pending {
+        applied.pending = pending;
+    }
+}
+
+/// System to spawn the label and buttons of new pending changes banners
+pub fn pending_changes_banner_spawn_system<R: Resource>(
+    mut commands: Commands,
+    banners: Query<Entity, Added<PendingChangesBanner<R>>>,
+    theme: Res<Theme>,
+) {
+    for banner in &banners {
+        let label = commands
+            .spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: UiFonts::MEDIUM,
+                        font_size: 18.0,
+                        color: theme.text.normal,
+                    },
+                )
+                .with_style(Style {
+                    flex_grow: 1.0,
+                    ..default()
+                }),
+                PendingChangesPart {
+                    banner,
+                    kind: PendingChangesPartKind::Label,
+                },
+            ))
+            .id();
+        let mut button = |text: &str, kind| {
+            let button = UiButtonBuilder::new(&theme)
+                .label(text)
+                .spawn(&mut commands);
+            commands
+                .entity(button)
+                .insert(PendingChangesPart { banner, kind });
+            button
+        };
+        let revert = button("Revert", PendingChangesPartKind::Revert);
+        let apply = button("Apply", PendingChangesPartKind::Apply);
+        commands
+            .entity(banner)
+            .push_children(&[label, revert, apply]);
+    }
+}
+
+/// System to show banners while `R` has pending changes, with their count
+pub fn pending_changes_banner_system<R: Resource + Reflect>(
+    applied: Option<Res<AppliedSettings<R>>>,
+    mut banners: Query<(Entity, &mut Style), With<PendingChangesBanner<R>>>,
+    mut labels: Query<(&PendingChangesPart, &mut Text)>,
+) {
+    let count = applied.map_or(0, |applied| applied.pending().len());
+    let display = if count > 0 {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for (banner, mut style) in &mut banners {
+        if style.display != display {
+            style.display = display;
+        }
+        let label = pending_label(count);
+        for (part, mut text) in &mut labels {
+            let Some(section) = text.sections.first_mut() else {
+                continue;
+            };
+            if part.banner == banner
+                && part.kind == PendingChangesPartKind::Label
+                && section.value != label
+            {
+                section.value.clone_from(&label);
+            }
+        }
+    }
+}
+
+/// System to apply or revert `R` when a banner button is pressed
+///
+/// With [`ConfirmApply<R>`], Apply also starts a [`PendingConfirmation<R>`]
+/// holding the values applied before.
+pub fn pending_changes_input_system<R: Resource + Reflect>(
+    (mut commands, confirm): (Commands, Option<Res<ConfirmApply<R>>>),
+    mut settings: ResMut<R>,
+    applied: Option<ResMut<AppliedSettings<R>>>,
+    parts: Query<(Entity, &PendingChangesPart)>,
+    banners: Query<(), With<PendingChangesBanner<R>>>,
+    interaction_state: Res<UiInteractionState>,
+    (mut applied_events, mut reverted_events): (
+        EventWriter<SettingsApplied<R>>,
+        EventWriter<SettingsReverted<R>>,
+    ),
+) {
+    let Some(mut applied) = applied else {
+        return;
+    };
+    for (entity, part) in &parts {
+        if !banners.contains(part.banner) || !interaction_state.just_pressed(entity) {
+            continue;
+        }
+        match part.kind {
+            PendingChangesPartKind::Apply => {
+                if let Some(confirm) = &confirm {
+                    commands.insert_resource(PendingConfirmation::<R>::from_value(
+                        applied.value.clone_value(),
+                        confirm.seconds,
+                    ));
+                }
+                applied.apply(settings.as_ref());
+                applied_events.send_default();
+            }
+            PendingChangesPartKind::Revert => {
+                applied.revert(settings.as_mut());
+                reverted_events.send_default();
+            }
+            PendingChangesPartKind::Label => {}
+        }
+    }
+}
+
+/// Body text of the confirmation modal with `remaining` seconds left
+fn confirmation_body(remaining: f32) -> String {
+    match remaining.ceil().max(0.0) as u32 {
+        1 => "Reverting in 1 second".to_string(),
+        seconds => format!("Reverting in {seconds} seconds"),
+    }
+}
+
+/// System to count down a [`PendingConfirmation<R>`] in its modal and keep
+/// or restore the values of `R` once it is answered or runs out
+pub fn pending_confirmation_system<R: Resource + Reflect>(
+    mut commands: Commands,
+    pending: Option<ResMut<PendingConfirmation<R>>>,
+    (mut settings, applied): (ResMut<R>, Option<ResMut<AppliedSettings<R>>>),
+    (theme, clock): (Res<Theme>, Res<UiClock>),
+    (mut modals, mut bodies): (Query<&mut ModalDialog>, Query<(&ModalBody, &mut Text)>),
+    mut results: EventReader<ModalResult>,
+    (mut confirmed_events, mut reverted_events): (
+        EventWriter<SettingsConfirmed<R>>,
+        EventWriter<SettingsReverted<R>>,
+    ),
+) {
+    let Some(mut pending) = pending else {
+        results.clear();
+        return;
+    };
+    let Some(modal) = pending.modal else {
+        let config = ModalConfig {
+            title: "Keep these settings?".to_string(),
+            body: confirmation_body(pending.remaining),
+            buttons: vec![
+                ModalButton::new("Revert", ModalResponse::Cancel),
+                ModalButton::new("Keep", ModalResponse::Ok),
+            ],
+        };
+        pending.modal = Some(spawn_modal(&mut commands, &theme, config));
+        return;
+    };
+
+    let response = results
+        .read()
+        .filter(|result| result.modal == modal)
+        .last()
+        .map(|result| result.response.clone());
+    pending.remaining -= clock.delta_seconds();
+    let keep = match response {
+        Some(response) => response == ModalResponse::Ok,
+        None => match modals.get_mut(modal) {
+            Ok(mut dialog) if pending.remaining <= 0.0 => {
+                if dialog.state.go(ModalState::Closed) {
+                    commands.entity(modal).despawn_recursive();
+                }
+                false
+            }
+            Ok(_) => {
+                let body = confirmation_body(pending.remaining);
+                for (part, mut text) in &mut bodies {
+                    let Some(section) = text.sections.first_mut() else {
+                        continue;
+                    };
+                    if part.modal == modal && section.value != body {
+                        section.value.clone_from(&body);
+                    }
+                }
+                return;
+            }
+            Err(_) => false,
+        },
+    };
+
+    commands.remove_resource::<PendingConfirmation<R>>();
+    if keep {
+        confirmed_events.send_default();
+    } else {
+        settings.apply(pending.previous.as_ref());
+        if let Some(mut applied) = applied {
+            applied.apply(settings.as_ref());
+        }
+        reverted_events.send_default();
+    }
+}
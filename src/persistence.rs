@@ -0,0 +1,178 @@
+//! Saving and restoring widget values across sessions
+//!
+//! A [`PersistentId`] names a slider, checkbox or dropdown whose value the
+//! game wants to keep. [`PersistentWidgets::snapshot`] collects the values
+//! of every named widget into a [`UiStateSnapshot`], which serializes with
+//! serde to any format, and [`PersistentWidgets::restore`] puts them back
+//! once the menu is spawned again:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{PersistentId, PersistentWidgets, SliderBuilder, Theme, UiStateSnapshot};
+//! fn setup(mut commands: Commands, theme: Res<Theme>) {
+//!     let slider = SliderBuilder::new(&theme).spawn(&mut commands);
+//!     commands.entity(slider).insert(PersistentId::new("music_volume"));
+//! }
+//!
+//! fn save_menu(widgets: PersistentWidgets) {
+//!     if let Ok(json) = serde_json::to_string(&widgets.snapshot()) {
+//!         let _ = std::fs::write("menu_state.json", json);
+//!     }
+//! }
+//!
+//! fn load_menu(mut widgets: PersistentWidgets) {
+//!     let saved = std::fs::read_to_string("menu_state.json").ok();
+//!     if let Some(snapshot) = saved.and_then(|json| serde_json::from_str(&json).ok()) {
+//!         widgets.restore(&snapshot);
+//!     }
+//! }
+//! ```
+//!
+//! Restored values go through the usual change detection, so widgets bound
+//! with [`Bind`](crate::Bind) write them on to their settings resource.
+//! Values whose widget is missing or of another kind are skipped, which
+//! keeps old save files loading after a menu changes.
+
+use std::collections::BTreeMap;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::binding::set_checked;
+use crate::components::{Dropdown, DropdownEntry, UiCheckbox, UiSlider};
+use crate::theme::Theme;
+
+/// Names a widget whose value is saved in a [`UiStateSnapshot`]
+///
+/// Goes on the widget entity itself, which for labeled builder widgets is
+/// the row's control child.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct PersistentId(pub String);
+
+impl PersistentId {
+    /// Creates an id
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+/// The saved value of one widget
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WidgetValue {
+    /// A slider's value
+    Slider(f32),
+    /// Whether a checkbox is checked
+    Checkbox(bool),
+    /// A dropdown's selected option index
+    Dropdown(usize),
+}
+
+/// Values of widgets carrying a [`PersistentId`], by id
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UiStateSnapshot {
+    /// Widget values, sorted by id so saved files stay stable
+    pub values: BTreeMap<String, WidgetValue>,
+}
+
+impl UiStateSnapshot {
+    /// The value saved for `id`
+    pub fn get(&self, id: &str) -> Option<WidgetValue> {
+        self.values.get(id).copied()
+    }
+
+    /// Saves `value` under `id`, replacing any earlier value
+    pub fn insert(&mut self, id: impl Into<String>, value: WidgetValue) {
+        self.values.insert(id.into(), value);
+    }
+
+    /// Keeps the values of `other` and adds those only this snapshot has,
+    /// such as widgets of a menu that isn't open
+    pub fn merge(&mut self, other: UiStateSnapshot) {
+        self.values.extend(other.values);
+    }
+}
+
+/// The widgets carrying a [`PersistentId`], for taking and restoring snapshots
+#[derive(SystemParam)]
+pub struct PersistentWidgets<'w, 's> {
+    sliders: Query<'w, 's, (&'static PersistentId, &'static mut UiSlider)>,
+    checkboxes: Query<
+        'w,
+        's,
+        (
+            &'static PersistentId,
+            &'static mut UiCheckbox,
+            &'static mut BackgroundColor,
+        ),
+    >,
+    dropdowns: Query<'w, 's, (&'static PersistentId, &'static mut Dropdown)>,
+    theme: Res<'w, Theme>,
+}
+
+impl<'w, 's> PersistentWidgets<'w, 's> {
+    /// Collects the current value of every widget with a [`PersistentId`]
+    pub fn snapshot(&self) -> UiStateSnapshot {
+        let sliders = self
+            .sliders
+            .iter()
+            .map(|(id, slider)| (id.0.clone(), WidgetValue::Slider(slider.value)));
+        let checkboxes = self
+            .checkboxes
+            .iter()
+            .map(|(id, checkbox, _)| (id.0.clone(), WidgetValue::Checkbox(checkbox.checked)));
+        let dropdowns = self
+            .dropdowns
+            .iter()
+            .map(|(id, dropdown)| (id.0.clone(), WidgetValue::Dropdown(dropdown.selected_index)));
+        UiStateSnapshot {
+            values: sliders.chain(checkboxes).chain(dropdowns).collect(),
+        }
+    }
+
+    /// Sets every widget with a [`PersistentId`] in `snapshot` to its saved
+    /// value, returning how many were restored
+    ///
+    /// Slider values are clamped to the slider's range, and dropdown indices
+    /// must point at a selectable option.
+    pub fn restore(&mut self, snapshot: &UiStateSnapshot) -> usize {
+        let mut restored = 0;
+        for (id, mut slider) in &mut self.sliders {
+            if let Some(WidgetValue::Slider(value)) = snapshot.get(&id.0) {
+                let value = value.clamp(slider.min, slider.max);
+                if slider.value != value {
+                    slider.value = value;
+                }
+                restored += 1;
+            }
+        }
+        for (id, mut checkbox, mut background) in &mut self.checkboxes {
+            if let Some(WidgetValue::Checkbox(checked)) = snapshot.get(&id.0) {
+                set_checked(&mut checkbox, &mut background, checked, &self.theme);
+                restored += 1;
+            }
+        }
+        for (id, mut dropdown) in &mut self.dropdowns {
+            let Some(WidgetValue::Dropdown(index)) = snapshot.get(&id.0) else {
+                continue;
+            };
+            if dropdown
+                .options
+                .get(index)
+                .is_some_and(DropdownEntry::is_selectable)
+            {
+                if dropdown.selected_index != index {
+                    dropdown.selected_index = index;
+                }
+                restored += 1;
+            }
+        }
+        restored
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<PersistentId>();
+}
@@ -0,0 +1,126 @@
+//! Keeping popups on the monitor they open on
+//!
+//! A window can span several monitors or hang partly off the edge of one, so
+//! staying inside the window isn't enough for a popup to be seen.
+//! [`PopupBounds`] holds the part of the primary window that each monitor
+//! shows, read from winit whenever the window moves or resizes. Tooltips,
+//! dropdown lists and menus look up the area under their anchor with
+//! [`PopupBounds::area_at`], open on the side of the anchor that has room,
+//! and are clamped into that area with [`clamp_to_area`].
+//!
+//! winit reports whole monitors rather than their work area, so game code
+//! that knows about a taskbar or other reserved edges can shrink the
+//! monitor rects after [`popup_bounds_system`] runs.
+
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowMoved, WindowResized, WindowScaleFactorChanged};
+use bevy::winit::WinitWindows;
+
+/// The parts of the primary window popups can be placed in, in UI pixels
+///
+/// Empty until the primary window exists.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct PopupBounds {
+    /// Size of the window
+    pub window: Vec2,
+    /// The part of the window each monitor shows, relative to the window's
+    /// top-left corner; empty when winit can't tell where the window is
+    pub monitors: Vec<Rect>,
+}
+
+impl PopupBounds {
+    /// The area a popup anchored at `point` should stay inside
+    ///
+    /// This is the part of the window shown by the monitor under `point`, or
+    /// the nearest monitor when `point` is on none, and the whole window when
+    /// no monitor is known.
+    pub fn area_at(&self, point: Vec2) -> Option<Rect> {
+        if self.window.cmple(Vec2::ZERO).any() {
+            return None;
+        }
+        let distance = |rect: &Rect| point.distance_squared(point.clamp(rect.min, rect.max));
+        let monitor = self
+            .monitors
+            .iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)));
+        Some(
+            monitor
+                .copied()
+                .unwrap_or(Rect::from_corners(Vec2::ZERO, self.window)),
+        )
+    }
+}
+
+/// Moves `position` so a popup of `size` stays inside `area`, `margin` from
+/// its edges
+///
+/// Popups larger than the area keep their top-left corner inside it.
+pub fn clamp_to_area(position: Vec2, size: Vec2, area: Rect, margin: f32) -> Vec2 {
+    let min = area.min + Vec2::splat(margin);
+    let max = (area.max - size - Vec2::splat(margin)).max(min);
+    position.clamp(min, max)
+}
+
+/// How far a popup of `size` at `position` reaches outside `area`, `margin`
+/// from its edges, on each axis
+pub fn area_overflow(position: Vec2, size: Vec2, area: Rect, margin: f32) -> Vec2 {
+    let before = area.min + Vec2::splat(margin) - position;
+    let after = position + size - area.max + Vec2::splat(margin);
+    before.max(Vec2::ZERO) + after.max(Vec2::ZERO)
+}
+
+/// System to read the monitors showing the primary window from winit
+pub fn popup_bounds_system(
+    windows: Query<(Entity, &Window), With<PrimaryWindow>>,
+    winit_windows: Option<NonSend<WinitWindows>>,
+    ui_scale: Res<UiScale>,
+    mut bounds: ResMut<PopupBounds>,
+    (mut moved, mut resized, mut rescaled): (
+        EventReader<WindowMoved>,
+        EventReader<WindowResized>,
+        EventReader<WindowScaleFactorChanged>,
+    ),
+) {
+    let window_changed =
+        moved.read().count() + resized.read().count() + rescaled.read().count() > 0;
+    let Ok((entity, window)) = windows.get_single() else {
+        bounds.set_if_neq(PopupBounds::default());
+        return;
+    };
+    if !window_changed && !ui_scale.is_changed() && bounds.window != Vec2::ZERO {
+        return;
+    }
+
+    let size = window.size() / ui_scale.0;
+    let window_rect = Rect::from_corners(Vec2::ZERO, size);
+    let scale = window.scale_factor() * ui_scale.0;
+    let winit_window = winit_windows
+        .as_ref()
+        .and_then(|winit_windows| winit_windows.get_window(entity));
+    let monitors = winit_window
+        .and_then(|winit_window| {
+            let origin = winit_window.inner_position().ok()?;
+            let origin = Vec2::new(origin.x as f32, origin.y as f32);
+            let monitors = winit_window.available_monitors().map(|monitor| {
+                let position = monitor.position();
+                let min = (Vec2::new(position.x as f32, position.y as f32) - origin) / scale;
+                let monitor_size = monitor.size();
+                let max =
+                    min + Vec2::new(monitor_size.width as f32, monitor_size.height as f32) / scale;
+                Rect::from_corners(min, max).intersect(window_rect)
+            });
+            Some(monitors.filter(|rect| !rect.is_empty()).collect())
+        })
+        .unwrap_or_default();
+    bounds.set_if_neq(PopupBounds {
+        window: size,
+        monitors,
+    });
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<PopupBounds>()
+        .init_resource::<PopupBounds>()
+        .add_systems(PreUpdate, popup_bounds_system);
+}
@@ -0,0 +1,62 @@
+//! UI rendering quality levels for low-spec hardware
+//!
+//! [`UiQuality`] is consulted by widget systems before using costly effects, so
+//! the UI stays responsive on integrated GPUs. [`ReducedMotion`] turns
+//! animations off on its own, for players who find motion uncomfortable,
+//! and systems read both through [`UiMotion`].
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+/// How much visual polish the UI renders
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Resource)]
+pub enum UiQuality {
+    /// All effects enabled (default)
+    #[default]
+    Full,
+    /// For games to drop their own costly effects at; this crate's widgets
+    /// keep their animations and textured skins
+    Reduced,
+    /// Flat colors only: no animations or textured skins
+    Minimal,
+}
+
+impl UiQuality {
+    /// Whether widgets should animate; when off, they snap to their end state
+    pub fn animations(self) -> bool {
+        self != UiQuality::Minimal
+    }
+
+    /// Whether 9-slice / sprite skins should be used instead of flat colors
+    pub fn textured_skins(self) -> bool {
+        self != UiQuality::Minimal
+    }
+}
+
+/// Whether the player asked for widgets not to animate, whatever the
+/// [`UiQuality`]
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct ReducedMotion(pub bool);
+
+/// Whether widgets should animate, from [`UiQuality`] and [`ReducedMotion`]
+#[derive(SystemParam)]
+pub struct UiMotion<'w> {
+    quality: Res<'w, UiQuality>,
+    reduced_motion: Res<'w, ReducedMotion>,
+}
+
+impl UiMotion<'_> {
+    /// Whether widgets should animate; when off, they snap to their end state
+    pub fn animations(&self) -> bool {
+        self.quality.animations() && !self.reduced_motion.0
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiQuality>()
+        .register_type::<ReducedMotion>()
+        .init_resource::<UiQuality>()
+        .init_resource::<ReducedMotion>();
+}
@@ -0,0 +1,176 @@
+//! Read-only widgets and forms
+//!
+//! [`ReadOnly`] locks a widget, or every widget inside a container, against
+//! edits while keeping its normal colors, unlike disabling it. Presses and
+//! keyboard or gamepad activations on locked widgets are dropped right after
+//! arbitration, so buttons don't click, checkboxes don't toggle and sliders
+//! don't drag. Hovering a locked widget shows a small padlock in its corner.
+//!
+//! Spectators looking at lobby settings, or a published map's metadata, can
+//! be shown the same form the host edits:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::ReadOnly;
+//! fn lock_settings(mut commands: Commands, form: Query<Entity, With<Node>>) {
+//!     for entity in &form {
+//!         commands.entity(entity).insert(ReadOnly);
+//!     }
+//! }
+//! ```
+
+use bevy::prelude::*;
+
+use crate::interaction::{InteractionArbitrationSet, UiInteractionState};
+use crate::theme::Theme;
+
+/// Blocks edits to a widget and everything inside it
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ReadOnly;
+
+/// The padlock shown on a hovered [`ReadOnly`] node
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ReadOnlyLock;
+
+/// The [`ReadOnly`] node `entity` is locked by, itself or its nearest locked ancestor
+pub(crate) fn locked_by(
+    entity: Entity,
+    read_only: &Query<(), With<ReadOnly>>,
+    parents: &Query<&Parent>,
+) -> Option<Entity> {
+    std::iter::once(entity)
+        .chain(parents.iter_ancestors(entity))
+        .find(|&node| read_only.contains(node))
+}
+
+/// System to drop presses and activations on read-only widgets
+///
+/// Runs after arbitration and activation, like the modal block; hovers are
+/// kept so scrolling and tooltips still work.
+pub fn read_only_block_system(
+    read_only: Query<(), With<ReadOnly>>,
+    parents: Query<&Parent>,
+    mut interaction_state: ResMut<UiInteractionState>,
+) {
+    if read_only.is_empty() {
+        return;
+    }
+    let frame = &mut interaction_state.current;
+    if frame
+        .pressed
+        .is_some_and(|entity| locked_by(entity, &read_only, &parents).is_some())
+    {
+        frame.pressed = None;
+    }
+    if frame
+        .activated
+        .is_some_and(|entity| locked_by(entity, &read_only, &parents).is_some())
+    {
+        frame.activated = None;
+    }
+}
+
+/// Creates a padlock drawn from plain nodes, so it needs no icon font
+fn lock_icon(commands: &mut Commands, theme: &Theme) -> Entity {
+    let color = theme.text.normal;
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(-6.0),
+                    right: Val::Px(-6.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Local(10),
+                ..default()
+            },
+            ReadOnlyLock,
+            Name::new("ReadOnlyLock"),
+        ))
+        .with_children(|lock| {
+            // Shackle
+            lock.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(8.0),
+                    height: Val::Px(6.0),
+                    border: UiRect {
+                        left: Val::Px(2.0),
+                        right: Val::Px(2.0),
+                        top: Val::Px(2.0),
+                        bottom: Val::ZERO,
+                    },
+                    ..default()
+                },
+                border_color: color.into(),
+                border_radius: BorderRadius::top(Val::Px(4.0)),
+                ..default()
+            });
+            // Body
+            lock.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(12.0),
+                    height: Val::Px(8.0),
+                    ..default()
+                },
+                background_color: color.into(),
+                border_radius: BorderRadius::all(Val::Px(1.0)),
+                ..default()
+            });
+        })
+        .id()
+}
+
+/// System to give read-only nodes a padlock and show it while they are hovered
+pub fn read_only_lock_system(
+    mut commands: Commands,
+    read_only_nodes: Query<(Entity, Option<&Children>), With<ReadOnly>>,
+    read_only: Query<(), With<ReadOnly>>,
+    mut locks: Query<(Entity, &Parent, &mut Visibility), With<ReadOnlyLock>>,
+    parents: Query<&Parent>,
+    interaction_state: Res<UiInteractionState>,
+    theme: Res<Theme>,
+) {
+    for (entity, children) in &read_only_nodes {
+        let has_lock =
+            children.is_some_and(|children| children.iter().any(|&child| locks.contains(child)));
+        if !has_lock {
+            let lock = lock_icon(&mut commands, &theme);
+            commands.entity(entity).add_child(lock);
+        }
+    }
+
+    let hovered = interaction_state
+        .current
+        .hovered
+        .and_then(|entity| locked_by(entity, &read_only, &parents));
+    for (lock, parent, mut visibility) in &mut locks {
+        if !read_only.contains(parent.get()) {
+            commands.entity(lock).despawn_recursive();
+            continue;
+        }
+        visibility.set_if_neq(if hovered == Some(parent.get()) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<ReadOnly>()
+        .register_type::<ReadOnlyLock>()
+        .add_systems(
+            PreUpdate,
+            read_only_block_system
+                .after(InteractionArbitrationSet)
+                .after(crate::focus::focus_activation_system)
+                .after(crate::split_screen::hud_focus_activation_system),
+        )
+        .add_systems(Update, read_only_lock_system);
+}
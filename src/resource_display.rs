@@ -0,0 +1,211 @@
+//! HUD resource counters
+//!
+//! A [`ResourceDisplay`] shows an icon next to a resource amount, written
+//! compactly by [`format_compact`] ("950", "1.2k", "3.4M"). Whenever the
+//! game changes [`ResourceDisplay::value`], the text updates and the counter
+//! [`Flash`]es green for a gain or red for a loss.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{resource_display_bundle, ResourceDisplay};
+//! #[derive(Component)]
+//! struct GoldCounter;
+//!
+//! fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     let display = ResourceDisplay::new(asset_server.load("icons/gold.png"), 1200.0);
+//!     commands.spawn((resource_display_bundle(display), GoldCounter));
+//! }
+//!
+//! fn pay(mut counters: Query<&mut ResourceDisplay, With<GoldCounter>>) {
+//!     for mut counter in &mut counters {
+//!         counter.value -= 150.0;
+//!     }
+//! }
+//! ```
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::attention::Flash;
+use crate::styles::hud;
+use crate::theme::Theme;
+use crate::utils::{first_section, first_section_is};
+
+/// Writes `value` with a k/M/B suffix once it reaches a thousand
+///
+/// Amounts below a thousand are rounded to whole numbers; larger ones keep
+/// one decimal, dropped when it is zero: `999`, `1k`, `1.2k`, `3.4M`.
+pub fn format_compact(value: f64) -> String {
+    const UNITS: [(f64, &str); 4] = [(1e3, "k"), (1e6, "M"), (1e9, "B"), (1e12, "T")];
+    let Some(mut unit) = UNITS
+        .iter()
+        .rposition(|&(scale, _)| value.abs().round() >= scale)
+    else {
+        // Adding zero turns -0 into 0
+        return format!("{:.0}", value.round() + 0.0);
+    };
+    let scaled = |unit: usize| (value / UNITS[unit].0 * 10.0).round() / 10.0;
+    // 999,960 rounds to 1000k, which reads better as 1M
+    if scaled(unit).abs() >= 1000.0 && unit + 1 < UNITS.len() {
+        unit += 1;
+    }
+    let (scaled, suffix) = (scaled(unit), UNITS[unit].1);
+    if scaled.fract() == 0.0 {
+        format!("{scaled:.0}{suffix}")
+    } else {
+        format!("{scaled:.1}{suffix}")
+    }
+}
+
+/// A HUD counter showing an icon and a resource amount
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ResourceDisplay {
+    /// Icon shown before the amount
+    pub icon: Handle<Image>,
+    /// Current amount
+    pub value: f64,
+    /// Whether the amount is written with [`format_compact`] rather than in full
+    pub compact: bool,
+    /// Amount the text shows
+    #[reflect(ignore)]
+    shown: Option<f64>,
+}
+
+impl ResourceDisplay {
+    /// Creates a compact counter showing `value`
+    pub fn new(icon: Handle<Image>, value: f64) -> Self {
+        Self {
+            icon,
+            value,
+            compact: true,
+            shown: None,
+        }
+    }
+
+    /// Writes the amount in full
+    pub fn full(mut self) -> Self {
+        self.compact = false;
+        self
+    }
+
+    /// The amount as shown
+    pub fn text(&self) -> String {
+        if self.compact {
+            format_compact(self.value)
+        } else {
+            format!("{:.0}", self.value.round() + 0.0)
+        }
+    }
+}
+
+/// Child nodes of a [`ResourceDisplay`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum ResourceDisplayPart {
+    /// The resource icon
+    Icon,
+    /// The amount text
+    Value,
+}
+
+/// Creates a resource counter
+pub fn resource_display_bundle(display: ResourceDisplay) -> (NodeBundle, ResourceDisplay) {
+    let mut node = hud::resource_display();
+    node.style.padding = UiRect::horizontal(Val::Px(4.0));
+    (node, display)
+}
+
+/// System to spawn the icon and text of new resource counters
+pub fn resource_display_spawn_system(
+    mut commands: Commands,
+    displays: Query<(Entity, &ResourceDisplay), Added<ResourceDisplay>>,
+    theme: Res<Theme>,
+) {
+    for (entity, display) in &displays {
+        commands.entity(entity).with_children(|row| {
+            let mut icon = hud::resource_icon();
+            icon.style.flex_shrink = 0.0;
+            row.spawn((
+                ImageBundle {
+                    style: icon.style,
+                    image: UiImage::new(display.icon.clone()),
+                    ..default()
+                },
+                ResourceDisplayPart::Icon,
+            ));
+            row.spawn((
+                TextBundle::from_section(
+                    display.text(),
+                    TextStyle {
+                        font: UiFonts::MEDIUM,
+                        font_size: 20.0,
+                        color: theme.text.normal,
+                    },
+                ),
+                ResourceDisplayPart::Value,
+            ));
+        });
+    }
+}
+
+/// System to refresh counters and flash them when the amount changes
+pub fn resource_display_update_system(
+    mut commands: Commands,
+    mut displays: Query<(Entity, &mut ResourceDisplay, &Children), Changed<ResourceDisplay>>,
+    mut parts: Query<(
+        &ResourceDisplayPart,
+        Option<&mut Text>,
+        Option<&mut UiImage>,
+    )>,
+    theme: Res<Theme>,
+) {
+    for (entity, mut display, children) in &mut displays {
+        let display = display.bypass_change_detection();
+        match display.shown {
+            Some(shown) if display.value > shown => {
+                commands
+                    .entity(entity)
+                    .insert(Flash::new(theme.status.success.with_alpha(0.6)));
+            }
+            Some(shown) if display.value < shown => {
+                commands
+                    .entity(entity)
+                    .insert(Flash::new(theme.status.error.with_alpha(0.6)));
+            }
+            _ => {}
+        }
+        display.shown = Some(display.value);
+
+        let mut parts = parts.iter_many_mut(children);
+        while let Some((part, text, image)) = parts.fetch_next() {
+            match (part, text, image) {
+                (ResourceDisplayPart::Value, Some(mut text), _) => {
+                    let value = display.text();
+                    if !first_section_is(&text, &value) {
+                        first_section(&mut text).value = value;
+                    }
+                }
+                (ResourceDisplayPart::Icon, _, Some(mut image))
+                    if image.texture != display.icon =>
+                {
+                    image.texture = display.icon.clone();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<ResourceDisplay>()
+        .register_type::<ResourceDisplayPart>()
+        .add_systems(
+            Update,
+            (
+                resource_display_spawn_system,
+                resource_display_update_system,
+            )
+                .chain(),
+        );
+}
@@ -0,0 +1,41 @@
+//! Widget scale factor
+//!
+//! [`WidgetScale`] multiplies every pixel length and font size of the UI, for
+//! 4K displays and players who need larger widgets. Unlike Bevy's
+//! [`UiScale`], which scales the whole UI coordinate space, it resizes the
+//! widgets themselves: percentages, window-relative positions and anything
+//! measured in logical pixels, such as the pointer, are left as they are.
+//!
+//! Nodes record the scale applied to them in a
+//! [`HudScaleBase`](crate::HudScaleBase) the first time the scale differs
+//! from 1, so changing the scale re-lays out existing widgets and new ones
+//! are scaled as they spawn:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::WidgetScale;
+//! fn larger_widgets(mut scale: ResMut<WidgetScale>) {
+//!     scale.0 = 1.5;
+//! }
+//! ```
+//!
+//! Under a [`HudRoot`](crate::HudRoot), the root's own scale multiplies this
+//! one.
+
+use bevy::prelude::*;
+
+/// Multiplier for the pixel lengths and font sizes of every widget
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct WidgetScale(pub f32);
+
+impl Default for WidgetScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<WidgetScale>()
+        .init_resource::<WidgetScale>();
+}
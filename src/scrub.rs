@@ -0,0 +1,219 @@
+//! Drag-to-scrub value labels
+//!
+//! With a [`Scrubber`] on a [`UiSlider`] or a [`NumericInput`], pressing its
+//! value label ([`SliderValueLabel`] or the input's field) and dragging
+//! sideways changes the value, as in the number fields of 3D and audio
+//! tools. The pointer is captured for the whole drag, so it keeps scrubbing
+//! past the label's edges. Holding Shift scrubs finely and Ctrl coarsely. A
+//! press that doesn't move still opens the label for typing.
+//!
+//! Sliders send [`UiSliderChanged`](crate::events::UiSliderChanged) as usual,
+//! and number inputs [`ValueCommitted`].
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{numeric_input_bundle, NumericInput, Scrubber};
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn((
+//!         numeric_input_bundle(NumericInput::new(1.0).range(0.0, 4.0).step(0.1).decimals(1)),
+//!         Scrubber::default(),
+//!     ));
+//! }
+//! ```
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::components::UiSlider;
+use crate::interaction::{PointerCapture, UiInteractionState};
+use crate::numeric_input::{
+    numeric_input_system, NumericInput, NumericInputPart, NumericInputPartKind, ValueCommitted,
+};
+use crate::slider_entry::{slider_value_entry_system, SliderValueLabel};
+
+/// Pixels the pointer must move before a press becomes a scrub
+const SCRUB_THRESHOLD: f32 = 3.0;
+
+/// A scrub in progress
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ScrubDrag {
+    /// The label being dragged
+    handle: Entity,
+    /// Pointer X where the press started
+    start_x: f32,
+    /// Pointer X of the previous frame
+    last_x: f32,
+    /// Value before snapping or clamping, so slow drags still add up
+    value: f32,
+    /// Whether the pointer has moved past [`SCRUB_THRESHOLD`]
+    active: bool,
+}
+
+/// Lets a slider's or number input's value label be dragged to change it
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Scrubber {
+    /// Value change per pixel dragged
+    ///
+    /// Defaults to a slider's range over 200 pixels, or a number input's
+    /// step every 10 pixels.
+    pub per_pixel: Option<f32>,
+    /// Multiplier while Shift is held
+    pub fine: f32,
+    /// Multiplier while Ctrl is held
+    pub coarse: f32,
+    #[reflect(ignore)]
+    drag: Option<ScrubDrag>,
+}
+
+impl Default for Scrubber {
+    fn default() -> Self {
+        Self {
+            per_pixel: None,
+            fine: 0.1,
+            coarse: 10.0,
+            drag: None,
+        }
+    }
+}
+
+impl Scrubber {
+    /// Sets the value change per pixel dragged
+    pub fn per_pixel(mut self, per_pixel: f32) -> Self {
+        self.per_pixel = Some(per_pixel);
+        self
+    }
+
+    /// Whether the label is being dragged
+    pub fn is_scrubbing(&self) -> bool {
+        self.drag.is_some_and(|drag| drag.active)
+    }
+}
+
+/// System to scrub slider and number input values by dragging their labels
+pub fn scrub_system(
+    mut scrubbers: Query<(
+        Entity,
+        &mut Scrubber,
+        Option<&mut UiSlider>,
+        Option<&mut NumericInput>,
+    )>,
+    mut labels: Query<(Entity, &mut SliderValueLabel)>,
+    fields: Query<(Entity, &NumericInputPart)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    (interaction_state, mut capture): (Res<UiInteractionState>, ResMut<PointerCapture>),
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut committed_events: EventWriter<ValueCommitted>,
+) {
+    let cursor = windows.get_single().ok().and_then(Window::cursor_position);
+
+    // Labels pressed this frame start a drag on the widget they belong to
+    let slider_handles = labels.iter().map(|(entity, label)| (entity, label.slider));
+    let field_handles = fields
+        .iter()
+        .filter(|(_, part)| part.kind == NumericInputPartKind::Field)
+        .map(|(entity, part)| (entity, part.input));
+    let pressed: Vec<(Entity, Entity)> = slider_handles
+        .chain(field_handles)
+        .filter(|(handle, _)| interaction_state.just_pointer_pressed(*handle))
+        .collect();
+    for (handle, owner) in pressed {
+        let (Ok((_, mut scrubber, slider, input)), Some(cursor)) =
+            (scrubbers.get_mut(owner), cursor)
+        else {
+            continue;
+        };
+        let value = match (slider, input) {
+            (Some(slider), _) if !slider.disabled => slider.value,
+            (_, Some(input)) if !input.disabled => input.value,
+            _ => continue,
+        };
+        capture.capture(handle);
+        scrubber.drag = Some(ScrubDrag {
+            handle,
+            start_x: cursor.x,
+            last_x: cursor.x,
+            value,
+            active: false,
+        });
+    }
+
+    let fine = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let coarse = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+
+    for (entity, mut scrubber, slider, input) in &mut scrubbers {
+        let Some(mut drag) = scrubber.drag else {
+            continue;
+        };
+        if !capture.is_captured(drag.handle) {
+            scrubber.drag = None;
+            continue;
+        }
+        let Some(cursor) = cursor else {
+            continue;
+        };
+        if !drag.active && (cursor.x - drag.start_x).abs() < SCRUB_THRESHOLD {
+            continue;
+        }
+        if !drag.active {
+            // A drag, not a click: don't leave the label open for typing
+            drag.active = true;
+            if let Ok((_, mut label)) = labels.get_mut(drag.handle) {
+                label.cancel_entry();
+            }
+        }
+
+        let per_pixel = scrubber
+            .per_pixel
+            .unwrap_or_else(|| match (&slider, &input) {
+                (Some(slider), _) if slider.max > slider.min => (slider.max - slider.min) / 200.0,
+                (Some(_), _) => 1.0 / 200.0,
+                (_, Some(input)) => input.step / 10.0,
+                _ => 0.0,
+            });
+        let factor = if fine {
+            scrubber.fine
+        } else if coarse {
+            scrubber.coarse
+        } else {
+            1.0
+        };
+        drag.value += (cursor.x - drag.last_x) * per_pixel * factor;
+        drag.last_x = cursor.x;
+
+        match (slider, input) {
+            (Some(mut slider), _) => {
+                drag.value = drag.value.clamp(slider.min, slider.max.max(slider.min));
+                let value = slider.snap(drag.value);
+                if slider.value != value {
+                    slider.value = value;
+                }
+            }
+            (_, Some(mut input)) => {
+                if input.is_editing() {
+                    input.cancel_entry();
+                }
+                drag.value = drag.value.clamp(input.min, input.max.max(input.min));
+                let value = input.clamp(drag.value);
+                if input.value != value {
+                    input.value = value;
+                    committed_events.send(ValueCommitted {
+                        input: entity,
+                        value,
+                    });
+                }
+            }
+            _ => {}
+        }
+        scrubber.bypass_change_detection().drag = Some(drag);
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Scrubber>().add_systems(
+        Update,
+        scrub_system
+            .after(slider_value_entry_system)
+            .after(numeric_input_system),
+    );
+}
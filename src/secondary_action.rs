@@ -0,0 +1,89 @@
+//! Right-click actions on widgets
+//!
+//! A [`SecondaryAction`] gives a button a second action on right-click, such
+//! as cancelling a queued unit or opening the economy panel from a resource
+//! counter. Right-clicking the widget sends [`SecondaryActionPressed`], apart
+//! from the [`UiButtonClicked`](crate::UiButtonClicked) of a left click, and
+//! the action's hint is appended to the widget's tooltip, or shown as its
+//! tooltip when it has none, so players can find it:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{SecondaryAction, SecondaryActionPressed, Theme, UiButtonBuilder};
+//! fn setup(mut commands: Commands, theme: Res<Theme>) {
+//!     let minerals = UiButtonBuilder::new(&theme)
+//!         .label("1500")
+//!         .tooltip("Minerals")
+//!         .spawn(&mut commands);
+//!     commands
+//!         .entity(minerals)
+//!         .insert(SecondaryAction::with_hint("Open economy"));
+//! }
+//!
+//! fn open_economy(mut pressed: EventReader<SecondaryActionPressed>) {
+//!     for SecondaryActionPressed(entity) in pressed.read() {
+//!         info!("economy panel requested from {entity:?}");
+//!     }
+//! }
+//! ```
+//!
+//! Disabled widgets ignore right-clicks. An entity shouldn't carry both a
+//! `SecondaryAction` and a [`ContextMenu`](crate::ContextMenu), which would
+//! answer the same click.
+
+use bevy::prelude::*;
+
+use crate::components::{is_disabled, WidgetDisabled};
+use crate::interaction::UiInteractionState;
+
+/// Makes a widget send [`SecondaryActionPressed`] when right-clicked
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SecondaryAction {
+    /// What right-clicking does, appended to the widget's tooltip
+    pub hint: Option<String>,
+}
+
+impl SecondaryAction {
+    /// A secondary action described by `hint` in the widget's tooltip
+    pub fn with_hint(hint: impl Into<String>) -> Self {
+        Self {
+            hint: Some(hint.into()),
+        }
+    }
+
+    /// The line appended to the widget's tooltip, if there is a hint
+    pub fn tooltip_hint(&self) -> Option<String> {
+        self.hint
+            .as_ref()
+            .map(|hint| format!("Right-click: {hint}"))
+    }
+}
+
+/// Sent when a widget with a [`SecondaryAction`] is right-clicked
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecondaryActionPressed(pub Entity);
+
+/// System to send [`SecondaryActionPressed`] for the hovered widget on
+/// right-click
+pub fn secondary_action_system(
+    actions: Query<(Entity, WidgetDisabled), With<SecondaryAction>>,
+    interaction_state: Res<UiInteractionState>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut pressed_events: EventWriter<SecondaryActionPressed>,
+) {
+    if !mouse_buttons.just_pressed(MouseButton::Right) {
+        return;
+    }
+    for (entity, widget) in &actions {
+        if interaction_state.is_hovered(entity) && !is_disabled(widget) {
+            pressed_events.send(SecondaryActionPressed(entity));
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<SecondaryAction>()
+        .add_event::<SecondaryActionPressed>()
+        .add_systems(Update, secondary_action_system);
+}
@@ -0,0 +1,200 @@
+//! Drag-to-select marquee
+//!
+//! While [`SelectionBox::enabled`] is set, holding the left mouse button on
+//! empty space (no UI under the pointer) and dragging draws a translucent
+//! rectangle over the screen. Releasing the button sends
+//! [`SelectionBoxCompleted`] with the rectangle in window coordinates, ready
+//! for the game to turn into a world selection through its camera. Drags
+//! shorter than [`SelectionBox::min_drag`] are left to the game as clicks.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{SelectionBox, SelectionBoxCompleted};
+//! fn enable(mut selection_box: ResMut<SelectionBox>) {
+//!     selection_box.enabled = true;
+//! }
+//!
+//! fn select(
+//!     mut completed: EventReader<SelectionBoxCompleted>,
+//!     cameras: Query<(&Camera, &GlobalTransform)>,
+//! ) {
+//!     let Ok((camera, transform)) = cameras.get_single() else {
+//!         return;
+//!     };
+//!     for event in completed.read() {
+//!         let corners = [event.min, event.max].map(|corner| camera.viewport_to_world(transform, corner));
+//!         info!("select between {corners:?}");
+//!     }
+//! }
+//! ```
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::interaction::{InteractionArbitrationSet, PointerCapture, UiInteractionState};
+
+/// Settings and state of the selection marquee
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct SelectionBox {
+    /// Whether dragging on empty space draws the marquee
+    pub enabled: bool,
+    /// Fill color of the rectangle
+    pub fill: Color,
+    /// Border color of the rectangle
+    pub border: Color,
+    /// Distance in pixels the pointer must travel before a press becomes a drag
+    pub min_drag: f32,
+    /// Window position where the current drag started
+    #[reflect(ignore)]
+    start: Option<Vec2>,
+    /// Window position the current drag has reached
+    #[reflect(ignore)]
+    end: Vec2,
+}
+
+impl Default for SelectionBox {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fill: Color::srgba(0.35, 0.6, 0.95, 0.15),
+            border: Color::srgba(0.35, 0.6, 0.95, 0.8),
+            min_drag: 4.0,
+            start: None,
+            end: Vec2::ZERO,
+        }
+    }
+}
+
+impl SelectionBox {
+    /// The rectangle being dragged, in window coordinates, once it is past
+    /// [`min_drag`](Self::min_drag)
+    pub fn dragging(&self) -> Option<Rect> {
+        let start = self.start?;
+        (start.distance(self.end) >= self.min_drag).then(|| Rect::from_corners(start, self.end))
+    }
+
+    /// Drops the current drag without sending [`SelectionBoxCompleted`]
+    pub fn cancel(&mut self) {
+        self.start = None;
+    }
+}
+
+/// Sent when a selection marquee is released
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct SelectionBoxCompleted {
+    /// Top-left corner in window coordinates
+    pub min: Vec2,
+    /// Bottom-right corner in window coordinates
+    pub max: Vec2,
+}
+
+/// Marks the node drawing the selection marquee
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SelectionBoxOverlay;
+
+/// System to track marquee drags and send [`SelectionBoxCompleted`] on release
+pub fn selection_box_input_system(
+    mut selection_box: ResMut<SelectionBox>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    interaction_state: Res<UiInteractionState>,
+    capture: Res<PointerCapture>,
+    mut completed_events: EventWriter<SelectionBoxCompleted>,
+) {
+    if !selection_box.enabled {
+        if selection_box.start.is_some() {
+            selection_box.cancel();
+        }
+        return;
+    }
+    let cursor = windows.get_single().ok().and_then(Window::cursor_position);
+
+    if mouse_input.just_pressed(MouseButton::Left) {
+        let over_ui = interaction_state.current.hovered.is_some() || capture.captured().is_some();
+        if let (Some(cursor), false) = (cursor, over_ui) {
+            selection_box.start = Some(cursor);
+            selection_box.end = cursor;
+        }
+        return;
+    }
+    if selection_box.start.is_none() {
+        return;
+    }
+    if let Some(cursor) = cursor {
+        selection_box.end = cursor;
+    }
+    if !mouse_input.pressed(MouseButton::Left) {
+        if let Some(rect) = selection_box.dragging() {
+            completed_events.send(SelectionBoxCompleted {
+                min: rect.min,
+                max: rect.max,
+            });
+        }
+        selection_box.cancel();
+    }
+}
+
+/// System to draw the marquee, spawning its overlay node on first use
+pub fn selection_box_visual_system(
+    mut commands: Commands,
+    selection_box: Res<SelectionBox>,
+    mut overlays: Query<
+        (
+            &mut Style,
+            &mut Visibility,
+            &mut BackgroundColor,
+            &mut BorderColor,
+        ),
+        With<SelectionBoxOverlay>,
+    >,
+    ui_scale: Res<UiScale>,
+) {
+    let rect = selection_box.dragging();
+    let Ok((mut style, mut visibility, mut background, mut border)) = overlays.get_single_mut()
+    else {
+        if rect.is_some() {
+            commands.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    visibility: Visibility::Hidden,
+                    z_index: ZIndex::Global(70),
+                    focus_policy: bevy::ui::FocusPolicy::Pass,
+                    ..default()
+                },
+                SelectionBoxOverlay,
+            ));
+        }
+        return;
+    };
+
+    let Some(rect) = rect else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    let scale = ui_scale.0;
+    style.left = Val::Px(rect.min.x / scale);
+    style.top = Val::Px(rect.min.y / scale);
+    style.width = Val::Px(rect.width() / scale);
+    style.height = Val::Px(rect.height() / scale);
+    background.0 = selection_box.fill;
+    border.0 = selection_box.border;
+    visibility.set_if_neq(Visibility::Visible);
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<SelectionBox>()
+        .register_type::<SelectionBoxOverlay>()
+        .init_resource::<SelectionBox>()
+        .add_event::<SelectionBoxCompleted>()
+        .add_systems(
+            PreUpdate,
+            selection_box_input_system.after(InteractionArbitrationSet),
+        )
+        .add_systems(Update, selection_box_visual_system);
+}
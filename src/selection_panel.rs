@@ -0,0 +1,376 @@
+//! Unit selection panel
+//!
+//! A [`SelectionPanel`] shows the portraits of the units the player has
+//! selected in a grid, each with a health bar along its bottom edge. When
+//! more units are selected than the grid holds, the panel pages through
+//! them with previous/next buttons.
+//!
+//! The panel only reports clicks: a click on a portrait sends
+//! [`PortraitClicked`] asking to select just that unit, and a ctrl-click asks
+//! to remove it from the selection. The game updates its own selection and
+//! writes it back to [`SelectionPanel::units`], which rebuilds the grid.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{PortraitAction, PortraitClicked, SelectionPanel};
+//! fn selection_changed(
+//!     mut clicks: EventReader<PortraitClicked>,
+//!     mut panels: Query<&mut SelectionPanel>,
+//! ) {
+//!     for click in clicks.read() {
+//!         let Ok(mut panel) = panels.get_mut(click.panel) else {
+//!             continue;
+//!         };
+//!         match click.action {
+//!             PortraitAction::Select => panel.units.retain(|unit| unit.unit == click.unit),
+//!             PortraitAction::Remove => panel.units.retain(|unit| unit.unit != click.unit),
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::ops::Range;
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::interaction::UiInteractionState;
+use crate::theme::Theme;
+
+/// A unit shown in a [`SelectionPanel`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct SelectedUnit {
+    /// The game's unit entity
+    pub unit: Entity,
+    /// Portrait image
+    pub portrait: Handle<Image>,
+    /// Remaining health, from 0 to 1
+    pub health: f32,
+}
+
+impl SelectedUnit {
+    /// A unit at full health
+    pub fn new(unit: Entity, portrait: Handle<Image>) -> Self {
+        Self {
+            unit,
+            portrait,
+            health: 1.0,
+        }
+    }
+
+    /// Sets the remaining health, from 0 to 1
+    pub fn with_health(mut self, health: f32) -> Self {
+        self.health = health;
+        self
+    }
+}
+
+/// A paged grid of selected-unit portraits
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct SelectionPanel {
+    /// Selected units, in the order they are shown
+    pub units: Vec<SelectedUnit>,
+    /// Portraits per row
+    pub columns: usize,
+    /// Rows per page
+    pub rows: usize,
+    /// Page shown, counted from zero
+    pub page: usize,
+    /// Width and height of each portrait in pixels
+    pub portrait_size: f32,
+}
+
+impl SelectionPanel {
+    /// Creates an empty panel with a `columns` by `rows` grid
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            units: Vec::new(),
+            columns: columns.max(1),
+            rows: rows.max(1),
+            page: 0,
+            portrait_size: 48.0,
+        }
+    }
+
+    /// Sets the selected units
+    pub fn with_units(mut self, units: Vec<SelectedUnit>) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Portraits that fit on one page
+    pub fn per_page(&self) -> usize {
+        (self.columns * self.rows).max(1)
+    }
+
+    /// Number of pages, at least one
+    pub fn page_count(&self) -> usize {
+        self.units.len().div_ceil(self.per_page()).max(1)
+    }
+
+    /// Indices into [`SelectionPanel::units`] on the page shown
+    ///
+    /// A page past the end, left behind when units were removed, shows the
+    /// last page.
+    pub fn page_range(&self) -> Range<usize> {
+        let page = self.page.min(self.page_count() - 1);
+        let start = page * self.per_page();
+        start..(start + self.per_page()).min(self.units.len())
+    }
+}
+
+/// What a [`SelectionPanelPart`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum SelectionPanelPartKind {
+    /// The portrait of [`SelectionPanel::units`] at this index
+    Portrait(usize),
+    /// The health bar of the unit at this index
+    Health(usize),
+    /// The button to the previous page
+    PreviousPage,
+    /// The button to the next page
+    NextPage,
+}
+
+/// A node spawned for a [`SelectionPanel`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SelectionPanelPart {
+    /// The panel this node belongs to
+    pub panel: Entity,
+    /// What this node is
+    pub kind: SelectionPanelPartKind,
+}
+
+/// What a [`PortraitClicked`] asks the game to do with the selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortraitAction {
+    /// Select only this unit (plain click)
+    Select,
+    /// Remove this unit from the selection (ctrl-click)
+    Remove,
+}
+
+/// Sent when a portrait in a [`SelectionPanel`] is clicked
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortraitClicked {
+    /// The panel entity
+    pub panel: Entity,
+    /// Index into [`SelectionPanel::units`]
+    pub index: usize,
+    /// The game's unit entity
+    pub unit: Entity,
+    /// What the click asks for
+    pub action: PortraitAction,
+}
+
+/// Creates the container bundle for a selection panel
+pub fn selection_panel_bundle(panel: SelectionPanel) -> (NodeBundle, SelectionPanel) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(4.0)),
+                ..default()
+            },
+            ..default()
+        },
+        panel,
+    )
+}
+
+/// Health bar color for `health`, from the theme's status palette
+fn health_color(health: f32, theme: &Theme) -> Color {
+    if health > 0.6 {
+        theme.status.success
+    } else if health > 0.3 {
+        theme.status.warning
+    } else {
+        theme.status.error
+    }
+}
+
+/// System to spawn the portrait grid and pager of selection panels, and
+/// rebuild them on change
+pub fn selection_panel_spawn_system(
+    mut commands: Commands,
+    panels: Query<(Entity, &SelectionPanel), Changed<SelectionPanel>>,
+    theme: Res<Theme>,
+) {
+    for (entity, panel) in &panels {
+        let part = |kind| SelectionPanelPart {
+            panel: entity,
+            kind,
+        };
+        let size = panel.portrait_size;
+        commands
+            .entity(entity)
+            .despawn_descendants()
+            .with_children(|root| {
+                root.spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Grid,
+                        grid_template_columns: vec![RepeatedGridTrack::px(
+                            panel.columns as u16,
+                            size,
+                        )],
+                        grid_auto_rows: vec![GridTrack::px(size)],
+                        column_gap: Val::Px(4.0),
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|grid| {
+                    for index in panel.page_range() {
+                        let unit = &panel.units[index];
+                        let health = unit.health.clamp(0.0, 1.0);
+                        grid.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    width: Val::Px(size),
+                                    height: Val::Px(size),
+                                    padding: UiRect::all(Val::Px(2.0)),
+                                    ..default()
+                                },
+                                background_color: theme.button.normal.into(),
+                                ..default()
+                            },
+                            Interaction::default(),
+                            part(SelectionPanelPartKind::Portrait(index)),
+                        ))
+                        .with_children(|slot| {
+                            slot.spawn(ImageBundle {
+                                style: Style {
+                                    width: Val::Percent(100.0),
+                                    height: Val::Percent(100.0),
+                                    ..default()
+                                },
+                                image: UiImage::new(unit.portrait.clone()),
+                                ..default()
+                            });
+                            slot.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        position_type: PositionType::Absolute,
+                                        left: Val::Px(2.0),
+                                        bottom: Val::Px(2.0),
+                                        width: Val::Px((size - 4.0) * health),
+                                        height: Val::Px(4.0),
+                                        ..default()
+                                    },
+                                    background_color: health_color(health, &theme).into(),
+                                    ..default()
+                                },
+                                part(SelectionPanelPartKind::Health(index)),
+                            ));
+                        });
+                    }
+                });
+
+                if panel.page_count() < 2 {
+                    return;
+                }
+                let text_style = TextStyle {
+                    font: UiFonts::MEDIUM,
+                    font_size: 14.0,
+                    color: theme.text.normal,
+                };
+                let page = panel.page_range().start / panel.per_page();
+                root.spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(8.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|pager| {
+                    let button = |kind| {
+                        (
+                            NodeBundle {
+                                style: Style {
+                                    padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                                    ..default()
+                                },
+                                background_color: theme.button.normal.into(),
+                                ..default()
+                            },
+                            Interaction::default(),
+                            part(kind),
+                        )
+                    };
+                    pager
+                        .spawn(button(SelectionPanelPartKind::PreviousPage))
+                        .with_children(|button| {
+                            button.spawn(TextBundle::from_section("<", text_style.clone()));
+                        });
+                    pager.spawn(TextBundle::from_section(
+                        format!("{}/{}", page + 1, panel.page_count()),
+                        text_style.clone(),
+                    ));
+                    pager
+                        .spawn(button(SelectionPanelPartKind::NextPage))
+                        .with_children(|button| {
+                            button.spawn(TextBundle::from_section(">", text_style.clone()));
+                        });
+                });
+            });
+    }
+}
+
+/// System to report portrait clicks and turn pages
+pub fn selection_panel_pointer_system(
+    mut panels: Query<&mut SelectionPanel>,
+    parts: Query<(Entity, &SelectionPanelPart)>,
+    interaction_state: Res<UiInteractionState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut clicked_events: EventWriter<PortraitClicked>,
+) {
+    let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    for (entity, part) in &parts {
+        if !interaction_state.just_pointer_pressed(entity) {
+            continue;
+        }
+        let Ok(mut panel) = panels.get_mut(part.panel) else {
+            continue;
+        };
+        let page = panel.page_range().start / panel.per_page();
+        match part.kind {
+            SelectionPanelPartKind::Portrait(index) => {
+                let Some(unit) = panel.units.get(index) else {
+                    continue;
+                };
+                clicked_events.send(PortraitClicked {
+                    panel: part.panel,
+                    index,
+                    unit: unit.unit,
+                    action: if ctrl {
+                        PortraitAction::Remove
+                    } else {
+                        PortraitAction::Select
+                    },
+                });
+            }
+            SelectionPanelPartKind::PreviousPage if page > 0 => panel.page = page - 1,
+            SelectionPanelPartKind::NextPage if page + 1 < panel.page_count() => {
+                panel.page = page + 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<SelectionPanel>()
+        .register_type::<SelectionPanelPart>()
+        .add_event::<PortraitClicked>()
+        .add_systems(
+            Update,
+            (selection_panel_pointer_system, selection_panel_spawn_system).chain(),
+        );
+}
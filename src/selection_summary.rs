@@ -0,0 +1,321 @@
+//! Group summary tooltip for the selection panel
+//!
+//! A [`SelectionSummaryTooltip`] on a [`SelectionPanel`] shows totals for the
+//! whole selection while the panel is hovered: combined health, damage per
+//! second and how many units of each type are selected, each type with its
+//! icon. The game computes the [`SelectionSummary`] from its own unit data
+//! and writes it to the component whenever the selection or its stats
+//! change; the tooltip is rebuilt to match.
+//!
+//! The tooltip waits out [`TooltipSettings::delay`] like plain tooltips, sits
+//! above the panel and only shows while more than one unit is selected.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{
+//! #     selection_panel_bundle, SelectionPanel, SelectionSummary, SelectionSummaryTooltip,
+//! # };
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn((
+//!         selection_panel_bundle(SelectionPanel::new(6, 2)),
+//!         SelectionSummaryTooltip::default(),
+//!     ));
+//! }
+//!
+//! fn summarize(asset_server: Res<AssetServer>, mut tooltips: Query<&mut SelectionSummaryTooltip>) {
+//!     for mut tooltip in &mut tooltips {
+//!         tooltip.summary = SelectionSummary::new(1450.0, 2000.0, 312.0)
+//!             .with_type("Rifleman", asset_server.load("icons/rifleman.png"), 8)
+//!             .with_type("Tank", asset_server.load("icons/tank.png"), 2);
+//!     }
+//! }
+//! ```
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::clock::UiClock;
+use crate::components::TooltipPosition;
+use crate::interaction::UiInteractionState;
+use crate::placement::PopupBounds;
+use crate::selection_panel::SelectionPanel;
+use crate::theme::Theme;
+use crate::tooltip::{place_tooltip, tooltip_anchor, TooltipSettings, TOOLTIP_Z_INDEX};
+
+/// Gap between the panel and the tooltip in pixels
+const TOOLTIP_OFFSET: f32 = 6.0;
+
+/// How many units of one type are selected
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct UnitTypeCount {
+    /// Type name
+    pub name: String,
+    /// Type icon
+    pub icon: Handle<Image>,
+    /// Number of selected units of this type
+    pub count: usize,
+}
+
+/// Aggregated stats of the selected units, computed by the game
+#[derive(Debug, Clone, Default, PartialEq, Reflect)]
+pub struct SelectionSummary {
+    /// Combined remaining health
+    pub health: f32,
+    /// Combined maximum health
+    pub max_health: f32,
+    /// Combined damage per second
+    pub dps: f32,
+    /// Selected units per type, in the order shown
+    pub types: Vec<UnitTypeCount>,
+}
+
+impl SelectionSummary {
+    /// Creates a summary without unit types
+    pub fn new(health: f32, max_health: f32, dps: f32) -> Self {
+        Self {
+            health,
+            max_health,
+            dps,
+            types: Vec::new(),
+        }
+    }
+
+    /// Adds a unit type and its count
+    pub fn with_type(mut self, name: impl Into<String>, icon: Handle<Image>, count: usize) -> Self {
+        self.types.push(UnitTypeCount {
+            name: name.into(),
+            icon,
+            count,
+        });
+        self
+    }
+
+    /// Total number of units over every type
+    pub fn unit_count(&self) -> usize {
+        self.types.iter().map(|unit_type| unit_type.count).sum()
+    }
+}
+
+/// Shows a [`SelectionSummary`] while the node it is on is hovered
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct SelectionSummaryTooltip {
+    /// Stats shown in the tooltip
+    pub summary: SelectionSummary,
+    /// UI time the current hover started
+    #[reflect(ignore)]
+    hovered_since: Option<f32>,
+    /// The spawned tooltip node
+    #[reflect(ignore)]
+    popup: Option<Entity>,
+}
+
+impl SelectionSummaryTooltip {
+    /// Creates a tooltip showing `summary`
+    pub fn new(summary: SelectionSummary) -> Self {
+        Self {
+            summary,
+            ..default()
+        }
+    }
+}
+
+/// The tooltip node of a [`SelectionSummaryTooltip`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SelectionSummaryPopup {
+    /// The entity carrying the [`SelectionSummaryTooltip`]
+    pub owner: Entity,
+}
+
+/// Spawns the rows of a summary into the tooltip node
+fn spawn_summary(parent: &mut ChildBuilder, summary: &SelectionSummary, theme: &Theme) {
+    let style = |font, color| TextStyle {
+        font,
+        font_size: 14.0,
+        color,
+    };
+    parent.spawn(TextBundle::from_section(
+        format!("{} units", summary.unit_count()),
+        style(UiFonts::BOLD, theme.text.normal),
+    ));
+    parent.spawn(TextBundle::from_sections([
+        TextSection::new("Health ", style(UiFonts::MEDIUM, theme.text.disabled)),
+        TextSection::new(
+            format!("{:.0} / {:.0}", summary.health, summary.max_health),
+            style(UiFonts::MEDIUM, theme.status.success),
+        ),
+    ]));
+    parent.spawn(TextBundle::from_sections([
+        TextSection::new("DPS ", style(UiFonts::MEDIUM, theme.text.disabled)),
+        TextSection::new(
+            format!("{:.0}", summary.dps),
+            style(UiFonts::MEDIUM, theme.text.normal),
+        ),
+    ]));
+    if summary.types.is_empty() {
+        return;
+    }
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                column_gap: Val::Px(8.0),
+                row_gap: Val::Px(4.0),
+                max_width: Val::Px(220.0),
+                margin: UiRect::top(Val::Px(2.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|types| {
+            for unit_type in &summary.types {
+                types
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(3.0),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|entry| {
+                        entry.spawn(ImageBundle {
+                            style: Style {
+                                width: Val::Px(20.0),
+                                height: Val::Px(20.0),
+                                ..default()
+                            },
+                            image: UiImage::new(unit_type.icon.clone()),
+                            ..default()
+                        });
+                        entry.spawn(TextBundle::from_section(
+                            format!("x{}", unit_type.count),
+                            style(UiFonts::MEDIUM, theme.text.normal),
+                        ));
+                    });
+            }
+        });
+}
+
+/// System to show, fill and place selection summary tooltips
+pub fn selection_summary_tooltip_system(
+    mut commands: Commands,
+    mut owners: Query<(
+        Entity,
+        &mut SelectionSummaryTooltip,
+        Option<&SelectionPanel>,
+    )>,
+    mut popups: Query<(
+        Entity,
+        &SelectionSummaryPopup,
+        &Node,
+        &mut Style,
+        &mut Visibility,
+    )>,
+    targets: Query<(&Node, &GlobalTransform)>,
+    (interaction_state, parents): (Res<UiInteractionState>, Query<&Parent>),
+    (settings, clock, theme): (Res<TooltipSettings>, Res<UiClock>, Res<Theme>),
+    bounds: Res<PopupBounds>,
+) {
+    let now = clock.elapsed_seconds();
+    let hovered = interaction_state.winner();
+
+    for (entity, mut tooltip, panel) in &mut owners {
+        let rebuild = tooltip.is_changed() || theme.is_changed();
+        let tooltip = tooltip.bypass_change_detection();
+        let over = hovered.is_some_and(|node| {
+            node == entity
+                || parents
+                    .iter_ancestors(node)
+                    .any(|ancestor| ancestor == entity)
+        });
+        let several = panel.is_none_or(|panel| panel.units.len() > 1);
+        tooltip.hovered_since = if over && several {
+            Some(tooltip.hovered_since.unwrap_or(now))
+        } else {
+            None
+        };
+
+        match tooltip.popup {
+            Some(popup) if rebuild => {
+                commands
+                    .entity(popup)
+                    .despawn_descendants()
+                    .with_children(|parent| {
+                        spawn_summary(parent, &tooltip.summary, &theme);
+                    });
+            }
+            None if tooltip.hovered_since.is_some() => {
+                let popup = commands
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(2.0),
+                                padding: UiRect::all(Val::Px(8.0)),
+                                ..default()
+                            },
+                            background_color: Color::srgba(0.1, 0.1, 0.1, 0.9).into(),
+                            visibility: Visibility::Hidden,
+                            z_index: ZIndex::Global(TOOLTIP_Z_INDEX),
+                            ..default()
+                        },
+                        SelectionSummaryPopup { owner: entity },
+                        Name::new("SelectionSummaryTooltip"),
+                    ))
+                    .with_children(|parent| {
+                        spawn_summary(parent, &tooltip.summary, &theme);
+                    })
+                    .id();
+                tooltip.popup = Some(popup);
+            }
+            _ => {}
+        }
+    }
+
+    for (popup, SelectionSummaryPopup { owner }, node, mut style, mut visibility) in &mut popups {
+        let Ok((_, tooltip, _)) = owners.get(*owner) else {
+            commands.entity(popup).despawn_recursive();
+            continue;
+        };
+        let ready = tooltip
+            .hovered_since
+            .is_some_and(|since| now - since >= settings.delay);
+        let Some(target) = targets
+            .get(*owner)
+            .ok()
+            .filter(|_| ready)
+            .map(|(node, transform)| node.logical_rect(transform))
+        else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        let size = node.size();
+        let position = TooltipPosition::Top;
+        let anchor = match bounds.area_at(target.center()) {
+            Some(area) => place_tooltip(
+                target,
+                size,
+                position,
+                TOOLTIP_OFFSET,
+                area,
+                settings.screen_margin,
+            ),
+            None => tooltip_anchor(target, size, position, TOOLTIP_OFFSET),
+        };
+        if style.left != Val::Px(anchor.x) || style.top != Val::Px(anchor.y) {
+            style.left = Val::Px(anchor.x);
+            style.top = Val::Px(anchor.y);
+        }
+        visibility.set_if_neq(Visibility::Visible);
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<SelectionSummaryTooltip>()
+        .register_type::<SelectionSummaryPopup>()
+        .add_systems(Update, selection_summary_tooltip_system);
+}
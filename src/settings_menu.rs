@@ -0,0 +1,372 @@
+//! Declarative settings screens
+//!
+//! [`SettingsMenuBuilder`] takes a list of [`SettingDefinition`]s per
+//! [`SettingsTab`] and spawns the whole tabbed settings screen: a
+//! [`settings_panel`] holding a tab bar, one [`settings_section`] per tab and a
+//! [`settings_row`] per setting. Each row carries a [`SettingRow`] and holds a
+//! label and a focusable control tagged with the setting's [`UiId`], with the
+//! help text as the control's tooltip:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{SettingDefinition, SettingsMenuBuilder, SettingsTab, Theme};
+//! fn setup(mut commands: Commands, theme: Res<Theme>) {
+//!     SettingsMenuBuilder::new(&theme)
+//!         .tab(SettingsTab::Video)
+//!         .setting(SettingDefinition::checkbox("vsync", "VSync", true).help("Limit frame rate to the display"))
+//!         .setting(SettingDefinition::dropdown("quality", "Quality", ["Low", "Medium", "High"], 1))
+//!         .tab(SettingsTab::Audio)
+//!         .setting(SettingDefinition::slider("master_volume", "Master volume", 0.0..=1.0, 0.8))
+//!         .spawn(&mut commands);
+//! }
+//! ```
+//!
+//! Read values back through [`Checkboxes`](crate::params::Checkboxes),
+//! [`Sliders`](crate::params::Sliders) and the widget events, or spawn with
+//! [`SettingsMenuBuilder::spawn_bound`] to bind every control to a settings
+//! resource and get Apply and Revert for it.
+
+use std::ops::RangeInclusive;
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::binding::Bind;
+use crate::builders::{spawn_tabbed_container, CheckboxBuilder, SliderBuilder};
+use crate::components::{
+    Dropdown, DropdownEntry, DropdownLabel, FocusState, Focusable, FocusableType, SettingRow,
+    Tooltip, TooltipPosition, UiId,
+};
+use crate::keybind::{key_bind_button_bundle, InputBinding, KeyBindButton};
+use crate::pending_changes::pending_changes_banner_bundle;
+use crate::styles::settings::{settings_panel, settings_row, settings_section};
+use crate::theme::Theme;
+use crate::types::SettingsTab;
+
+/// The control a setting is edited with
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingControl {
+    /// A slider over `min..=max`
+    Slider {
+        /// Minimum value
+        min: f32,
+        /// Maximum value
+        max: f32,
+        /// Initial value
+        value: f32,
+        /// Snap increment; continuous when absent
+        step: Option<f32>,
+    },
+    /// A checkbox
+    Checkbox {
+        /// Initial state
+        checked: bool,
+    },
+    /// A dropdown of options
+    Dropdown {
+        /// Options, group headers and separators
+        options: Vec<DropdownEntry>,
+        /// Initially selected option
+        selected: usize,
+    },
+    /// A [`KeyBindButton`] whose action is the setting id
+    KeyBind {
+        /// Initial binding
+        binding: Option<InputBinding>,
+    },
+}
+
+/// One row of a settings screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingDefinition {
+    /// Identifier set as the control's [`UiId`]
+    pub id: String,
+    /// Row label
+    pub label: String,
+    /// Tooltip shown while the control is hovered
+    pub help_text: Option<String>,
+    /// How the setting is edited
+    pub control: SettingControl,
+}
+
+impl SettingDefinition {
+    /// Creates a setting with the given control
+    pub fn new(id: impl Into<String>, label: impl Into<String>, control: SettingControl) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            help_text: None,
+            control,
+        }
+    }
+
+    /// A slider setting
+    pub fn slider(
+        id: impl Into<String>,
+        label: impl Into<String>,
+        range: RangeInclusive<f32>,
+        value: f32,
+    ) -> Self {
+        let control = SettingControl::Slider {
+            min: *range.start(),
+            max: *range.end(),
+            value,
+            step: None,
+        };
+        Self::new(id, label, control)
+    }
+
+    /// A checkbox setting
+    pub fn checkbox(id: impl Into<String>, label: impl Into<String>, checked: bool) -> Self {
+        Self::new(id, label, SettingControl::Checkbox { checked })
+    }
+
+    /// A dropdown setting; `selected` indexes into `options`
+    pub fn dropdown(
+        id: impl Into<String>,
+        label: impl Into<String>,
+        options: impl IntoIterator<Item = impl Into<DropdownEntry>>,
+        selected: usize,
+    ) -> Self {
+        let control = SettingControl::Dropdown {
+            options: options.into_iter().map(Into::into).collect(),
+            selected,
+        };
+        Self::new(id, label, control)
+    }
+
+    /// A key binding setting
+    pub fn keybind(
+        id: impl Into<String>,
+        label: impl Into<String>,
+        binding: Option<InputBinding>,
+    ) -> Self {
+        Self::new(id, label, SettingControl::KeyBind { binding })
+    }
+
+    /// Sets the help text shown as the control's tooltip
+    pub fn help(mut self, help_text: impl Into<String>) -> Self {
+        self.help_text = Some(help_text.into());
+        self
+    }
+
+    /// Snaps a slider setting to multiples of `step`
+    pub fn step(mut self, step: f32) -> Self {
+        if let SettingControl::Slider {
+            step: slider_step, ..
+        } = &mut self.control
+        {
+            *slider_step = Some(step);
+        }
+        self
+    }
+}
+
+/// Builds a tabbed settings screen from setting definitions
+#[derive(Debug, Clone)]
+pub struct SettingsMenuBuilder {
+    tabs: Vec<(SettingsTab, Vec<SettingDefinition>)>,
+    theme: Theme,
+}
+
+impl SettingsMenuBuilder {
+    /// Starts an empty settings screen
+    pub fn new(theme: &Theme) -> Self {
+        Self {
+            tabs: Vec::new(),
+            theme: theme.clone(),
+        }
+    }
+
+    /// Adds a tab; following settings are placed in it
+    pub fn tab(mut self, tab: SettingsTab) -> Self {
+        self.tabs.push((tab, Vec::new()));
+        self
+    }
+
+    /// Adds a setting to the last tab, starting a [`SettingsTab::Video`] tab if there is none
+    pub fn setting(mut self, setting: SettingDefinition) -> Self {
+        if self.tabs.is_empty() {
+            self.tabs.push((SettingsTab::default(), Vec::new()));
+        }
+        if let Some((_, settings)) = self.tabs.last_mut() {
+            settings.push(setting);
+        }
+        self
+    }
+
+    /// Adds several settings to the last tab
+    pub fn settings(self, settings: impl IntoIterator<Item = SettingDefinition>) -> Self {
+        settings.into_iter().fold(self, Self::setting)
+    }
+
+    /// Spawns the settings screen and returns its root panel
+    pub fn spawn(self, commands: &mut Commands) -> Entity {
+        self.spawn_controls(commands).0
+    }
+
+    /// Spawns the settings screen with each control bound to the field of `R`
+    /// named by its setting id, and a
+    /// [`PendingChangesBanner<R>`](crate::PendingChangesBanner) under the tabs
+    ///
+    /// Key bindings aren't bound, as [`Bind`] doesn't cover them. Add
+    /// [`BindPlugin<R>`](crate::BindPlugin) for the bindings to take effect.
+    pub fn spawn_bound<R: Resource + Reflect>(self, commands: &mut Commands) -> Entity {
+        let (panel, controls) = self.spawn_controls(commands);
+        for (control, setting) in controls {
+            if !matches!(setting.control, SettingControl::KeyBind { .. }) {
+                commands.entity(control).insert(Bind::<R>::new(setting.id));
+            }
+        }
+        let banner = commands.spawn(pending_changes_banner_bundle::<R>()).id();
+        commands.entity(panel).add_child(banner);
+        panel
+    }
+
+    /// Spawns the settings screen, returning its root panel and each setting's control
+    fn spawn_controls(self, commands: &mut Commands) -> (Entity, Vec<(Entity, SettingDefinition)>) {
+        let Self { tabs, theme } = self;
+
+        let names: Vec<String> = tabs.iter().map(|(tab, _)| format!("{tab:?}")).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let (container, panes) = spawn_tabbed_container(commands, &theme, &names);
+
+        let mut controls = Vec::new();
+        for ((_, settings), pane) in tabs.into_iter().zip(panes) {
+            let mut rows = Vec::new();
+            for setting in settings {
+                let (row, control) = spawn_setting_row(commands, &theme, &setting);
+                rows.push(row);
+                controls.push((control, setting));
+            }
+            let section = commands.spawn(settings_section()).push_children(&rows).id();
+            commands.entity(pane).add_child(section);
+        }
+
+        let panel = commands.spawn(settings_panel()).add_child(container).id();
+        (panel, controls)
+    }
+}
+
+/// Spawns a row with a label and the setting's control, returning both
+fn spawn_setting_row(
+    commands: &mut Commands,
+    theme: &Theme,
+    setting: &SettingDefinition,
+) -> (Entity, Entity) {
+    let font = UiFonts::MEDIUM;
+    let label = commands
+        .spawn(TextBundle::from_section(
+            setting.label.clone(),
+            TextStyle {
+                font: font.clone(),
+                font_size: 18.0,
+                color: theme.text.normal,
+            },
+        ))
+        .id();
+
+    let control = match &setting.control {
+        SettingControl::Slider {
+            min,
+            max,
+            value,
+            step,
+        } => {
+            let mut slider = SliderBuilder::new(theme)
+                .id(setting.id.clone())
+                .range(*min, *max)
+                .value(*value);
+            if let Some(step) = step {
+                slider = slider.step(*step);
+            }
+            if let Some(help_text) = &setting.help_text {
+                slider = slider.tooltip(help_text.clone());
+            }
+            slider.spawn(commands)
+        }
+        SettingControl::Checkbox { checked } => {
+            let mut checkbox = CheckboxBuilder::new(theme)
+                .id(setting.id.clone())
+                .checked(*checked);
+            if let Some(help_text) = &setting.help_text {
+                checkbox = checkbox.tooltip(help_text.clone());
+            }
+            checkbox.spawn(commands)
+        }
+        SettingControl::Dropdown { options, selected } => {
+            let text = commands
+                .spawn((
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font,
+                            font_size: 18.0,
+                            color: theme.text.normal,
+                        },
+                    ),
+                    DropdownLabel,
+                ))
+                .id();
+            commands
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(160.0),
+                            padding: UiRect::all(Val::Px(8.0)),
+                            ..default()
+                        },
+                        background_color: theme.button.normal.into(),
+                        ..default()
+                    },
+                    Dropdown {
+                        options: options.clone(),
+                        selected_index: *selected,
+                        ..default()
+                    },
+                    Focusable {
+                        state: FocusState::NotFocused,
+                        focus_type: FocusableType::Dropdown,
+                    },
+                    UiId::new(setting.id.clone()),
+                ))
+                .add_child(text)
+                .id()
+        }
+        SettingControl::KeyBind { binding } => commands
+            .spawn((
+                key_bind_button_bundle(KeyBindButton::new(setting.id.clone(), *binding)),
+                Focusable {
+                    state: FocusState::NotFocused,
+                    focus_type: FocusableType::Button,
+                },
+                UiId::new(setting.id.clone()),
+            ))
+            .id(),
+    };
+
+    // The builders add the tooltip to sliders and checkboxes themselves
+    let builds_tooltip = matches!(
+        setting.control,
+        SettingControl::Slider { .. } | SettingControl::Checkbox { .. }
+    );
+    if let (Some(help_text), false) = (&setting.help_text, builds_tooltip) {
+        commands.entity(control).insert(Tooltip {
+            text: help_text.clone(),
+            position: TooltipPosition::Top,
+            offset: 4.0,
+        });
+    }
+
+    let row = commands
+        .spawn((
+            settings_row(),
+            SettingRow {
+                label: setting.label.clone(),
+                help_text: setting.help_text.clone(),
+            },
+        ))
+        .push_children(&[label, control])
+        .id();
+    (row, control)
+}
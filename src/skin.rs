@@ -0,0 +1,366 @@
+//! Image-backed widget skins
+//!
+//! A [`WidgetSkin`] replaces a widget's flat background with an image, picked
+//! per interaction state from a [`SkinDefinition`]. Definitions can slice the
+//! image as a nine-patch so borders keep their size, and can index into a
+//! texture atlas so every state lives in one sheet. The interaction systems
+//! are untouched; the skin only changes how the widget is drawn.
+//!
+//! Definitions are assets, loaded from `.skin.ron` files:
+//!
+//! ```ron
+//! (
+//!     texture: "ui/button.png",
+//!     atlas: Some((tile_size: (64, 32), columns: 4, rows: 1)),
+//!     slice: Some((border: 8.0)),
+//!     normal: (index: 0),
+//!     hovered: Some((index: 1)),
+//!     pressed: Some((index: 2)),
+//!     disabled: Some((index: 3, tint: (0.8, 0.8, 0.8, 1.0))),
+//! )
+//! ```
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::components::{UiButton, UiCheckbox, UiSlider};
+use crate::interaction::UiInteractionState;
+use crate::quality::UiQuality;
+use crate::theme::Theme;
+
+/// Interaction state a skin draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum SkinStateKind {
+    /// Idle
+    Normal,
+    /// Under the pointer
+    Hovered,
+    /// Held down
+    Pressed,
+    /// Not accepting input
+    Disabled,
+}
+
+/// Image to draw for one state
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct SkinState {
+    /// Atlas index; ignored without an atlas
+    pub index: usize,
+    /// Color multiplied with the image
+    pub tint: Color,
+}
+
+impl Default for SkinState {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            tint: Color::WHITE,
+        }
+    }
+}
+
+/// How a skin image is drawn in each state
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct SkinDefinition {
+    /// The image, or atlas sheet, to draw
+    #[dependency]
+    pub texture: Handle<Image>,
+    /// Atlas layout when `texture` holds several states
+    #[dependency]
+    pub atlas: Option<Handle<TextureAtlasLayout>>,
+    /// Nine-slice borders; the image is stretched when `None`
+    pub slice: Option<TextureSlicer>,
+    /// Idle state
+    pub normal: SkinState,
+    /// Hovered state; falls back to `normal`
+    pub hovered: Option<SkinState>,
+    /// Pressed state; falls back to `hovered`, then `normal`
+    pub pressed: Option<SkinState>,
+    /// Disabled state; falls back to `normal`
+    pub disabled: Option<SkinState>,
+}
+
+impl SkinDefinition {
+    /// A single stretched image for every state
+    pub fn image(texture: Handle<Image>) -> Self {
+        Self {
+            texture,
+            atlas: None,
+            slice: None,
+            normal: SkinState::default(),
+            hovered: None,
+            pressed: None,
+            disabled: None,
+        }
+    }
+
+    /// Nine-slices the image with `border` pixels on every side
+    pub fn with_slice(mut self, border: f32) -> Self {
+        self.slice = Some(TextureSlicer {
+            border: BorderRect::square(border),
+            ..default()
+        });
+        self
+    }
+
+    /// Returns the image to draw for `kind`, applying fallbacks
+    pub fn state(&self, kind: SkinStateKind) -> SkinState {
+        match kind {
+            SkinStateKind::Normal => None,
+            SkinStateKind::Hovered => self.hovered,
+            SkinStateKind::Pressed => self.pressed.or(self.hovered),
+            SkinStateKind::Disabled => self.disabled,
+        }
+        .unwrap_or(self.normal)
+    }
+}
+
+/// Draws a widget with a [`SkinDefinition`] instead of its background color
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct WidgetSkin(pub Handle<SkinDefinition>);
+
+#[derive(Deserialize)]
+struct SkinFile {
+    texture: String,
+    #[serde(default)]
+    atlas: Option<AtlasFile>,
+    #[serde(default)]
+    slice: Option<SliceFile>,
+    #[serde(default)]
+    normal: StateFile,
+    #[serde(default)]
+    hovered: Option<StateFile>,
+    #[serde(default)]
+    pressed: Option<StateFile>,
+    #[serde(default)]
+    disabled: Option<StateFile>,
+}
+
+#[derive(Deserialize)]
+struct AtlasFile {
+    tile_size: (u32, u32),
+    columns: u32,
+    rows: u32,
+    #[serde(default)]
+    padding: Option<(u32, u32)>,
+}
+
+#[derive(Deserialize)]
+struct SliceFile {
+    border: f32,
+    #[serde(default = "default_corner_scale")]
+    max_corner_scale: f32,
+}
+
+fn default_corner_scale() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize, Default)]
+struct StateFile {
+    #[serde(default)]
+    index: usize,
+    #[serde(default)]
+    tint: Option<(f32, f32, f32, f32)>,
+}
+
+impl From<StateFile> for SkinState {
+    fn from(state: StateFile) -> Self {
+        Self {
+            index: state.index,
+            tint: state
+                .tint
+                .map_or(Color::WHITE, |(r, g, b, a)| Color::srgba(r, g, b, a)),
+        }
+    }
+}
+
+/// Error loading a `.skin.ron` file
+#[derive(Debug)]
+pub enum SkinLoadError {
+    /// The file couldn't be read
+    Io(std::io::Error),
+    /// The file isn't a valid skin definition
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for SkinLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkinLoadError::Io(error) => write!(f, "could not read skin: {error}"),
+            SkinLoadError::Ron(error) => write!(f, "invalid skin definition: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for SkinLoadError {}
+
+/// Loads [`SkinDefinition`]s from `.skin.ron` files
+#[derive(Default)]
+pub struct SkinDefinitionLoader;
+
+impl AssetLoader for SkinDefinitionLoader {
+    type Asset = SkinDefinition;
+    type Settings = ();
+    type Error = SkinLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<SkinDefinition, SkinLoadError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.map_err(SkinLoadError::Io)?;
+        let file: SkinFile = ron::de::from_bytes(&bytes).map_err(SkinLoadError::Ron)?;
+
+        let atlas = file.atlas.map(|atlas| {
+            let layout = TextureAtlasLayout::from_grid(
+                UVec2::new(atlas.tile_size.0, atlas.tile_size.1),
+                atlas.columns,
+                atlas.rows,
+                atlas.padding.map(|(x, y)| UVec2::new(x, y)),
+                None,
+            );
+            load_context.add_labeled_asset("atlas".to_string(), layout)
+        });
+
+        Ok(SkinDefinition {
+            texture: load_context.load(file.texture),
+            atlas,
+            slice: file.slice.map(|slice| TextureSlicer {
+                border: BorderRect::square(slice.border),
+                max_corner_scale: slice.max_corner_scale,
+                ..default()
+            }),
+            normal: file.normal.into(),
+            hovered: file.hovered.map(Into::into),
+            pressed: file.pressed.map(Into::into),
+            disabled: file.disabled.map(Into::into),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["skin.ron"]
+    }
+}
+
+/// System to draw skinned widgets from their definition and current state
+///
+/// Runs after the widget systems so the skin wins over their background
+/// colors, which are cleared to let the image show through. When
+/// [`UiQuality::textured_skins`] is off, skinned widgets go back to flat
+/// theme colors.
+pub fn widget_skin_system(
+    mut commands: Commands,
+    definitions: Res<Assets<SkinDefinition>>,
+    interaction_state: Res<UiInteractionState>,
+    quality: Res<UiQuality>,
+    theme: Res<Theme>,
+    mut skinned: Query<(
+        Entity,
+        &WidgetSkin,
+        Option<&Interaction>,
+        (Option<&UiButton>, Option<&UiCheckbox>, Option<&UiSlider>),
+        Option<&mut UiImage>,
+        Option<&mut TextureAtlas>,
+        Has<ImageScaleMode>,
+        Option<&mut BackgroundColor>,
+    )>,
+) {
+    for (entity, skin, interaction, widgets, image, atlas, has_scale_mode, background) in &mut skinned {
+        let disabled = match widgets {
+            (Some(button), ..) => button.disabled,
+            (_, Some(checkbox), _) => checkbox.disabled,
+            (.., Some(slider)) => slider.disabled,
+            _ => false,
+        };
+        let kind = match interaction {
+            _ if disabled => SkinStateKind::Disabled,
+            Some(Interaction::Pressed) if interaction_state.is_pressed(entity) => SkinStateKind::Pressed,
+            Some(Interaction::Pressed | Interaction::Hovered) => SkinStateKind::Hovered,
+            _ => SkinStateKind::Normal,
+        };
+        let mut entity_commands = commands.entity(entity);
+
+        if !quality.textured_skins() {
+            if image.is_some_and(|image| image.texture != UiImage::default().texture) {
+                entity_commands.insert(UiImage::default());
+            }
+            if atlas.is_some() || has_scale_mode {
+                entity_commands.remove::<(TextureAtlas, ImageScaleMode)>();
+            }
+            if let Some(mut background) = background.filter(|background| background.0 == Color::NONE) {
+                background.0 = match kind {
+                    SkinStateKind::Disabled => theme.button.disabled,
+                    SkinStateKind::Pressed => theme.button.pressed,
+                    SkinStateKind::Hovered => theme.button.hovered,
+                    SkinStateKind::Normal => theme.button.normal,
+                };
+            }
+            continue;
+        }
+
+        let Some(definition) = definitions.get(&skin.0) else {
+            continue;
+        };
+        let state = definition.state(kind);
+        match image {
+            Some(mut image) => {
+                if image.texture != definition.texture {
+                    image.texture = definition.texture.clone();
+                }
+                if image.color != state.tint {
+                    image.color = state.tint;
+                }
+            }
+            None => {
+                entity_commands.insert(UiImage::new(definition.texture.clone()).with_color(state.tint));
+            }
+        }
+        match (&definition.atlas, atlas) {
+            (Some(layout), Some(mut atlas)) => {
+                if atlas.layout != *layout {
+                    atlas.layout = layout.clone();
+                }
+                if atlas.index != state.index {
+                    atlas.index = state.index;
+                }
+            }
+            (Some(layout), None) => {
+                entity_commands.insert(TextureAtlas {
+                    layout: layout.clone(),
+                    index: state.index,
+                });
+            }
+            (None, Some(_)) => {
+                entity_commands.remove::<TextureAtlas>();
+            }
+            (None, None) => {}
+        }
+        match (&definition.slice, has_scale_mode) {
+            (Some(slicer), false) => {
+                entity_commands.insert(ImageScaleMode::Sliced(slicer.clone()));
+            }
+            (None, true) => {
+                entity_commands.remove::<ImageScaleMode>();
+            }
+            _ => {}
+        }
+        if let Some(mut background) = background {
+            if background.0 != Color::NONE {
+                background.0 = Color::NONE;
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<WidgetSkin>()
+        .init_asset::<SkinDefinition>()
+        .init_asset_loader::<SkinDefinitionLoader>()
+        .add_systems(PostUpdate, widget_skin_system);
+}
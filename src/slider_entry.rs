@@ -0,0 +1,253 @@
+//! Typed number entry for slider value labels
+//!
+//! A [`SliderValueLabel`] shows its slider's value in the slider's
+//! [`format`](UiSlider::format). Clicking it turns it into a small text
+//! field holding the number: typing replaces it, Enter sets the slider to the
+//! typed value (clamped to the range and snapped to the step) and flashes the
+//! label, and Escape or a click elsewhere puts the label back unchanged. Text
+//! that is not a number shakes the label and stays open for fixing. `SliderBuilder::show_value`
+//! adds a label after the track.
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+
+use crate::attention::{Flash, Shake};
+use crate::components::UiSlider;
+use crate::interaction::UiInteractionState;
+use crate::theme::Theme;
+
+/// Text being typed into a [`SliderValueLabel`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ValueEntry {
+    pub(crate) text: String,
+    /// The text is still the slider's value, and the first key replaces it
+    fresh: bool,
+}
+
+impl ValueEntry {
+    /// Starts an entry showing `text`, replaced by the first key typed
+    pub(crate) fn new(text: String) -> Self {
+        Self { text, fresh: true }
+    }
+
+    /// Applies typed keys, keeping only characters that can form a number
+    pub(crate) fn type_keys(&mut self, keys: &[Key]) {
+        for key in keys {
+            match key {
+                Key::Character(text) => {
+                    let accepted: String = text
+                        .chars()
+                        .filter_map(|c| match c {
+                            '0'..='9' | '.' | '-' => Some(c),
+                            ',' => Some('.'),
+                            _ => None,
+                        })
+                        .collect();
+                    if accepted.is_empty() {
+                        continue;
+                    }
+                    if self.fresh {
+                        self.text.clear();
+                    }
+                    self.text.push_str(&accepted);
+                }
+                Key::Backspace if self.fresh => self.text.clear(),
+                Key::Backspace => {
+                    self.text.pop();
+                }
+                _ => continue,
+            }
+            self.fresh = false;
+        }
+    }
+}
+
+/// A label showing a slider's value that can be clicked to type a number
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct SliderValueLabel {
+    /// The [`UiSlider`] shown
+    pub slider: Entity,
+    #[reflect(ignore)]
+    entry: Option<ValueEntry>,
+}
+
+impl SliderValueLabel {
+    /// Creates a label for `slider`
+    pub fn new(slider: Entity) -> Self {
+        Self {
+            slider,
+            entry: None,
+        }
+    }
+
+    /// Whether a number is being typed
+    pub fn is_editing(&self) -> bool {
+        self.entry.is_some()
+    }
+
+    /// Drops the number being typed, keeping the slider's value
+    pub fn cancel_entry(&mut self) {
+        self.entry = None;
+    }
+}
+
+/// Creates a value label for `slider`
+pub fn slider_value_label_bundle(
+    slider: Entity,
+    font: Handle<Font>,
+    theme: &Theme,
+) -> (TextBundle, Interaction, SliderValueLabel) {
+    (
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font,
+                    font_size: 18.0,
+                    color: theme.text.normal,
+                },
+            ),
+            style: Style {
+                min_width: Val::Px(48.0),
+                padding: UiRect::horizontal(Val::Px(4.0)),
+                ..default()
+            },
+            ..default()
+        },
+        Interaction::default(),
+        SliderValueLabel::new(slider),
+    )
+}
+
+/// System to start, type into, commit and cancel number entry
+pub fn slider_value_entry_system(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    mut labels: Query<(Entity, &mut SliderValueLabel)>,
+    mut sliders: Query<&mut UiSlider>,
+    interaction_state: Res<UiInteractionState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    (mouse_buttons, mut key_events): (Res<ButtonInput<MouseButton>>, EventReader<KeyboardInput>),
+) {
+    let clicked = labels.iter().find_map(|(entity, label)| {
+        let enabled = sliders
+            .get(label.slider)
+            .is_ok_and(|slider| !slider.disabled);
+        (enabled && interaction_state.just_pointer_pressed(entity)).then_some(entity)
+    });
+    let typed: Vec<Key> = key_events
+        .read()
+        .filter(|event| event.state.is_pressed())
+        .map(|event| event.logical_key.clone())
+        .collect();
+
+    for (entity, mut label) in &mut labels {
+        if clicked == Some(entity) {
+            if !label.is_editing() {
+                if let Ok(slider) = sliders.get(label.slider) {
+                    label.entry = Some(ValueEntry::new(slider.format_number(slider.value)));
+                }
+            }
+            continue;
+        }
+        let Some(entry) = &label.entry else {
+            continue;
+        };
+        // One field at a time, and clicking away cancels
+        if clicked.is_some() || mouse_buttons.just_pressed(MouseButton::Left) {
+            label.entry = None;
+            continue;
+        }
+        if keyboard_input.just_pressed(KeyCode::Escape) {
+            label.entry = None;
+            continue;
+        }
+        if keyboard_input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+            // Text that is not a number shakes and stays open for fixing
+            let Ok(value) = entry.text.parse::<f32>() else {
+                commands.entity(entity).insert(Shake::default());
+                continue;
+            };
+            if let Ok(mut slider) = sliders.get_mut(label.slider) {
+                let value = slider.snap(value);
+                if slider.value != value {
+                    slider.value = value;
+                    commands
+                        .entity(entity)
+                        .insert(Flash::new(theme.focus.highlight));
+                }
+            }
+            label.entry = None;
+            continue;
+        }
+
+        let mut entry = entry.clone();
+        entry.type_keys(&typed);
+        if label.entry.as_ref() != Some(&entry) {
+            label.entry = Some(entry);
+        }
+    }
+}
+
+/// System to show the slider's value, or the number being typed
+///
+/// Runs after the generic button colors, which would otherwise tint the label.
+pub fn slider_value_label_system(
+    theme: Res<Theme>,
+    mut labels: Query<(
+        Ref<SliderValueLabel>,
+        Ref<Interaction>,
+        &mut Text,
+        &mut BackgroundColor,
+    )>,
+    sliders: Query<Ref<UiSlider>>,
+) {
+    for (label, interaction, mut text, mut background) in &mut labels {
+        let Ok(slider) = sliders.get(label.slider) else {
+            continue;
+        };
+        if !label.is_changed()
+            && !interaction.is_changed()
+            && !slider.is_changed()
+            && !theme.is_changed()
+        {
+            continue;
+        }
+        let Some(section) = text.sections.first_mut() else {
+            continue;
+        };
+        match &label.entry {
+            Some(entry) => {
+                section.value = format!("{}|", entry.text);
+                section.style.color = theme.focus.text;
+                background.0 = theme.focus.highlight;
+            }
+            None => {
+                section.value = slider.display_value();
+                section.style.color = if slider.disabled {
+                    theme.text.disabled
+                } else {
+                    theme.text.normal
+                };
+                // Hovering hints that the label can be clicked
+                background.0 = if *interaction != Interaction::None && !slider.disabled {
+                    theme.button.hovered
+                } else {
+                    Color::NONE
+                };
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<SliderValueLabel>().add_systems(
+        Update,
+        (
+            slider_value_entry_system,
+            slider_value_label_system.after(crate::button_interaction_system),
+        )
+            .chain(),
+    );
+}
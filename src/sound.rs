@@ -0,0 +1,194 @@
+//! UI sound feedback
+//!
+//! Buttons, checkboxes, sliders and dropdowns play a sound from [`UiSounds`]
+//! when they are hovered, pressed or toggled, and an error sound when pressed
+//! while disabled. A widget can swap or mute its sounds with
+//! [`UiSoundOverride`]; dropdown options use their dropdown's override.
+//!
+//! Every sound is `None` by default, so the UI stays silent until the game
+//! fills in [`UiSounds`].
+
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+use crate::components::{
+    is_disabled, Dropdown, DropdownOption, UiCheckbox, UiSlider, WidgetDisabled,
+};
+use crate::interaction::UiInteractionState;
+
+/// Which feedback sound to play
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum UiSoundKind {
+    /// The pointer moved onto a widget
+    Hover,
+    /// A button, slider or dropdown was pressed
+    Click,
+    /// A checkbox changed state
+    Toggle,
+    /// A disabled widget was pressed
+    Error,
+}
+
+/// Default feedback sounds for all widgets
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct UiSounds {
+    /// Played when the pointer moves onto a widget
+    pub hover: Option<Handle<AudioSource>>,
+    /// Played when a widget is pressed
+    pub click: Option<Handle<AudioSource>>,
+    /// Played when a checkbox is toggled
+    pub toggle: Option<Handle<AudioSource>>,
+    /// Played when a disabled widget is pressed
+    pub error: Option<Handle<AudioSource>>,
+    /// Volume multiplier for every UI sound
+    pub volume: f32,
+}
+
+impl Default for UiSounds {
+    fn default() -> Self {
+        Self {
+            hover: None,
+            click: None,
+            toggle: None,
+            error: None,
+            volume: 1.0,
+        }
+    }
+}
+
+impl UiSounds {
+    /// Returns the sound for `kind`, if one is set
+    pub fn get(&self, kind: UiSoundKind) -> Option<&Handle<AudioSource>> {
+        match kind {
+            UiSoundKind::Hover => self.hover.as_ref(),
+            UiSoundKind::Click => self.click.as_ref(),
+            UiSoundKind::Toggle => self.toggle.as_ref(),
+            UiSoundKind::Error => self.error.as_ref(),
+        }
+    }
+}
+
+/// Replaces or mutes a widget's feedback sounds
+///
+/// Sounds left `None` fall back to [`UiSounds`].
+#[derive(Component, Debug, Clone, PartialEq, Reflect, Default)]
+#[reflect(Component)]
+pub struct UiSoundOverride {
+    /// Hover sound for this widget
+    pub hover: Option<Handle<AudioSource>>,
+    /// Click sound for this widget
+    pub click: Option<Handle<AudioSource>>,
+    /// Toggle sound for this widget
+    pub toggle: Option<Handle<AudioSource>>,
+    /// Error sound for this widget
+    pub error: Option<Handle<AudioSource>>,
+    /// Plays no sounds at all for this widget
+    pub muted: bool,
+}
+
+impl UiSoundOverride {
+    /// Silences the widget
+    pub fn muted() -> Self {
+        Self {
+            muted: true,
+            ..default()
+        }
+    }
+
+    /// Uses `sound` for `kind` on this widget
+    pub fn with(mut self, kind: UiSoundKind, sound: Handle<AudioSource>) -> Self {
+        *match kind {
+            UiSoundKind::Hover => &mut self.hover,
+            UiSoundKind::Click => &mut self.click,
+            UiSoundKind::Toggle => &mut self.toggle,
+            UiSoundKind::Error => &mut self.error,
+        } = Some(sound);
+        self
+    }
+
+    fn get(&self, kind: UiSoundKind) -> Option<&Handle<AudioSource>> {
+        match kind {
+            UiSoundKind::Hover => self.hover.as_ref(),
+            UiSoundKind::Click => self.click.as_ref(),
+            UiSoundKind::Toggle => self.toggle.as_ref(),
+            UiSoundKind::Error => self.error.as_ref(),
+        }
+    }
+}
+
+/// System to play feedback sounds for the hovered and pressed widgets
+///
+/// Reads the arbitrated [`UiInteractionState`], so at most one hover and one
+/// press sound play per frame no matter how many nodes overlap.
+pub fn ui_sound_system(
+    mut commands: Commands,
+    sounds: Res<UiSounds>,
+    interaction_state: Res<UiInteractionState>,
+    widgets: Query<
+        (
+            WidgetDisabled,
+            Option<&DropdownOption>,
+            Option<&UiSoundOverride>,
+        ),
+        Or<(With<Button>, With<UiCheckbox>, With<UiSlider>, With<Dropdown>)>,
+    >,
+    overrides: Query<&UiSoundOverride>,
+) {
+    if sounds.volume <= 0.0 {
+        return;
+    }
+    let current = interaction_state.current;
+    let previous = interaction_state.previous;
+
+    let hovered = current.hovered.filter(|&entity| previous.hovered != Some(entity));
+    let pressed = current
+        .pressed
+        .filter(|&entity| interaction_state.just_pointer_pressed(entity))
+        .or(current.activated);
+
+    let events = hovered
+        .map(|entity| (entity, false))
+        .into_iter()
+        .chain(pressed.map(|entity| (entity, true)));
+
+    for (entity, is_press) in events {
+        let Ok((widgets, option, sound_override)) = widgets.get(entity) else {
+            continue;
+        };
+        let disabled = is_disabled(widgets);
+        let kind = match widgets {
+            _ if !is_press => UiSoundKind::Hover,
+            _ if disabled => UiSoundKind::Error,
+            (_, Some(_), _) => UiSoundKind::Toggle,
+            _ => UiSoundKind::Click,
+        };
+        if disabled && kind == UiSoundKind::Hover {
+            continue;
+        }
+
+        let sound_override =
+            sound_override.or_else(|| option.and_then(|option| overrides.get(option.dropdown).ok()));
+        if sound_override.is_some_and(|sound_override| sound_override.muted) {
+            continue;
+        }
+        let Some(source) = sound_override
+            .and_then(|sound_override| sound_override.get(kind))
+            .or_else(|| sounds.get(kind))
+        else {
+            continue;
+        };
+
+        commands.spawn(AudioBundle {
+            source: source.clone(),
+            settings: PlaybackSettings::DESPAWN.with_volume(Volume::new(sounds.volume)),
+        });
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiSounds>()
+        .register_type::<UiSoundOverride>()
+        .init_resource::<UiSounds>()
+        .add_systems(Update, ui_sound_system);
+}
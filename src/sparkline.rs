@@ -0,0 +1,244 @@
+//! Sparklines
+//!
+//! A [`Sparkline`] is a small line chart of recent samples, such as income
+//! over the last minute next to a resource counter or ping next to the
+//! network indicator. It keeps a fixed number of samples, dropping the oldest
+//! as new ones are pushed, and scales to their range unless given a fixed one.
+//! Samples at or above a threshold take that threshold's color.
+//!
+//! The line is drawn from flat and upright segments, two nodes per sample
+//! spawned once, so updating it every frame only moves existing nodes.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{sparkline_bundle, Sparkline};
+//! #[derive(Component)]
+//! struct PingGraph;
+//!
+//! fn setup(mut commands: Commands) {
+//!     let ping = Sparkline::new(60)
+//!         .with_range(0.0, 300.0)
+//!         .with_threshold(150.0, Color::srgb(0.95, 0.7, 0.2))
+//!         .with_threshold(250.0, Color::srgb(0.9, 0.3, 0.3));
+//!     commands.spawn((sparkline_bundle(ping, 80.0, 20.0), PingGraph));
+//! }
+//!
+//! fn sample(mut graphs: Query<&mut Sparkline, With<PingGraph>>) {
+//!     for mut graph in &mut graphs {
+//!         graph.push(42.0);
+//!     }
+//! }
+//! ```
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+/// A small line chart of the most recent samples
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Sparkline {
+    /// Samples, oldest first
+    samples: VecDeque<f32>,
+    /// Number of samples kept
+    capacity: usize,
+    /// Fixed `(min, max)` range; scaled to the samples when `None`
+    pub range: Option<(f32, f32)>,
+    /// Line color
+    pub color: Color,
+    /// Colors for samples at or above each value, in ascending order
+    pub thresholds: Vec<(f32, Color)>,
+    /// Line thickness in pixels
+    pub thickness: f32,
+    /// Capacity the segments were spawned for
+    #[reflect(ignore)]
+    built: usize,
+}
+
+impl Sparkline {
+    /// Creates an empty sparkline keeping `capacity` samples
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+            range: None,
+            color: Color::srgb(0.35, 0.6, 0.95),
+            thresholds: Vec::new(),
+            thickness: 1.5,
+            built: 0,
+        }
+    }
+
+    /// Scales the line to a fixed range instead of the samples'
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Sets the line color
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Colors samples at or above `value`
+    pub fn with_threshold(mut self, value: f32, color: Color) -> Self {
+        self.thresholds.push((value, color));
+        self.thresholds.sort_by(|a, b| a.0.total_cmp(&b.0));
+        self
+    }
+
+    /// Appends a sample, dropping the oldest when full
+    pub fn push(&mut self, value: f32) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    /// Removes every sample
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Samples, oldest first
+    pub fn samples(&self) -> impl Iterator<Item = f32> + '_ {
+        self.samples.iter().copied()
+    }
+
+    /// The most recent sample
+    pub fn latest(&self) -> Option<f32> {
+        self.samples.back().copied()
+    }
+
+    /// Number of samples kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The `(min, max)` range the line is drawn over
+    pub fn bounds(&self) -> (f32, f32) {
+        let (min, max) = self.range.unwrap_or_else(|| {
+            self.samples()
+                .fold((f32::MAX, f32::MIN), |(min, max), value| {
+                    (min.min(value), max.max(value))
+                })
+        });
+        if min > max {
+            (0.0, 1.0)
+        } else if min == max {
+            (min - 0.5, max + 0.5)
+        } else {
+            (min, max)
+        }
+    }
+
+    /// The color of a sample, following the thresholds
+    pub fn color_of(&self, value: f32) -> Color {
+        self.thresholds
+            .iter()
+            .rev()
+            .find(|(threshold, _)| value >= *threshold)
+            .map_or(self.color, |(_, color)| *color)
+    }
+}
+
+/// A segment node of a [`Sparkline`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SparklineSegment {
+    /// Sample slot, from the left
+    pub slot: usize,
+    /// Whether this is the upright segment joining the previous sample
+    pub riser: bool,
+}
+
+/// Creates the container bundle for a sparkline of `width` by `height` pixels
+pub fn sparkline_bundle(sparkline: Sparkline, width: f32, height: f32) -> (NodeBundle, Sparkline) {
+    (
+        NodeBundle {
+            style: Style {
+                width: Val::Px(width),
+                height: Val::Px(height),
+                ..default()
+            },
+            ..default()
+        },
+        sparkline,
+    )
+}
+
+/// System to spawn sparkline segments and move them to the current samples
+pub fn sparkline_system(
+    mut commands: Commands,
+    mut sparklines: Query<(Entity, &mut Sparkline, Option<&Children>), Changed<Sparkline>>,
+    mut segments: Query<(&SparklineSegment, &mut Style, &mut BackgroundColor)>,
+) {
+    for (entity, mut sparkline, children) in &mut sparklines {
+        if sparkline.built != sparkline.capacity {
+            sparkline.bypass_change_detection().built = sparkline.capacity;
+            commands.entity(entity).despawn_descendants();
+            commands.entity(entity).with_children(|parent| {
+                for slot in 0..sparkline.capacity {
+                    for riser in [false, true] {
+                        parent.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    display: Display::None,
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            SparklineSegment { slot, riser },
+                        ));
+                    }
+                }
+            });
+            // Placed once the segments exist, on the next change
+            sparkline.set_changed();
+            continue;
+        }
+
+        let (min, max) = sparkline.bounds();
+        let height = |value: f32| ((value - min) / (max - min)).clamp(0.0, 1.0) * 100.0;
+        let slot_width = 100.0 / sparkline.capacity as f32;
+        // Newest sample in the rightmost slot
+        let first_slot = sparkline.capacity - sparkline.samples.len();
+
+        for &child in children.into_iter().flatten() {
+            let Ok((segment, mut style, mut background)) = segments.get_mut(child) else {
+                continue;
+            };
+            let Some(index) = segment.slot.checked_sub(first_slot) else {
+                style.display = Display::None;
+                continue;
+            };
+            let value = sparkline.samples[index];
+            let y = height(value);
+            background.0 = sparkline.color_of(value);
+            style.display = Display::Flex;
+            style.left = Val::Percent(segment.slot as f32 * slot_width);
+            if segment.riser {
+                let previous = index
+                    .checked_sub(1)
+                    .map_or(y, |previous| height(sparkline.samples[previous]));
+                style.width = Val::Px(sparkline.thickness);
+                style.bottom = Val::Percent(previous.min(y));
+                style.height = Val::Percent((previous - y).abs());
+            } else {
+                style.width = Val::Percent(slot_width);
+                style.bottom = Val::Percent(y);
+                style.height = Val::Px(sparkline.thickness);
+                style.margin.bottom = Val::Px(-sparkline.thickness / 2.0);
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Sparkline>()
+        .register_type::<SparklineSegment>()
+        .add_systems(Update, sparkline_system);
+}
@@ -0,0 +1,254 @@
+//! Split buttons
+//!
+//! A [`SplitButton`] is a button with two segments: the main one fires the
+//! primary action and sends [`SplitButtonPressed`], and a narrow arrow beside
+//! it opens a drop-down [`Menu`](crate::menu::Menu) of alternate actions.
+//! Picking one sends [`SplitButtonActionSelected`] with the entry's id.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{split_button_bundle, MenuEntry, SplitButton, SplitButtonActionSelected};
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn(split_button_bundle(SplitButton::new(
+//!         "Save",
+//!         vec![
+//!             MenuEntry::item("save_as", "Save As…"),
+//!             MenuEntry::item("save_copy", "Save a Copy…"),
+//!         ],
+//!     )));
+//! }
+//!
+//! fn alternate(mut selected: EventReader<SplitButtonActionSelected>) {
+//!     for event in selected.read() {
+//!         info!("{} picked", event.id.0);
+//!     }
+//! }
+//! ```
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::components::UiId;
+use crate::interaction::UiInteractionState;
+use crate::menu::{open_context_menu, Menu, MenuEntry, MenuItemSelected};
+use crate::theme::Theme;
+
+/// A button with a primary action and a menu of alternate ones
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct SplitButton {
+    /// Label of the primary action
+    pub label: String,
+    /// Alternate actions listed by the arrow segment
+    pub actions: Vec<MenuEntry>,
+    /// Label the segments were spawned for
+    #[reflect(ignore)]
+    built: Option<String>,
+    /// Whether the arrow was pressed with the menu closed
+    #[reflect(ignore)]
+    open_requested: bool,
+}
+
+impl SplitButton {
+    /// Creates a split button for the primary action `label`
+    pub fn new(label: impl Into<String>, actions: Vec<MenuEntry>) -> Self {
+        Self {
+            label: label.into(),
+            actions,
+            built: None,
+            open_requested: false,
+        }
+    }
+}
+
+/// Which segment of a [`SplitButton`] a [`SplitButtonPart`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum SplitButtonPartKind {
+    /// The main segment
+    Primary,
+    /// The arrow opening the alternate actions
+    Arrow,
+}
+
+/// A segment spawned for a [`SplitButton`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SplitButtonPart {
+    /// The split button this segment belongs to
+    pub button: Entity,
+    /// Which segment this is
+    pub kind: SplitButtonPartKind,
+}
+
+/// Sent when the main segment of a [`SplitButton`] is pressed
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitButtonPressed {
+    /// The split button entity
+    pub button: Entity,
+}
+
+/// Sent when an alternate action is picked from a [`SplitButton`]'s menu
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct SplitButtonActionSelected {
+    /// The split button entity
+    pub button: Entity,
+    /// The picked entry's [`MenuEntry::Item::id`]
+    pub id: UiId,
+    /// Position of the entry among the entries of its level
+    pub index: usize,
+}
+
+/// Creates the container bundle for a split button
+pub fn split_button_bundle(button: SplitButton) -> (NodeBundle, SplitButton) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Stretch,
+                column_gap: Val::Px(1.0),
+                ..default()
+            },
+            ..default()
+        },
+        button,
+    )
+}
+
+/// System to spawn the segments of split buttons, and rebuild them when the
+/// label changes
+pub fn split_button_spawn_system(
+    mut commands: Commands,
+    mut buttons: Query<(Entity, &mut SplitButton), Changed<SplitButton>>,
+    theme: Res<Theme>,
+) {
+    for (entity, mut button) in &mut buttons {
+        if button.built.as_ref() == Some(&button.label) {
+            continue;
+        }
+        let label = button.label.clone();
+        button.bypass_change_detection().built = Some(label.clone());
+        commands.entity(entity).despawn_descendants();
+
+        let segment = |kind, padding| {
+            (
+                ButtonBundle {
+                    style: Style {
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        padding,
+                        ..default()
+                    },
+                    background_color: theme.button.normal.into(),
+                    ..default()
+                },
+                SplitButtonPart {
+                    button: entity,
+                    kind,
+                },
+            )
+        };
+        let text_style = |font| TextStyle {
+            font,
+            font_size: 18.0,
+            color: theme.text.normal,
+        };
+        commands.entity(entity).with_children(|button| {
+            button
+                .spawn(segment(
+                    SplitButtonPartKind::Primary,
+                    UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                ))
+                .with_children(|primary| {
+                    primary.spawn(TextBundle::from_section(label, text_style(UiFonts::MEDIUM)));
+                });
+            button
+                .spawn(segment(
+                    SplitButtonPartKind::Arrow,
+                    UiRect::axes(Val::Px(6.0), Val::Px(6.0)),
+                ))
+                .with_children(|arrow| {
+                    arrow.spawn(TextBundle::from_section("v", text_style(UiFonts::BOLD)));
+                });
+        });
+    }
+}
+
+/// System to send [`SplitButtonPressed`] and open the menu of alternate actions
+///
+/// Runs before menus are dismissed: pressing the arrow while the menu is
+/// open lets the dismissal close it, and otherwise the menu opens on the next
+/// frame, once the press that would dismiss it has passed.
+pub fn split_button_input_system(
+    mut commands: Commands,
+    parts: Query<(Entity, &SplitButtonPart)>,
+    mut buttons: Query<(Entity, &mut SplitButton, &Node, &GlobalTransform)>,
+    menus: Query<&Menu>,
+    interaction_state: Res<UiInteractionState>,
+    mut pressed_events: EventWriter<SplitButtonPressed>,
+) {
+    for (entity, mut button, node, transform) in &mut buttons {
+        if !button.open_requested {
+            continue;
+        }
+        button.bypass_change_detection().open_requested = false;
+        let rect = node.logical_rect(transform);
+        open_context_menu(
+            &mut commands,
+            entity,
+            button.actions.clone(),
+            Vec2::new(rect.min.x, rect.max.y),
+        );
+    }
+
+    for (entity, part) in &parts {
+        if !interaction_state.just_pressed(entity) {
+            continue;
+        }
+        match part.kind {
+            SplitButtonPartKind::Primary => {
+                pressed_events.send(SplitButtonPressed {
+                    button: part.button,
+                });
+            }
+            SplitButtonPartKind::Arrow => {
+                let open = menus.iter().any(|menu| menu.owner == Some(part.button));
+                if let (false, Ok((_, mut button, ..))) = (open, buttons.get_mut(part.button)) {
+                    button.bypass_change_detection().open_requested = true;
+                }
+            }
+        }
+    }
+}
+
+/// System to send [`SplitButtonActionSelected`] for items picked from split button menus
+pub fn split_button_selected_system(
+    mut selected_events: EventReader<MenuItemSelected>,
+    buttons: Query<(), With<SplitButton>>,
+    mut action_events: EventWriter<SplitButtonActionSelected>,
+) {
+    for event in selected_events.read() {
+        let Some(button) = event.owner.filter(|&owner| buttons.contains(owner)) else {
+            continue;
+        };
+        action_events.send(SplitButtonActionSelected {
+            button,
+            id: event.id.clone(),
+            index: event.index,
+        });
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<SplitButton>()
+        .register_type::<SplitButtonPart>()
+        .add_event::<SplitButtonPressed>()
+        .add_event::<SplitButtonActionSelected>()
+        .add_systems(
+            Update,
+            (
+                split_button_spawn_system,
+                split_button_input_system.before(crate::menu::menu_dismiss_system),
+                split_button_selected_system,
+            ),
+        );
+}
@@ -0,0 +1,407 @@
+//! Per-player HUD roots for local split-screen
+//!
+//! Each player's HUD hangs off a [`HudRoot`] node that is rendered to that
+//! player's camera with [`TargetCamera`]. Every root keeps its own focus in
+//! [`HudFocus`], is navigated only by the gamepad bound to it, and can be
+//! scaled independently of the others:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{hud_root_bundle, HudRoot};
+//! fn setup(mut commands: Commands, cameras: Query<Entity, With<Camera>>) {
+//!     for (player, camera) in cameras.iter().enumerate() {
+//!         let root = HudRoot::new(player as u8)
+//!             .with_gamepad(Gamepad::new(player))
+//!             .with_scale(0.75);
+//!         commands.spawn(hud_root_bundle(camera, root));
+//!     }
+//! }
+//! ```
+//!
+//! Focusable elements under a root are tagged with [`HudScope`] and left out
+//! of the global [`UiFocus`](crate::UiFocus) navigation, and their gamepads
+//! no longer drive it.
+
+use bevy::prelude::*;
+
+use crate::components::{is_disabled, FocusState, Focusable, WidgetDisabled};
+use crate::focus::{focus_activation_system, spatial_neighbor, GamepadNavInput};
+use crate::interaction::{InteractionArbitrationSet, UiInteractionState};
+use crate::modal::modal_block_system;
+use crate::scale::WidgetScale;
+use crate::theme::Theme;
+
+/// Root node of one player's HUD
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct HudRoot {
+    /// Local player index
+    pub player: u8,
+    /// Gamepad that navigates this root; `None` leaves it pointer-only
+    pub gamepad: Option<Gamepad>,
+    /// Whether the mouse cursor may interact with this root
+    pub pointer: bool,
+    /// Size multiplier for pixel sizes and fonts under this root
+    pub scale: f32,
+}
+
+impl HudRoot {
+    /// A root for `player` that accepts the mouse and has no gamepad yet
+    pub fn new(player: u8) -> Self {
+        Self {
+            player,
+            gamepad: None,
+            pointer: true,
+            scale: 1.0,
+        }
+    }
+
+    /// Binds a gamepad to this root
+    pub fn with_gamepad(mut self, gamepad: Gamepad) -> Self {
+        self.gamepad = Some(gamepad);
+        self
+    }
+
+    /// Sets whether the mouse cursor may interact with this root
+    pub fn with_pointer(mut self, pointer: bool) -> Self {
+        self.pointer = pointer;
+        self
+    }
+
+    /// Sets the size multiplier
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+/// The focused element within a [`HudRoot`]
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct HudFocus {
+    /// The focused entity, if any
+    pub focused: Option<Entity>,
+    /// Whether the stick must re-center before the next step
+    stick_engaged: bool,
+}
+
+/// Marks a [`Focusable`] that belongs to a [`HudRoot`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct HudScope {
+    /// The root the element belongs to
+    pub root: Entity,
+}
+
+/// Scale applied to a node under a scaled [`HudRoot`], or to any node while
+/// [`WidgetScale`] isn't 1
+///
+/// Inserted the first time the node is scaled. Pixel lengths and font sizes
+/// written to a scaled node are taken as already scaled, so multiply
+/// unscaled values by [`applied`](Self::applied) when restyling it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct HudScaleBase {
+    /// Scale currently applied
+    applied: f32,
+}
+
+impl HudScaleBase {
+    /// The scale currently applied to the node's lengths and font sizes
+    pub fn applied(&self) -> f32 {
+        self.applied
+    }
+}
+
+/// Creates a full-viewport HUD root rendered to `camera`
+pub fn hud_root_bundle(camera: Entity, root: HudRoot) -> (NodeBundle, TargetCamera, HudRoot, HudFocus) {
+    (
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            ..default()
+        },
+        TargetCamera(camera),
+        root,
+        HudFocus::default(),
+    )
+}
+
+/// Returns the nearest [`HudRoot`] ancestor of `entity`, including itself
+fn owning_root(entity: Entity, roots: &Query<&HudRoot>, parents: &Query<&Parent>) -> Option<Entity> {
+    std::iter::once(entity)
+        .chain(parents.iter_ancestors(entity))
+        .find(|&ancestor| roots.contains(ancestor))
+}
+
+/// System to tag focusable elements with the root they belong to
+pub fn hud_scope_system(
+    mut commands: Commands,
+    focusables: Query<(Entity, Option<&HudScope>), With<Focusable>>,
+    roots: Query<&HudRoot>,
+    parents: Query<&Parent>,
+) {
+    for (entity, scope) in &focusables {
+        let root = owning_root(entity, &roots, &parents);
+        match (root, scope) {
+            (Some(root), Some(scope)) if scope.root == root => {}
+            (Some(root), _) => {
+                commands.entity(entity).insert(HudScope { root });
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<HudScope>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// System to move each root's focus with its own gamepad
+pub fn hud_focus_navigation_system(
+    mut roots: Query<(Entity, &HudRoot, &mut HudFocus)>,
+    mut focusables: Query<(
+        Entity,
+        &HudScope,
+        &mut Focusable,
+        &mut BackgroundColor,
+        &mut BorderColor,
+        &GlobalTransform,
+        Option<&Interaction>,
+    )>,
+    widgets: Query<WidgetDisabled>,
+    gamepad: GamepadNavInput,
+    theme: Res<Theme>,
+) {
+    for (root, hud, mut focus) in &mut roots {
+        let in_root = |scope: &HudScope| scope.root == root;
+        let enabled = |entity| !widgets.get(entity).is_ok_and(is_disabled);
+        if focus.focused.is_some_and(|entity| {
+            !enabled(entity) || !focusables.get(entity).is_ok_and(|item| in_root(item.1))
+        }) {
+            focus.focused = None;
+        }
+
+        let Some(pad) = hud.gamepad else {
+            continue;
+        };
+        let mut stick_engaged = focus.stick_engaged;
+        let direction = gamepad.direction_on(pad, &mut stick_engaged);
+        if focus.stick_engaged != stick_engaged {
+            focus.stick_engaged = stick_engaged;
+        }
+        let Some(direction) = direction else {
+            continue;
+        };
+
+        let positions = focusables
+            .iter()
+            .filter(|item| in_root(item.1) && enabled(item.0))
+            .map(|item| (item.0, item.5.translation().truncate()));
+        match focus.focused.and_then(|entity| focusables.get(entity).ok()) {
+            Some((current, .., transform, _)) => {
+                let from = transform.translation().truncate();
+                let candidates = positions.filter(|(entity, _)| *entity != current);
+                if let Some(next) = spatial_neighbor(from, direction, candidates) {
+                    focus.focused = Some(next);
+                }
+            }
+            None => {
+                // Nothing focused yet: start from the top-left element
+                focus.focused = positions
+                    .min_by(|a, b| a.1.y.total_cmp(&b.1.y).then(a.1.x.total_cmp(&b.1.x)))
+                    .map(|(entity, _)| entity);
+            }
+        }
+    }
+
+    for (entity, scope, mut focusable, mut bg_color, mut border_color, _, interaction) in
+        &mut focusables
+    {
+        let is_focused = roots
+            .get(scope.root)
+            .is_ok_and(|(_, _, focus)| focus.focused == Some(entity));
+        let state = if is_focused {
+            FocusState::Focused
+        } else {
+            FocusState::NotFocused
+        };
+        if focusable.state == state {
+            continue;
+        }
+        focusable.state = state;
+        if is_focused {
+            *bg_color = theme.focus.highlight.into();
+            *border_color = theme.focus.border.into();
+        } else {
+            // Back to the look the widget has without focus
+            *bg_color = if widgets.get(entity).is_ok_and(is_disabled) {
+                theme.button.disabled
+            } else {
+                theme.button_color(interaction.copied().unwrap_or_default())
+            }
+            .into();
+            *border_color = BorderColor::default();
+        }
+    }
+}
+
+/// System to activate each root's focused element with its gamepad's A (south) button
+///
+/// Only one activation is delivered per frame; if two players press on the
+/// same frame, the later root in query order wins and the other retries.
+pub fn hud_focus_activation_system(
+    roots: Query<(&HudRoot, &HudFocus)>,
+    gamepad: GamepadNavInput,
+    mut interaction_state: ResMut<UiInteractionState>,
+) {
+    for (hud, focus) in &roots {
+        let (Some(pad), Some(focused)) = (hud.gamepad, focus.focused) else {
+            continue;
+        };
+        if gamepad.just_pressed_on(pad, GamepadButtonType::South) {
+            interaction_state.activate(focused);
+        }
+    }
+}
+
+/// System to keep the mouse away from roots with `pointer` disabled
+///
+/// Clears the resolved hover and press targets when they belong to such a
+/// root, so a mouse user can't click through another player's HUD.
+pub fn hud_pointer_routing_system(
+    roots: Query<&HudRoot>,
+    parents: Query<&Parent>,
+    mut interaction_state: ResMut<UiInteractionState>,
+) {
+    if roots.iter().all(|root| root.pointer) {
+        return;
+    }
+    let blocked = |entity: Entity| {
+        owning_root(entity, &roots, &parents)
+            .and_then(|root| roots.get(root).ok())
+            .is_some_and(|root| !root.pointer)
+    };
+
+    let frame = &mut interaction_state.current;
+    if frame.hovered.is_some_and(blocked) {
+        frame.hovered = None;
+    }
+    if frame.pressed.is_some_and(blocked) {
+        frame.pressed = None;
+    }
+}
+
+fn scale_val(val: Val, scale: f32) -> Val {
+    match val {
+        Val::Px(px) => Val::Px(px * scale),
+        other => other,
+    }
+}
+
+fn scale_rect(rect: UiRect, scale: f32) -> UiRect {
+    UiRect {
+        left: scale_val(rect.left, scale),
+        right: scale_val(rect.right, scale),
+        top: scale_val(rect.top, scale),
+        bottom: scale_val(rect.bottom, scale),
+    }
+}
+
+/// Multiplies every pixel length of `style` by `factor`, leaving the rest of
+/// it alone
+fn scale_lengths(style: &mut Style, factor: f32) {
+    for val in [
+        &mut style.left,
+        &mut style.right,
+        &mut style.top,
+        &mut style.bottom,
+        &mut style.width,
+        &mut style.height,
+        &mut style.min_width,
+        &mut style.min_height,
+        &mut style.max_width,
+        &mut style.max_height,
+        &mut style.flex_basis,
+        &mut style.row_gap,
+        &mut style.column_gap,
+    ] {
+        *val = scale_val(*val, factor);
+    }
+    style.margin = scale_rect(style.margin, factor);
+    style.padding = scale_rect(style.padding, factor);
+    style.border = scale_rect(style.border, factor);
+}
+
+/// System to apply [`WidgetScale`] and each root's `scale` to the nodes beneath it
+///
+/// Pixel lengths in [`Style`] and text font sizes are multiplied by the
+/// widget scale, times the root's scale under a [`HudRoot`]. Nodes are only
+/// touched once their scale differs from 1, and only looked at when they are
+/// spawned or a scale changes.
+pub fn hud_scale_system(
+    mut commands: Commands,
+    widget_scale: Res<WidgetScale>,
+    roots: Query<Ref<HudRoot>>,
+    parents: Query<&Parent>,
+    mut nodes: Query<(
+        Entity,
+        &mut Style,
+        Option<&mut Text>,
+        Option<&mut HudScaleBase>,
+    )>,
+) {
+    let rescale = widget_scale.is_changed() || roots.iter().any(|root| root.is_changed());
+    for (entity, mut style, text, base) in &mut nodes {
+        if !rescale && !style.is_added() {
+            continue;
+        }
+        let root_scale = parents
+            .iter_ancestors(entity)
+            .find_map(|ancestor| roots.get(ancestor).ok())
+            .map_or(1.0, |root| root.scale);
+        let scale = widget_scale.0 * root_scale;
+        // Lengths are rescaled in place, so the rest of the style, such as a
+        // display or position set since, is kept
+        let applied = base.as_ref().map_or(1.0, |base| base.applied);
+        // A zero scale couldn't be undone, so it is ignored
+        if applied == scale || scale <= 0.0 {
+            continue;
+        }
+        let factor = scale / applied;
+        scale_lengths(&mut style, factor);
+        if let Some(mut text) = text {
+            for section in text.sections.iter_mut() {
+                section.style.font_size *= factor;
+            }
+        }
+        match base {
+            Some(mut base) => base.applied = scale,
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(HudScaleBase { applied: scale });
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<HudRoot>()
+        .register_type::<HudFocus>()
+        .register_type::<HudScope>()
+        .register_type::<HudScaleBase>()
+        .add_systems(
+            PreUpdate,
+            (hud_focus_activation_system, hud_pointer_routing_system)
+                .after(InteractionArbitrationSet)
+                .after(focus_activation_system)
+                .before(modal_block_system),
+        )
+        .add_systems(
+            Update,
+            (hud_scope_system, hud_focus_navigation_system, hud_scale_system).chain(),
+        );
+}
@@ -0,0 +1,98 @@
+//! Declarative state machines for widgets
+//!
+//! A [`UiStateMachine`] holds a widget's current state, and the state type
+//! declares the moves it is allowed to make as `(from, to)` pairs in
+//! [`UiState::TRANSITIONS`]. Moving along an undeclared transition is
+//! refused, so a control can't end up, say, dragging something that was
+//! never pressed. Dropdowns, modals and drag and drop keep their phase in
+//! one; composite widgets can do the same:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{UiState, UiStateMachine};
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+//! enum DoorState {
+//!     Closed,
+//!     Opening,
+//!     Open,
+//! }
+//!
+//! impl UiState for DoorState {
+//!     const TRANSITIONS: &'static [(Self, Self)] = &[
+//!         (DoorState::Closed, DoorState::Opening),
+//!         (DoorState::Opening, DoorState::Open),
+//!         (DoorState::Open, DoorState::Closed),
+//!     ];
+//! }
+//!
+//! let mut door = UiStateMachine::new(DoorState::Closed);
+//! assert!(!door.go(DoorState::Open));
+//! assert!(door.go(DoorState::Opening));
+//! ```
+//!
+//! The transitions live on the state type rather than in the machine, so a
+//! machine is just its current state, and one rebuilt through reflection,
+//! say from a scene, keeps the same rules.
+
+use std::fmt::Debug;
+
+use bevy::prelude::*;
+
+/// A state of a [`UiStateMachine`]
+pub trait UiState: Copy + Eq + Debug + Send + Sync + 'static {
+    /// Allowed `(from, to)` moves
+    const TRANSITIONS: &'static [(Self, Self)];
+}
+
+/// A widget's current state, moved only along its [`UiState::TRANSITIONS`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct UiStateMachine<S: 'static> {
+    current: S,
+}
+
+impl<S: UiState + Default> Default for UiStateMachine<S> {
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S: UiState> UiStateMachine<S> {
+    /// Creates a machine in `initial`
+    pub const fn new(initial: S) -> Self {
+        Self { current: initial }
+    }
+
+    /// The current state
+    pub fn state(&self) -> S {
+        self.current
+    }
+
+    /// Whether the machine is in `state`
+    pub fn is(&self, state: S) -> bool {
+        self.current == state
+    }
+
+    /// Whether a transition from the current state to `to` is declared
+    pub fn can_go(&self, to: S) -> bool {
+        S::TRANSITIONS
+            .iter()
+            .any(|&(from, target)| from == self.current && target == to)
+    }
+
+    /// Moves to `to` if the transition is declared; returns whether it moved
+    pub fn go(&mut self, to: S) -> bool {
+        if !self.can_go(to) {
+            return false;
+        }
+        self.current = to;
+        true
+    }
+
+    /// States reachable from the current one
+    pub fn next_states(&self) -> impl Iterator<Item = S> + '_ {
+        S::TRANSITIONS
+            .iter()
+            .filter(|&&(from, _)| from == self.current)
+            .map(|&(_, to)| to)
+    }
+}
@@ -0,0 +1,320 @@
+//! Step indicators for multi-stage processes
+//!
+//! A [`StepIndicator`] draws numbered circles joined by lines, one per stage
+//! of a wizard, matchmaking or a campaign mission. Each [`Step`] is done,
+//! current, upcoming or failed, colored from the theme. Spawn one with
+//! [`step_indicator_bundle`]; the circles, labels and connectors are added as
+//! children and follow later edits to the component.
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::theme::Theme;
+use crate::utils::first_section;
+
+/// Diameter of a step circle in pixels
+const CIRCLE_SIZE: f32 = 24.0;
+
+/// Progress of one [`Step`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum StepState {
+    /// Finished
+    Done,
+    /// In progress
+    Current,
+    /// Not reached yet
+    #[default]
+    Upcoming,
+    /// Failed
+    Error,
+}
+
+/// One stage of a [`StepIndicator`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Step {
+    /// Text under the circle
+    pub label: String,
+    /// Progress of this stage
+    pub state: StepState,
+}
+
+/// A row of numbered steps
+#[derive(Component, Debug, Clone, PartialEq, Reflect, Default)]
+#[reflect(Component)]
+pub struct StepIndicator {
+    /// The stages, in order
+    pub steps: Vec<Step>,
+}
+
+impl StepIndicator {
+    /// Creates an indicator with every step upcoming
+    pub fn new(labels: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            steps: labels
+                .into_iter()
+                .map(|label| Step {
+                    label: label.into(),
+                    state: StepState::Upcoming,
+                })
+                .collect(),
+        }
+    }
+
+    /// Starts the indicator at step `index`
+    pub fn with_current(mut self, index: usize) -> Self {
+        self.set_current(index);
+        self
+    }
+
+    /// Marks steps before `index` done, `index` current and the rest upcoming
+    pub fn set_current(&mut self, index: usize) {
+        for (i, step) in self.steps.iter_mut().enumerate() {
+            step.state = match i.cmp(&index) {
+                std::cmp::Ordering::Less => StepState::Done,
+                std::cmp::Ordering::Equal => StepState::Current,
+                std::cmp::Ordering::Greater => StepState::Upcoming,
+            };
+        }
+    }
+
+    /// Marks step `index` as failed
+    pub fn set_error(&mut self, index: usize) {
+        if let Some(step) = self.steps.get_mut(index) {
+            step.state = StepState::Error;
+        }
+    }
+
+    /// Index of the current step, if any
+    pub fn current(&self) -> Option<usize> {
+        self.steps.iter().position(|step| step.state == StepState::Current)
+    }
+}
+
+/// What a [`StepIndicatorPart`] draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum StepPartKind {
+    /// The column holding a circle and its label
+    Step,
+    /// The circle
+    Circle,
+    /// The number inside the circle
+    Number,
+    /// The text under the circle
+    Label,
+    /// The line leading into a step from the previous one
+    Connector,
+}
+
+/// A node spawned for a [`StepIndicator`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct StepIndicatorPart {
+    /// The indicator this node belongs to
+    pub indicator: Entity,
+    /// Index into [`StepIndicator::steps`]
+    pub index: usize,
+    /// What this node draws
+    pub kind: StepPartKind,
+}
+
+/// Creates the container bundle for a step indicator
+pub fn step_indicator_bundle(indicator: StepIndicator) -> (NodeBundle, StepIndicator) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::FlexStart,
+                ..default()
+            },
+            ..default()
+        },
+        indicator,
+    )
+}
+
+/// Border and fill of a circle, and its number color
+fn step_colors(state: StepState, theme: &Theme) -> (Color, Color, Color) {
+    match state {
+        StepState::Done => (theme.status.success, theme.status.success, theme.text.normal),
+        StepState::Current => (theme.focus.border, Color::NONE, theme.focus.border),
+        StepState::Upcoming => (theme.text.disabled, Color::NONE, theme.text.disabled),
+        StepState::Error => (theme.status.error, theme.status.error, theme.text.normal),
+    }
+}
+
+/// System to rebuild an indicator's nodes when its number of steps changes
+pub fn step_indicator_sync_system(
+    mut commands: Commands,
+    indicators: Query<(Entity, &StepIndicator, Option<&Children>), Changed<StepIndicator>>,
+    parts: Query<&StepIndicatorPart>,
+) {
+    for (entity, indicator, children) in &indicators {
+        let existing = parts
+            .iter_many(children.into_iter().flatten())
+            .filter(|part| part.kind == StepPartKind::Step)
+            .count();
+        if existing == indicator.steps.len() {
+            continue;
+        }
+
+        let font = UiFonts::BOLD;
+        let part = |index, kind| StepIndicatorPart {
+            indicator: entity,
+            index,
+            kind,
+        };
+        commands.entity(entity).despawn_descendants().with_children(|row| {
+            for index in 0..indicator.steps.len() {
+                if index > 0 {
+                    row.spawn((
+                        NodeBundle {
+                            style: Style {
+                                flex_grow: 1.0,
+                                min_width: Val::Px(16.0),
+                                height: Val::Px(2.0),
+                                margin: UiRect::top(Val::Px(CIRCLE_SIZE / 2.0 - 1.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        part(index, StepPartKind::Connector),
+                    ));
+                }
+                row.spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: Val::Px(4.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    part(index, StepPartKind::Step),
+                ))
+                .with_children(|step| {
+                    step.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Px(CIRCLE_SIZE),
+                                height: Val::Px(CIRCLE_SIZE),
+                                border: UiRect::all(Val::Px(2.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            border_radius: BorderRadius::MAX,
+                            ..default()
+                        },
+                        part(index, StepPartKind::Circle),
+                    ))
+                    .with_children(|circle| {
+                        circle.spawn((
+                            TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                            ),
+                            part(index, StepPartKind::Number),
+                        ));
+                    });
+                    step.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 14.0,
+                                ..default()
+                            },
+                        ),
+                        part(index, StepPartKind::Label),
+                    ));
+                });
+            }
+        });
+    }
+}
+
+/// System to refresh step colors and text when the indicator or theme changes
+pub fn step_indicator_update_system(
+    theme: Res<Theme>,
+    indicators: Query<Ref<StepIndicator>>,
+    mut parts: Query<(
+        &StepIndicatorPart,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+        Option<&mut Text>,
+    )>,
+) {
+    for (part, background, border, text) in &mut parts {
+        let Ok(indicator) = indicators.get(part.indicator) else {
+            continue;
+        };
+        if !indicator.is_changed() && !theme.is_changed() {
+            continue;
+        }
+        let Some(step) = indicator.steps.get(part.index) else {
+            continue;
+        };
+        let (circle_border, circle_fill, number_color) = step_colors(step.state, &theme);
+
+        match part.kind {
+            StepPartKind::Step => {}
+            StepPartKind::Circle => {
+                if let Some(mut border) = border {
+                    border.0 = circle_border;
+                }
+                if let Some(mut background) = background {
+                    background.0 = circle_fill;
+                }
+            }
+            StepPartKind::Number => {
+                if let Some(mut text) = text {
+                    let section = first_section(&mut text);
+                    section.value = match step.state {
+                        StepState::Error => "!".to_string(),
+                        _ => (part.index + 1).to_string(),
+                    };
+                    section.style.color = number_color;
+                }
+            }
+            StepPartKind::Label => {
+                if let Some(mut text) = text {
+                    let section = first_section(&mut text);
+                    section.value.clone_from(&step.label);
+                    section.style.color = match step.state {
+                        StepState::Upcoming => theme.text.disabled,
+                        StepState::Error => theme.status.error,
+                        StepState::Done | StepState::Current => theme.text.normal,
+                    };
+                }
+            }
+            StepPartKind::Connector => {
+                // A connector is lit once the step it leaves is done
+                let reached = indicator
+                    .steps
+                    .get(part.index - 1)
+                    .is_some_and(|previous| previous.state == StepState::Done);
+                if let Some(mut background) = background {
+                    background.0 = if reached {
+                        theme.status.success
+                    } else {
+                        theme.text.disabled
+                    };
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<StepIndicator>()
+        .register_type::<StepIndicatorPart>()
+        .add_systems(
+            Update,
+            (step_indicator_sync_system, step_indicator_update_system).chain(),
+        );
+}
@@ -3,6 +3,8 @@
 use bevy::prelude::*;
 use bevy::ui::{Style, UiRect, Val};
 
+use crate::assets::UiFonts;
+
 /// Common UI styles
 pub mod common {
     use super::*;
@@ -25,9 +27,9 @@ pub mod common {
     }
 
     /// Default text style for buttons
-    pub fn button_text(asset_server: &AssetServer) -> TextStyle {
+    pub fn button_text() -> TextStyle {
         TextStyle {
-            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+            font: UiFonts::BOLD,
             font_size: 24.0,
             color: Color::WHITE,
         }
@@ -50,18 +52,18 @@ pub mod common {
     }
 
     /// Style for subsection titles
-    pub fn subsection_title_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    pub fn subsection_title_style() -> TextStyle {
         TextStyle {
-            font: asset_server.load("fonts/FiraSans-SemiBold.ttf"),
+            font: UiFonts::SEMIBOLD,
             font_size: 18.0,
             color: Color::WHITE,
         }
     }
 
     /// Style for regular text content
-    pub fn regular_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    pub fn regular_text_style() -> TextStyle {
         TextStyle {
-            font: asset_server.load("fonts/FiraSans-Regular.ttf"),
+            font: UiFonts::REGULAR,
             font_size: 14.0,
             color: Color::WHITE,
         }
@@ -82,9 +84,9 @@ pub mod menu {
     }
 
     /// Menu title text style
-    pub fn title_text(asset_server: &AssetServer) -> TextStyle {
+    pub fn title_text() -> TextStyle {
         TextStyle {
-            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+            font: UiFonts::BOLD,
             font_size: 48.0,
             color: Color::WHITE,
         }
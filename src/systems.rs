@@ -2,33 +2,58 @@
 
 use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
-use bevy::ui::{Interaction, BackgroundColor, BorderColor};
+use bevy::ui::{Interaction, BackgroundColor, BorderColor, UiSystem};
+use crate::assets::UiFonts;
 use crate::components::*;
-use crate::colors;
+use crate::events::{
+    DropdownSelectionChanged, SetEnabled, TabChanged, UiButtonClicked, UiCheckboxToggled, UiSliderChanged,
+};
+use crate::focus::{focus_order_system, spatial_neighbor, FocusOrder, GamepadNavInput, UiFocus};
+use crate::interaction::{PointerCapture, UiInteractionState};
+use crate::placement::PopupBounds;
+use crate::split_screen::HudScope;
+use bevy::window::PrimaryWindow;
+use bevy::utils::HashMap;
+use crate::theme::Theme;
+use crate::utils::{first_section, first_section_is};
 
 /// System to handle button interactions and visual feedback
 pub fn button_interaction_system(
     mut interaction_query: Query<
         (
-            &Interaction,
+            Entity,
+            Ref<Interaction>,
             &mut UiButton,
             &mut BackgroundColor,
             Option<&Children>,
         ),
-        (Changed<Interaction>, With<Button>),
+        With<Button>,
     >,
     mut text_query: Query<&mut Text>,
+    interaction_state: Res<UiInteractionState>,
+    theme: Res<Theme>,
+    mut clicked_events: EventWriter<UiButtonClicked>,
 ) {
-    for (interaction, mut button, mut bg_color, children) in &mut interaction_query {
+    for (entity, interaction, mut button, mut bg_color, children) in &mut interaction_query {
+        // Clicks and focus activations both arrive through the arbitration state
+        if interaction_state.just_pressed(entity) && !button.disabled {
+            clicked_events.send(UiButtonClicked(entity));
+        }
+        // Disabled buttons keep their disabled colors, set by `disabled_visual_system`
+        if !interaction.is_changed() || button.disabled {
+            continue;
+        }
+
+        // A press only counts if this button won arbitration for the frame
+        let interaction = match *interaction {
+            Interaction::Pressed if !interaction_state.is_pressed(entity) => Interaction::Hovered,
+            other => other,
+        };
         button.hovered = matches!(interaction, Interaction::Hovered);
         button.pressed = matches!(interaction, Interaction::Pressed);
 
         // Update background color based on state
-        *bg_color = match interaction {
-            Interaction::Pressed => colors::button::PRESSED.into(),
-            Interaction::Hovered => colors::button::HOVERED.into(),
-            _ => colors::button::NORMAL.into(),
-        };
+        *bg_color = theme.button_color(interaction).into();
 
         // Update text color if this button has text children
         if let Some(children) = children {
@@ -36,9 +61,8 @@ pub fn button_interaction_system(
                 if let Ok(mut text) = text_query.get_mut(child) {
                     for section in text.sections.iter_mut() {
                         section.style.color = match interaction {
-                            Interaction::Pressed => colors::focus::TEXT,
-                            Interaction::Hovered => colors::text::NORMAL,
-                            _ => colors::text::NORMAL,
+                            Interaction::Pressed => theme.focus.text,
+                            _ => theme.text.normal,
                         };
                     }
                 }
@@ -49,137 +73,365 @@ pub fn button_interaction_system(
 
 /// System to handle checkbox interactions
 pub fn checkbox_interaction_system(
-    mut query: Query<(&Interaction, &mut UiCheckbox, &mut BackgroundColor), Changed<Interaction>>,
-    mut text_query: Query<&mut Text>,
+    mut query: Query<(Entity, &mut UiCheckbox, &mut BackgroundColor)>,
+    interaction_state: Res<UiInteractionState>,
+    theme: Res<Theme>,
+    mut toggled_events: EventWriter<UiCheckboxToggled>,
 ) {
-    for (interaction, mut checkbox, mut bg_color) in &mut query {
-        if *interaction == Interaction::Pressed && !checkbox.disabled {
+    for (entity, mut checkbox, mut bg_color) in &mut query {
+        if interaction_state.just_pressed(entity) && !checkbox.disabled {
             checkbox.checked = !checkbox.checked;
+            toggled_events.send(UiCheckboxToggled {
+                entity,
+                checked: checkbox.checked,
+            });
             // Update checkbox background
             *bg_color = if checkbox.checked {
-                colors::button::PRESSED.into()
+                theme.button.pressed.into()
             } else {
-                colors::button::NORMAL.into()
+                theme.button.normal.into()
             };
         }
     }
 }
 
-/// System to handle slider interactions and visual feedback
+/// System to keep each checkbox's [`CheckmarkNode`] in step with its state
+///
+/// Checkboxes built by hand without a marked child use their first text
+/// child as the mark, or get a new one with a warning, instead of never
+/// showing their state.
+pub fn checkmark_system(
+    mut commands: Commands,
+    checkboxes: Query<(Entity, Ref<UiCheckbox>, Option<&Children>)>,
+    mut marks: Query<(&mut Text, Has<CheckmarkNode>)>,
+    theme: Res<Theme>,
+) {
+    for (entity, checkbox, children) in &checkboxes {
+        if !checkbox.is_changed() {
+            continue;
+        }
+        let symbol = if checkbox.checked { "X" } else { "" };
+        let children = children.map_or(&[][..], |children| &children[..]);
+        let marked = children
+            .iter()
+            .find(|&&child| marks.get(child).is_ok_and(|(_, marked)| marked));
+        let mark = marked.or_else(|| children.iter().find(|&&child| marks.contains(child)));
+        let Some(&mark) = mark else {
+            warn!("Checkbox {entity} has no check mark text, spawning one");
+            let mark = commands
+                .spawn((
+                    TextBundle::from_section(
+                        symbol,
+                        TextStyle {
+                            font: UiFonts::BOLD,
+                            font_size: 20.0,
+                            color: theme.text.normal,
+                        },
+                    ),
+                    CheckmarkNode,
+                ))
+                .id();
+            commands.entity(entity).add_child(mark);
+            continue;
+        };
+        let Ok((mut text, marked)) = marks.get_mut(mark) else {
+            continue;
+        };
+        if !marked {
+            commands.entity(mark).insert(CheckmarkNode);
+        }
+        match text.sections.first_mut() {
+            Some(section) if section.value == symbol => {}
+            Some(section) => section.value = symbol.to_string(),
+            None => text.sections.push(TextSection::from(symbol)),
+        }
+    }
+}
+
+/// System to handle slider dragging
+///
+/// Pressing a slider captures the pointer; while captured, the cursor's X
+/// position over the track sets the value, snapped to `step`. The capture is
+/// released on mouse-up, even if the cursor has left the slider.
 pub fn slider_interaction_system(
-    mut query: Query<(&Interaction, &UiSlider, &mut Style), (Changed<Interaction>, With<UiSlider>)>,
+    mut sliders: Query<(Entity, &mut UiSlider, &Node, &GlobalTransform)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    interaction_state: Res<UiInteractionState>,
+    mut capture: ResMut<PointerCapture>,
 ) {
-    for (interaction, slider, mut style) in &mut query {
-        match interaction {
-            Interaction::Pressed => {
-                style.width = Val::Px(slider.value * 100.0);
-            }
-            Interaction::Hovered => {
-                style.width = Val::Px(slider.value * 100.0);
-            }
-            Interaction::None => {
-                style.width = Val::Px(slider.value * 100.0);
-            }
+    let cursor = windows.get_single().ok().and_then(Window::cursor_position);
+
+    for (entity, mut slider, node, transform) in &mut sliders {
+        if interaction_state.just_pointer_pressed(entity) && !slider.disabled {
+            capture.capture(entity);
+        }
+        if !capture.is_captured(entity) {
+            continue;
+        }
+        if slider.disabled {
+            capture.release();
+            continue;
+        }
+
+        let Some(cursor) = cursor else {
+            continue;
+        };
+        let rect = node.logical_rect(transform);
+        if rect.width() <= 0.0 {
+            continue;
+        }
+        let value = slider.value_at((cursor.x - rect.min.x) / rect.width());
+        if slider.value != value {
+            slider.value = value;
         }
     }
 }
 
-/// System to update progress bars
-fn update_progress_bars(
-    mut query: Query<(&ProgressBar, &mut Style, &Children), Changed<ProgressBar>>,
-    mut text_query: Query<&mut Text>,
+/// System to position slider fill and handle children from the slider value
+///
+/// Both are placed in percent of the track, and the handle is centered on its
+/// position with a negative margin of half its width, so neither depends on a
+/// layout that hasn't run yet or goes stale when the track is resized.
+pub fn slider_visual_system(
+    sliders: Query<(&UiSlider, &Children), Changed<UiSlider>>,
+    mut fills: Query<&mut Style, (With<SliderFill>, Without<SliderHandle>)>,
+    mut handles: Query<&mut Style, (With<SliderHandle>, Without<SliderFill>)>,
 ) {
-    for (progress_bar, mut style, children) in &mut query {
-        // Update width based on progress
-        if let Val::Percent(_) = style.width {
-            style.width = Val::Percent((progress_bar.value * 100.0).clamp(0.0, 100.0));
-        }
-        
-        // Update text if enabled
-        if progress_bar.show_text {
-            if let Ok(mut text) = text_query.get_mut(children[0]) {
-                text.sections[0].value = format!("{:.0}%", progress_bar.value * 100.0);
+    for (slider, children) in &sliders {
+        let percent = Val::Percent(slider.normalized() * 100.0);
+        for &child in children.iter() {
+            if let Ok(mut style) = fills.get_mut(child) {
+                style.width = percent;
+            } else if let Ok(mut style) = handles.get_mut(child) {
+                style.left = percent;
+                if let Val::Px(width) = style.width {
+                    style.margin.left = Val::Px(-width / 2.0);
+                }
             }
         }
     }
 }
 
-/// System to handle tooltip visibility and positioning
-pub fn tooltip_system(
-    mut commands: Commands,
-    mut tooltip_query: Query<(Entity, &Tooltip, &Parent), Without<Text>>,
-    parent_query: Query<(), With<Node>>,
-    asset_server: Res<AssetServer>,
+/// System to emit [`UiSliderChanged`] whenever a slider's value changes
+///
+/// Tracks the last seen value per slider so edits to other fields (range,
+/// format, disabled) don't produce events.
+pub fn slider_changed_event_system(
+    sliders: Query<(Entity, Ref<UiSlider>)>,
+    mut removed: RemovedComponents<UiSlider>,
+    mut last_values: Local<HashMap<Entity, f32>>,
+    mut changed_events: EventWriter<UiSliderChanged>,
 ) {
-    for (entity, tooltip, _) in &mut tooltip_query {
-        if parent_query.get(entity).is_ok() {
-            commands.entity(entity).insert(
-                TextBundle::from_section(
-                    &tooltip.text,
-                    TextStyle {
-                        font: asset_server.load("fonts/FiraSans-Medium.ttf"),
-                        font_size: 16.0,
-                        color: colors::WHITE,
-                    },
-                )
-                .with_style(Style {
-                    position_type: PositionType::Absolute,
-                    padding: UiRect::all(Val::Px(8.0)),
-                    ..default()
-                }),
-            );
+    for entity in removed.read() {
+        last_values.remove(&entity);
+    }
+
+    for (entity, slider) in &sliders {
+        if !slider.is_changed() {
+            continue;
+        }
+        let previous = last_values.insert(entity, slider.value);
+        if previous.is_some_and(|value| value != slider.value) {
+            changed_events.send(UiSliderChanged {
+                entity,
+                value: slider.value,
+            });
+        }
+    }
+}
+
+/// System to apply [`SetEnabled`] events to buttons, checkboxes and sliders
+pub fn set_enabled_system(
+    mut events: EventReader<SetEnabled>,
+    mut widgets: Query<(
+        Option<&mut UiButton>,
+        Option<&mut UiCheckbox>,
+        Option<&mut UiSlider>,
+    )>,
+) {
+    for event in events.read() {
+        let Ok((button, checkbox, slider)) = widgets.get_mut(event.entity) else {
+            continue;
+        };
+        let disabled = !event.enabled;
+        if let Some(mut button) = button.filter(|button| button.disabled != disabled) {
+            button.disabled = disabled;
+            button.hovered = false;
+            button.pressed = false;
+        }
+        if let Some(mut checkbox) = checkbox.filter(|checkbox| checkbox.disabled != disabled) {
+            checkbox.disabled = disabled;
+        }
+        if let Some(mut slider) = slider.filter(|slider| slider.disabled != disabled) {
+            slider.disabled = disabled;
         }
     }
 }
 
-/// System to handle keyboard navigation between focusable elements
+/// System to recolor widgets when they are disabled or enabled
+///
+/// Tracks the last seen state per widget, like [`slider_changed_event_system`],
+/// so only actual switches repaint. Disabled widgets use the theme's disabled
+/// button and text colors; re-enabled ones get their normal colors back.
+pub fn disabled_visual_system(
+    mut widgets: Query<
+        (Entity, WidgetDisabled, &mut BackgroundColor, Option<&Children>),
+        Or<(With<UiButton>, With<UiCheckbox>, With<UiSlider>)>,
+    >,
+    mut handles: Query<
+        &mut BackgroundColor,
+        (With<SliderHandle>, Without<UiButton>, Without<UiCheckbox>, Without<UiSlider>),
+    >,
+    mut texts: Query<&mut Text>,
+    theme: Res<Theme>,
+    mut last_states: Local<HashMap<Entity, bool>>,
+) {
+    last_states.retain(|&entity, _| widgets.contains(entity));
+
+    for (entity, widget, mut bg_color, children) in &mut widgets {
+        let disabled = is_disabled(widget);
+        // Widgets are spawned with the right colors; only repaint on a switch
+        let previous = last_states.insert(entity, disabled);
+        if previous.unwrap_or(false) == disabled {
+            continue;
+        }
+
+        let (_, checkbox, slider) = widget;
+        let text_color = if disabled {
+            theme.text.disabled
+        } else {
+            theme.text.normal
+        };
+        if slider.is_some() {
+            // The track keeps its color; the handle shows the state
+            for &child in children.into_iter().flatten() {
+                if let Ok(mut handle) = handles.get_mut(child) {
+                    handle.0 = if disabled {
+                        theme.button.disabled
+                    } else {
+                        theme.slider.handle
+                    };
+                }
+            }
+            continue;
+        }
+
+        bg_color.0 = match (disabled, checkbox) {
+            (true, _) => theme.button.disabled,
+            (false, Some(checkbox)) if checkbox.checked => theme.button.pressed,
+            _ => theme.button.normal,
+        };
+        for &child in children.into_iter().flatten() {
+            if let Ok(mut text) = texts.get_mut(child) {
+                for section in &mut text.sections {
+                    section.style.color = text_color;
+                }
+            }
+        }
+    }
+}
+
+/// System to handle keyboard and gamepad navigation between focusable elements
+///
+/// Tab cycles through elements in [`FocusOrder`]. Gamepad D-pad and left stick
+/// move focus spatially to the nearest element in the pressed direction.
+/// Elements inside a split-screen [`HudRoot`](crate::HudRoot) are skipped;
+/// each root tracks its own focus. Disabled widgets are skipped too, and
+/// lose focus when they become disabled.
 pub fn focus_navigation_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut focus_query: Query<(Entity, &mut Focusable, &mut BackgroundColor, &mut BorderColor, &GlobalTransform)>,
-    mut current_focus: Local<Option<Entity>>,
+    gamepad: GamepadNavInput,
+    mut focus_query: Query<
+        (
+            Entity,
+            &mut Focusable,
+            &mut BackgroundColor,
+            &mut BorderColor,
+            &GlobalTransform,
+            WidgetDisabled,
+            Option<&Interaction>,
+        ),
+        Without<HudScope>,
+    >,
+    mut focus: ResMut<UiFocus>,
+    (order, mut stick_engaged): (Res<FocusOrder>, Local<bool>),
+    theme: Res<Theme>,
 ) {
+    if focus
+        .focused
+        .is_some_and(|entity| focus_query.get(entity).is_ok_and(|item| is_disabled(item.5)))
+    {
+        focus.focused = None;
+    }
+    let current_focus = focus
+        .focused
+        .filter(|&entity| focus_query.contains(entity));
+
     // Handle tab navigation
     if keyboard_input.just_pressed(KeyCode::Tab) {
-        let mut focusables: Vec<_> = focus_query.iter_mut().collect();
-        
-        // Sort by vertical then horizontal position
-        focusables.sort_by(|a, b| {
-            let a_pos = a.4.translation();
-            let b_pos = b.4.translation();
-            a_pos.y.total_cmp(&b_pos.y).then(a_pos.x.total_cmp(&b_pos.x))
+        let next = order.next(current_focus, |entity| {
+            focus_query.get(entity).is_ok_and(|item| !is_disabled(item.5))
         });
-        
-        if let Some(current) = *current_focus {
-            if let Some(pos) = focusables.iter().position(|(e, _, _, _, _)| *e == current) {
-                let next_pos = (pos + 1) % focusables.len();
-                *current_focus = Some(focusables[next_pos].0);
+        if next.is_some() {
+            focus.focused = next;
+        }
+    } else if let Some(direction) = gamepad.direction(&mut stick_engaged) {
+        let positions = focus_query
+            .iter()
+            .filter(|item| !is_disabled(item.5))
+            .map(|item| (item.0, item.4.translation().truncate()));
+        match current_focus.and_then(|current| focus_query.get(current).ok()) {
+            Some((current, _, _, _, transform, ..)) => {
+                let from = transform.translation().truncate();
+                let candidates = positions.filter(|(entity, _)| *entity != current);
+                if let Some(next) = spatial_neighbor(from, direction, candidates) {
+                    focus.focused = Some(next);
+                }
+            }
+            None => {
+                // Nothing focused yet: start from the top-left element
+                focus.focused = positions
+                    .min_by(|a, b| a.1.y.total_cmp(&b.1.y).then(a.1.x.total_cmp(&b.1.x)))
+                    .map(|(entity, _)| entity);
             }
-        } else if !focusables.is_empty() {
-            *current_focus = Some(focusables[0].0);
         }
     }
     
-    // Update focus states
-    for (entity, mut focusable, mut bg_color, mut border_color, _) in &mut focus_query {
-        let is_focused = *current_focus == Some(entity);
-        
-        focusable.state = if is_focused {
+    // Update focus states, only touching elements whose state changed
+    for (entity, mut focusable, mut bg_color, mut border_color, _, widget, interaction) in
+        &mut focus_query
+    {
+        let is_focused = focus.focused == Some(entity);
+        let state = if is_focused {
             FocusState::Focused
         } else {
             FocusState::NotFocused
         };
-        
+        if focusable.state == state {
+            continue;
+        }
+        focusable.state = state;
+
         // Visual feedback
         if is_focused {
-            *bg_color = colors::focus::HIGHLIGHT.into();
-            *border_color = colors::focus::BORDER.into();
+            *bg_color = theme.focus.highlight.into();
+            *border_color = theme.focus.border.into();
+        } else {
+            // Back to the look the widget has without focus
+            *bg_color = if is_disabled(widget) {
+                theme.button.disabled
+            } else {
+                theme.button_color(interaction.copied().unwrap_or_default())
+            }
+            .into();
+            *border_color = BorderColor::default();
         }
     }
 }
 
 /// System to update visual feedback for focused elements
 pub fn focus_visual_system(
-    mut query: Query<(
+    _query: Query<(
         &Focusable,
         &mut BackgroundColor,
         &mut BorderColor,
@@ -189,78 +441,344 @@ pub fn focus_visual_system(
     // TODO: Implement focus visuals
 }
 
-/// System to handle dropdown interactions
+/// System to open and close dropdowns when their header is pressed
 pub fn dropdown_system(
+    mut dropdown_query: Query<(Entity, &mut Dropdown)>,
+    interaction_state: Res<UiInteractionState>,
+) {
+    for (entity, mut dropdown) in &mut dropdown_query {
+        if interaction_state.just_pressed(entity) {
+            let open = !dropdown.is_open();
+            dropdown.set_open(open);
+        }
+    }
+}
+
+/// System to apply option clicks to their dropdown
+///
+/// Updates `selected_index`, closes the list and emits
+/// [`DropdownSelectionChanged`] when the selection actually changes. Presses
+/// on disabled options are ignored and leave the list open.
+pub fn dropdown_option_system(
+    options: Query<(Entity, &DropdownOption)>,
+    mut dropdowns: Query<&mut Dropdown>,
+    interaction_state: Res<UiInteractionState>,
+    mut selection_events: EventWriter<DropdownSelectionChanged>,
+) {
+    for (entity, option) in &options {
+        if !interaction_state.just_pressed(entity) {
+            continue;
+        }
+        let Ok(mut dropdown) = dropdowns.get_mut(option.dropdown) else {
+            continue;
+        };
+        let selectable = dropdown
+            .options
+            .get(option.index)
+            .is_some_and(DropdownEntry::is_selectable);
+        if !selectable {
+            continue;
+        }
+        dropdown.set_open(false);
+        if dropdown.selected_index != option.index {
+            dropdown.selected_index = option.index;
+            selection_events.send(DropdownSelectionChanged {
+                entity: option.dropdown,
+                index: option.index,
+                value: dropdown.selected().unwrap_or_default().to_string(),
+            });
+        }
+    }
+}
+
+/// System to close open dropdowns on Escape or a click outside them
+pub fn dropdown_dismiss_system(
+    mut dropdowns: Query<(Entity, &mut Dropdown)>,
+    parents: Query<&Parent>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    interaction_state: Res<UiInteractionState>,
+) {
+    let escape = keyboard_input.just_pressed(KeyCode::Escape);
+    let clicked = mouse_buttons.just_pressed(MouseButton::Left);
+    if !escape && !clicked {
+        return;
+    }
+
+    let pressed = interaction_state.current.pressed;
+    for (entity, mut dropdown) in &mut dropdowns {
+        if !dropdown.is_open() {
+            continue;
+        }
+        let inside = pressed.is_some_and(|target| {
+            target == entity || parents.iter_ancestors(target).any(|ancestor| ancestor == entity)
+        });
+        if escape || !inside {
+            dropdown.set_open(false);
+        }
+    }
+}
+
+/// Spawns one row of a dropdown's list
+///
+/// Options become buttons tagged with [`DropdownOption`]; disabled ones are
+/// dimmed and ignored by [`dropdown_option_system`]. Headers and separators
+/// are plain nodes.
+fn spawn_dropdown_entry(
+    list: &mut ChildBuilder,
+    dropdown: Entity,
+    index: usize,
+    entry: &DropdownEntry,
+    font: &Handle<Font>,
+    theme: &Theme,
+) {
+    match entry {
+        DropdownEntry::Item {
+            text,
+            disabled,
+            icon,
+            tooltip,
+        } => {
+            let mut option = list.spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(30.0),
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(6.0),
+                        padding: UiRect::horizontal(Val::Px(6.0)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                DropdownOption { dropdown, index },
+                Name::new(format!("DropdownOption_{}", index)),
+            ));
+            if let Some(tooltip) = tooltip {
+                option.insert(Tooltip {
+                    text: tooltip.clone(),
+                    position: TooltipPosition::Right,
+                    offset: 4.0,
+                });
+            }
+            option.with_children(|parent| {
+                if let Some(icon) = icon {
+                    parent.spawn(ImageBundle {
+                        style: Style {
+                            width: Val::Px(16.0),
+                            height: Val::Px(16.0),
+                            ..default()
+                        },
+                        image: UiImage::new(icon.clone()),
+                        ..default()
+                    });
+                }
+                parent.spawn(TextBundle::from_section(
+                    text.clone(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 16.0,
+                        color: if *disabled {
+                            theme.text.disabled
+                        } else {
+                            theme.text.normal
+                        },
+                    },
+                ));
+            });
+        }
+        DropdownEntry::Header(text) => {
+            list.spawn((
+                TextBundle::from_section(
+                    text.clone(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 13.0,
+                        color: theme.text.disabled,
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::new(Val::Px(6.0), Val::Px(6.0), Val::Px(6.0), Val::Px(2.0)),
+                    ..default()
+                }),
+                Name::new(format!("DropdownHeader_{}", index)),
+            ));
+        }
+        DropdownEntry::Separator => {
+            list.spawn((
+                NodeBundle {
+                    style: Style {
+                        height: Val::Px(1.0),
+                        margin: UiRect::vertical(Val::Px(3.0)),
+                        ..default()
+                    },
+                    background_color: theme.text.disabled.into(),
+                    ..default()
+                },
+                Name::new(format!("DropdownSeparator_{}", index)),
+            ));
+        }
+    }
+}
+
+/// System to spawn or despawn the option list and refresh the label
+pub fn dropdown_list_system(
     mut commands: Commands,
-    mut dropdown_query: Query<(Entity, &mut Dropdown, &Interaction, &GlobalTransform, &Node), Changed<Interaction>>,
-    _text_query: Query<&mut Text>,
-    asset_server: Res<AssetServer>,
+    dropdowns: Query<(Entity, &Dropdown, Option<&Children>), Changed<Dropdown>>,
+    lists: Query<(), With<DropdownList>>,
+    mut labels: Query<&mut Text, With<DropdownLabel>>,
+    theme: Res<Theme>,
 ) {
-    for (entity, mut dropdown, interaction, transform, node) in &mut dropdown_query {
-        match interaction {
-            Interaction::Pressed => {
-                dropdown.opened = !dropdown.opened;
-                
-                if dropdown.opened {
-                    commands.entity(entity).with_children(|parent| {
-                        for (i, option) in dropdown.options.iter().enumerate() {
-                            parent.spawn((
-                                ButtonBundle {
-                                    style: Style {
-                                        width: Val::Percent(100.0),
-                                        height: Val::Px(30.0),
-                                        ..default()
-                                    },
+    for (entity, dropdown, children) in &dropdowns {
+        let mut list = None;
+        for &child in children.into_iter().flatten() {
+            if lists.contains(child) {
+                list = Some(child);
+            } else if let Ok(mut text) = labels.get_mut(child) {
+                let selected = dropdown.selected().unwrap_or_default();
+                if !first_section_is(&text, selected) {
+                    first_section(&mut text).value = selected.to_string();
+                }
+            }
+        }
+
+        match (dropdown.is_open(), list) {
+            (true, None) => {
+                commands.entity(entity).with_children(|parent| {
+                    parent
+                        .spawn((
+                            NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    top: Val::Percent(100.0),
+                                    left: Val::Px(0.0),
+                                    width: Val::Percent(100.0),
+                                    flex_direction: FlexDirection::Column,
                                     ..default()
                                 },
-                                Name::new(format!("DropdownOption_{}", i)),
-                            )).with_children(|parent| {
-                                parent.spawn(TextBundle::from_section(
-                                    option,
-                                    TextStyle {
-                                        font: asset_server.load("fonts/FiraSans-Regular.ttf"),
-                                        font_size: 16.0,
-                                        ..default()
-                                    },
-                                ));
-                            });
-                        }
-                    });
-                } else {
-                    // Despawn dropdown options
-                    commands.entity(entity).despawn_descendants();
-                }
+                                background_color: Color::srgba(0.1, 0.1, 0.1, 0.95).into(),
+                                z_index: ZIndex::Global(100),
+                                ..default()
+                            },
+                            DropdownList { dropdown: entity },
+                            Name::new("DropdownList"),
+                        ))
+                        .with_children(|list| {
+                            let font = UiFonts::REGULAR;
+                            for (i, entry) in dropdown.options.iter().enumerate() {
+                                spawn_dropdown_entry(list, entity, i, entry, &font, &theme);
+                            }
+                        });
+                });
+            }
+            (false, Some(list)) => {
+                commands.entity(list).despawn_recursive();
             }
             _ => {}
         }
     }
 }
 
-/// System to handle tab switching
+/// System to open dropdown lists upward when the monitor under the dropdown
+/// has no room for them below but more above
+pub fn dropdown_list_placement_system(
+    bounds: Res<PopupBounds>,
+    dropdowns: Query<(&Node, &GlobalTransform), With<Dropdown>>,
+    mut lists: Query<(&DropdownList, Ref<Node>, &mut Style)>,
+) {
+    for (list, node, mut style) in &mut lists {
+        if !node.is_changed() && !bounds.is_changed() {
+            continue;
+        }
+        let Ok((header, transform)) = dropdowns.get(list.dropdown) else {
+            continue;
+        };
+        let header = header.logical_rect(transform);
+        let Some(area) = bounds.area_at(header.center()) else {
+            continue;
+        };
+        let below = area.max.y - header.max.y;
+        let above = header.min.y - area.min.y;
+        let (top, bottom) = if node.size().y > below && above > below {
+            (Val::Auto, Val::Percent(100.0))
+        } else {
+            (Val::Percent(100.0), Val::Auto)
+        };
+        if style.top != top || style.bottom != bottom {
+            style.top = top;
+            style.bottom = bottom;
+        }
+    }
+}
+
+/// System to switch tabs when a tab button is pressed
+///
+/// Emits [`TabChanged`] when the active tab actually changes.
 pub fn tab_system(
-    mut commands: Commands,
-    mut tab_query: Query<(&mut TabbedContainer, &Children)>, 
-    mut button_query: Query<(&Interaction, &Parent), (Changed<Interaction>, With<Button>)>,
+    tabs: Query<(Entity, &TabButton)>,
+    mut containers: Query<&mut TabbedContainer>,
+    interaction_state: Res<UiInteractionState>,
+    mut changed_events: EventWriter<TabChanged>,
 ) {
-    for (interaction, parent) in &mut button_query {
-        if let Interaction::Pressed = interaction {
-            if let Ok((mut tab_container, children)) = tab_query.get_mut(parent.get()) {
-                if let Some(index) = children.iter().position(|&child| child == parent.get()) {
-                    tab_container.active_tab = index;
-                    
-                    // Update tab visibility
-                    for (i, &child) in children.iter().enumerate() {
-                        commands.entity(child).insert(Visibility::Visible);
-                        if i != index {
-                            commands.entity(child).insert(Visibility::Hidden);
-                        }
-                    }
-                }
-            }
+    for (entity, tab) in &tabs {
+        if !interaction_state.just_pressed(entity) {
+            continue;
+        }
+        let Ok(mut container) = containers.get_mut(tab.container) else {
+            continue;
+        };
+        if container.active_tab != tab.index {
+            changed_events.send(TabChanged {
+                container: tab.container,
+                previous: container.active_tab,
+                index: tab.index,
+            });
+            container.active_tab = tab.index;
         }
     }
 }
 
+/// System to show the active pane and highlight the active tab button
+///
+/// Inactive panes are removed from layout with `Display::None`. Also picks up
+/// `active_tab` changes made directly by game code.
+pub fn tab_visual_system(
+    containers: Query<Ref<TabbedContainer>>,
+    mut panes: Query<(&TabPane, &mut Style)>,
+    mut tabs: Query<(&TabButton, Ref<Interaction>, &mut BackgroundColor, &mut BorderColor)>,
+    theme: Res<Theme>,
+) {
+    for (pane, mut style) in &mut panes {
+        let Ok(container) = containers.get(pane.container) else {
+            continue;
+        };
+        let display = if container.active_tab == pane.index {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+
+    for (tab, interaction, mut background, mut border) in &mut tabs {
+        let Ok(container) = containers.get(tab.container) else {
+            continue;
+        };
+        if !container.is_changed() && !interaction.is_changed() && !theme.is_changed() {
+            continue;
+        }
+        let active = container.active_tab == tab.index;
+        background.0 = match *interaction {
+            _ if active => theme.button.pressed,
+            Interaction::None => theme.button.normal,
+            _ => theme.button.hovered,
+        };
+        border.0 = if active { theme.focus.border } else { Color::NONE };
+    }
+}
+
 /// System to handle scroll pane interactions
 pub fn scroll_pane_system(
     mut panes: Query<(&mut ScrollPane, &Node, &GlobalTransform)>, 
@@ -282,7 +800,8 @@ pub fn scroll_pane_system(
                         MouseScrollUnit::Pixel => event.y,
                     };
                     
-                    pane.scroll_position.y = (pane.scroll_position.y + scroll_delta)
+                    // Wheel up moves back towards the top of the content
+                    pane.scroll_position.y = (pane.scroll_position.y - scroll_delta)
                         .max(0.0).min(pane.max_scroll.y);
                 }
             }
@@ -290,6 +809,188 @@ pub fn scroll_pane_system(
     }
 }
 
+/// System to derive scroll bounds from the laid-out content size
+///
+/// Runs after layout so margins, wrapping and nested containers are already
+/// reflected in the content node's size. Rows being added or removed change
+/// that size, so bounds follow dynamic content without extra bookkeeping.
+pub fn calculate_scroll_bounds_system(
+    mut panes: Query<(&mut ScrollPane, &Node, &Children)>,
+    content_query: Query<(&Node, Has<ScrollContent>), Without<ScrollBar>>,
+) {
+    for (mut pane, node, children) in &mut panes {
+        let content = children
+            .iter()
+            .filter_map(|&child| content_query.get(child).ok())
+            .find(|(_, marked)| *marked)
+            .or_else(|| children.iter().find_map(|&child| content_query.get(child).ok()));
+        let Some((content_node, _)) = content else {
+            continue;
+        };
+
+        let max_scroll = (content_node.size() - node.size()).max(Vec2::ZERO);
+        let scroll_position = pane.scroll_position.clamp(Vec2::ZERO, max_scroll);
+        if pane.max_scroll != max_scroll || pane.scroll_position != scroll_position {
+            pane.max_scroll = max_scroll;
+            pane.scroll_position = scroll_position;
+        }
+    }
+}
+
+/// Returns the content container of a scroll pane
+fn scroll_content(
+    children: &Children,
+    content_query: &Query<Has<ScrollContent>, (With<Node>, Without<ScrollBar>)>,
+) -> Option<Entity> {
+    children
+        .iter()
+        .copied()
+        .find(|&child| content_query.get(child).unwrap_or(false))
+        .or_else(|| children.iter().copied().find(|&child| content_query.contains(child)))
+}
+
+/// System to offset scroll content by the pane's scroll position
+pub fn scroll_content_offset_system(
+    panes: Query<(&ScrollPane, &Children), Changed<ScrollPane>>,
+    content_query: Query<Has<ScrollContent>, (With<Node>, Without<ScrollBar>)>,
+    mut styles: Query<&mut Style>,
+) {
+    for (pane, children) in &panes {
+        let Some(content) = scroll_content(children, &content_query) else {
+            continue;
+        };
+        if let Ok(mut style) = styles.get_mut(content) {
+            let top = Val::Px(-pane.scroll_position.y);
+            let left = Val::Px(-pane.scroll_position.x);
+            if style.top != top || style.left != left {
+                style.top = top;
+                style.left = left;
+            }
+        }
+    }
+}
+
+/// System to size and place scroll bar thumbs from their pane's scroll state
+///
+/// The thumb's length reflects the visible share of the content; the bar is
+/// hidden when everything fits.
+pub fn scroll_bar_thumb_system(
+    panes: Query<(&ScrollPane, &Node)>,
+    mut bars: Query<(&ScrollBar, &mut Visibility)>,
+    mut thumbs: Query<(&ScrollThumb, &mut Style)>,
+) {
+    for (bar, mut visibility) in &mut bars {
+        let Ok((pane, _)) = panes.get(bar.pane) else {
+            continue;
+        };
+        let target = if pane.max_scroll.y > 0.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+
+    for (thumb, mut style) in &mut thumbs {
+        let Ok((pane, node)) = panes.get(thumb.pane) else {
+            continue;
+        };
+        let viewport = node.size().y;
+        let content = viewport + pane.max_scroll.y;
+        if content <= 0.0 {
+            continue;
+        }
+        let length = (viewport / content * 100.0).clamp(10.0, 100.0);
+        let progress = if pane.max_scroll.y > 0.0 {
+            pane.scroll_position.y / pane.max_scroll.y
+        } else {
+            0.0
+        };
+        let height = Val::Percent(length);
+        let top = Val::Percent(progress * (100.0 - length));
+        if style.height != height || style.top != top {
+            style.height = height;
+            style.top = top;
+        }
+    }
+}
+
+/// System to keep scroll bar tracks and thumbs in the slider colors
+///
+/// Thumbs are buttons, so this runs after
+/// [`button_interaction_system`](crate::button_interaction_system) and puts
+/// back the colors it paints over them.
+pub fn scroll_bar_visual_system(
+    mut tracks: Query<
+        (Ref<Interaction>, &mut BackgroundColor),
+        (With<ScrollBar>, Without<ScrollThumb>),
+    >,
+    mut thumbs: Query<
+        (Ref<Interaction>, &mut BackgroundColor),
+        (With<ScrollThumb>, Without<ScrollBar>),
+    >,
+    theme: Res<Theme>,
+) {
+    for (interaction, mut color) in &mut tracks {
+        if (interaction.is_changed() || theme.is_changed()) && color.0 != theme.slider.background {
+            color.0 = theme.slider.background;
+        }
+    }
+    for (interaction, mut color) in &mut thumbs {
+        if (interaction.is_changed() || theme.is_changed()) && color.0 != theme.slider.handle {
+            color.0 = theme.slider.handle;
+        }
+    }
+}
+
+/// System to drag scroll bar thumbs
+///
+/// Pressing a thumb captures the pointer; while captured, moving the cursor
+/// maps the thumb's position along the track back onto the pane's scroll
+/// position, the same value the mouse wheel updates.
+pub fn scroll_thumb_drag_system(
+    mut thumbs: Query<(Entity, &mut ScrollThumb, &Node, &GlobalTransform, &Parent)>,
+    tracks: Query<(&Node, &GlobalTransform), With<ScrollBar>>,
+    mut panes: Query<&mut ScrollPane>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    interaction_state: Res<UiInteractionState>,
+    mut capture: ResMut<PointerCapture>,
+) {
+    let Some(cursor) = windows.get_single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+
+    for (entity, mut thumb, node, transform, parent) in &mut thumbs {
+        let thumb_rect = node.logical_rect(transform);
+        if interaction_state.just_pointer_pressed(entity) {
+            capture.capture(entity);
+            thumb.grab_offset = cursor.y - thumb_rect.min.y;
+        }
+        if !capture.is_captured(entity) {
+            continue;
+        }
+
+        let Ok((track_node, track_transform)) = tracks.get(parent.get()) else {
+            continue;
+        };
+        let Ok(mut pane) = panes.get_mut(thumb.pane) else {
+            continue;
+        };
+        let track_rect = track_node.logical_rect(track_transform);
+        let travel = track_rect.height() - thumb_rect.height();
+        if travel <= 0.0 {
+            continue;
+        }
+        let progress = ((cursor.y - thumb.grab_offset - track_rect.min.y) / travel).clamp(0.0, 1.0);
+        let scroll = progress * pane.max_scroll.y;
+        if pane.scroll_position.y != scroll {
+            pane.scroll_position.y = scroll;
+        }
+    }
+}
+
 /// System to handle setting row hover/select
 pub fn setting_row_system(
     mut row_query: Query<(
@@ -325,26 +1026,60 @@ pub fn setting_row_system(
     }
 }
 
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<CheckmarkNode>()
+        // Shared by both entry points, so sliders are only dragged once a frame
+        .add_systems(Update, (slider_interaction_system, slider_visual_system).chain())
+        .add_systems(PostUpdate, checkmark_system.before(UiSystem::Layout));
+}
+
 /// Registers all UI systems and components with the Bevy app
 /// 
 /// # Arguments
 /// * `app` - The Bevy App to register systems with
 pub fn update(app: &mut App) {
-    app.register_type::<Tooltip>()
-        .register_type::<UiSlider>()
+    crate::add_core_plugins(app);
+    app.register_type::<UiSlider>()
+        .register_type::<SliderFill>()
+        .register_type::<SliderHandle>()
         .register_type::<Dropdown>()
+        .register_type::<DropdownState>()
+        .register_type::<DropdownLabel>()
+        .register_type::<DropdownList>()
+        .register_type::<DropdownOption>()
         .register_type::<ScrollPane>()
+        .register_type::<ScrollContent>()
+        .register_type::<ScrollBar>()
+        .register_type::<ScrollThumb>()
+        .register_type::<TabbedContainer>()
+        .register_type::<TabButton>()
+        .register_type::<TabPane>()
         .add_systems(
             Update,
             (
-                tooltip_system,
-                slider_interaction_system,
-                dropdown_system,
-                scroll_pane_system,
+                (
+                    dropdown_system,
+                    dropdown_option_system,
+                    dropdown_dismiss_system,
+                    dropdown_list_system,
+                    dropdown_list_placement_system,
+                )
+                    .chain(),
+                (scroll_pane_system, scroll_thumb_drag_system, scroll_content_offset_system)
+                    .chain(),
                 setting_row_system,
-                tab_system,
-                focus_navigation_system,
-                
+                scroll_bar_visual_system.after(crate::button_interaction_system),
+                // After the generic button colors so the active tab keeps its highlight
+                (tab_system, tab_visual_system)
+                    .chain()
+                    .after(crate::button_interaction_system),
+                (focus_order_system, focus_navigation_system).chain(),
             ),
+        )
+        .add_systems(
+            PostUpdate,
+            (calculate_scroll_bounds_system, scroll_bar_thumb_system)
+                .chain()
+                .after(UiSystem::Layout),
         );
 }
@@ -0,0 +1,423 @@
+//! Tables with sortable columns
+//!
+//! A [`UiTable`] shows a header row of [`UiTableColumn`]s above its
+//! [`rows`](UiTable::rows) of cells. Clicking a sortable column's header
+//! sorts the rows by that column, ascending first and descending on the next
+//! click, marks the header with an arrow and sends [`TableSorted`]. Rows can
+//! be striped for readability.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{table_bundle, TableCell, UiTable, UiTableColumn};
+//! fn setup(mut commands: Commands) {
+//!     let table = UiTable::new(vec![
+//!         UiTableColumn::new("Player").width(Val::Px(160.0)),
+//!         UiTableColumn::new("Score"),
+//!     ])
+//!     .row(vec![TableCell::text("Ada"), TableCell::number(1200.0)])
+//!     .row(vec![TableCell::text("Brin"), TableCell::number(950.0)]);
+//!     commands.spawn(table_bundle(table));
+//! }
+//! ```
+//!
+//! The rows are the table's data: replacing them, or changing the columns or
+//! sort, rebuilds the spawned rows. Sorting only changes the order they are
+//! shown in, so [`TablePartKind::Row`] keeps the index into
+//! [`UiTable::rows`].
+
+use std::cmp::Ordering;
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::interaction::UiInteractionState;
+use crate::theme::Theme;
+
+/// Order of a sorted column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum SortDirection {
+    /// Smallest first
+    #[default]
+    Ascending,
+    /// Largest first
+    Descending,
+}
+
+impl SortDirection {
+    /// The other direction
+    pub fn toggled(self) -> Self {
+        match self {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        }
+    }
+
+    /// Arrow shown after the header title
+    pub fn indicator(self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "↑",
+            SortDirection::Descending => "↓",
+        }
+    }
+}
+
+/// The column a table is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub struct TableSort {
+    /// Index into [`UiTable::columns`]
+    pub column: usize,
+    /// Sort order
+    pub direction: SortDirection,
+}
+
+/// A column of a [`UiTable`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct UiTableColumn {
+    /// Header text
+    pub title: String,
+    /// Width of the header and cells
+    pub width: Val,
+    /// Whether clicking the header sorts by this column
+    pub sortable: bool,
+}
+
+impl UiTableColumn {
+    /// Creates a sortable column 120 pixels wide
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            width: Val::Px(120.0),
+            sortable: true,
+        }
+    }
+
+    /// Sets the column width
+    pub fn width(mut self, width: Val) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Stops the header from sorting the table
+    pub fn unsortable(mut self) -> Self {
+        self.sortable = false;
+        self
+    }
+}
+
+/// A cell of a [`UiTable`] row
+#[derive(Debug, Clone, Default, PartialEq, Reflect)]
+pub struct TableCell {
+    /// Text shown
+    pub text: String,
+    /// Number sorted by instead of the text, when set
+    pub sort_key: Option<f64>,
+}
+
+impl TableCell {
+    /// Creates a cell sorted by its text
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            sort_key: None,
+        }
+    }
+
+    /// Creates a cell showing and sorted by `value`
+    pub fn number(value: f64) -> Self {
+        Self {
+            text: value.to_string(),
+            sort_key: Some(value),
+        }
+    }
+
+    /// Sorts the cell by `key` instead of its text, such as seconds for a "3m 20s" cell
+    pub fn sort_by(mut self, key: f64) -> Self {
+        self.sort_key = Some(key);
+        self
+    }
+
+    /// Orders two cells by number when both have one, by text otherwise
+    fn compare(&self, other: &Self) -> Ordering {
+        match (self.sort_key, other.sort_key) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            _ => self.text.to_lowercase().cmp(&other.text.to_lowercase()),
+        }
+    }
+}
+
+impl From<&str> for TableCell {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<String> for TableCell {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+/// A table of text cells with sortable columns
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct UiTable {
+    /// Columns, left to right
+    pub columns: Vec<UiTableColumn>,
+    /// Cells of each row, in column order
+    pub rows: Vec<Vec<TableCell>>,
+    /// Column the rows are shown sorted by
+    pub sort: Option<TableSort>,
+    /// Height of each row in pixels
+    pub row_height: f32,
+    /// Whether every other row gets a background
+    pub striped: bool,
+}
+
+impl UiTable {
+    /// Creates an empty, striped table with the given columns
+    pub fn new(columns: Vec<UiTableColumn>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+            sort: None,
+            row_height: 28.0,
+            striped: true,
+        }
+    }
+
+    /// Appends a row
+    pub fn row<C: Into<TableCell>>(mut self, cells: impl IntoIterator<Item = C>) -> Self {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Shows the rows sorted by `column`
+    pub fn sorted_by(mut self, column: usize, direction: SortDirection) -> Self {
+        self.sort = Some(TableSort { column, direction });
+        self
+    }
+
+    /// Turns row stripes on or off
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    /// Indices into [`UiTable::rows`] in the order they are shown
+    ///
+    /// Rows that compare equal keep their order.
+    pub fn sorted_rows(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.rows.len()).collect();
+        if let Some(sort) = self.sort {
+            let empty = TableCell::default();
+            let cell = |row: usize| self.rows[row].get(sort.column).unwrap_or(&empty);
+            order.sort_by(|&a, &b| {
+                let ordering = cell(a).compare(cell(b));
+                match sort.direction {
+                    SortDirection::Ascending => ordering,
+                    SortDirection::Descending => ordering.reverse(),
+                }
+            });
+        }
+        order
+    }
+}
+
+/// What a [`TablePart`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum TablePartKind {
+    /// The header button of the column at this index
+    Header(usize),
+    /// The row showing [`UiTable::rows`] at this index
+    Row(usize),
+}
+
+/// A node spawned for a [`UiTable`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct TablePart {
+    /// The table this node belongs to
+    pub table: Entity,
+    /// What this node is
+    pub kind: TablePartKind,
+}
+
+/// Sent when a header click changes how a table is sorted
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableSorted {
+    /// The table entity
+    pub table: Entity,
+    /// The new sort
+    pub sort: TableSort,
+}
+
+/// Creates the container bundle for a table
+pub fn table_bundle(table: UiTable) -> (NodeBundle, UiTable) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            ..default()
+        },
+        table,
+    )
+}
+
+/// Style of a header or body cell in `column`
+fn cell_style(column: &UiTableColumn) -> Style {
+    Style {
+        width: column.width,
+        flex_shrink: 0.0,
+        align_items: AlignItems::Center,
+        padding: UiRect::horizontal(Val::Px(8.0)),
+        overflow: Overflow::clip(),
+        ..default()
+    }
+}
+
+/// System to spawn the header and rows of tables, and rebuild them on change
+pub fn table_spawn_system(
+    mut commands: Commands,
+    tables: Query<(Entity, &UiTable), Changed<UiTable>>,
+    theme: Res<Theme>,
+) {
+    let font = UiFonts::MEDIUM;
+    let header_style = TextStyle {
+        font: font.clone(),
+        font_size: 16.0,
+        color: theme.text.normal,
+    };
+    let cell_text_style = TextStyle {
+        font,
+        font_size: 16.0,
+        color: theme.text.normal,
+    };
+    for (entity, table) in &tables {
+        let part = |kind| TablePart {
+            table: entity,
+            kind,
+        };
+        commands
+            .entity(entity)
+            .despawn_descendants()
+            .with_children(|root| {
+                root.spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        border: UiRect::bottom(Val::Px(1.0)),
+                        ..default()
+                    },
+                    border_color: theme.text.disabled.into(),
+                    ..default()
+                })
+                .with_children(|header| {
+                    for (index, column) in table.columns.iter().enumerate() {
+                        let title = match table.sort {
+                            Some(sort) if sort.column == index => {
+                                format!("{} {}", column.title, sort.direction.indicator())
+                            }
+                            _ => column.title.clone(),
+                        };
+                        let mut cell = header.spawn(NodeBundle {
+                            style: Style {
+                                height: Val::Px(table.row_height),
+                                ..cell_style(column)
+                            },
+                            ..default()
+                        });
+                        if column.sortable {
+                            cell.insert((
+                                Interaction::default(),
+                                BackgroundColor(theme.button.normal),
+                                part(TablePartKind::Header(index)),
+                            ));
+                        }
+                        cell.with_children(|cell| {
+                            cell.spawn(TextBundle::from_section(title, header_style.clone()));
+                        });
+                    }
+                });
+
+                for (position, row) in table.sorted_rows().into_iter().enumerate() {
+                    let background = if table.striped && position % 2 == 1 {
+                        theme.button.normal.with_alpha(0.4)
+                    } else {
+                        Color::NONE
+                    };
+                    root.spawn((
+                        NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Row,
+                                height: Val::Px(table.row_height),
+                                flex_shrink: 0.0,
+                                ..default()
+                            },
+                            background_color: background.into(),
+                            ..default()
+                        },
+                        part(TablePartKind::Row(row)),
+                    ))
+                    .with_children(|cells| {
+                        for (index, column) in table.columns.iter().enumerate() {
+                            let text = table.rows[row]
+                                .get(index)
+                                .map_or_else(String::new, |cell| cell.text.clone());
+                            cells
+                                .spawn(NodeBundle {
+                                    style: cell_style(column),
+                                    ..default()
+                                })
+                                .with_children(|cell| {
+                                    cell.spawn(TextBundle::from_section(
+                                        text,
+                                        cell_text_style.clone(),
+                                    ));
+                                });
+                        }
+                    });
+                }
+            });
+    }
+}
+
+/// System to sort tables when a sortable header is clicked
+///
+/// Clicking the sorted column flips its direction; clicking another column
+/// sorts by it ascending.
+pub fn table_header_system(
+    mut tables: Query<&mut UiTable>,
+    parts: Query<(Entity, &TablePart)>,
+    interaction_state: Res<UiInteractionState>,
+    mut sorted_events: EventWriter<TableSorted>,
+) {
+    for (entity, part) in &parts {
+        let TablePartKind::Header(column) = part.kind else {
+            continue;
+        };
+        if !interaction_state.just_pointer_pressed(entity) {
+            continue;
+        }
+        let Ok(mut table) = tables.get_mut(part.table) else {
+            continue;
+        };
+        let direction = match table.sort {
+            Some(sort) if sort.column == column => sort.direction.toggled(),
+            _ => SortDirection::Ascending,
+        };
+        let sort = TableSort { column, direction };
+        table.sort = Some(sort);
+        sorted_events.send(TableSorted {
+            table: part.table,
+            sort,
+        });
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiTable>()
+        .register_type::<TablePart>()
+        .add_event::<TableSorted>()
+        .add_systems(Update, (table_header_system, table_spawn_system).chain());
+}
@@ -0,0 +1,552 @@
+//! Runtime tab management
+//!
+//! The tab bar of a [`TabbedContainer`] is rebuilt whenever its tab names or
+//! options change. [`AddTab`] and [`RemoveTab`] are commands that change the
+//! names and the panes together, so a multi-document editor can open and
+//! close documents:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{add_tab, RemoveTab, TabCloseRequested};
+//! fn open_document(mut commands: Commands, container: Entity) {
+//!     let pane = add_tab(&mut commands, container, "untitled.map");
+//!     commands.entity(pane).with_children(|pane| {
+//!         pane.spawn(TextBundle::from_section("", TextStyle::default()));
+//!     });
+//! }
+//!
+//! fn close_document(mut commands: Commands, mut requests: EventReader<TabCloseRequested>) {
+//!     for request in requests.read() {
+//!         // Ask to save first if needed
+//!         commands.add(RemoveTab {
+//!             container: request.container,
+//!             index: request.index,
+//!         });
+//!     }
+//! }
+//! ```
+//!
+//! With [`closable`](TabbedContainer::closable) set each tab has a small
+//! close button, which only sends [`TabCloseRequested`]; removing the tab is
+//! up to the game. With [`reorderable`](TabbedContainer::reorderable) set,
+//! tabs can be dragged onto one another to move them, which sends
+//! [`TabMoved`]. Tabs that don't fit in the bar scroll sideways with the
+//! mouse wheel, and the bar scrolls to bring the active tab into view.
+
+use bevy::ecs::world::Command;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::assets::UiFonts;
+use crate::components::{
+    FocusState, Focusable, FocusableType, TabButton, TabPane, TabbedContainer,
+};
+use crate::drag::{Draggable, DropTarget, DroppedOn};
+use crate::events::TabChanged;
+use crate::interaction::{HitboxPadding, UiInteractionState};
+use crate::theme::Theme;
+
+/// [`Draggable::kind`] of tab buttons
+pub const TAB_DRAG_KIND: &str = "tab";
+
+/// The scrolling tab bar of a [`TabbedContainer`]
+///
+/// Its only child is the row holding the tab buttons.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct TabStrip {
+    /// The container the bar belongs to
+    pub container: Entity,
+    /// How far the row of tabs is scrolled, in pixels
+    pub offset: f32,
+}
+
+/// The close button of a tab
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct TabCloseButton {
+    /// The container the tab belongs to
+    pub container: Entity,
+    /// Index into [`TabbedContainer::tabs`]
+    pub index: usize,
+}
+
+/// Sent when the player presses a tab's close button
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabCloseRequested {
+    /// The container entity
+    pub container: Entity,
+    /// Index of the tab
+    pub index: usize,
+}
+
+/// Sent when the player drags a tab to a new place
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabMoved {
+    /// The container entity
+    pub container: Entity,
+    /// Previous index of the tab
+    pub from: usize,
+    /// New index of the tab
+    pub to: usize,
+}
+
+/// Adds a tab with `pane` as its content to a container
+///
+/// [`add_tab`] spawns the pane and queues this in one go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddTab {
+    /// The container entity
+    pub container: Entity,
+    /// Name shown on the tab
+    pub name: String,
+    /// The tab's content node
+    pub pane: Entity,
+    /// Where to insert the tab; at the end when `None`
+    pub index: Option<usize>,
+    /// Whether to switch to the new tab
+    pub activate: bool,
+}
+
+impl Command for AddTab {
+    fn apply(self, world: &mut World) {
+        let Some(len) = world
+            .get::<TabbedContainer>(self.container)
+            .map(|container| container.tabs.len())
+        else {
+            warn!("no tabbed container {:?} to add a tab to", self.container);
+            return;
+        };
+        if world.get_entity(self.pane).is_none() {
+            warn!("no tab pane {:?} to add to {:?}", self.pane, self.container);
+            return;
+        }
+        let index = self.index.unwrap_or(len).min(len);
+        reindex_panes(world, self.container, |pane| {
+            (pane >= index).then_some(pane + 1)
+        });
+
+        let mut container = world
+            .get_mut::<TabbedContainer>(self.container)
+            .expect("checked above");
+        container.tabs.insert(index, self.name);
+        let previous = container.active_tab;
+        if self.activate {
+            container.active_tab = index;
+        } else if len > 0 && previous >= index {
+            container.active_tab += 1;
+        }
+        let active = container.active_tab;
+
+        world.entity_mut(self.pane).insert(TabPane {
+            container: self.container,
+            index,
+        });
+        world.entity_mut(self.container).add_child(self.pane);
+        if self.activate && len > 0 {
+            world.send_event(TabChanged {
+                container: self.container,
+                previous: if previous >= index {
+                    previous + 1
+                } else {
+                    previous
+                },
+                index: active,
+            });
+        }
+    }
+}
+
+/// Removes a tab and despawns its pane
+///
+/// When the removed tab was active the next one, or the new last one,
+/// becomes active and [`TabChanged`] is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoveTab {
+    /// The container entity
+    pub container: Entity,
+    /// Index of the tab
+    pub index: usize,
+}
+
+impl Command for RemoveTab {
+    fn apply(self, world: &mut World) {
+        let Some(mut container) = world.get_mut::<TabbedContainer>(self.container) else {
+            warn!(
+                "no tabbed container {:?} to remove a tab from",
+                self.container
+            );
+            return;
+        };
+        if self.index >= container.tabs.len() {
+            return;
+        }
+        container.tabs.remove(self.index);
+        let previous = container.active_tab;
+        if previous > self.index {
+            container.active_tab -= 1;
+        } else if previous == self.index {
+            container.active_tab = self.index.min(container.tabs.len().saturating_sub(1));
+        }
+        let active = container.active_tab;
+        let remaining = container.tabs.len();
+
+        let removed: Vec<Entity> = world
+            .query::<(Entity, &TabPane)>()
+            .iter(world)
+            .filter(|(_, pane)| pane.container == self.container && pane.index == self.index)
+            .map(|(entity, _)| entity)
+            .collect();
+        for pane in removed {
+            despawn_with_children_recursive(world, pane);
+        }
+        reindex_panes(world, self.container, |pane| {
+            (pane > self.index).then(|| pane - 1)
+        });
+        if previous == self.index && remaining > 0 {
+            world.send_event(TabChanged {
+                container: self.container,
+                previous,
+                index: active,
+            });
+        }
+    }
+}
+
+/// Renumbers the panes of `container` for which `map` returns a new index
+fn reindex_panes(world: &mut World, container: Entity, map: impl Fn(usize) -> Option<usize>) {
+    let mut panes = world.query::<&mut TabPane>();
+    for mut pane in panes.iter_mut(world) {
+        if pane.container != container {
+            continue;
+        }
+        if let Some(index) = map(pane.index) {
+            pane.index = index;
+        }
+    }
+}
+
+/// Where the tab at `index` ends up when the tab at `from` moves to `to`
+fn moved_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < to && (from + 1..=to).contains(&index) {
+        index - 1
+    } else if to < from && (to..from).contains(&index) {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Spawns an empty pane and queues an [`AddTab`] that appends and activates it
+///
+/// Returns the pane; spawn the tab's content as its children.
+pub fn add_tab(commands: &mut Commands, container: Entity, name: impl Into<String>) -> Entity {
+    let pane = commands.spawn(tab_pane_bundle()).id();
+    commands.add(AddTab {
+        container,
+        name: name.into(),
+        pane,
+        index: None,
+        activate: true,
+    });
+    pane
+}
+
+/// Creates the node bundle of a tab's content pane, hidden until its tab is active
+pub fn tab_pane_bundle() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            flex_grow: 1.0,
+            display: Display::None,
+            ..default()
+        },
+        ..default()
+    }
+}
+
+/// Creates the clipping bar the tab buttons of `container` scroll in
+pub(crate) fn tab_strip_bundle(container: Entity) -> (NodeBundle, TabStrip) {
+    (
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                overflow: Overflow::clip_x(),
+                ..default()
+            },
+            ..default()
+        },
+        TabStrip {
+            container,
+            offset: 0.0,
+        },
+    )
+}
+
+/// Creates the row of tab buttons inside a [`TabStrip`]
+pub(crate) fn tab_row_bundle() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            flex_shrink: 0.0,
+            column_gap: Val::Px(2.0),
+            ..default()
+        },
+        ..default()
+    }
+}
+
+/// Spawns the buttons for the tabs of `container`, in order
+pub(crate) fn spawn_tab_buttons(
+    commands: &mut Commands,
+    font: &Handle<Font>,
+    theme: &Theme,
+    entity: Entity,
+    container: &TabbedContainer,
+) -> Vec<Entity> {
+    container
+        .tabs
+        .iter()
+        .enumerate()
+        .map(|(index, tab)| {
+            let mut button = commands.spawn((
+                ButtonBundle {
+                    style: Style {
+                        flex_shrink: 0.0,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(6.0),
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                        border: UiRect::bottom(Val::Px(2.0)),
+                        ..default()
+                    },
+                    background_color: theme.button.normal.into(),
+                    ..default()
+                },
+                TabButton {
+                    container: entity,
+                    index,
+                },
+                Focusable {
+                    state: FocusState::NotFocused,
+                    focus_type: FocusableType::Button,
+                },
+            ));
+            if container.reorderable {
+                button.insert((
+                    Draggable::new(TAB_DRAG_KIND),
+                    DropTarget::accepting([TAB_DRAG_KIND]),
+                ));
+            }
+            button.with_children(|button| {
+                button.spawn(TextBundle::from_section(
+                    tab.clone(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 18.0,
+                        color: theme.text.normal,
+                    },
+                ));
+                if !container.closable {
+                    return;
+                }
+                button
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(16.0),
+                                height: Val::Px(16.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: theme.button.normal.into(),
+                            ..default()
+                        },
+                        HitboxPadding::all(Val::Px(3.0)),
+                        TabCloseButton {
+                            container: entity,
+                            index,
+                        },
+                    ))
+                    .with_children(|close| {
+                        close.spawn(TextBundle::from_section(
+                            "x",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 12.0,
+                                color: theme.text.normal,
+                            },
+                        ));
+                    });
+            });
+            button.id()
+        })
+        .collect()
+}
+
+/// System to rebuild the tab buttons of containers whose tabs changed
+pub fn tab_strip_system(
+    mut commands: Commands,
+    mut containers: Query<(Entity, &mut TabbedContainer), Changed<TabbedContainer>>,
+    strips: Query<(&TabStrip, &Children)>,
+    theme: Res<Theme>,
+) {
+    for (entity, mut container) in &mut containers {
+        let layout = (
+            container.tabs.clone(),
+            container.closable,
+            container.reorderable,
+        );
+        if container.built.as_ref() == Some(&layout) {
+            continue;
+        }
+        let Some(&row) = strips
+            .iter()
+            .find(|(strip, _)| strip.container == entity)
+            .and_then(|(_, children)| children.first())
+        else {
+            continue;
+        };
+        container.bypass_change_detection().built = Some(layout);
+
+        let font = UiFonts::BOLD;
+        let buttons = spawn_tab_buttons(&mut commands, &font, &theme, entity, &container);
+        commands
+            .entity(row)
+            .despawn_descendants()
+            .push_children(&buttons);
+    }
+}
+
+/// System to report presses of tab close buttons
+pub fn tab_close_system(
+    buttons: Query<(Entity, &TabCloseButton)>,
+    interaction_state: Res<UiInteractionState>,
+    mut requests: EventWriter<TabCloseRequested>,
+) {
+    for (entity, button) in &buttons {
+        if interaction_state.just_pressed(entity) {
+            requests.send(TabCloseRequested {
+                container: button.container,
+                index: button.index,
+            });
+        }
+    }
+}
+
+/// System to move tabs dropped onto other tabs of the same container
+pub fn tab_drop_system(
+    mut drops: EventReader<DroppedOn>,
+    buttons: Query<&TabButton>,
+    mut containers: Query<&mut TabbedContainer>,
+    mut panes: Query<&mut TabPane>,
+    mut moved_events: EventWriter<TabMoved>,
+) {
+    for drop in drops.read() {
+        let (Ok(source), Ok(target)) = (buttons.get(drop.source), buttons.get(drop.target)) else {
+            continue;
+        };
+        let (from, to) = (source.index, target.index);
+        if source.container != target.container || from == to {
+            continue;
+        }
+        let Ok(mut container) = containers.get_mut(source.container) else {
+            continue;
+        };
+        if !container.reorderable || from >= container.tabs.len() || to >= container.tabs.len() {
+            continue;
+        }
+        let tab = container.tabs.remove(from);
+        container.tabs.insert(to, tab);
+        container.active_tab = moved_index(container.active_tab, from, to);
+        for mut pane in &mut panes {
+            if pane.container == source.container {
+                pane.index = moved_index(pane.index, from, to);
+            }
+        }
+        moved_events.send(TabMoved {
+            container: source.container,
+            from,
+            to,
+        });
+    }
+}
+
+/// System to scroll tab bars with the mouse wheel and keep the active tab in view
+pub fn tab_strip_scroll_system(
+    mut strips: Query<(&mut TabStrip, &Node, &GlobalTransform, &Children)>,
+    mut rows: Query<(&Node, &mut Style)>,
+    containers: Query<Ref<TabbedContainer>>,
+    buttons: Query<(&TabButton, &Node, &GlobalTransform)>,
+    mut wheel: EventReader<MouseWheel>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let cursor = windows.get_single().ok().and_then(Window::cursor_position);
+    let scrolled: f32 = wheel
+        .read()
+        .map(|event| {
+            let delta = if event.x != 0.0 { event.x } else { event.y };
+            match event.unit {
+                MouseScrollUnit::Line => delta * 20.0,
+                MouseScrollUnit::Pixel => delta,
+            }
+        })
+        .sum();
+
+    for (mut strip, node, transform, children) in &mut strips {
+        let Some(&row) = children.first() else {
+            continue;
+        };
+        let Ok((row_node, mut row_style)) = rows.get_mut(row) else {
+            continue;
+        };
+        let rect = node.logical_rect(transform);
+        let mut offset = strip.offset;
+        if scrolled != 0.0 && cursor.is_some_and(|cursor| rect.contains(cursor)) {
+            offset -= scrolled;
+        }
+        // Bring the active tab into view when it changes
+        if let Ok(container) = containers.get(strip.container) {
+            let active = buttons.iter().find(|(button, ..)| {
+                button.container == strip.container && button.index == container.active_tab
+            });
+            if let (true, Some((_, button_node, button_transform))) =
+                (container.is_changed(), active)
+            {
+                let button = button_node.logical_rect(button_transform);
+                if button.min.x < rect.min.x {
+                    offset -= rect.min.x - button.min.x;
+                } else if button.max.x > rect.max.x {
+                    offset += button.max.x - rect.max.x;
+                }
+            }
+        }
+        let max = (row_node.size().x - node.size().x).max(0.0);
+        let offset = offset.clamp(0.0, max);
+        if strip.offset != offset {
+            strip.offset = offset;
+        }
+        if row_style.left != Val::Px(-offset) {
+            row_style.left = Val::Px(-offset);
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<TabStrip>()
+        .register_type::<TabCloseButton>()
+        .add_event::<TabCloseRequested>()
+        .add_event::<TabMoved>()
+        .add_systems(
+            Update,
+            (
+                (tab_close_system, tab_drop_system, tab_strip_system)
+                    .chain()
+                    .before(crate::systems::tab_system),
+                tab_strip_scroll_system.after(crate::systems::tab_visual_system),
+            ),
+        );
+}
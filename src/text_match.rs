@@ -0,0 +1,130 @@
+//! Fuzzy text matching for search boxes and list filters
+//!
+//! [`fuzzy_match`] scores a query against one text and reports which letters
+//! matched; [`rank_matches`] filters and sorts a whole list, as the command
+//! palette does. [`FuzzyMatch::spans`] and [`FuzzyMatch::sections`] turn the
+//! matched letters into highlight runs for egui layouts or Bevy text.
+
+use std::ops::Range;
+
+use bevy::prelude::*;
+
+/// How well a query matched a text
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better
+    pub score: i32,
+    /// Character indices of the matched letters in the text
+    pub positions: Vec<usize>,
+}
+
+impl FuzzyMatch {
+    /// Splits `text` into runs of matched and unmatched letters
+    ///
+    /// Each span is a byte range into `text` and whether its letters
+    /// matched, so it can be fed to any rich text layout.
+    pub fn spans(&self, text: &str) -> Vec<(Range<usize>, bool)> {
+        let mut spans: Vec<(Range<usize>, bool)> = Vec::new();
+        for (index, (start, c)) in text.char_indices().enumerate() {
+            let matched = self.positions.contains(&index);
+            let end = start + c.len_utf8();
+            match spans.last_mut() {
+                Some((range, run)) if *run == matched => range.end = end,
+                _ => spans.push((start..end, matched)),
+            }
+        }
+        spans
+    }
+
+    /// Text sections for `text` in `style`, with the matched letters in `highlight`
+    pub fn sections(&self, text: &str, style: &TextStyle, highlight: Color) -> Vec<TextSection> {
+        self.spans(text)
+            .into_iter()
+            .map(|(range, matched)| {
+                let color = if matched { highlight } else { style.color };
+                TextSection::new(
+                    &text[range],
+                    TextStyle {
+                        color,
+                        ..style.clone()
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// Matches `query` against `text` as a case-insensitive subsequence
+///
+/// Letters that continue a run or start a word score higher, so "tg" ranks
+/// "Toggle Grid" above "Settings". Spaces in the query are ignored.
+///
+/// ```no_run
+/// # use sf_ui_common::fuzzy_match;
+/// let found = fuzzy_match("tg", "Toggle Grid").unwrap();
+/// assert_eq!(found.positions, vec![0, 7]);
+/// assert!(found.score > fuzzy_match("tg", "Settings").unwrap().score);
+/// assert!(fuzzy_match("xyz", "Toggle Grid").is_none());
+/// ```
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let text: Vec<char> = text.chars().collect();
+    // Taking each letter as early as possible can miss word starts further
+    // on, and jumping to word starts can miss a match entirely, so try both
+    let earliest = match_letters(query, &text, false);
+    let word_starts = match_letters(query, &text, true);
+    match (earliest, word_starts) {
+        (Some(a), Some(b)) => Some(if b.score > a.score { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Whether the character at `index` begins a word
+fn is_word_start(text: &[char], index: usize) -> bool {
+    let Some(previous) = index.checked_sub(1).map(|previous| text[previous]) else {
+        return true;
+    };
+    !previous.is_alphanumeric() || previous.is_lowercase() && text[index].is_uppercase()
+}
+
+/// Matches each query letter at its next occurrence, or its next word start if `prefer_word_starts`
+fn match_letters(query: &str, text: &[char], prefer_word_starts: bool) -> Option<FuzzyMatch> {
+    let mut found = FuzzyMatch::default();
+    let mut next = 0;
+    for wanted in query.chars().filter(|c| !c.is_whitespace()) {
+        let matches = |index: &usize| text[*index].to_lowercase().eq(wanted.to_lowercase());
+        let word_start = (next..text.len())
+            .filter(|index| prefer_word_starts && is_word_start(text, *index))
+            .find(matches);
+        let index = word_start.or_else(|| (next..text.len()).find(matches))?;
+        found.score += 1;
+        if is_word_start(text, index) {
+            found.score += 8;
+        }
+        if index > 0 && found.positions.last() == Some(&(index - 1)) {
+            found.score += 5;
+        }
+        found.positions.push(index);
+        next = index + 1;
+    }
+    // Prefer matches that start early
+    found.score -= found.positions.first().copied().unwrap_or_default().min(10) as i32;
+    Some(found)
+}
+
+/// Matches `query` against every text, best first
+///
+/// Returns the index of each matching text with its match. Equal scores keep
+/// the texts' order, and an empty query matches everything in order.
+pub fn rank_matches<'a>(
+    query: &str,
+    texts: impl IntoIterator<Item = &'a str>,
+) -> Vec<(usize, FuzzyMatch)> {
+    let mut matches: Vec<_> = texts
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, text)| Some((index, fuzzy_match(query, text)?)))
+        .collect();
+    // Stable, so equal scores keep their order
+    matches.sort_by_key(|(_, found)| std::cmp::Reverse(found.score));
+    matches
+}
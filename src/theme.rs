@@ -0,0 +1,251 @@
+//! Runtime-switchable color themes
+//!
+//! [`Theme`] mirrors the structure of the [`colors`](crate::colors) module so
+//! games can offer light/dark or faction palettes. The constants in `colors`
+//! make up [`Theme::default`].
+
+use bevy::prelude::*;
+
+use crate::colors;
+
+/// Button colors for a theme
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ButtonPalette {
+    /// Default button color
+    pub normal: Color,
+    /// Button color when hovered
+    pub hovered: Color,
+    /// Button color when pressed
+    pub pressed: Color,
+    /// Button color when disabled
+    pub disabled: Color,
+}
+
+impl Default for ButtonPalette {
+    fn default() -> Self {
+        Self {
+            normal: colors::button::NORMAL,
+            hovered: colors::button::HOVERED,
+            pressed: colors::button::PRESSED,
+            disabled: colors::button::DISABLED,
+        }
+    }
+}
+
+/// Focus-related colors for a theme
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct FocusPalette {
+    /// Focus highlight color
+    pub highlight: Color,
+    /// Focus border color
+    pub border: Color,
+    /// Focus text color
+    pub text: Color,
+}
+
+impl Default for FocusPalette {
+    fn default() -> Self {
+        Self {
+            highlight: colors::focus::HIGHLIGHT,
+            border: colors::focus::BORDER,
+            text: colors::focus::TEXT,
+        }
+    }
+}
+
+/// Text colors for a theme
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct TextPalette {
+    /// Default text color
+    pub normal: Color,
+    /// Disabled text color
+    pub disabled: Color,
+}
+
+impl Default for TextPalette {
+    fn default() -> Self {
+        Self {
+            normal: colors::text::NORMAL,
+            disabled: colors::text::DISABLED,
+        }
+    }
+}
+
+/// Slider colors and metrics for a theme
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct SliderPalette {
+    /// Slider background color
+    pub background: Color,
+    /// Slider foreground (fill) color
+    pub foreground: Color,
+    /// Slider handle color
+    pub handle: Color,
+    /// Slider height in pixels
+    pub height: f32,
+    /// Slider handle size in pixels
+    pub handle_size: f32,
+}
+
+impl Default for SliderPalette {
+    fn default() -> Self {
+        Self {
+            background: colors::slider::BACKGROUND,
+            foreground: colors::slider::FOREGROUND,
+            handle: colors::slider::HANDLE,
+            height: colors::slider::HEIGHT,
+            handle_size: colors::slider::HANDLE_SIZE,
+        }
+    }
+}
+
+/// Semantic status colors for a theme
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct StatusPalette {
+    /// Positive state, e.g. "Ready"
+    pub success: Color,
+    /// Needs attention, e.g. "Outdated"
+    pub warning: Color,
+    /// Failure or blocked, e.g. "Full"
+    pub error: Color,
+    /// Neutral information
+    pub info: Color,
+}
+
+impl Default for StatusPalette {
+    fn default() -> Self {
+        Self {
+            success: Color::srgb(0.3, 0.75, 0.35),
+            warning: Color::srgb(0.95, 0.7, 0.2),
+            error: Color::srgb(0.9, 0.3, 0.3),
+            info: Color::srgb(0.35, 0.6, 0.95),
+        }
+    }
+}
+
+/// Spacing metrics for a theme
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct SpacingMetrics {
+    /// Gap between an inline icon and its text, in pixels
+    pub icon_gap: f32,
+    /// Inline icon size relative to the text's font size
+    pub icon_scale: f32,
+}
+
+impl Default for SpacingMetrics {
+    fn default() -> Self {
+        Self {
+            icon_gap: 4.0,
+            icon_scale: 1.0,
+        }
+    }
+}
+
+/// Active UI color theme
+///
+/// Replace or mutate this resource to restyle every entity tagged with
+/// [`ThemeRole`].
+#[derive(Resource, Debug, Clone, PartialEq, Reflect, Default)]
+#[reflect(Resource)]
+pub struct Theme {
+    /// Button colors
+    pub button: ButtonPalette,
+    /// Focus-related colors
+    pub focus: FocusPalette,
+    /// Text colors
+    pub text: TextPalette,
+    /// Slider colors
+    pub slider: SliderPalette,
+    /// Spacing metrics
+    pub spacing: SpacingMetrics,
+    /// Semantic status colors
+    pub status: StatusPalette,
+}
+
+impl Theme {
+    /// Returns the button background color for the given interaction state
+    pub fn button_color(&self, interaction: Interaction) -> Color {
+        match interaction {
+            Interaction::Pressed => self.button.pressed,
+            Interaction::Hovered => self.button.hovered,
+            Interaction::None => self.button.normal,
+        }
+    }
+}
+
+/// Tags an entity so [`theme_apply_system`] restyles it when the theme changes
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum ThemeRole {
+    /// Button background, following the entity's [`Interaction`] if present
+    Button,
+    /// Disabled button background
+    ButtonDisabled,
+    /// Regular text
+    Text,
+    /// Disabled text
+    TextDisabled,
+    /// Focus highlight background with focus border
+    FocusHighlight,
+    /// Slider track background
+    SliderTrack,
+    /// Slider fill
+    SliderFill,
+    /// Slider handle
+    SliderHandle,
+}
+
+/// Restyles tagged entities when the theme changes or a role is added
+pub fn theme_apply_system(
+    theme: Res<Theme>,
+    mut query: Query<(
+        Ref<ThemeRole>,
+        Option<&Interaction>,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+        Option<&mut Text>,
+    )>,
+) {
+    let theme_changed = theme.is_changed();
+    for (role, interaction, background, border, text) in &mut query {
+        if !theme_changed && !role.is_changed() {
+            continue;
+        }
+
+        let (fill, outline) = match *role {
+            ThemeRole::Button => (
+                Some(theme.button_color(interaction.copied().unwrap_or(Interaction::None))),
+                None,
+            ),
+            ThemeRole::ButtonDisabled => (Some(theme.button.disabled), None),
+            ThemeRole::FocusHighlight => (Some(theme.focus.highlight), Some(theme.focus.border)),
+            ThemeRole::SliderTrack => (Some(theme.slider.background), None),
+            ThemeRole::SliderFill => (Some(theme.slider.foreground), None),
+            ThemeRole::SliderHandle => (Some(theme.slider.handle), None),
+            ThemeRole::Text | ThemeRole::TextDisabled => (None, None),
+        };
+
+        if let (Some(color), Some(mut background)) = (fill, background) {
+            background.0 = color;
+        }
+        if let (Some(color), Some(mut border)) = (outline, border) {
+            border.0 = color;
+        }
+        if let Some(mut text) = text {
+            let color = match *role {
+                ThemeRole::TextDisabled | ThemeRole::ButtonDisabled => theme.text.disabled,
+                ThemeRole::FocusHighlight => theme.focus.text,
+                _ => theme.text.normal,
+            };
+            for section in text.sections.iter_mut() {
+                section.style.color = color;
+            }
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Theme>()
+        .register_type::<ThemeRole>()
+        .init_resource::<Theme>()
+        .add_systems(Update, theme_apply_system);
+}
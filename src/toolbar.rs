@@ -0,0 +1,447 @@
+//! Toolbars with an overflow menu
+//!
+//! A [`Toolbar`] lays out icon buttons and separators in a row. Items that
+//! don't fit in the toolbar's width move, in order, into a dropdown behind a
+//! trailing "…" button; the split is recomputed whenever the toolbar is
+//! resized. Pressing an item, in the row or the menu, sends
+//! [`ToolbarButtonPressed`].
+//!
+//! Give the toolbar a definite width (such as `Val::Percent(100.0)`) so it
+//! is sized by its parent rather than by its items.
+
+use bevy::prelude::*;
+
+use crate::assets::UiFonts;
+use crate::components::{Tooltip, TooltipPosition, UiId};
+use crate::interaction::UiInteractionState;
+use crate::theme::Theme;
+
+/// Space between toolbar items in pixels
+const ITEM_GAP: f32 = 4.0;
+
+/// Width taken by a separator, including its margins, in pixels
+const SEPARATOR_WIDTH: f32 = 9.0;
+
+/// An entry in a [`Toolbar`]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub enum ToolbarItem {
+    /// An icon button
+    Button {
+        /// Identifier sent with [`ToolbarButtonPressed`] and set as the button's [`UiId`]
+        id: String,
+        /// Button icon
+        icon: Handle<Image>,
+        /// Tooltip in the row, and label in the overflow menu
+        label: String,
+    },
+    /// A vertical divider between groups of buttons
+    Separator,
+}
+
+/// A row of icon buttons that overflows into a menu
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Toolbar {
+    /// Items, left to right
+    pub items: Vec<ToolbarItem>,
+    /// Width and height of each button in pixels
+    pub button_size: f32,
+}
+
+impl Default for Toolbar {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            button_size: 32.0,
+        }
+    }
+}
+
+impl Toolbar {
+    /// Creates an empty toolbar
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an icon button
+    pub fn button(
+        mut self,
+        id: impl Into<String>,
+        icon: Handle<Image>,
+        label: impl Into<String>,
+    ) -> Self {
+        self.items.push(ToolbarItem::Button {
+            id: id.into(),
+            icon,
+            label: label.into(),
+        });
+        self
+    }
+
+    /// Appends a separator
+    pub fn separator(mut self) -> Self {
+        self.items.push(ToolbarItem::Separator);
+        self
+    }
+
+    /// Width an item takes in the row, including the gap before it
+    fn item_width(&self, item: &ToolbarItem) -> f32 {
+        ITEM_GAP
+            + match item {
+                ToolbarItem::Button { .. } => self.button_size,
+                ToolbarItem::Separator => SEPARATOR_WIDTH,
+            }
+    }
+}
+
+/// What a [`ToolbarPart`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum ToolbarPartKind {
+    /// The item at this index in the row
+    Item(usize),
+    /// The "…" button
+    Overflow,
+    /// The overflow dropdown
+    Menu,
+    /// The overflow menu entry for the item at this index
+    MenuItem(usize),
+}
+
+/// A node spawned for a [`Toolbar`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ToolbarPart {
+    /// The toolbar this node belongs to
+    pub toolbar: Entity,
+    /// What this node is
+    pub kind: ToolbarPartKind,
+}
+
+/// Sent when a toolbar button is pressed, in the row or the overflow menu
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ToolbarButtonPressed {
+    /// The toolbar entity
+    pub toolbar: Entity,
+    /// The button's [`ToolbarItem::Button::id`]
+    pub id: String,
+}
+
+/// Creates the container bundle for a toolbar
+pub fn toolbar_bundle(toolbar: Toolbar) -> (NodeBundle, Toolbar) {
+    (
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(ITEM_GAP),
+                width: Val::Percent(100.0),
+                ..default()
+            },
+            ..default()
+        },
+        toolbar,
+    )
+}
+
+/// System to rebuild a toolbar's buttons and overflow menu when its items change
+pub fn toolbar_spawn_system(
+    mut commands: Commands,
+    toolbars: Query<(Entity, &Toolbar), Changed<Toolbar>>,
+    theme: Res<Theme>,
+) {
+    for (entity, toolbar) in &toolbars {
+        let part = |kind| ToolbarPart {
+            toolbar: entity,
+            kind,
+        };
+        let font = UiFonts::MEDIUM;
+        let text_style = TextStyle {
+            font,
+            font_size: 16.0,
+            color: theme.text.normal,
+        };
+        let icon = |icon: &Handle<Image>, size| ImageBundle {
+            style: Style {
+                width: Val::Px(size),
+                height: Val::Px(size),
+                ..default()
+            },
+            image: UiImage::new(icon.clone()),
+            ..default()
+        };
+        let icon_size = toolbar.button_size * 0.7;
+
+        commands
+            .entity(entity)
+            .despawn_descendants()
+            .with_children(|row| {
+                for (index, item) in toolbar.items.iter().enumerate() {
+                    match item {
+                        ToolbarItem::Button {
+                            id,
+                            icon: image,
+                            label,
+                        } => {
+                            row.spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        width: Val::Px(toolbar.button_size),
+                                        height: Val::Px(toolbar.button_size),
+                                        flex_shrink: 0.0,
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..default()
+                                    },
+                                    background_color: theme.button.normal.into(),
+                                    ..default()
+                                },
+                                part(ToolbarPartKind::Item(index)),
+                                UiId::new(id.clone()),
+                                Tooltip {
+                                    text: label.clone(),
+                                    position: TooltipPosition::Bottom,
+                                    offset: 4.0,
+                                },
+                            ))
+                            .with_children(|button| {
+                                button.spawn(icon(image, icon_size));
+                            });
+                        }
+                        ToolbarItem::Separator => {
+                            row.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        width: Val::Px(1.0),
+                                        height: Val::Px(toolbar.button_size * 0.7),
+                                        flex_shrink: 0.0,
+                                        margin: UiRect::horizontal(Val::Px(
+                                            (SEPARATOR_WIDTH - 1.0) / 2.0,
+                                        )),
+                                        ..default()
+                                    },
+                                    background_color: theme.text.disabled.into(),
+                                    ..default()
+                                },
+                                part(ToolbarPartKind::Item(index)),
+                            ));
+                        }
+                    }
+                }
+
+                row.spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(toolbar.button_size),
+                            height: Val::Px(toolbar.button_size),
+                            flex_shrink: 0.0,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            display: Display::None,
+                            ..default()
+                        },
+                        background_color: theme.button.normal.into(),
+                        ..default()
+                    },
+                    part(ToolbarPartKind::Overflow),
+                ))
+                .with_children(|overflow| {
+                    overflow.spawn(TextBundle::from_section("…", text_style.clone()));
+                    overflow
+                        .spawn((
+                            NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    top: Val::Percent(100.0),
+                                    right: Val::Px(0.0),
+                                    flex_direction: FlexDirection::Column,
+                                    padding: UiRect::all(Val::Px(4.0)),
+                                    display: Display::None,
+                                    ..default()
+                                },
+                                background_color: theme.button.normal.into(),
+                                z_index: ZIndex::Global(100),
+                                ..default()
+                            },
+                            part(ToolbarPartKind::Menu),
+                        ))
+                        .with_children(|menu| {
+                            for (index, item) in toolbar.items.iter().enumerate() {
+                                let ToolbarItem::Button {
+                                    icon: image, label, ..
+                                } = item
+                                else {
+                                    continue;
+                                };
+                                menu.spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            flex_direction: FlexDirection::Row,
+                                            align_items: AlignItems::Center,
+                                            column_gap: Val::Px(8.0),
+                                            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                                            display: Display::None,
+                                            ..default()
+                                        },
+                                        background_color: theme.button.normal.into(),
+                                        ..default()
+                                    },
+                                    part(ToolbarPartKind::MenuItem(index)),
+                                ))
+                                .with_children(|entry| {
+                                    entry.spawn(icon(image, 16.0));
+                                    entry.spawn(TextBundle::from_section(
+                                        label.clone(),
+                                        text_style.clone(),
+                                    ));
+                                });
+                            }
+                        });
+                });
+            });
+    }
+}
+
+/// System to move items that don't fit into the overflow menu
+///
+/// Runs when the toolbar is resized or its items change. Items keep their
+/// order: once one doesn't fit, it and everything after it overflow.
+pub fn toolbar_overflow_system(
+    toolbars: Query<(Entity, Ref<Toolbar>, Ref<Node>)>,
+    mut parts: Query<(&ToolbarPart, &mut Style)>,
+) {
+    for (entity, toolbar, node) in &toolbars {
+        if !toolbar.is_changed() && !node.is_changed() {
+            continue;
+        }
+        let available = node.size().x;
+        let total: f32 = toolbar
+            .items
+            .iter()
+            .map(|item| toolbar.item_width(item))
+            .sum::<f32>()
+            - ITEM_GAP;
+
+        // Number of leading items shown in the row
+        let mut shown = toolbar.items.len();
+        if total > available {
+            let mut used = toolbar.button_size;
+            shown = toolbar
+                .items
+                .iter()
+                .take_while(|item| {
+                    used += toolbar.item_width(item);
+                    used <= available
+                })
+                .count();
+            // Don't end the row on a separator
+            while shown > 0 && toolbar.items[shown - 1] == ToolbarItem::Separator {
+                shown -= 1;
+            }
+        }
+        let overflowing = shown < toolbar.items.len();
+
+        for (part, mut style) in &mut parts {
+            if part.toolbar != entity {
+                continue;
+            }
+            let visible = match part.kind {
+                ToolbarPartKind::Item(index) => index < shown,
+                ToolbarPartKind::MenuItem(index) => index >= shown,
+                ToolbarPartKind::Overflow => overflowing,
+                ToolbarPartKind::Menu => {
+                    if !overflowing {
+                        style.display = Display::None;
+                    }
+                    continue;
+                }
+            };
+            let display = if visible {
+                Display::Flex
+            } else {
+                Display::None
+            };
+            if style.display != display {
+                style.display = display;
+            }
+        }
+    }
+}
+
+/// System to handle toolbar and overflow menu presses
+///
+/// The menu closes on Escape, after an entry is picked, or on a click
+/// outside it.
+pub fn toolbar_interaction_system(
+    toolbars: Query<&Toolbar>,
+    mut parts: Query<(Entity, &ToolbarPart, &mut Style)>,
+    interaction_state: Res<UiInteractionState>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut pressed_events: EventWriter<ToolbarButtonPressed>,
+) {
+    let mut toggled = Vec::new();
+    for (entity, part, _) in &parts {
+        if !interaction_state.just_pressed(entity) {
+            continue;
+        }
+        match part.kind {
+            ToolbarPartKind::Item(index) | ToolbarPartKind::MenuItem(index) => {
+                let item = toolbars
+                    .get(part.toolbar)
+                    .ok()
+                    .and_then(|toolbar| toolbar.items.get(index));
+                if let Some(ToolbarItem::Button { id, .. }) = item {
+                    pressed_events.send(ToolbarButtonPressed {
+                        toolbar: part.toolbar,
+                        id: id.clone(),
+                    });
+                }
+            }
+            ToolbarPartKind::Overflow => toggled.push(part.toolbar),
+            ToolbarPartKind::Menu => {}
+        }
+    }
+
+    let pressed = interaction_state.current.pressed;
+    let pressed_part = pressed
+        .and_then(|target| parts.get(target).ok())
+        .map(|(_, part, _)| *part);
+    let dismiss = keyboard_input.just_pressed(KeyCode::Escape)
+        || mouse_buttons.just_pressed(MouseButton::Left);
+
+    for (_, part, mut style) in &mut parts {
+        if part.kind != ToolbarPartKind::Menu {
+            continue;
+        }
+        let open = style.display != Display::None;
+        let open = if toggled.contains(&part.toolbar) {
+            !open
+        } else if dismiss && open {
+            // Keep the menu open only while pressing inside it
+            pressed_part.is_some_and(|pressed| {
+                pressed.toolbar == part.toolbar && pressed.kind == ToolbarPartKind::Menu
+            }) && !keyboard_input.just_pressed(KeyCode::Escape)
+        } else {
+            open
+        };
+        let display = if open { Display::Flex } else { Display::None };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Toolbar>()
+        .register_type::<ToolbarPart>()
+        .add_event::<ToolbarButtonPressed>()
+        .add_systems(
+            Update,
+            (
+                toolbar_spawn_system,
+                toolbar_overflow_system,
+                toolbar_interaction_system,
+            )
+                .chain(),
+        );
+}
@@ -0,0 +1,583 @@
+//! Pooled tooltip subsystem
+//!
+//! Targets carry a [`Tooltip`] component; instead of spawning a text entity per
+//! hover, a single tooltip entity per [`TooltipLayer`] is kept in
+//! [`TooltipPool`] and its content is swapped in place for whichever target is
+//! hovered.
+//!
+//! A tooltip appears once its target has been hovered for
+//! [`TooltipSettings::delay`] (or the target's own [`TooltipDelay`]), is placed
+//! around the target per its [`TooltipPosition`] or next to the cursor with
+//! [`TooltipFollowCursor`], and hides as soon as the pointer leaves the
+//! target. Tooltips that would run off the monitor under their target or
+//! the cursor open on the other side instead, and are kept inside that
+//! monitor's part of the window (see [`PopupBounds`]).
+//!
+//! A [`RichTooltip`] shows a styled panel instead of plain text, with a
+//! title, icon, hotkey, body text and cost lines, as RTS command buttons
+//! need:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{RichTooltip, Theme, UiButtonBuilder};
+//! fn setup(mut commands: Commands, theme: Res<Theme>, asset_server: Res<AssetServer>) {
+//!     let tooltip = RichTooltip::new("Barracks")
+//!         .icon(asset_server.load("icons/barracks.png"))
+//!         .hotkey("B")
+//!         .body("Trains infantry.")
+//!         .cost("Minerals", "150")
+//!         .cost("Build time", "46s");
+//!     UiButtonBuilder::new(&theme)
+//!         .label("Barracks")
+//!         .rich_tooltip(tooltip)
+//!         .spawn(&mut commands);
+//! }
+//! ```
+//!
+//! A target with only a [`RichTooltip`] opens it above itself; a [`Tooltip`]
+//! on the same target picks the position and offset instead, and its text
+//! goes unused.
+
+use std::borrow::Cow;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::ui::UiStack;
+use bevy::utils::HashMap;
+use bevy::window::PrimaryWindow;
+
+use crate::assets::UiFonts;
+use crate::clock::UiClock;
+use crate::components::{Tooltip, TooltipPosition};
+use crate::placement::{area_overflow, clamp_to_area, PopupBounds};
+use crate::secondary_action::SecondaryAction;
+use crate::utils::{first_section, first_section_is};
+
+/// Base z-index for pooled tooltips; the layer number is added on top
+pub(crate) const TOOLTIP_Z_INDEX: i32 = 1000;
+
+/// Offset of a [`RichTooltip`] from a target without a [`Tooltip`]
+const RICH_TOOLTIP_OFFSET: f32 = 4.0;
+
+/// Size of the icon in a [`RichTooltip`]
+const RICH_TOOLTIP_ICON_SIZE: f32 = 32.0;
+
+/// Color of the hotkey in a [`RichTooltip`]
+const RICH_TOOLTIP_HOTKEY_COLOR: Color = Color::srgb(1.0, 0.82, 0.3);
+
+/// Color of body text and cost labels in a [`RichTooltip`]
+const RICH_TOOLTIP_DIM_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
+
+/// Color of the [`SecondaryAction`] hint in a [`RichTooltip`]
+const RICH_TOOLTIP_HINT_COLOR: Color = Color::srgb(0.55, 0.8, 1.0);
+
+/// Selects which pooled tooltip a target uses
+///
+/// Targets without this component use layer 0. Use separate layers when
+/// tooltips on different UI roots must be able to show at the same time.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+#[reflect(Component)]
+pub struct TooltipLayer(pub u32);
+
+/// Global tooltip behavior
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct TooltipSettings {
+    /// Seconds a target must be hovered before its tooltip shows
+    pub delay: f32,
+    /// Offset from the cursor for [`TooltipFollowCursor`] targets
+    pub cursor_offset: Vec2,
+    /// Minimum distance kept between a tooltip and the edges of the window or
+    /// monitor
+    pub screen_margin: f32,
+}
+
+impl Default for TooltipSettings {
+    fn default() -> Self {
+        Self {
+            delay: 0.5,
+            cursor_offset: Vec2::new(16.0, 16.0),
+            screen_margin: 4.0,
+        }
+    }
+}
+
+/// Overrides [`TooltipSettings::delay`] for one target, in seconds
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct TooltipDelay(pub f32);
+
+/// Places a target's tooltip next to the cursor instead of around the target
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+#[reflect(Component)]
+pub struct TooltipFollowCursor;
+
+/// Structured tooltip content, shown in a styled panel instead of plain text
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct RichTooltip {
+    /// Bold heading
+    pub title: String,
+    /// Description under the heading; hidden when empty
+    pub body: String,
+    /// Icon left of the heading
+    pub icon: Option<Handle<Image>>,
+    /// Label and value pairs listed under the body, such as resource costs
+    pub cost_lines: Vec<(String, String)>,
+    /// Key shown right of the heading
+    pub hotkey: Option<String>,
+}
+
+impl RichTooltip {
+    /// Creates a tooltip with just a title
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..default()
+        }
+    }
+
+    /// Sets the body text
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Sets the icon
+    pub fn icon(mut self, icon: Handle<Image>) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Adds a cost line
+    pub fn cost(mut self, label: impl Into<String>, value: impl Into<String>) -> Self {
+        self.cost_lines.push((label.into(), value.into()));
+        self
+    }
+
+    /// Sets the hotkey
+    pub fn hotkey(mut self, hotkey: impl Into<String>) -> Self {
+        self.hotkey = Some(hotkey.into());
+        self
+    }
+}
+
+/// Marks a pooled tooltip entity owned by the tooltip subsystem
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct PooledTooltip {
+    /// The layer this tooltip serves
+    pub layer: u32,
+    /// The text child displaying a plain tooltip
+    pub text: Entity,
+    /// The child holding the panel of a [`RichTooltip`]
+    pub rich: Entity,
+}
+
+/// Pooled tooltip entities, one per layer
+#[derive(Resource, Debug, Default)]
+pub struct TooltipPool {
+    tooltips: HashMap<u32, Entity>,
+    /// Hovered target per layer and the UI time its hover started
+    hovers: HashMap<u32, (Entity, f32)>,
+    /// Target whose [`RichTooltip`] each layer's panel was built for
+    rendered: HashMap<u32, Entity>,
+}
+
+impl TooltipPool {
+    /// Returns the pooled tooltip entity for a layer, if it has been spawned
+    pub fn get(&self, layer: u32) -> Option<Entity> {
+        self.tooltips.get(&layer).copied()
+    }
+}
+
+fn spawn_pooled_tooltip(commands: &mut Commands, layer: u32) -> Entity {
+    let text = commands
+        .spawn(TextBundle::from_section(
+            "",
+            TextStyle {
+                font: UiFonts::MEDIUM,
+                font_size: 16.0,
+                color: Color::WHITE,
+            },
+        ))
+        .id();
+    let rich = commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                max_width: Val::Px(320.0),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                background_color: Color::srgba(0.1, 0.1, 0.1, 0.9).into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(TOOLTIP_Z_INDEX + layer as i32),
+                ..default()
+            },
+            PooledTooltip { layer, text, rich },
+            Name::new(format!("Tooltip_{}", layer)),
+        ))
+        .push_children(&[text, rich])
+        .id()
+}
+
+/// Text style of a [`RichTooltip`] line
+fn rich_text_style(font: Handle<Font>, font_size: f32, color: Color) -> TextStyle {
+    TextStyle {
+        font,
+        font_size,
+        color,
+    }
+}
+
+/// Spawns the heading, body and cost lines of a [`RichTooltip`], and the
+/// hint of the target's [`SecondaryAction`]
+fn spawn_rich_tooltip_content(
+    panel: &mut ChildBuilder,
+    tooltip: &RichTooltip,
+    hint: Option<String>,
+) {
+    panel
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(8.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|heading| {
+            if let Some(icon) = &tooltip.icon {
+                heading.spawn(ImageBundle {
+                    style: Style {
+                        width: Val::Px(RICH_TOOLTIP_ICON_SIZE),
+                        height: Val::Px(RICH_TOOLTIP_ICON_SIZE),
+                        ..default()
+                    },
+                    image: UiImage::new(icon.clone()),
+                    ..default()
+                });
+            }
+            heading.spawn(
+                TextBundle::from_section(
+                    tooltip.title.clone(),
+                    rich_text_style(UiFonts::BOLD, 18.0, Color::WHITE),
+                )
+                .with_style(Style {
+                    flex_grow: 1.0,
+                    ..default()
+                }),
+            );
+            if let Some(hotkey) = &tooltip.hotkey {
+                heading.spawn(TextBundle::from_section(
+                    format!("[{hotkey}]"),
+                    rich_text_style(UiFonts::SEMIBOLD, 14.0, RICH_TOOLTIP_HOTKEY_COLOR),
+                ));
+            }
+        });
+    if !tooltip.body.is_empty() {
+        panel.spawn(TextBundle::from_section(
+            tooltip.body.clone(),
+            rich_text_style(UiFonts::REGULAR, 14.0, RICH_TOOLTIP_DIM_COLOR),
+        ));
+    }
+    for (label, value) in &tooltip.cost_lines {
+        panel
+            .spawn(NodeBundle {
+                style: Style {
+                    justify_content: JustifyContent::SpaceBetween,
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|line| {
+                line.spawn(TextBundle::from_section(
+                    label.clone(),
+                    rich_text_style(UiFonts::REGULAR, 14.0, RICH_TOOLTIP_DIM_COLOR),
+                ));
+                line.spawn(TextBundle::from_section(
+                    value.clone(),
+                    rich_text_style(UiFonts::SEMIBOLD, 14.0, Color::WHITE),
+                ));
+            });
+    }
+    if let Some(hint) = hint {
+        panel.spawn(TextBundle::from_section(
+            hint,
+            rich_text_style(UiFonts::REGULAR, 14.0, RICH_TOOLTIP_HINT_COLOR),
+        ));
+    }
+}
+
+/// Computes the top-left corner of a tooltip of `size` placed around `target`
+pub fn tooltip_anchor(target: Rect, size: Vec2, position: TooltipPosition, offset: f32) -> Vec2 {
+    match position {
+        TooltipPosition::Top => Vec2::new(target.min.x, target.min.y - offset - size.y),
+        TooltipPosition::Bottom => Vec2::new(target.min.x, target.max.y + offset),
+        TooltipPosition::Left => Vec2::new(target.min.x - offset - size.x, target.min.y),
+        TooltipPosition::Right => Vec2::new(target.max.x + offset, target.min.y),
+    }
+}
+
+/// Moves `anchor` so a tooltip of `size` stays inside `bounds`, `margin` from the edges
+pub fn clamp_tooltip(anchor: Vec2, size: Vec2, bounds: Vec2, margin: f32) -> Vec2 {
+    let max = (bounds - size - Vec2::splat(margin)).max(Vec2::splat(margin));
+    anchor.clamp(Vec2::splat(margin), max)
+}
+
+/// Places a tooltip of `size` around `target` per `position`, on the
+/// opposite side when that leaves less of it outside `area`, then clamps it
+/// into `area`, `margin` from the edges
+pub fn place_tooltip(
+    target: Rect,
+    size: Vec2,
+    position: TooltipPosition,
+    offset: f32,
+    area: Rect,
+    margin: f32,
+) -> Vec2 {
+    let along = |overflow: Vec2| match position {
+        TooltipPosition::Top | TooltipPosition::Bottom => overflow.y,
+        TooltipPosition::Left | TooltipPosition::Right => overflow.x,
+    };
+    let mut anchor = tooltip_anchor(target, size, position, offset);
+    let overflow = along(area_overflow(anchor, size, area, margin));
+    if overflow > 0.0 {
+        let flipped = tooltip_anchor(target, size, position.opposite(), offset);
+        if along(area_overflow(flipped, size, area, margin)) < overflow {
+            anchor = flipped;
+        }
+    }
+    clamp_to_area(anchor, size, area, margin)
+}
+
+/// Places a tooltip of `size` at `offset` from `cursor`, mirroring the
+/// offset on an axis where the tooltip would leave `area`, then clamps it
+/// into `area`, `margin` from the edges
+pub fn place_at_cursor(cursor: Vec2, size: Vec2, offset: Vec2, area: Rect, margin: f32) -> Vec2 {
+    let mut anchor = cursor + offset;
+    let overflow = area_overflow(anchor, size, area, margin);
+    if overflow.x > 0.0 {
+        anchor.x = cursor.x - offset.x - size.x;
+    }
+    if overflow.y > 0.0 {
+        anchor.y = cursor.y - offset.y - size.y;
+    }
+    clamp_to_area(anchor, size, area, margin)
+}
+
+/// Timing and screen state read by [`tooltip_system`]
+#[derive(SystemParam)]
+pub struct TooltipEnvironment<'w, 's> {
+    settings: Res<'w, TooltipSettings>,
+    clock: Res<'w, UiClock>,
+    ui_scale: Res<'w, UiScale>,
+    bounds: Res<'w, PopupBounds>,
+    ui_stack: Res<'w, UiStack>,
+    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+impl<'w, 's> TooltipEnvironment<'w, 's> {
+    /// Cursor position in UI pixels
+    fn cursor(&self) -> Option<Vec2> {
+        let window = self.windows.get_single().ok()?;
+        window
+            .cursor_position()
+            .map(|cursor| cursor / self.ui_scale.0)
+    }
+}
+
+/// Sets the display of a pooled tooltip's text or rich panel
+fn set_display(
+    parts: &mut Query<&mut Style, Without<PooledTooltip>>,
+    part: Entity,
+    display: Display,
+) {
+    if let Ok(mut style) = parts.get_mut(part) {
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+/// System to handle tooltip visibility and positioning
+///
+/// Shows the pooled tooltip for each layer whose hovered target has waited out
+/// its delay and hides the rest. When nested targets of one layer are all
+/// hovered, the front-most one in the [`UiStack`] gets the tooltip. Pooled entities are spawned lazily and never
+/// despawned; the panel of a [`RichTooltip`] is rebuilt when the target or its
+/// content changes. The hint of a target's [`SecondaryAction`] is appended to
+/// either kind of tooltip, or shown alone on targets without one.
+pub fn tooltip_system(
+    mut commands: Commands,
+    mut pool: ResMut<TooltipPool>,
+    environment: TooltipEnvironment,
+    targets: Query<
+        (
+            Entity,
+            Option<&Tooltip>,
+            Option<Ref<RichTooltip>>,
+            Option<Ref<SecondaryAction>>,
+            &Interaction,
+            &Node,
+            &GlobalTransform,
+            Option<&TooltipLayer>,
+            Option<&TooltipDelay>,
+            Has<TooltipFollowCursor>,
+        ),
+        Or<(With<Tooltip>, With<RichTooltip>, With<SecondaryAction>)>,
+    >,
+    mut pooled: Query<(&PooledTooltip, &Node, &mut Style, &mut Visibility)>,
+    (mut text_query, mut parts): (Query<&mut Text>, Query<&mut Style, Without<PooledTooltip>>),
+) {
+    let settings = *environment.settings;
+    let now = environment.clock.elapsed_seconds();
+    let hovered: Vec<_> = targets
+        .iter()
+        .filter(|(_, tooltip, rich, secondary, interaction, ..)| {
+            let hinted = secondary
+                .as_ref()
+                .is_some_and(|secondary| secondary.hint.is_some());
+            **interaction != Interaction::None && (tooltip.is_some() || rich.is_some() || hinted)
+        })
+        .collect();
+    // Nested targets are hovered together; only rank them when there are several
+    let depths: HashMap<Entity, usize> = if hovered.len() > 1 {
+        environment
+            .ui_stack
+            .uinodes
+            .iter()
+            .enumerate()
+            .map(|(depth, &node)| (node, depth))
+            .collect()
+    } else {
+        HashMap::default()
+    };
+    let mut fronts: HashMap<_, Option<usize>> = HashMap::default();
+    let mut active = HashMap::default();
+    for (entity, tooltip, rich, secondary, _, node, transform, layer, delay, follow) in hovered {
+        let layer = layer.copied().unwrap_or_default().0;
+        let depth = depths.get(&entity).copied();
+        if fronts.get(&layer).is_some_and(|&front| front > depth) {
+            continue;
+        }
+        fronts.insert(layer, depth);
+        let delay = delay.map_or(settings.delay, |delay| delay.0);
+        let target = node.logical_rect(transform);
+        active.insert(
+            layer,
+            (entity, tooltip, rich, secondary, target, delay, follow),
+        );
+    }
+
+    // Restart the delay whenever a layer's hovered target changes
+    pool.hovers.retain(|layer, (entity, _)| {
+        active.get(layer).is_some_and(|(target, ..)| target == entity)
+    });
+    for (&layer, &(entity, ..)) in active.iter() {
+        pool.hovers.entry(layer).or_insert((entity, now));
+        if pool.get(layer).is_none() {
+            let tooltip = spawn_pooled_tooltip(&mut commands, layer);
+            pool.tooltips.insert(layer, tooltip);
+        }
+    }
+
+    let cursor = environment.cursor();
+
+    for (pooled_tooltip, node, mut style, mut visibility) in &mut pooled {
+        let layer = pooled_tooltip.layer;
+        let ready = pool
+            .hovers
+            .get(&layer)
+            .zip(active.get(&layer))
+            .filter(|((_, since), (.., delay, _))| now - since >= *delay);
+        let Some((_, (entity, tooltip, rich, secondary, target, _, follow))) = ready else {
+            if *visibility != Visibility::Hidden {
+                *visibility = Visibility::Hidden;
+            }
+            continue;
+        };
+
+        let hint = secondary
+            .as_ref()
+            .and_then(|secondary| secondary.tooltip_hint());
+        if let Some(rich) = rich {
+            set_display(&mut parts, pooled_tooltip.text, Display::None);
+            set_display(&mut parts, pooled_tooltip.rich, Display::Flex);
+            let hint_changed = secondary.as_ref().is_some_and(Ref::is_changed);
+            if pool.rendered.get(&layer) != Some(entity) || rich.is_changed() || hint_changed {
+                commands
+                    .entity(pooled_tooltip.rich)
+                    .despawn_descendants()
+                    .with_children(|panel| spawn_rich_tooltip_content(panel, rich, hint));
+                pool.rendered.insert(layer, *entity);
+            }
+        } else {
+            set_display(&mut parts, pooled_tooltip.text, Display::Flex);
+            set_display(&mut parts, pooled_tooltip.rich, Display::None);
+            pool.rendered.remove(&layer);
+            let wanted = match (tooltip, hint) {
+                (Some(tooltip), Some(hint)) => Cow::Owned(format!("{}\n{hint}", tooltip.text)),
+                (Some(tooltip), None) => Cow::Borrowed(tooltip.text.as_str()),
+                (None, hint) => Cow::Owned(hint.unwrap_or_default()),
+            };
+            if let Ok(mut text) = text_query.get_mut(pooled_tooltip.text) {
+                if !first_section_is(&text, &wanted) {
+                    first_section(&mut text).value = wanted.into_owned();
+                }
+            }
+        }
+        let (position, offset) = tooltip
+            .map_or((TooltipPosition::Top, RICH_TOOLTIP_OFFSET), |tooltip| {
+                (tooltip.position, tooltip.offset)
+            });
+
+        let size = node.size();
+        let margin = settings.screen_margin;
+        let anchor = match cursor.filter(|_| *follow) {
+            Some(cursor) => match environment.bounds.area_at(cursor) {
+                Some(area) => place_at_cursor(cursor, size, settings.cursor_offset, area, margin),
+                None => cursor + settings.cursor_offset,
+            },
+            None => match environment.bounds.area_at(target.center()) {
+                Some(area) => place_tooltip(*target, size, position, offset, area, margin),
+                None => tooltip_anchor(*target, size, position, offset),
+            },
+        };
+        if style.left != Val::Px(anchor.x) || style.top != Val::Px(anchor.y) {
+            style.left = Val::Px(anchor.x);
+            style.top = Val::Px(anchor.y);
+        }
+        if *visibility != Visibility::Visible {
+            *visibility = Visibility::Visible;
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Tooltip>()
+        .register_type::<RichTooltip>()
+        .register_type::<TooltipLayer>()
+        .register_type::<TooltipDelay>()
+        .register_type::<TooltipFollowCursor>()
+        .register_type::<TooltipSettings>()
+        .register_type::<PooledTooltip>()
+        .init_resource::<TooltipSettings>()
+        .init_resource::<TooltipPool>()
+        .add_systems(Update, tooltip_system);
+}
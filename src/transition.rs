@@ -0,0 +1,304 @@
+//! Enter and exit transitions for whole menus
+//!
+//! Insert a [`UiTransition`] on a menu's root node to fade it in or out, or
+//! slide it in from a window edge. The whole subtree is animated, and
+//! [`TransitionFinished`] is sent once it is done, which is the cue to switch
+//! menu states:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{TransitionFinished, TransitionKind, UiTransition};
+//! fn leave_menu(mut commands: Commands, menu: Entity) {
+//!     commands.entity(menu).insert(UiTransition::fade_out(0.25));
+//! }
+//!
+//! fn on_finished(mut commands: Commands, mut finished: EventReader<TransitionFinished>) {
+//!     for event in finished.read() {
+//!         if event.kind == TransitionKind::FadeOut {
+//!             commands.entity(event.entity).despawn_recursive();
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Fades scale the alpha of every background, border, image and text color
+//! in the subtree, as captured on the transition's first frame; a node faded
+//! out is left hidden with its colors restored. Slides offset the root's
+//! `left`/`right` or `top`/`bottom`, so they suit roots positioned with pixel
+//! or auto offsets.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::clock::UiClock;
+use crate::quality::UiMotion;
+use crate::tween::Easing;
+
+/// A side of the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum Edge {
+    /// The left side
+    Left,
+    /// The right side
+    Right,
+    /// The top side
+    Top,
+    /// The bottom side
+    Bottom,
+}
+
+/// What a [`UiTransition`] does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum TransitionKind {
+    /// Shows the subtree and fades it from transparent to its colors
+    FadeIn,
+    /// Fades the subtree to transparent, then hides it
+    FadeOut,
+    /// Moves the root in from beyond the given window edge
+    SlideInFrom(Edge),
+}
+
+/// Alphas of one node's colors before a fade
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct NodeAlphas {
+    background: Option<f32>,
+    border: Option<f32>,
+    image: Option<f32>,
+    text: Vec<f32>,
+}
+
+/// State captured on a transition's first frame
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TransitionStart {
+    alphas: Vec<(Entity, NodeAlphas)>,
+    offsets: [Val; 4],
+    distance: Vec2,
+}
+
+/// Animates a root node and its subtree in or out
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct UiTransition {
+    /// What the transition does
+    pub kind: TransitionKind,
+    /// Length in seconds
+    pub duration: f32,
+    /// Progress curve
+    pub easing: Easing,
+    /// Seconds played so far
+    pub elapsed: f32,
+    #[reflect(ignore)]
+    start: Option<TransitionStart>,
+}
+
+impl UiTransition {
+    /// Creates a transition lasting `duration` seconds
+    pub fn new(kind: TransitionKind, duration: f32) -> Self {
+        Self {
+            kind,
+            duration,
+            easing: Easing::CubicOut,
+            elapsed: 0.0,
+            start: None,
+        }
+    }
+
+    /// Fades in over `duration` seconds
+    pub fn fade_in(duration: f32) -> Self {
+        Self::new(TransitionKind::FadeIn, duration)
+    }
+
+    /// Fades out over `duration` seconds
+    pub fn fade_out(duration: f32) -> Self {
+        Self::new(TransitionKind::FadeOut, duration)
+    }
+
+    /// Slides in from `edge` over `duration` seconds
+    pub fn slide_in_from(edge: Edge, duration: f32) -> Self {
+        Self::new(TransitionKind::SlideInFrom(edge), duration)
+    }
+
+    /// Sets the easing curve
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Sent when a [`UiTransition`] finishes and is removed
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionFinished {
+    /// The root node
+    pub entity: Entity,
+    /// The transition that finished
+    pub kind: TransitionKind,
+}
+
+/// The color components a fade scales
+pub(crate) type FadedColors<'a> = (
+    Option<&'a mut BackgroundColor>,
+    Option<&'a mut BorderColor>,
+    Option<&'a mut UiImage>,
+    Option<&'a mut Text>,
+);
+
+/// Reads the alphas of `root` and every node below it
+pub(crate) fn capture_alphas(
+    root: Entity,
+    children: &Query<&Children>,
+    colors: &Query<FadedColors>,
+) -> Vec<(Entity, NodeAlphas)> {
+    std::iter::once(root)
+        .chain(children.iter_descendants(root))
+        .filter_map(|node| {
+            let (background, border, image, text) = colors.get(node).ok()?;
+            Some((
+                node,
+                NodeAlphas {
+                    background: background.map(|color| color.0.alpha()),
+                    border: border.map(|color| color.0.alpha()),
+                    image: image.map(|image| image.color.alpha()),
+                    text: text.map_or_else(Vec::new, |text| {
+                        text.sections
+                            .iter()
+                            .map(|section| section.style.color.alpha())
+                            .collect()
+                    }),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Sets every captured node's alphas to their start values times `opacity`
+pub(crate) fn apply_opacity(
+    alphas: &[(Entity, NodeAlphas)],
+    opacity: f32,
+    colors: &mut Query<FadedColors>,
+) {
+    for (entity, alphas) in alphas {
+        let Ok((background, border, image, text)) = colors.get_mut(*entity) else {
+            continue;
+        };
+        if let (Some(mut background), Some(alpha)) = (background, alphas.background) {
+            background.0.set_alpha(alpha * opacity);
+        }
+        if let (Some(mut border), Some(alpha)) = (border, alphas.border) {
+            border.0.set_alpha(alpha * opacity);
+        }
+        if let (Some(mut image), Some(alpha)) = (image, alphas.image) {
+            image.color.set_alpha(alpha * opacity);
+        }
+        if let Some(mut text) = text {
+            for (section, alpha) in text.sections.iter_mut().zip(&alphas.text) {
+                section.style.color.set_alpha(alpha * opacity);
+            }
+        }
+    }
+}
+
+/// Shifts a pixel or auto offset by `by` pixels
+fn shift(val: Val, by: f32) -> Val {
+    match val {
+        Val::Px(px) => Val::Px(px + by),
+        Val::Auto => Val::Px(by),
+        other => other,
+    }
+}
+
+/// Offsets the root by `offset`, starting from the captured `left`, `right`, `top` and `bottom`
+fn apply_offset(style: &mut Style, [left, right, top, bottom]: [Val; 4], offset: Vec2) {
+    // Move whichever side positions the node
+    if left != Val::Auto || right == Val::Auto {
+        style.left = shift(left, offset.x);
+    } else {
+        style.right = shift(right, -offset.x);
+    }
+    if top != Val::Auto || bottom == Val::Auto {
+        style.top = shift(top, offset.y);
+    } else {
+        style.bottom = shift(bottom, -offset.y);
+    }
+}
+
+/// System to play transitions and send [`TransitionFinished`]
+pub fn ui_transition_system(
+    mut commands: Commands,
+    (clock, motion, ui_scale): (Res<UiClock>, UiMotion, Res<UiScale>),
+    mut transitions: Query<(Entity, &mut UiTransition, &mut Style, &mut Visibility)>,
+    children: Query<&Children>,
+    mut colors: Query<FadedColors>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut finished_events: EventWriter<TransitionFinished>,
+) {
+    for (entity, mut transition, mut style, mut visibility) in &mut transitions {
+        let transition = &mut *transition;
+        let start = transition.start.get_or_insert_with(|| {
+            let alphas = capture_alphas(entity, &children, &colors);
+            let distance = windows.get_single().map_or(Vec2::ZERO, |window| {
+                Vec2::new(window.width(), window.height()) / ui_scale.0
+            });
+            TransitionStart {
+                alphas,
+                offsets: [style.left, style.right, style.top, style.bottom],
+                distance,
+            }
+        });
+
+        if transition.kind == TransitionKind::FadeIn && *visibility == Visibility::Hidden {
+            *visibility = Visibility::Inherited;
+        }
+        transition.elapsed += clock.delta_seconds();
+        if !motion.animations() {
+            transition.elapsed = transition.duration;
+        }
+        let progress = if transition.duration > 0.0 {
+            (transition.elapsed / transition.duration).min(1.0)
+        } else {
+            1.0
+        };
+        let t = transition.easing.ease(progress);
+        let finished = progress >= 1.0;
+
+        match transition.kind {
+            TransitionKind::FadeIn => apply_opacity(&start.alphas, t, &mut colors),
+            TransitionKind::FadeOut if finished => {
+                // Hide, and restore the colors for the next time it is shown
+                *visibility = Visibility::Hidden;
+                apply_opacity(&start.alphas, 1.0, &mut colors);
+            }
+            TransitionKind::FadeOut => apply_opacity(&start.alphas, 1.0 - t, &mut colors),
+            TransitionKind::SlideInFrom(_) if finished => {
+                let [left, right, top, bottom] = start.offsets;
+                style.left = left;
+                style.right = right;
+                style.top = top;
+                style.bottom = bottom;
+            }
+            TransitionKind::SlideInFrom(edge) => {
+                let remaining = (1.0 - t) * start.distance;
+                let offset = match edge {
+                    Edge::Left => Vec2::new(-remaining.x, 0.0),
+                    Edge::Right => Vec2::new(remaining.x, 0.0),
+                    Edge::Top => Vec2::new(0.0, -remaining.y),
+                    Edge::Bottom => Vec2::new(0.0, remaining.y),
+                };
+                apply_offset(&mut style, start.offsets, offset);
+            }
+        }
+
+        if finished {
+            commands.entity(entity).remove::<UiTransition>();
+            finished_events.send(TransitionFinished {
+                entity,
+                kind: transition.kind,
+            });
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiTransition>()
+        .add_event::<TransitionFinished>()
+        .add_systems(Update, ui_transition_system);
+}
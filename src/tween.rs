@@ -0,0 +1,481 @@
+//! Tweened style, color, opacity and scale changes
+//!
+//! A [`UiTween`] animates properties of its node from their current values to
+//! the given targets over a duration, shaped by an [`Easing`] curve. The start
+//! values are captured on the first frame, so a tween can be inserted at any
+//! time without knowing where the node currently is:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{Easing, UiTween};
+//! fn grow(mut commands: Commands, panel: Entity) {
+//!     commands.entity(panel).insert(
+//!         UiTween::new(0.3)
+//!             .width(Val::Px(480.0))
+//!             .background_color(Color::srgb(0.2, 0.2, 0.3))
+//!             .easing(Easing::CubicOut),
+//!     );
+//! }
+//! ```
+//!
+//! A finished one-shot tween is removed and [`UiTweenCompleted`] is sent;
+//! looping tweens send it at the end of every cycle. Tweens run on
+//! [`UiClock`] and snap to their targets when [`UiQuality`](crate::UiQuality)
+//! or [`ReducedMotion`](crate::ReducedMotion) turns animations off.
+//!
+//! [`UiHoverTransition`] uses tweens to fade button colors and scale buttons
+//! up on hover instead of swapping colors instantly.
+
+use bevy::color::Mix;
+use bevy::prelude::*;
+
+use crate::clock::UiClock;
+use crate::quality::UiMotion;
+
+/// Shape of a tween's progress over time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum Easing {
+    /// Constant speed
+    Linear,
+    /// Starts slow
+    QuadIn,
+    /// Ends slow
+    QuadOut,
+    /// Starts and ends slow
+    #[default]
+    QuadInOut,
+    /// Starts slow, more strongly than [`Easing::QuadIn`]
+    CubicIn,
+    /// Ends slow, more strongly than [`Easing::QuadOut`]
+    CubicOut,
+    /// Starts and ends slow, more strongly than [`Easing::QuadInOut`]
+    CubicInOut,
+    /// Overshoots the target slightly before settling
+    BackOut,
+}
+
+impl Easing {
+    /// Maps linear progress in `0..=1` to eased progress
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut if t < 0.5 => 2.0 * t * t,
+            Easing::QuadInOut => 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0,
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::CubicInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+            Easing::BackOut => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.0;
+                1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+            }
+        }
+    }
+}
+
+/// What a tween does after reaching its end
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum TweenRepeat {
+    /// Stops and removes the tween
+    #[default]
+    Once,
+    /// Jumps back to the start values and plays again
+    Loop,
+    /// Plays backwards to the start values, then forwards again
+    PingPong,
+}
+
+/// A property a [`UiTween`] animates, holding its target value
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum TweenTarget {
+    /// `Style::width`
+    Width(Val),
+    /// `Style::height`
+    Height(Val),
+    /// `Style::margin`
+    Margin(UiRect),
+    /// The node's [`BackgroundColor`]
+    BackgroundColor(Color),
+    /// Alpha of the node's background, border, image and text colors
+    Opacity(f32),
+    /// Uniform `Transform` scale, around the node's center
+    Scale(f32),
+}
+
+/// Animates properties of a node towards target values
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct UiTween {
+    /// Properties and their target values
+    pub targets: Vec<TweenTarget>,
+    /// Length of one pass in seconds
+    pub duration: f32,
+    /// Progress curve
+    pub easing: Easing,
+    /// Behavior at the end of a pass
+    pub repeat: TweenRepeat,
+    /// Seconds played so far in the current pass
+    pub elapsed: f32,
+    /// Whether the current pass runs from the targets back to the start
+    pub reversed: bool,
+    /// Start values, captured on the first frame
+    starts: Option<Vec<TweenTarget>>,
+}
+
+impl UiTween {
+    /// Creates a tween lasting `duration` seconds with no targets yet
+    pub fn new(duration: f32) -> Self {
+        Self {
+            targets: Vec::new(),
+            duration,
+            easing: Easing::default(),
+            repeat: TweenRepeat::default(),
+            elapsed: 0.0,
+            reversed: false,
+            starts: None,
+        }
+    }
+
+    /// Adds a target
+    pub fn target(mut self, target: TweenTarget) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Animates `Style::width`
+    pub fn width(self, width: Val) -> Self {
+        self.target(TweenTarget::Width(width))
+    }
+
+    /// Animates `Style::height`
+    pub fn height(self, height: Val) -> Self {
+        self.target(TweenTarget::Height(height))
+    }
+
+    /// Animates `Style::margin`
+    pub fn margin(self, margin: UiRect) -> Self {
+        self.target(TweenTarget::Margin(margin))
+    }
+
+    /// Animates the background color
+    pub fn background_color(self, color: Color) -> Self {
+        self.target(TweenTarget::BackgroundColor(color))
+    }
+
+    /// Animates the node's opacity
+    pub fn opacity(self, opacity: f32) -> Self {
+        self.target(TweenTarget::Opacity(opacity))
+    }
+
+    /// Animates the node's scale
+    pub fn scale(self, scale: f32) -> Self {
+        self.target(TweenTarget::Scale(scale))
+    }
+
+    /// Sets the easing curve
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets the behavior at the end of a pass
+    pub fn repeat(mut self, repeat: TweenRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Linear progress through the current pass, in `0..=1`
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Sent when a [`UiTween`] finishes a pass
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiTweenCompleted {
+    /// The tweened node
+    pub entity: Entity,
+}
+
+/// Fades a button's background and scales it on hover
+///
+/// The button's colors still come from the usual interaction systems; this
+/// only turns their instant swaps into short tweens.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct UiHoverTransition {
+    /// Transition length in seconds
+    pub duration: f32,
+    /// Scale while hovered or pressed
+    pub hover_scale: f32,
+    /// Progress curve
+    pub easing: Easing,
+    /// Background color shown at the end of the last frame
+    displayed: Option<Color>,
+}
+
+impl Default for UiHoverTransition {
+    fn default() -> Self {
+        Self::new(0.12, 1.05)
+    }
+}
+
+impl UiHoverTransition {
+    /// Creates a transition lasting `duration` seconds that scales to `hover_scale`
+    pub fn new(duration: f32, hover_scale: f32) -> Self {
+        Self {
+            duration,
+            hover_scale,
+            easing: Easing::QuadOut,
+            displayed: None,
+        }
+    }
+}
+
+/// Interpolates two values of the same unit; mixed units switch at the end
+fn lerp_val(from: Val, to: Val, t: f32) -> Val {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    match (from, to) {
+        (Val::Px(a), Val::Px(b)) => Val::Px(lerp(a, b)),
+        (Val::Percent(a), Val::Percent(b)) => Val::Percent(lerp(a, b)),
+        (Val::Vw(a), Val::Vw(b)) => Val::Vw(lerp(a, b)),
+        (Val::Vh(a), Val::Vh(b)) => Val::Vh(lerp(a, b)),
+        (Val::VMin(a), Val::VMin(b)) => Val::VMin(lerp(a, b)),
+        (Val::VMax(a), Val::VMax(b)) => Val::VMax(lerp(a, b)),
+        _ if t >= 1.0 => to,
+        _ => from,
+    }
+}
+
+fn lerp_rect(from: UiRect, to: UiRect, t: f32) -> UiRect {
+    UiRect {
+        left: lerp_val(from.left, to.left, t),
+        right: lerp_val(from.right, to.right, t),
+        top: lerp_val(from.top, to.top, t),
+        bottom: lerp_val(from.bottom, to.bottom, t),
+    }
+}
+
+/// The node components a tween reads and writes
+type TweenedNode<'a> = (
+    Option<Mut<'a, Style>>,
+    Option<Mut<'a, BackgroundColor>>,
+    Option<Mut<'a, BorderColor>>,
+    Option<Mut<'a, UiImage>>,
+    Option<Mut<'a, Text>>,
+    Option<Mut<'a, Transform>>,
+);
+
+/// Reads the current value of the property `target` animates
+fn current_value(target: &TweenTarget, node: &TweenedNode) -> TweenTarget {
+    let (style, background, _, image, text, transform) = node;
+    match *target {
+        TweenTarget::Width(to) => TweenTarget::Width(style.as_ref().map_or(to, |s| s.width)),
+        TweenTarget::Height(to) => TweenTarget::Height(style.as_ref().map_or(to, |s| s.height)),
+        TweenTarget::Margin(to) => TweenTarget::Margin(style.as_ref().map_or(to, |s| s.margin)),
+        TweenTarget::BackgroundColor(to) => {
+            TweenTarget::BackgroundColor(background.as_ref().map_or(to, |b| b.0))
+        }
+        TweenTarget::Opacity(to) => {
+            let alpha = background
+                .as_ref()
+                .map(|b| b.0.alpha())
+                .or_else(|| image.as_ref().map(|i| i.color.alpha()))
+                .or_else(|| {
+                    text.as_ref()
+                        .and_then(|t| t.sections.first())
+                        .map(|s| s.style.color.alpha())
+                });
+            TweenTarget::Opacity(alpha.unwrap_or(to))
+        }
+        TweenTarget::Scale(to) => TweenTarget::Scale(transform.as_ref().map_or(to, |t| t.scale.x)),
+    }
+}
+
+/// Writes the value `t` of the way from `from` to `to`
+fn apply_value(from: &TweenTarget, to: &TweenTarget, t: f32, node: &mut TweenedNode) {
+    let (style, background, border, image, text, transform) = node;
+    match (*from, *to) {
+        (TweenTarget::Width(a), TweenTarget::Width(b)) => {
+            if let Some(style) = style {
+                style.width = lerp_val(a, b, t);
+            }
+        }
+        (TweenTarget::Height(a), TweenTarget::Height(b)) => {
+            if let Some(style) = style {
+                style.height = lerp_val(a, b, t);
+            }
+        }
+        (TweenTarget::Margin(a), TweenTarget::Margin(b)) => {
+            if let Some(style) = style {
+                style.margin = lerp_rect(a, b, t);
+            }
+        }
+        (TweenTarget::BackgroundColor(a), TweenTarget::BackgroundColor(b)) => {
+            if let Some(background) = background {
+                background.0 = a.mix(&b, t);
+            }
+        }
+        (TweenTarget::Opacity(a), TweenTarget::Opacity(b)) => {
+            let alpha = a + (b - a) * t;
+            if let Some(background) = background {
+                background.0.set_alpha(alpha);
+            }
+            if let Some(border) = border {
+                border.0.set_alpha(alpha);
+            }
+            if let Some(image) = image {
+                image.color.set_alpha(alpha);
+            }
+            if let Some(text) = text {
+                for section in &mut text.sections {
+                    section.style.color.set_alpha(alpha);
+                }
+            }
+        }
+        (TweenTarget::Scale(a), TweenTarget::Scale(b)) => {
+            if let Some(transform) = transform {
+                transform.scale = Vec3::splat(a + (b - a) * t);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// System to advance tweens and apply their values
+pub fn ui_tween_system(
+    mut commands: Commands,
+    clock: Res<UiClock>,
+    motion: UiMotion,
+    mut tweens: Query<(
+        Entity,
+        &mut UiTween,
+        Option<&mut Style>,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+        Option<&mut UiImage>,
+        Option<&mut Text>,
+        Option<&mut Transform>,
+    )>,
+    mut completed_events: EventWriter<UiTweenCompleted>,
+) {
+    for (entity, mut tween, style, background, border, image, text, transform) in &mut tweens {
+        let mut node = (style, background, border, image, text, transform);
+        let tween = &mut *tween;
+        let starts = tween.starts.get_or_insert_with(|| {
+            tween
+                .targets
+                .iter()
+                .map(|target| current_value(target, &node))
+                .collect()
+        });
+
+        tween.elapsed += clock.delta_seconds();
+        if !motion.animations() {
+            tween.elapsed = tween.duration;
+        }
+        let finished = tween.elapsed >= tween.duration;
+        let progress = if tween.duration > 0.0 {
+            (tween.elapsed / tween.duration).min(1.0)
+        } else {
+            1.0
+        };
+        let progress = if tween.reversed {
+            1.0 - progress
+        } else {
+            progress
+        };
+        let t = tween.easing.ease(progress);
+        for (from, to) in starts.iter().zip(&tween.targets) {
+            apply_value(from, to, t, &mut node);
+        }
+
+        if !finished {
+            continue;
+        }
+        completed_events.send(UiTweenCompleted { entity });
+        match tween.repeat {
+            // Without animations a repeating tween would complete every frame
+            _ if !motion.animations() => {
+                commands.entity(entity).remove::<UiTween>();
+            }
+            TweenRepeat::Once => {
+                commands.entity(entity).remove::<UiTween>();
+            }
+            TweenRepeat::Loop => tween.elapsed = 0.0,
+            TweenRepeat::PingPong => {
+                tween.elapsed = 0.0;
+                tween.reversed = !tween.reversed;
+            }
+        }
+    }
+}
+
+/// System to turn hover color swaps into tweens
+///
+/// Runs after the interaction systems have set the new color: the node is put
+/// back to the color it showed last frame and tweened to the new one.
+pub fn ui_hover_transition_system(
+    mut commands: Commands,
+    mut buttons: Query<(
+        Entity,
+        Ref<Interaction>,
+        &mut BackgroundColor,
+        &UiHoverTransition,
+    )>,
+) {
+    for (entity, interaction, mut background, transition) in &mut buttons {
+        let Some(displayed) = transition.displayed else {
+            continue;
+        };
+        if !interaction.is_changed() {
+            continue;
+        }
+        let target = background.0;
+        background.0 = displayed;
+        let scale = match *interaction {
+            Interaction::None => 1.0,
+            Interaction::Hovered | Interaction::Pressed => transition.hover_scale,
+        };
+        commands.entity(entity).insert(
+            UiTween::new(transition.duration)
+                .background_color(target)
+                .scale(scale)
+                .easing(transition.easing),
+        );
+    }
+}
+
+/// System to record the color hover transitions start from next frame
+pub fn ui_hover_transition_record_system(
+    mut buttons: Query<(&BackgroundColor, &mut UiHoverTransition)>,
+) {
+    for (background, mut transition) in &mut buttons {
+        transition.displayed = Some(background.0);
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<UiTween>()
+        .register_type::<UiHoverTransition>()
+        .add_event::<UiTweenCompleted>()
+        .add_systems(
+            Update,
+            (
+                ui_hover_transition_system
+                    .after(crate::button_interaction_system)
+                    .after(crate::systems::button_interaction_system),
+                ui_tween_system,
+                ui_hover_transition_record_system,
+            )
+                .chain(),
+        );
+}
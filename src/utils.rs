@@ -2,10 +2,14 @@
 
 use bevy::{
     prelude::*,
-    ui::{AlignItems, JustifyContent, Style, UiRect, Val},
+    ui::{AlignItems, JustifyContent, Style, UiRect, UiSystem, Val},
+    window::PrimaryWindow,
 };
 
+use crate::assets::UiFonts;
 use crate::colors;
+use crate::components::{ScrollBar, ScrollThumb};
+use crate::theme::Theme;
 
 
 /// Creates a centered container with the given content
@@ -49,16 +53,18 @@ pub fn h_stack(style: Style) -> NodeBundle {
 }
 
 /// Creates a simple text bundle with the given style
+///
+/// The asset server is no longer used: the text is drawn with [`UiFonts`].
 pub fn text_bundle(
     text: impl Into<String>,
-    asset_server: &AssetServer,
+    _asset_server: &AssetServer,
     font_size: f32,
     color: Color,
 ) -> TextBundle {
     TextBundle::from_section(
         text,
         TextStyle {
-            font: asset_server.load("fonts/FiraSans-Medium.ttf"),
+            font: UiFonts::MEDIUM,
             font_size,
             color,
         },
@@ -70,9 +76,11 @@ pub fn text_bundle(
 }
 
 /// Creates a button with the given text and style
+///
+/// The asset server is no longer used: the text is drawn with [`UiFonts`].
 pub fn button_bundle(
     text: impl Into<String>,
-    asset_server: &AssetServer,
+    _asset_server: &AssetServer,
     style: Style,
 ) -> (ButtonBundle, TextBundle) {
     let button = ButtonBundle {
@@ -90,7 +98,7 @@ pub fn button_bundle(
     let text = TextBundle::from_section(
         text,
         TextStyle {
-            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+            font: UiFonts::BOLD,
             font_size: 24.0,
             color: Color::WHITE,
         },
@@ -100,6 +108,11 @@ pub fn button_bundle(
 }
 
 /// Creates a slider with the given range and value
+///
+/// Returns the track, fill and handle bundles. Spawn the track with a
+/// [`UiSlider`](crate::UiSlider) and [`Interaction`], and tag the fill and
+/// handle children with [`SliderFill`](crate::SliderFill) and
+/// [`SliderHandle`](crate::SliderHandle) so dragging updates them.
 pub fn slider_bundle(
     min: f32,
     max: f32,
@@ -117,10 +130,10 @@ pub fn slider_bundle(
         ..default()
     };
 
-    let fill_width = ((value - min) / (max - min)).clamp(0.0, 1.0) * 200.0;
+    let percent = ((value - min) / (max - min)).clamp(0.0, 1.0) * 100.0;
     let fill = NodeBundle {
         style: Style {
-            width: Val::Px(fill_width),
+            width: Val::Percent(percent),
             height: Val::Percent(100.0),
             ..default()
         },
@@ -133,7 +146,8 @@ pub fn slider_bundle(
             width: Val::Px(16.0),
             height: Val::Px(24.0),
             position_type: bevy::ui::PositionType::Absolute,
-            left: Val::Px(fill_width - 8.0),
+            left: Val::Percent(percent),
+            margin: UiRect::left(Val::Px(-8.0)),
             ..default()
         },
         background_color: colors::slider::HANDLE.into(),
@@ -144,10 +158,9 @@ pub fn slider_bundle(
 }
 
 /// Creates a checkbox with the given state
-pub fn checkbox_bundle(
-    checked: bool,
-    asset_server: &AssetServer,
-) -> (NodeBundle, TextBundle) {
+///
+/// The asset server is no longer used: the text is drawn with [`UiFonts`].
+pub fn checkbox_bundle(checked: bool, _asset_server: &AssetServer) -> (NodeBundle, TextBundle) {
     let checkbox = NodeBundle {
         style: Style {
             width: Val::Px(24.0),
@@ -166,7 +179,7 @@ pub fn checkbox_bundle(
     let check = TextBundle::from_section(
         if checked { "X" } else { "" },
         TextStyle {
-            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+            font: UiFonts::BOLD,
             font_size: 20.0,
             color: Color::WHITE,
         },
@@ -176,9 +189,11 @@ pub fn checkbox_bundle(
 }
 
 /// Creates a tooltip component
+///
+/// The asset server is no longer used: the text is drawn with [`UiFonts`].
 pub fn tooltip_bundle(
     text: impl Into<String>,
-    asset_server: &AssetServer,
+    _asset_server: &AssetServer,
     position: Vec2,
 ) -> (NodeBundle, TextBundle) {
     let tooltip = NodeBundle {
@@ -196,7 +211,7 @@ pub fn tooltip_bundle(
     let text = TextBundle::from_section(
         text,
         TextStyle {
-            font: asset_server.load("fonts/FiraSans-Medium.ttf"),
+            font: UiFonts::MEDIUM,
             font_size: 16.0,
             color: Color::WHITE,
         },
@@ -204,3 +219,376 @@ pub fn tooltip_bundle(
 
     (tooltip, text)
 }
+
+/// Builder for an inline icon + text row
+///
+/// Created by [`labeled_icon`]. Use this for costs, requirements and buffs
+/// instead of assembling an [`h_stack`] by hand.
+#[derive(Debug, Clone)]
+pub struct LabeledIcon {
+    icon: Handle<Image>,
+    text: String,
+    font: Handle<Font>,
+    font_size: f32,
+    color: Color,
+    icon_size: Option<f32>,
+    icon_scale: f32,
+    gap: f32,
+}
+
+/// Starts an icon + text row using spacing and colors from `theme`
+///
+/// The icon is sized from the font size and vertically centered on the text
+/// line, so rows line up regardless of icon source dimensions.
+pub fn labeled_icon(icon: Handle<Image>, text: impl Into<String>, theme: &Theme) -> LabeledIcon {
+    LabeledIcon {
+        icon,
+        text: text.into(),
+        font: UiFonts::MEDIUM,
+        font_size: 16.0,
+        color: theme.text.normal,
+        icon_size: None,
+        icon_scale: theme.spacing.icon_scale,
+        gap: theme.spacing.icon_gap,
+    }
+}
+
+impl LabeledIcon {
+    /// Sets the font size (the icon follows unless sized explicitly)
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the text color
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets an explicit icon size in pixels
+    pub fn icon_size(mut self, size: f32) -> Self {
+        self.icon_size = Some(size);
+        self
+    }
+
+    /// Sets the gap between icon and text in pixels
+    pub fn gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Returns the row, icon and text bundles
+    pub fn bundles(self) -> (NodeBundle, ImageBundle, TextBundle) {
+        let icon_size = self.icon_size.unwrap_or(self.font_size * self.icon_scale);
+
+        let row = NodeBundle {
+            style: Style {
+                flex_direction: bevy::ui::FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(self.gap),
+                ..default()
+            },
+            ..default()
+        };
+
+        let icon = ImageBundle {
+            style: Style {
+                width: Val::Px(icon_size),
+                height: Val::Px(icon_size),
+                flex_shrink: 0.0,
+                ..default()
+            },
+            image: UiImage::new(self.icon),
+            ..default()
+        };
+
+        let text = TextBundle::from_section(
+            self.text,
+            TextStyle {
+                font: self.font,
+                font_size: self.font_size,
+                color: self.color,
+            },
+        );
+
+        (row, icon, text)
+    }
+
+    /// Spawns the row under `parent` and returns the row entity
+    pub fn spawn(self, parent: &mut ChildBuilder) -> Entity {
+        let (row, icon, text) = self.bundles();
+        parent
+            .spawn(row)
+            .with_children(|row| {
+                row.spawn(icon);
+                row.spawn(text);
+            })
+            .id()
+    }
+}
+
+/// Spawns a vertical scroll bar for `pane` under `parent` (usually the pane itself)
+///
+/// Mark the pane's content container with
+/// [`ScrollContent`](crate::components::ScrollContent) so the bar isn't
+/// mistaken for it. Returns the track entity.
+pub fn spawn_scroll_bar(parent: &mut ChildBuilder, pane: Entity, width: f32) -> Entity {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: bevy::ui::PositionType::Absolute,
+                    right: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Px(width),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: colors::slider::BACKGROUND.into(),
+                ..default()
+            },
+            Interaction::default(),
+            ScrollBar { pane },
+        ))
+        .with_children(|track| {
+            track.spawn((
+                ButtonBundle {
+                    style: Style {
+                        position_type: bevy::ui::PositionType::Absolute,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    background_color: colors::slider::HANDLE.into(),
+                    ..default()
+                },
+                ScrollThumb {
+                    pane,
+                    grab_offset: 0.0,
+                },
+            ));
+        })
+        .id()
+}
+
+/// Creates an absolutely positioned style placed and sized in percent of the parent
+pub fn percent_rect(left: f32, top: f32, width: f32, height: f32) -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        left: Val::Percent(left),
+        top: Val::Percent(top),
+        width: Val::Percent(width),
+        height: Val::Percent(height),
+        ..default()
+    }
+}
+
+/// Point of the screen a [`ScreenAnchor`] holds a node to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum AnchorPoint {
+    /// Top-left corner
+    #[default]
+    TopLeft,
+    /// Middle of the top edge
+    TopCenter,
+    /// Top-right corner
+    TopRight,
+    /// Middle of the left edge
+    CenterLeft,
+    /// Center of the screen
+    Center,
+    /// Middle of the right edge
+    CenterRight,
+    /// Bottom-left corner
+    BottomLeft,
+    /// Middle of the bottom edge
+    BottomCenter,
+    /// Bottom-right corner
+    BottomRight,
+}
+
+/// Keeps a root HUD container at a point of the screen, inside the [`SafeArea`]
+///
+/// Offsets push the node inward from the edges it is anchored to; on a
+/// centered axis they move it right or down. The node's position offsets are
+/// rewritten whenever the window, the safe area or the node's size changes.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ScreenAnchor {
+    /// Point of the screen the node is held to
+    pub point: AnchorPoint,
+    /// Offset from that point in logical pixels
+    pub offset: Vec2,
+}
+
+impl ScreenAnchor {
+    /// Anchors to `point`, `offset` pixels inward
+    pub fn new(point: AnchorPoint, offset: Vec2) -> Self {
+        Self { point, offset }
+    }
+}
+
+/// Anchors a node to the top-left corner
+pub fn anchor_top_left(offset: Vec2) -> ScreenAnchor {
+    ScreenAnchor::new(AnchorPoint::TopLeft, offset)
+}
+
+/// Anchors a node to the middle of the top edge
+pub fn anchor_top_center(offset: Vec2) -> ScreenAnchor {
+    ScreenAnchor::new(AnchorPoint::TopCenter, offset)
+}
+
+/// Anchors a node to the top-right corner
+pub fn anchor_top_right(offset: Vec2) -> ScreenAnchor {
+    ScreenAnchor::new(AnchorPoint::TopRight, offset)
+}
+
+/// Anchors a node to the middle of the left edge
+pub fn anchor_center_left(offset: Vec2) -> ScreenAnchor {
+    ScreenAnchor::new(AnchorPoint::CenterLeft, offset)
+}
+
+/// Anchors a node to the center of the screen
+pub fn anchor_center(offset: Vec2) -> ScreenAnchor {
+    ScreenAnchor::new(AnchorPoint::Center, offset)
+}
+
+/// Anchors a node to the middle of the right edge
+pub fn anchor_center_right(offset: Vec2) -> ScreenAnchor {
+    ScreenAnchor::new(AnchorPoint::CenterRight, offset)
+}
+
+/// Anchors a node to the bottom-left corner
+pub fn anchor_bottom_left(offset: Vec2) -> ScreenAnchor {
+    ScreenAnchor::new(AnchorPoint::BottomLeft, offset)
+}
+
+/// Anchors a node to the middle of the bottom edge
+pub fn anchor_bottom_center(offset: Vec2) -> ScreenAnchor {
+    ScreenAnchor::new(AnchorPoint::BottomCenter, offset)
+}
+
+/// Anchors a node to the bottom-right corner
+pub fn anchor_bottom_right(offset: Vec2) -> ScreenAnchor {
+    ScreenAnchor::new(AnchorPoint::BottomRight, offset)
+}
+
+/// Part of the window kept clear of notches, rounded corners and TV overscan
+///
+/// Insets are in logical pixels and set by the game from the platform;
+/// `overscan` additionally trims that fraction of the width and height from
+/// every edge.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct SafeArea {
+    /// Inset from the left edge
+    pub left: f32,
+    /// Inset from the right edge
+    pub right: f32,
+    /// Inset from the top edge
+    pub top: f32,
+    /// Inset from the bottom edge
+    pub bottom: f32,
+    /// Fraction of the window trimmed from each edge, such as `0.05` on TVs
+    pub overscan: f32,
+}
+
+impl SafeArea {
+    /// The safe rectangle of a window of `size` logical pixels
+    pub fn rect(&self, size: Vec2) -> Rect {
+        let overscan = size * self.overscan;
+        let min = Vec2::new(self.left, self.top) + overscan;
+        let max = size - Vec2::new(self.right, self.bottom) - overscan;
+        Rect::from_corners(min, max.max(min))
+    }
+}
+
+/// Position offsets holding a node of `size` at `anchor` inside `safe`, in a window of `window`
+fn anchored_offsets(anchor: &ScreenAnchor, size: Vec2, safe: Rect, window: Vec2) -> [Val; 4] {
+    use AnchorPoint::*;
+    let offset = anchor.offset;
+    let (left, right) = match anchor.point {
+        TopLeft | CenterLeft | BottomLeft => (Val::Px(safe.min.x + offset.x), Val::Auto),
+        TopRight | CenterRight | BottomRight => {
+            (Val::Auto, Val::Px(window.x - safe.max.x + offset.x))
+        }
+        TopCenter | Center | BottomCenter => (
+            Val::Px(safe.min.x + (safe.width() - size.x) / 2.0 + offset.x),
+            Val::Auto,
+        ),
+    };
+    let (top, bottom) = match anchor.point {
+        TopLeft | TopCenter | TopRight => (Val::Px(safe.min.y + offset.y), Val::Auto),
+        BottomLeft | BottomCenter | BottomRight => {
+            (Val::Auto, Val::Px(window.y - safe.max.y + offset.y))
+        }
+        CenterLeft | Center | CenterRight => (
+            Val::Px(safe.min.y + (safe.height() - size.y) / 2.0 + offset.y),
+            Val::Auto,
+        ),
+    };
+    [left, right, top, bottom]
+}
+
+/// System to reposition [`ScreenAnchor`] nodes when the window, safe area or
+/// their size changes
+///
+/// Runs just before UI layout, so centered nodes use last frame's size.
+pub fn screen_anchor_system(
+    mut nodes: Query<(Ref<ScreenAnchor>, Ref<Node>, &mut Style)>,
+    windows: Query<Ref<Window>, With<PrimaryWindow>>,
+    safe_area: Res<SafeArea>,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let refresh = window.is_changed() || safe_area.is_changed() || ui_scale.is_changed();
+    let size = Vec2::new(window.width(), window.height()) / ui_scale.0;
+    let safe = safe_area.rect(size);
+
+    for (anchor, node, mut style) in &mut nodes {
+        if !refresh && !anchor.is_changed() && !node.is_changed() {
+            continue;
+        }
+        let [left, right, top, bottom] = anchored_offsets(&anchor, node.size(), safe, size);
+        if style.position_type != PositionType::Absolute
+            || style.left != left
+            || style.right != right
+            || style.top != top
+            || style.bottom != bottom
+        {
+            style.position_type = PositionType::Absolute;
+            style.left = left;
+            style.right = right;
+            style.top = top;
+            style.bottom = bottom;
+        }
+    }
+}
+
+/// The first section of `text`, pushing an empty one when there is none
+///
+/// Widget systems write labels through this so hand-built text without
+/// sections is filled in rather than indexed out of bounds.
+pub(crate) fn first_section(text: &mut Text) -> &mut TextSection {
+    if text.sections.is_empty() {
+        text.sections.push(TextSection::default());
+    }
+    &mut text.sections[0]
+}
+
+/// Whether the first section of `text` reads `value`
+pub(crate) fn first_section_is(text: &Text, value: &str) -> bool {
+    text.sections
+        .first()
+        .is_some_and(|section| section.value == value)
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<ScreenAnchor>()
+        .register_type::<SafeArea>()
+        .init_resource::<SafeArea>()
+        .add_systems(PostUpdate, screen_anchor_system.before(UiSystem::Layout));
+}
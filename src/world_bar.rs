@@ -0,0 +1,179 @@
+//! UI bars that follow world entities
+//!
+//! A [`WorldAnchoredBar`] pins a UI node, typically a [`ProgressBar`] used as
+//! a unit health bar, above an entity in the game world. Every frame the
+//! target's position is projected through the game camera and the node is
+//! centered on that point, scaled down as the target moves away and hidden
+//! when it is behind the camera, off screen or too far away. The node is
+//! despawned once its target is.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{spawn_progress_bar, ProgressBar, WorldAnchoredBar};
+//! fn add_health_bar(
+//!     mut commands: Commands,
+//!     units: Query<Entity, Added<GlobalTransform>>,
+//! ) {
+//!     for unit in &units {
+//!         let style = Style {
+//!             width: Val::Px(48.0),
+//!             height: Val::Px(6.0),
+//!             ..default()
+//!         };
+//!         let health = ProgressBar {
+//!             value: 1.0,
+//!             background_color: Color::BLACK,
+//!             fill_color: Color::srgb(0.2, 0.8, 0.2),
+//!             show_text: false,
+//!         };
+//!         let bar = spawn_progress_bar(&mut commands, health, style);
+//!         commands.entity(bar).insert(WorldAnchoredBar::new(unit));
+//!     }
+//! }
+//! ```
+//!
+//! [`ProgressBar`]: crate::components::ProgressBar
+
+use bevy::prelude::*;
+use bevy::render::camera::CameraUpdateSystem;
+use bevy::ui::UiSystem;
+
+/// Keeps a UI node above a world entity
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct WorldAnchoredBar {
+    /// The world entity to follow
+    pub target: Entity,
+    /// World-space offset from the target's origin, usually straight up
+    pub offset: Vec3,
+    /// Camera to project with; the active camera with the lowest order when `None`
+    pub camera: Option<Entity>,
+    /// Camera distance at which the node is drawn at its own size
+    pub reference_distance: f32,
+    /// Smallest and largest scale applied with distance
+    pub scale_range: (f32, f32),
+    /// Camera distance beyond which the node is hidden
+    pub max_distance: Option<f32>,
+}
+
+impl WorldAnchoredBar {
+    /// Follows `target`, two units above its origin
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            offset: Vec3::Y * 2.0,
+            camera: None,
+            reference_distance: 20.0,
+            scale_range: (0.5, 1.5),
+            max_distance: None,
+        }
+    }
+
+    /// Sets the world-space offset from the target
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Hides the node when the target is farther than `distance` from the camera
+    pub fn with_max_distance(mut self, distance: f32) -> Self {
+        self.max_distance = Some(distance);
+        self
+    }
+
+    /// Scale of the node when the target is `distance` from the camera
+    pub fn scale_at(&self, distance: f32) -> f32 {
+        let (min, max) = self.scale_range;
+        (self.reference_distance / distance.max(f32::EPSILON)).clamp(min, max)
+    }
+}
+
+/// The active camera with the lowest order, used when a node names none
+pub(crate) fn default_camera(
+    cameras: &Query<(Entity, &Camera, &GlobalTransform)>,
+) -> Option<Entity> {
+    cameras
+        .iter()
+        .filter(|(_, camera, _)| camera.is_active)
+        .min_by_key(|(_, camera, _)| camera.order)
+        .map(|(entity, ..)| entity)
+}
+
+/// Projects a world position to UI coordinates, `None` when it is off screen
+pub(crate) fn world_to_ui(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    world: Vec3,
+    ui_scale: f32,
+) -> Option<Vec2> {
+    let viewport = camera.logical_viewport_size().unwrap_or_default();
+    camera
+        .world_to_viewport(camera_transform, world)
+        .filter(|point| point.cmpge(Vec2::ZERO).all() && point.cmple(viewport).all())
+        .map(|point| point / ui_scale)
+}
+
+/// The parts of an anchored node the system moves
+type AnchoredNode<'a> = (
+    Entity,
+    &'a WorldAnchoredBar,
+    &'a Node,
+    &'a mut Style,
+    &'a mut Transform,
+    &'a mut Visibility,
+);
+
+/// System to place anchored nodes over their targets
+///
+/// Runs just before UI layout, which itself comes before transform
+/// propagation, so targets are projected where they were drawn last frame.
+pub fn world_anchored_bar_system(
+    mut commands: Commands,
+    mut bars: Query<AnchoredNode>,
+    targets: Query<&GlobalTransform>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    ui_scale: Res<UiScale>,
+) {
+    let default_camera = default_camera(&cameras);
+
+    for (entity, bar, node, mut style, mut transform, mut visibility) in &mut bars {
+        let Ok(target) = targets.get(bar.target) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        let camera = bar.camera.or(default_camera);
+        let Some((_, camera, camera_transform)) =
+            camera.and_then(|camera| cameras.get(camera).ok())
+        else {
+            continue;
+        };
+        let world = target.translation() + bar.offset;
+        let distance = camera_transform.translation().distance(world);
+        let screen = world_to_ui(camera, camera_transform, world, ui_scale.0)
+            .filter(|_| bar.max_distance.is_none_or(|max| distance <= max));
+
+        let Some(screen) = screen else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        visibility.set_if_neq(Visibility::Inherited);
+        // The node is centered horizontally and sits on the anchor point
+        let position = screen - Vec2::new(node.size().x / 2.0, node.size().y);
+        style.position_type = PositionType::Absolute;
+        style.left = Val::Px(position.x);
+        style.top = Val::Px(position.y);
+        let scale = bar.scale_at(distance);
+        if transform.scale.x != scale {
+            transform.scale = Vec3::new(scale, scale, 1.0);
+        }
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<WorldAnchoredBar>().add_systems(
+        PostUpdate,
+        world_anchored_bar_system
+            .after(CameraUpdateSystem)
+            .before(UiSystem::Layout),
+    );
+}
@@ -0,0 +1,161 @@
+//! Fading world-anchored UI with camera distance
+//!
+//! A [`ZoomVisibility`] on a [`WorldAnchoredBar`] node, such as a health bar
+//! or a nameplate, fades the node out as the camera zooms away from its
+//! target and hides it entirely past a cutoff, so a zoomed-out battlefield
+//! isn't buried under bars. The distances come from the global
+//! [`ZoomVisibilitySettings`], which the interface settings screen can bind
+//! to, unless the component overrides them:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use sf_ui_common::{
+//! #     Bind, BindPlugin, SliderBuilder, Theme, WorldAnchoredBar, ZoomVisibility,
+//! #     ZoomVisibilitySettings,
+//! # };
+//! fn add_nameplate(mut commands: Commands, nameplate: Entity, unit: Entity) {
+//!     commands
+//!         .entity(nameplate)
+//!         .insert((WorldAnchoredBar::new(unit), ZoomVisibility::default()));
+//! }
+//!
+//! fn interface_settings(mut commands: Commands, theme: Res<Theme>) {
+//!     let slider = SliderBuilder::new(&theme)
+//!         .range(20.0, 200.0)
+//!         .spawn(&mut commands);
+//!     commands
+//!         .entity(slider)
+//!         .insert(Bind::<ZoomVisibilitySettings>::new("cutoff"));
+//! }
+//!
+//! # let mut app = App::new();
+//! app.add_plugins(BindPlugin::<ZoomVisibilitySettings>::default());
+//! ```
+//!
+//! Fading scales the alpha of every color in the node's subtree, as captured
+//! when the fade starts, and restores them once the camera is back in range.
+
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+use crate::transition::{apply_opacity, capture_alphas, FadedColors, NodeAlphas};
+use crate::world_bar::{default_camera, world_anchored_bar_system, WorldAnchoredBar};
+
+/// Global camera distances for [`ZoomVisibility`] nodes
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct ZoomVisibilitySettings {
+    /// Camera distance at which nodes start fading out
+    pub fade_start: f32,
+    /// Camera distance beyond which nodes are hidden
+    pub cutoff: f32,
+}
+
+impl Default for ZoomVisibilitySettings {
+    fn default() -> Self {
+        Self {
+            fade_start: 60.0,
+            cutoff: 90.0,
+        }
+    }
+}
+
+/// Fades a [`WorldAnchoredBar`] node out as the camera moves away from its target
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ZoomVisibility {
+    /// Overrides [`ZoomVisibilitySettings::fade_start`] for this node
+    pub fade_start: Option<f32>,
+    /// Overrides [`ZoomVisibilitySettings::cutoff`] for this node
+    pub cutoff: Option<f32>,
+    /// Alphas captured when the node started fading
+    #[reflect(ignore)]
+    faded: Option<Vec<(Entity, NodeAlphas)>>,
+}
+
+impl ZoomVisibility {
+    /// Fades between `fade_start` and `cutoff` instead of the global distances
+    pub fn with_distances(mut self, fade_start: f32, cutoff: f32) -> Self {
+        self.fade_start = Some(fade_start);
+        self.cutoff = Some(cutoff);
+        self
+    }
+
+    /// Opacity of the node when its target is `distance` from the camera
+    pub fn opacity_at(&self, settings: &ZoomVisibilitySettings, distance: f32) -> f32 {
+        let cutoff = self.cutoff.unwrap_or(settings.cutoff);
+        let fade_start = self.fade_start.unwrap_or(settings.fade_start).min(cutoff);
+        if distance >= cutoff {
+            0.0
+        } else if distance <= fade_start {
+            1.0
+        } else {
+            1.0 - (distance - fade_start) / (cutoff - fade_start)
+        }
+    }
+}
+
+/// System to fade and hide [`ZoomVisibility`] nodes by camera distance
+///
+/// Runs after [`world_anchored_bar_system`], which shows nodes whose targets
+/// are on screen; this only ever hides them.
+pub fn zoom_visibility_system(
+    settings: Res<ZoomVisibilitySettings>,
+    mut nodes: Query<(
+        Entity,
+        &mut ZoomVisibility,
+        &WorldAnchoredBar,
+        &mut Visibility,
+    )>,
+    targets: Query<&GlobalTransform>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    children: Query<&Children>,
+    mut colors: Query<FadedColors>,
+) {
+    let default_camera = default_camera(&cameras);
+
+    for (entity, mut zoom, bar, mut visibility) in &mut nodes {
+        let Ok(target) = targets.get(bar.target) else {
+            continue;
+        };
+        let Some((_, _, camera_transform)) = bar
+            .camera
+            .or(default_camera)
+            .and_then(|camera| cameras.get(camera).ok())
+        else {
+            continue;
+        };
+        let distance = camera_transform
+            .translation()
+            .distance(target.translation() + bar.offset);
+        let opacity = zoom.opacity_at(&settings, distance);
+
+        if opacity <= 0.0 {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        let zoom = zoom.bypass_change_detection();
+        if opacity >= 1.0 {
+            if let Some(alphas) = zoom.faded.take() {
+                apply_opacity(&alphas, 1.0, &mut colors);
+            }
+            continue;
+        }
+        let alphas = zoom
+            .faded
+            .get_or_insert_with(|| capture_alphas(entity, &children, &colors));
+        apply_opacity(alphas, opacity, &mut colors);
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<ZoomVisibility>()
+        .register_type::<ZoomVisibilitySettings>()
+        .init_resource::<ZoomVisibilitySettings>()
+        .add_systems(
+            PostUpdate,
+            zoom_visibility_system
+                .after(world_anchored_bar_system)
+                .before(UiSystem::Layout),
+        );
+}